@@ -33,6 +33,9 @@ defmodule TheoryCraftTA do
 
   """
 
+  # max/2 and min/2 are the MAX and MIN indicators here
+  import Kernel, except: [max: 2, min: 2]
+
   alias TheoryCraft.{DataSeries, TimeSeries}
 
   @type source :: [float() | nil] | DataSeries.t(float() | nil) | TimeSeries.t(float() | nil)
@@ -47,6 +50,54 @@ defmodule TheoryCraftTA do
   defdelegate trima(data, period), to: TheoryCraftTA.Overlap.TRIMA
   defdelegate t3(data, period, vfactor), to: TheoryCraftTA.Overlap.T3
   defdelegate midpoint(data, period), to: TheoryCraftTA.Overlap.MIDPOINT
+  defdelegate kama(data, period), to: TheoryCraftTA.Overlap.KAMA
+  defdelegate rsi(data, period), to: TheoryCraftTA.Momentum.RSI
+  defdelegate atr(high, low, close, period), to: TheoryCraftTA.Volatility.ATR
+  defdelegate natr(high, low, close, period), to: TheoryCraftTA.Volatility.NATR
+  defdelegate trange(high, low, close), to: TheoryCraftTA.Volatility.TRANGE
+  defdelegate stddev(data, period, nbdev), to: TheoryCraftTA.Stats.STDDEV
+  defdelegate var(data, period, nbdev), to: TheoryCraftTA.Stats.VAR
+  defdelegate mom(data, period), to: TheoryCraftTA.Momentum.MOM
+  defdelegate roc(data, period), to: TheoryCraftTA.Momentum.ROC
+  defdelegate midprice(high, low, period), to: TheoryCraftTA.Overlap.MIDPRICE
+  defdelegate obv(close, volume), to: TheoryCraftTA.Volume.OBV
+  defdelegate ad(high, low, close, volume), to: TheoryCraftTA.Volume.AD
+  defdelegate adosc(high, low, close, volume, fast_period, slow_period),
+    to: TheoryCraftTA.Volume.ADOSC
+  defdelegate bbands(data, period, nb_dev_up, nb_dev_dn, ma_type),
+    to: TheoryCraftTA.Overlap.BBANDS
+  defdelegate cci(high, low, close, period), to: TheoryCraftTA.Momentum.CCI
+  defdelegate willr(high, low, close, period), to: TheoryCraftTA.Momentum.WILLR
+  defdelegate macd(data, fast_period, slow_period, signal_period),
+    to: TheoryCraftTA.Momentum.MACD
+  defdelegate ma(data, period, ma_type), to: TheoryCraftTA.Overlap.MA
+  defdelegate rma(data, period), to: TheoryCraftTA.Overlap.RMA
+  defdelegate hma(data, period), to: TheoryCraftTA.Overlap.HMA
+  defdelegate zlema(data, period), to: TheoryCraftTA.Overlap.ZLEMA
+  defdelegate vwma(close, volume, period), to: TheoryCraftTA.Overlap.VWMA
+  defdelegate median(data, period), to: TheoryCraftTA.Stats.Median
+  defdelegate apo(data, fast_period, slow_period, ma_type), to: TheoryCraftTA.Momentum.APO
+  defdelegate ppo(data, fast_period, slow_period, ma_type), to: TheoryCraftTA.Momentum.PPO
+  defdelegate trix(data, period), to: TheoryCraftTA.Momentum.TRIX
+  defdelegate cmo(data, period), to: TheoryCraftTA.Momentum.CMO
+  defdelegate adx(high, low, close, period), to: TheoryCraftTA.Momentum.ADX
+  defdelegate max(data, period), to: TheoryCraftTA.Math.MAX
+  defdelegate min(data, period), to: TheoryCraftTA.Math.MIN
+  defdelegate sum(data, period), to: TheoryCraftTA.Math.SUM
+  defdelegate linearreg(data, period), to: TheoryCraftTA.Stats.LINEARREG
+  defdelegate tsf(data, period), to: TheoryCraftTA.Stats.TSF
+  defdelegate correl(x, y, period), to: TheoryCraftTA.Stats.CORREL
+  defdelegate beta(x, y, period), to: TheoryCraftTA.Stats.BETA
+  defdelegate mcginley(data, period), to: TheoryCraftTA.Overlap.McGinley
+  defdelegate frama(data, period), to: TheoryCraftTA.Overlap.FRAMA
+  defdelegate dx(high, low, close, period), to: TheoryCraftTA.Momentum.DX
+  defdelegate rocp(data, period), to: TheoryCraftTA.Momentum.ROCP
+  defdelegate rocr(data, period), to: TheoryCraftTA.Momentum.ROCR
+  defdelegate rocr100(data, period), to: TheoryCraftTA.Momentum.ROCR100
+  defdelegate ultosc(high, low, close, period1, period2, period3),
+    to: TheoryCraftTA.Momentum.ULTOSC
+  defdelegate aroon(high, low, period), to: TheoryCraftTA.Momentum.AROON
+  defdelegate mfi(high, low, close, volume, period), to: TheoryCraftTA.Volume.MFI
 
   ## Batch indicators - Bang functions
 
@@ -82,6 +133,221 @@ defmodule TheoryCraftTA do
   @spec midpoint!(source(), pos_integer()) :: source()
   def midpoint!(data, period), do: unwrap_batch!(midpoint(data, period), "MIDPOINT")
 
+  @doc "Kaufman Adaptive Moving Average. See `kama/2` for details."
+  @spec kama!(source(), pos_integer()) :: source()
+  def kama!(data, period), do: unwrap_batch!(kama(data, period), "KAMA")
+
+  @doc "Relative Strength Index. See `rsi/2` for details."
+  @spec rsi!(source(), pos_integer()) :: source()
+  def rsi!(data, period), do: unwrap_batch!(rsi(data, period), "RSI")
+
+  @doc "Average True Range. See `atr/4` for details."
+  @spec atr!(source(), source(), source(), pos_integer()) :: source()
+  def atr!(high, low, close, period), do: unwrap_batch!(atr(high, low, close, period), "ATR")
+
+  @doc "Normalized Average True Range. See `natr/4` for details."
+  @spec natr!(source(), source(), source(), pos_integer()) :: source()
+  def natr!(high, low, close, period), do: unwrap_batch!(natr(high, low, close, period), "NATR")
+
+  @doc "True Range. See `trange/3` for details."
+  @spec trange!(source(), source(), source()) :: source()
+  def trange!(high, low, close), do: unwrap_batch!(trange(high, low, close), "TRANGE")
+
+  @doc "Standard Deviation. See `stddev/3` for details."
+  @spec stddev!(source(), pos_integer(), float()) :: source()
+  def stddev!(data, period, nbdev), do: unwrap_batch!(stddev(data, period, nbdev), "STDDEV")
+
+  @doc "Variance. See `var/3` for details."
+  @spec var!(source(), pos_integer(), float()) :: source()
+  def var!(data, period, nbdev), do: unwrap_batch!(var(data, period, nbdev), "VAR")
+
+  @doc "Momentum. See `mom/2` for details."
+  @spec mom!(source(), pos_integer()) :: source()
+  def mom!(data, period), do: unwrap_batch!(mom(data, period), "MOM")
+
+  @doc "Rate of Change. See `roc/2` for details."
+  @spec roc!(source(), pos_integer()) :: source()
+  def roc!(data, period), do: unwrap_batch!(roc(data, period), "ROC")
+
+  @doc "Midpoint Price over period. See `midprice/3` for details."
+  @spec midprice!(source(), source(), pos_integer()) :: source()
+  def midprice!(high, low, period), do: unwrap_batch!(midprice(high, low, period), "MIDPRICE")
+
+  @doc "On Balance Volume. See `obv/2` for details."
+  @spec obv!(source(), source()) :: source()
+  def obv!(close, volume), do: unwrap_batch!(obv(close, volume), "OBV")
+
+  @doc "Chaikin A/D Line. See `ad/4` for details."
+  @spec ad!(source(), source(), source(), source()) :: source()
+  def ad!(high, low, close, volume), do: unwrap_batch!(ad(high, low, close, volume), "AD")
+
+  @doc "Chaikin A/D Oscillator. See `adosc/6` for details."
+  @spec adosc!(source(), source(), source(), source(), pos_integer(), pos_integer()) :: source()
+  def adosc!(high, low, close, volume, fast_period, slow_period) do
+    unwrap_batch!(adosc(high, low, close, volume, fast_period, slow_period), "ADOSC")
+  end
+
+  @doc "Bollinger Bands. See `bbands/5` for details."
+  @spec bbands!(source(), pos_integer(), float(), float(), non_neg_integer()) ::
+          {source(), source(), source()}
+  def bbands!(data, period, nb_dev_up, nb_dev_dn, ma_type) do
+    unwrap_batch!(bbands(data, period, nb_dev_up, nb_dev_dn, ma_type), "BBANDS")
+  end
+
+  @doc "Commodity Channel Index. See `cci/4` for details."
+  @spec cci!(source(), source(), source(), pos_integer()) :: source()
+  def cci!(high, low, close, period), do: unwrap_batch!(cci(high, low, close, period), "CCI")
+
+  @doc "Williams' %R. See `willr/4` for details."
+  @spec willr!(source(), source(), source(), pos_integer()) :: source()
+  def willr!(high, low, close, period) do
+    unwrap_batch!(willr(high, low, close, period), "WILLR")
+  end
+
+  @doc "Moving Average Convergence/Divergence. See `macd/4` for details."
+  @spec macd!(source(), pos_integer(), pos_integer(), pos_integer()) ::
+          {source(), source(), source()}
+  def macd!(data, fast_period, slow_period, signal_period) do
+    unwrap_batch!(macd(data, fast_period, slow_period, signal_period), "MACD")
+  end
+
+  @doc "Moving Average. See `ma/3` for details."
+  @spec ma!(source(), pos_integer(), non_neg_integer()) :: source()
+  def ma!(data, period, ma_type), do: unwrap_batch!(ma(data, period, ma_type), "MA")
+
+  @doc "Wilder's Moving Average. See `rma/2` for details."
+  @spec rma!(source(), pos_integer()) :: source()
+  def rma!(data, period), do: unwrap_batch!(rma(data, period), "RMA")
+
+  @doc "Hull Moving Average. See `hma/2` for details."
+  @spec hma!(source(), pos_integer()) :: source()
+  def hma!(data, period), do: unwrap_batch!(hma(data, period), "HMA")
+
+  @doc "Zero-Lag Exponential Moving Average. See `zlema/2` for details."
+  @spec zlema!(source(), pos_integer()) :: source()
+  def zlema!(data, period), do: unwrap_batch!(zlema(data, period), "ZLEMA")
+
+  @doc "Volume-Weighted Moving Average. See `vwma/3` for details."
+  @spec vwma!(source(), source(), pos_integer()) :: source()
+  def vwma!(close, volume, period), do: unwrap_batch!(vwma(close, volume, period), "VWMA")
+
+  @doc "Rolling Median. See `median/2` for details."
+  @spec median!(source(), pos_integer()) :: source()
+  def median!(data, period), do: unwrap_batch!(median(data, period), "MEDIAN")
+
+  @doc "Absolute Price Oscillator. See `apo/4` for details."
+  @spec apo!(source(), pos_integer(), pos_integer(), non_neg_integer()) :: source()
+  def apo!(data, fast_period, slow_period, ma_type) do
+    unwrap_batch!(apo(data, fast_period, slow_period, ma_type), "APO")
+  end
+
+  @doc "Percentage Price Oscillator. See `ppo/4` for details."
+  @spec ppo!(source(), pos_integer(), pos_integer(), non_neg_integer()) :: source()
+  def ppo!(data, fast_period, slow_period, ma_type) do
+    unwrap_batch!(ppo(data, fast_period, slow_period, ma_type), "PPO")
+  end
+
+  @doc "Triple Exponential Average. See `trix/2` for details."
+  @spec trix!(source(), pos_integer()) :: source()
+  def trix!(data, period), do: unwrap_batch!(trix(data, period), "TRIX")
+
+  @doc "Chande Momentum Oscillator. See `cmo/2` for details."
+  @spec cmo!(source(), pos_integer()) :: source()
+  def cmo!(data, period), do: unwrap_batch!(cmo(data, period), "CMO")
+
+  @doc "Average Directional Movement Index. See `adx/4` for details."
+  @spec adx!(source(), source(), source(), pos_integer()) :: source()
+  def adx!(high, low, close, period) do
+    unwrap_batch!(adx(high, low, close, period), "ADX")
+  end
+
+  @doc "Highest Value. See `max/2` for details."
+  @spec max!(source(), pos_integer()) :: source()
+  def max!(data, period), do: unwrap_batch!(max(data, period), "MAX")
+
+  @doc "Lowest Value. See `min/2` for details."
+  @spec min!(source(), pos_integer()) :: source()
+  def min!(data, period), do: unwrap_batch!(min(data, period), "MIN")
+
+  @doc "Summation. See `sum/2` for details."
+  @spec sum!(source(), pos_integer()) :: source()
+  def sum!(data, period), do: unwrap_batch!(sum(data, period), "SUM")
+
+  @doc "Linear Regression. See `linearreg/2` for details."
+  @spec linearreg!(source(), pos_integer()) :: source()
+  def linearreg!(data, period), do: unwrap_batch!(linearreg(data, period), "LINEARREG")
+
+  @doc "Time Series Forecast. See `tsf/2` for details."
+  @spec tsf!(source(), pos_integer()) :: source()
+  def tsf!(data, period), do: unwrap_batch!(tsf(data, period), "TSF")
+
+  @doc "Pearson's Correlation Coefficient. See `correl/3` for details."
+  @spec correl!(source(), source(), pos_integer()) :: source()
+  def correl!(x, y, period), do: unwrap_batch!(correl(x, y, period), "CORREL")
+
+  @doc "Beta. See `beta/3` for details."
+  @spec beta!(source(), source(), pos_integer()) :: source()
+  def beta!(x, y, period), do: unwrap_batch!(beta(x, y, period), "BETA")
+
+  @doc "McGinley Dynamic. See `mcginley/2` for details."
+  @spec mcginley!(source(), pos_integer()) :: source()
+  def mcginley!(data, period), do: unwrap_batch!(mcginley(data, period), "McGinley")
+
+  @doc "Fractal Adaptive Moving Average. See `frama/2` for details."
+  @spec frama!(source(), pos_integer()) :: source()
+  def frama!(data, period), do: unwrap_batch!(frama(data, period), "FRAMA")
+
+  @doc "Directional Movement Index. See `dx/4` for details."
+  @spec dx!(source(), source(), source(), pos_integer()) :: source()
+  def dx!(high, low, close, period) do
+    unwrap_batch!(dx(high, low, close, period), "DX")
+  end
+
+  @doc "Rate of Change Percentage. See `rocp/2` for details."
+  @spec rocp!(source(), pos_integer()) :: source()
+  def rocp!(data, period), do: unwrap_batch!(rocp(data, period), "ROCP")
+
+  @doc "Rate of Change Ratio. See `rocr/2` for details."
+  @spec rocr!(source(), pos_integer()) :: source()
+  def rocr!(data, period), do: unwrap_batch!(rocr(data, period), "ROCR")
+
+  @doc "Rate of Change Ratio 100 scale. See `rocr100/2` for details."
+  @spec rocr100!(source(), pos_integer()) :: source()
+  def rocr100!(data, period), do: unwrap_batch!(rocr100(data, period), "ROCR100")
+
+  @doc "Ultimate Oscillator. See `ultosc/6` for details."
+  @spec ultosc!(source(), source(), source(), pos_integer(), pos_integer(), pos_integer()) ::
+          source()
+  def ultosc!(high, low, close, period1, period2, period3) do
+    unwrap_batch!(ultosc(high, low, close, period1, period2, period3), "ULTOSC")
+  end
+
+  @doc "Aroon. See `aroon/3` for details."
+  @spec aroon!(source(), source(), pos_integer()) :: {source(), source()}
+  def aroon!(high, low, period), do: unwrap_batch!(aroon(high, low, period), "AROON")
+
+  @doc "Money Flow Index. See `mfi/5` for details."
+  @spec mfi!(source(), source(), source(), source(), pos_integer()) :: source()
+  def mfi!(high, low, close, volume, period) do
+    unwrap_batch!(mfi(high, low, close, volume, period), "MFI")
+  end
+
+  ## Backend information
+
+  @doc """
+  Returns the version of the TA-Lib library the NIF is linked against.
+
+  Returns `"unavailable"` when the NIF was built without TA-Lib.
+  """
+  @spec ta_lib_version() :: String.t()
+  def ta_lib_version(), do: TheoryCraftTA.Native.ta_lib_version()
+
+  @doc """
+  Returns whether the NIF was built with TA-Lib.
+  """
+  @spec has_talib?() :: boolean()
+  def has_talib?(), do: TheoryCraftTA.Native.has_talib()
+
   ## Private functions
 
   defp unwrap_batch!(result, indicator_name) do
@@ -0,0 +1,183 @@
+defmodule TheoryCraftTA.Momentum.ADX do
+  @moduledoc """
+  Average Directional Movement Index (ADX).
+
+  The ADX measures the strength of a trend, whatever its direction. It smooths the
+  directional index (DX) computed from the plus and minus directional indicators (+DI
+  and -DI), and needs the high, low and close of each bar.
+
+  ## Calculation
+
+  ADX = Wilder's smoothing of DX over `period` bars
+
+  Where:
+  - +DI = 100 × smoothed +DM / smoothed True Range
+  - -DI = 100 × smoothed -DM / smoothed True Range
+  - DX = 100 × |+DI - -DI| / (+DI + -DI)
+  - +DI and -DI are emitted on bar index `period`, the ADX on bar index `2 × period - 1`
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.adx(eurusd_m5, 14, name: "adx14"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Average Directional Movement Index (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+    - `period` - Number of periods for the smoothing (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `close` with ADX values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [12.0, 12.5, 12.2, 13.0, 13.4, 13.1, 13.8, 14.2]
+      iex> low = [11.0, 11.6, 11.4, 12.1, 12.6, 12.4, 12.9, 13.3]
+      iex> close = [11.5, 12.3, 11.9, 12.8, 13.2, 12.6, 13.6, 13.9]
+      iex> {:ok, result} = TheoryCraftTA.Momentum.ADX.adx(high, low, close, 3)
+      iex> Enum.map(result, &(&1 && Float.round(&1, 4)))
+      [nil, nil, nil, nil, nil, 71.434, 72.6449, 75.9626]
+
+  """
+  @spec adx(
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          pos_integer()
+        ) :: {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def adx(high, low, close, period) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+
+    case Native.momentum_adx(high_data, low_data, close_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(close, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new ADX state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The ADX period (must be >= 2)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.ADX.init(period: 14, data: "eurusd", name: "adx14")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_adx_state_init(period) do
+      {:ok, native_state} ->
+        state = %ADX{
+          period: period,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next ADX value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with the ADX, +DI and -DI lines
+  - `{:error, message}` on error
+
+  ## Value
+
+  The value is a map with the `:adx`, `:plus_di` and `:minus_di` lines. It is `nil`
+  until +DI and -DI are available, and `:adx` stays `nil` during its own warmup.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %ADX{} = state) do
+    %ADX{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {adx, plus_di, minus_di, new_native_state}} =
+      Native.momentum_adx_state_next(native_state, high, low, close, is_new_bar)
+
+    new_state = %ADX{state | state: new_native_state}
+
+    lines =
+      case plus_di do
+        nil -> nil
+        _ -> %{adx: adx, plus_di: plus_di, minus_di: minus_di}
+      end
+
+    indicator_value = %IndicatorValue{
+      value: lines,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,182 @@
+defmodule TheoryCraftTA.Momentum.AROON do
+  @moduledoc """
+  Aroon (AROON).
+
+  The Aroon indicator tells how many bars ago the highest high and the lowest low of the
+  window were reached. A new high pushes the Aroon Up to 100, a new low pushes the Aroon
+  Down to 100. It needs the high and low of each bar.
+
+  ## Calculation
+
+  Aroon Up = 100 × (period - bars since the highest high) / period
+  Aroon Down = 100 × (period - bars since the lowest low) / period
+
+  Where:
+  - The window spans the last `period + 1` bars
+  - Like TA-Lib, the most recent bar wins a tie
+  - The first values are emitted on bar index `period`
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.aroon(eurusd_m5, 14, name: "aroon14"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Aroon (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `period` - Number of bars in the window (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, {aroon_down, aroon_up}}` where each line is the same type as `high`
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [12.0, 12.5, 12.2, 13.0, 13.4]
+      iex> low = [11.0, 11.6, 11.4, 12.1, 12.6]
+      iex> {:ok, {down, up}} = TheoryCraftTA.Momentum.AROON.aroon(high, low, 3)
+      iex> Enum.map(down, &(&1 && Float.round(&1, 4)))
+      [nil, nil, nil, 0.0, 33.3333]
+      iex> Enum.map(up, &(&1 && Float.round(&1, 4)))
+      [nil, nil, nil, 100.0, 100.0]
+
+  """
+  @spec aroon(TheoryCraftTA.source(), TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, {TheoryCraftTA.source(), TheoryCraftTA.source()}} | {:error, String.t()}
+  def aroon(high, low, period) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+
+    case Native.momentum_aroon(high_data, low_data, period) do
+      {:ok, {aroon_down, aroon_up}} ->
+        {:ok,
+         {Helpers.rebuild_same_type(high, aroon_down), Helpers.rebuild_same_type(high, aroon_up)}}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new AROON state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The AROON period (must be >= 2)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.AROON.init(period: 14, data: "eurusd", name: "aroon14")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_aroon_state_init(period) do
+      {:ok, native_state} ->
+        state = %AROON{
+          period: period,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next AROON value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with the Aroon Down and Up lines
+  - `{:error, message}` on error
+
+  ## Value
+
+  The value is a map with the `:down` and `:up` lines, or `nil` until the window is full.
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), its high and low replace the previous
+  ones, so a lower high or a higher low than before is taken into account.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %AROON{} = state) do
+    %AROON{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {aroon_down, aroon_up, new_native_state}} =
+      Native.momentum_aroon_state_next(native_state, high, low, is_new_bar)
+
+    new_state = %AROON{state | state: new_native_state}
+
+    lines =
+      case aroon_down do
+        nil -> nil
+        _ -> %{down: aroon_down, up: aroon_up}
+      end
+
+    indicator_value = %IndicatorValue{
+      value: lines,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
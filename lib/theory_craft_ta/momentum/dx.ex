@@ -0,0 +1,176 @@
+defmodule TheoryCraftTA.Momentum.DX do
+  @moduledoc """
+  Directional Movement Index (DX).
+
+  The DX measures the strength of the trend on each bar, from the spread between the
+  plus and minus directional indicators (+DI and -DI). The ADX is its smoothed version.
+  It needs the high, low and close of each bar.
+
+  ## Calculation
+
+  DX = 100 × |+DI - -DI| / (+DI + -DI)
+
+  Where:
+  - +DI = 100 × smoothed +DM / smoothed True Range
+  - -DI = 100 × smoothed -DM / smoothed True Range
+  - The first value is emitted on bar index `period`
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.dx(eurusd_m5, 14, name: "dx14"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Directional Movement Index (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+    - `period` - Number of periods for the smoothing (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `close` with DX values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [12.0, 12.5, 12.2, 13.0, 13.4]
+      iex> low = [11.0, 11.6, 11.4, 12.1, 12.6]
+      iex> close = [11.5, 12.3, 11.9, 12.8, 13.2]
+      iex> {:ok, [nil, nil, nil, dx, _]} = TheoryCraftTA.Momentum.DX.dx(high, low, close, 3)
+      iex> Float.round(dx, 4)
+      78.9474
+
+  """
+  @spec dx(
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          pos_integer()
+        ) :: {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def dx(high, low, close, period) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+
+    case Native.momentum_dx(high_data, low_data, close_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(close, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new DX state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The DX period (must be >= 2)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.DX.init(period: 14, data: "eurusd", name: "dx14")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_dx_state_init(period) do
+      {:ok, native_state} ->
+        state = %DX{
+          period: period,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next DX value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with DX calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), its high, low and close replace the
+  previous ones and the smoothing restarts from the previous bar.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %DX{} = state) do
+    %DX{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {dx_value, new_native_state}} =
+      Native.momentum_dx_state_next(native_state, high, low, close, is_new_bar)
+
+    new_state = %DX{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: dx_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,203 @@
+defmodule TheoryCraftTA.Momentum.MACD do
+  @moduledoc """
+  Moving Average Convergence/Divergence (MACD).
+
+  The MACD line is the difference between a fast and a slow EMA of the price. The
+  signal line is an EMA of the MACD line, and the histogram is the distance between
+  the two.
+
+  ## Calculation
+
+  MACD = EMA(price, fast_period) - EMA(price, slow_period)
+  Signal = EMA(MACD, signal_period)
+  Histogram = MACD - Signal
+
+  Where:
+  - Like TA-Lib, the fast EMA is seeded on the same bar as the slow one
+  - Like TA-Lib, the periods are swapped when `slow_period` is shorter than `fast_period`
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`. Each value is a map with the `:macd`, `:signal`
+  and `:hist` lines, or `nil` during warmup:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.macd(eurusd_m5[:close], 12, 26, 9, name: "macd"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type lines :: %{macd: float(), signal: float() | nil, hist: float() | nil}
+
+  @type t :: %__MODULE__{
+          fast_period: pos_integer(),
+          slow_period: pos_integer(),
+          signal_period: pos_integer(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:fast_period, :slow_period, :signal_period, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Moving Average Convergence/Divergence (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `fast_period` - Number of periods for the fast EMA (must be >= 2)
+    - `slow_period` - Number of periods for the slow EMA (must be >= 2)
+    - `signal_period` - Number of periods for the signal EMA (must be >= 1)
+
+  ## Returns
+
+    - `{:ok, {macd, signal, hist}}` where each line is the same type as input
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  Like TA-Lib, the three lines start on the same bar, once the signal is available.
+
+  ## Examples
+
+      iex> data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      iex> {:ok, {macd, signal, hist}} = TheoryCraftTA.Momentum.MACD.macd(data, 3, 6, 3)
+      iex> Enum.map([macd, signal, hist], &(&1 |> List.last() |> Float.round(4)))
+      [0.8333, 0.7778, 0.0556]
+
+  """
+  @spec macd(TheoryCraftTA.source(), pos_integer(), pos_integer(), pos_integer()) ::
+          {:ok, {TheoryCraftTA.source(), TheoryCraftTA.source(), TheoryCraftTA.source()}}
+          | {:error, String.t()}
+  def macd(data, fast_period, slow_period, signal_period) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.momentum_macd(list_data, fast_period, slow_period, signal_period) do
+      {:ok, {macd, signal, hist}} ->
+        {:ok,
+         {Helpers.rebuild_same_type(data, macd), Helpers.rebuild_same_type(data, signal),
+          Helpers.rebuild_same_type(data, hist)}}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new MACD state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:fast_period` (optional) - The fast EMA period (must be >= 2, default: 12)
+    - `:slow_period` (optional) - The slow EMA period (must be >= 2, default: 26)
+    - `:signal_period` (optional) - The signal EMA period (must be >= 1, default: 9)
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If a period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.MACD.init(data: "eurusd", name: "macd", source: :close)
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    fast_period = Keyword.get(opts, :fast_period, 12)
+    slow_period = Keyword.get(opts, :slow_period, 26)
+    signal_period = Keyword.get(opts, :signal_period, 9)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_macd_state_init(fast_period, slow_period, signal_period) do
+      {:ok, native_state} ->
+        state = %MACD{
+          fast_period: fast_period,
+          slow_period: slow_period,
+          signal_period: signal_period,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next MACD lines.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue whose value is a `t:lines/0`
+    map, or `nil` during warmup
+  - `{:error, message}` on error
+
+  Unlike the batch calculation, the MACD line is emitted as soon as the slow EMA is
+  available: `:signal` and `:hist` stay `nil` until the signal EMA is warmed up.
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %MACD{} = state) do
+    %MACD{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {macd, signal, hist, new_native_state}} =
+      Native.momentum_macd_state_next(native_state, value, is_new_bar)
+
+    new_state = %MACD{state | state: new_native_state}
+
+    lines =
+      case macd do
+        nil -> nil
+        _ -> %{macd: macd, signal: signal, hist: hist}
+      end
+
+    indicator_value = %IndicatorValue{
+      value: lines,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,193 @@
+defmodule TheoryCraftTA.Momentum.PPO do
+  @moduledoc """
+  Percentage Price Oscillator (PPO).
+
+  The Percentage Price Oscillator is the APO expressed as a percentage of the slow moving
+  average, which makes it comparable across instruments.
+
+  ## Calculation
+
+  PPO = 100 × (MA(price, fast_period) - MA(price, slow_period)) / MA(price, slow_period)
+
+  PPO is 0 when the slow MA is 0.
+
+  Where:
+  - `ma_type` selects the moving averages, as TA-Lib's `TA_MAType`
+    (0 = SMA, 1 = EMA, 2 = WMA, 3 = DEMA, 4 = TEMA, 5 = TRIMA, 6 = KAMA, 7 = MAMA, 8 = T3)
+  - Like TA-Lib, the periods are swapped when `slow_period` is shorter than `fast_period`
+
+  The streaming state supports every `ma_type` except MAMA (`ma_type: 7`).
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.ppo(eurusd_m5[:close], 12, 26, 1, name: "ppo"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          fast_period: pos_integer(),
+          slow_period: pos_integer(),
+          ma_type: non_neg_integer(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:fast_period, :slow_period, :ma_type, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Percentage Price Oscillator (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `fast_period` - Number of periods for the fast MA (must be >= 2)
+    - `slow_period` - Number of periods for the slow MA (must be >= 2)
+    - `ma_type` - Type of the moving averages (0 to 8, see the module documentation)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with PPO values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+      iex> {:ok, result} = TheoryCraftTA.Momentum.PPO.ppo(data, 2, 3, 0)
+      iex> Enum.map(result, &(&1 && Float.round(&1, 4)))
+      [nil, nil, 25.0, 16.6667, 12.5, 10.0]
+
+  """
+  @spec ppo(TheoryCraftTA.source(), pos_integer(), pos_integer(), non_neg_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def ppo(data, fast_period, slow_period, ma_type) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.momentum_ppo(list_data, fast_period, slow_period, ma_type) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new PPO state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:fast_period` (optional) - The fast MA period (must be >= 2, default: 12)
+    - `:slow_period` (optional) - The slow MA period (must be >= 2, default: 26)
+    - `:ma_type` (optional) - The type of the moving averages (default: 0, SMA)
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If a period or the ma_type is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.PPO.init(ma_type: 1, data: "eurusd", name: "ppo", source: :close)
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()} | {:error, String.t()}
+  def init(opts) when is_list(opts) do
+    fast_period = Keyword.get(opts, :fast_period, 12)
+    slow_period = Keyword.get(opts, :slow_period, 26)
+    ma_type = Keyword.get(opts, :ma_type, 0)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_ppo_state_init(fast_period, slow_period, ma_type) do
+      {:ok, native_state} ->
+        state = %PPO{
+          fast_period: fast_period,
+          slow_period: slow_period,
+          ma_type: ma_type,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next PPO value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with PPO calculation
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  ## Data Types
+
+  The data extracted from `event.data[data_name]` can be:
+  - A bar/struct with fields like `:close`, `:high`, etc. - uses the `:source` field
+  - A float/nil value directly (e.g., from another indicator) - uses the value as-is
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %PPO{} = state) do
+    %PPO{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {ppo_value, new_native_state}} =
+      Native.momentum_ppo_state_next(native_state, value, is_new_bar)
+
+    new_state = %PPO{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: ppo_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,175 @@
+defmodule TheoryCraftTA.Momentum.ROCR100 do
+  @moduledoc """
+  Rate of Change Ratio 100 scale (ROCR100).
+
+  ROCR100 is a variant of the `TheoryCraftTA.Momentum.ROC` that returns the ratio, in percent,
+  of the price over a given number of bars.
+
+  ## Calculation
+
+  ROCR100 = 100 × Price / Price[period bars ago]
+
+  Like TA-Lib, a zero price `period` bars ago gives 0 in the batch calculation, while
+  the streaming state returns `nil`. The first value is emitted on bar index `period`.
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.rocr100(eurusd_m5[:close], 10, name: "rocr100_10"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Rate of Change Ratio 100 scale (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of bars to look back (must be >= 1)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with ROCR100 values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> TheoryCraftTA.Momentum.ROCR100.rocr100([1.0, 2.0, 4.0, 5.0], 1)
+      {:ok, [nil, 200.0, 200.0, 125.0]}
+
+  """
+  @spec rocr100(TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def rocr100(data, period) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.momentum_rocr100(list_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new ROCR100 state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The ROCR100 period (must be >= 1)
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.ROCR100.init(period: 10, data: "eurusd", name: "rocr100_10", source: :close)
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_rocr100_state_init(period) do
+      {:ok, native_state} ->
+        state = %ROCR100{
+          period: period,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next ROCR100 value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with ROCR100 calculation
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  ## Data Types
+
+  The data extracted from `event.data[data_name]` can be:
+  - A bar/struct with fields like `:close`, `:high`, etc. - uses the `:source` field
+  - A float/nil value directly (e.g., from another indicator) - uses the value as-is
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %ROCR100{} = state) do
+    %ROCR100{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {rocr100_value, new_native_state}} =
+      Native.momentum_rocr100_state_next(native_state, value, is_new_bar)
+
+    new_state = %ROCR100{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: rocr100_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
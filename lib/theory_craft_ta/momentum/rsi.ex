@@ -0,0 +1,178 @@
+defmodule TheoryCraftTA.Momentum.RSI do
+  @moduledoc """
+  Relative Strength Index (RSI).
+
+  The Relative Strength Index is a momentum oscillator that measures the speed and
+  magnitude of recent price changes. It oscillates between 0 and 100.
+
+  ## Calculation
+
+  RSI = 100 × AvgGain / (AvgGain + AvgLoss)
+
+  Where:
+  - AvgGain / AvgLoss are seeded with the simple average of the first `period` gains/losses
+  - Then smoothed with Wilder's method: Avg = (Avg_prev × (period - 1) + current) / period
+
+  The first value is emitted after `period` price changes (i.e. on bar index `period`).
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.rsi(eurusd_m5[:close], 14, name: "rsi14"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Relative Strength Index (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of periods for Wilder's smoothing (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with RSI values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> TheoryCraftTA.Momentum.RSI.rsi([1.0, 2.0, 3.0, 4.0, 5.0], 3)
+      {:ok, [nil, nil, nil, 100.0, 100.0]}
+
+  """
+  @spec rsi(TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def rsi(data, period) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.momentum_rsi(list_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new RSI state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The RSI period (must be >= 2)
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.RSI.init(period: 14, data: "eurusd", name: "rsi14", source: :close)
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_rsi_state_init(period) do
+      {:ok, native_state} ->
+        state = %RSI{
+          period: period,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next RSI value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with RSI calculation
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  ## Data Types
+
+  The data extracted from `event.data[data_name]` can be:
+  - A bar/struct with fields like `:close`, `:high`, etc. - uses the `:source` field
+  - A float/nil value directly (e.g., from another indicator) - uses the value as-is
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %RSI{} = state) do
+    %RSI{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {rsi_value, new_native_state}} =
+      Native.momentum_rsi_state_next(native_state, value, is_new_bar)
+
+    new_state = %RSI{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: rsi_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,191 @@
+defmodule TheoryCraftTA.Momentum.ULTOSC do
+  @moduledoc """
+  Ultimate Oscillator (ULTOSC).
+
+  Larry Williams' Ultimate Oscillator averages the buying pressure of three windows of
+  different lengths, so that a divergence on one timeframe is confirmed by the others.
+  It goes from 0 to 100 and needs the high, low and close of each bar.
+
+  ## Calculation
+
+  ULTOSC = 100 × (4 × A1 + 2 × A2 + A3) / 7
+
+  Where:
+  - BP = Close - min(Low, Close[1 bar ago]), the buying pressure
+  - TR = max(High, Close[1 bar ago]) - min(Low, Close[1 bar ago]), the true range
+  - A1, A2, A3 = sum(BP) / sum(TR) over the shortest, middle and longest period
+  - Like TA-Lib, the periods are sorted, so their order doesn't matter
+  - The first value is emitted on the bar index of the longest period
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.ultosc(eurusd_m5, 7, 14, 28, name: "ultosc"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period1: pos_integer(),
+          period2: pos_integer(),
+          period3: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period1, :period2, :period3, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Ultimate Oscillator (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+    - `period1` - Number of bars of the first window (must be >= 1)
+    - `period2` - Number of bars of the second window (must be >= 1)
+    - `period3` - Number of bars of the third window (must be >= 1)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `close` with ULTOSC values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [10.0, 11.0, 12.0, 11.5, 13.0]
+      iex> low = [9.0, 10.0, 10.5, 10.0, 11.0]
+      iex> close = [9.5, 10.5, 11.5, 10.5, 12.5]
+      iex> {:ok, [nil, nil, nil | values]} =
+      ...>   TheoryCraftTA.Momentum.ULTOSC.ultosc(high, low, close, 1, 2, 3)
+      iex> Enum.map(values, &Float.round(&1, 4))
+      [41.2698, 72.6623]
+
+  """
+  @spec ultosc(
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          pos_integer(),
+          pos_integer(),
+          pos_integer()
+        ) :: {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def ultosc(high, low, close, period1, period2, period3) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+
+    case Native.momentum_ultosc(high_data, low_data, close_data, period1, period2, period3) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(close, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new ULTOSC state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period1` (optional) - The first window (must be >= 1, default: 7)
+    - `:period2` (optional) - The second window (must be >= 1, default: 14)
+    - `:period3` (optional) - The third window (must be >= 1, default: 28)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If a period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.ULTOSC.init(data: "eurusd", name: "ultosc")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period1 = Keyword.get(opts, :period1, 7)
+    period2 = Keyword.get(opts, :period2, 14)
+    period3 = Keyword.get(opts, :period3, 28)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_ultosc_state_init(period1, period2, period3) do
+      {:ok, native_state} ->
+        state = %ULTOSC{
+          period1: period1,
+          period2: period2,
+          period3: period3,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next ULTOSC value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with ULTOSC calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), its high, low and close replace the
+  previous ones. The close of the previous bar is kept for the true range.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %ULTOSC{} = state) do
+    %ULTOSC{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {ultosc_value, new_native_state}} =
+      Native.momentum_ultosc_state_next(native_state, high, low, close, is_new_bar)
+
+    new_state = %ULTOSC{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: ultosc_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
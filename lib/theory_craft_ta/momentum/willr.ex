@@ -0,0 +1,175 @@
+defmodule TheoryCraftTA.Momentum.WILLR do
+  @moduledoc """
+  Williams' %R (WILLR).
+
+  Williams' %R is a momentum oscillator that locates the close within the range of
+  the last `period` bars. It goes from -100 (close at the lowest low) to 0 (close at
+  the highest high) and needs the high, low and close of each bar.
+
+  ## Calculation
+
+  WILLR = -100 × (Highest High - Close) / (Highest High - Lowest Low)
+
+  Where:
+  - Highest High / Lowest Low are taken over the last `period` bars
+  - Like TA-Lib, the WILLR is 0 when the window has no range
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.willr(eurusd_m5, 14, name: "willr14"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Williams' %R (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+    - `period` - Number of bars in the window (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `close` with WILLR values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [12.0, 12.5, 12.2, 13.0, 13.4]
+      iex> low = [11.0, 11.6, 11.4, 12.1, 12.6]
+      iex> close = [11.5, 12.3, 11.9, 12.8, 13.2]
+      iex> {:ok, [nil, nil | values]} = TheoryCraftTA.Momentum.WILLR.willr(high, low, close, 3)
+      iex> Enum.map(values, &Float.round(&1, 4))
+      [-40.0, -12.5, -10.0]
+
+  """
+  @spec willr(
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          pos_integer()
+        ) :: {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def willr(high, low, close, period) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+
+    case Native.momentum_willr(high_data, low_data, close_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(close, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new WILLR state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The WILLR period (must be >= 2)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Momentum.WILLR.init(period: 14, data: "eurusd", name: "willr14")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.momentum_willr_state_init(period) do
+      {:ok, native_state} ->
+        state = %WILLR{
+          period: period,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next WILLR value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with WILLR calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), its high, low and close replace the
+  previous ones, so a lower high or a higher low than before is taken into account.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %WILLR{} = state) do
+    %WILLR{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {willr_value, new_native_state}} =
+      Native.momentum_willr_state_next(native_state, high, low, close, is_new_bar)
+
+    new_state = %WILLR{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: willr_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -40,24 +40,266 @@ defmodule TheoryCraftTA.Native do
   def overlap_trima(_data, _period), do: error()
   def overlap_t3(_data, _period, _vfactor), do: error()
   def overlap_midpoint(_data, _period), do: error()
+  def overlap_kama(_data, _period), do: error()
+  def momentum_rsi(_data, _period), do: error()
+  def volatility_atr(_high, _low, _close, _period), do: error()
+  def volatility_natr(_high, _low, _close, _period), do: error()
+  def volatility_trange(_high, _low, _close), do: error()
+  def stats_stddev(_data, _period, _nbdev), do: error()
+  def stats_var(_data, _period, _nbdev), do: error()
+  def stats_linearreg(_data, _period), do: error()
+  def stats_tsf(_data, _period), do: error()
+  def stats_correl(_x, _y, _period), do: error()
+  def stats_beta(_x, _y, _period), do: error()
+  def stats_median(_data, _period), do: error()
+  def stats_percent_rank(_data, _period), do: error()
+  def stats_zscore(_data, _period), do: error()
+  def stats_sem(_data, _period), do: error()
+  def momentum_mom(_data, _period), do: error()
+  def momentum_roc(_data, _period), do: error()
+  def overlap_midprice(_high, _low, _period), do: error()
+
+  def overlap_sarext(
+        _high,
+        _low,
+        _start_value,
+        _offset_on_reverse,
+        _accel_init_long,
+        _accel_long,
+        _accel_max_long,
+        _accel_init_short,
+        _accel_short,
+        _accel_max_short
+      ),
+      do: error()
+
+  def volume_obv(_close, _volume), do: error()
+  def volume_ad(_high, _low, _close, _volume), do: error()
+  def volume_adosc(_high, _low, _close, _volume, _fast_period, _slow_period), do: error()
+  def volume_mfi(_high, _low, _close, _volume, _period), do: error()
+  def overlap_bbands(_data, _period, _nb_dev_up, _nb_dev_dn, _ma_type), do: error()
+  def momentum_cci(_high, _low, _close, _period), do: error()
+  def momentum_willr(_high, _low, _close, _period), do: error()
+  def momentum_macd(_data, _fast_period, _slow_period, _signal_period), do: error()
+  def overlap_ma(_data, _period, _ma_type), do: error()
+  def momentum_apo(_data, _fast_period, _slow_period, _ma_type), do: error()
+  def momentum_ppo(_data, _fast_period, _slow_period, _ma_type), do: error()
+  def momentum_trix(_data, _period), do: error()
+  def momentum_adx(_high, _low, _close, _period), do: error()
+  def momentum_dx(_high, _low, _close, _period), do: error()
+  def overlap_supertrend(_high, _low, _close, _period, _multiplier), do: error()
+  def overlap_keltner(_high, _low, _close, _ema_period, _atr_period, _multiplier), do: error()
+  def overlap_donchian(_high, _low, _period), do: error()
+  def overlap_chandelier(_high, _low, _close, _period, _multiplier), do: error()
+  def overlap_rma(_data, _period), do: error()
+  def overlap_hma(_data, _period), do: error()
+  def overlap_zlema(_data, _period), do: error()
+  def overlap_vwma(_close, _volume, _period), do: error()
+  def momentum_cmo(_data, _period), do: error()
+  def overlap_mcginley(_data, _period), do: error()
+  def overlap_frama(_data, _period), do: error()
+  def math_minmaxindex(_data, _period), do: error()
+  def math_sum(_data, _period), do: error()
+  def math_max(_data, _period), do: error()
+  def math_min(_data, _period), do: error()
+  def momentum_stochf(_high, _low, _close, _fastk_period, _fastd_period, _fastd_ma_type),
+    do: error()
+  def momentum_stochrsi(_data, _period, _fastk_period, _fastd_period, _fastd_ma_type), do: error()
+  def momentum_rocp(_data, _period), do: error()
+  def momentum_rocr(_data, _period), do: error()
+  def momentum_rocr100(_data, _period), do: error()
+  def momentum_ultosc(_high, _low, _close, _period1, _period2, _period3), do: error()
+  def momentum_aroon(_high, _low, _period), do: error()
+  def price_heikin_ashi(_open, _high, _low, _close), do: error()
+  def price_pivots(_high, _low, _close), do: error()
+  def price_typprice(_high, _low, _close), do: error()
+  def price_medprice(_high, _low), do: error()
+  def price_wclprice(_high, _low, _close), do: error()
+
+  # Batch functions on f32 series (computed in f64, narrowed on return)
+  def overlap_sma_f32(_data, _period), do: error()
+  def overlap_ema_f32(_data, _period), do: error()
+  def momentum_rsi_f32(_data, _period), do: error()
+
+  # Batch functions returning {first_index, values} without the leading nil padding
+  def overlap_sma_trimmed(_data, _period), do: error()
+  def overlap_ema_trimmed(_data, _period), do: error()
+
+  # Batch functions returning {first_index, binary} with the values as native f64 (NaN gaps)
+  def overlap_sma_iodata(_data, _period), do: error()
+
+  # Batch functions on plain float series, a sentinel value standing for missing values
+  def overlap_sma_with_sentinel(_data, _period, _sentinel), do: error()
+
+  # Batch functions computing several series or periods in a single call
+  def overlap_sma_multi(_series, _period), do: error()
+  def overlap_ema_multi(_data, _periods), do: error()
+
+  # Batch functions over sliding windows of a single series, one result per window
+  def overlap_sma_windows(_data, _period, _window, _step), do: error()
+
+  # Batch functions filling the warmup with the mean of the partial window
+  def overlap_sma_partial(_data, _period), do: error()
+
+  # Batch functions returning {value, final} pairs, the last bar possibly still forming
+  def overlap_sma_annotated(_data, _period, _last_bar_closed), do: error()
+
+  # Batch functions on a price derived from the OHLC (:close, :hl2, :hlc3 or :ohlc4)
+  def overlap_sma_source(_ohlc, _period, _source), do: error()
+
+  # Batch functions computing several indicators on the same OHLC in a single call
+  def compute_batch(_ohlc, _specs), do: error()
+  def max_lookback(_specs), do: error()
 
   # State-based functions
   def overlap_sma_state_init(_period), do: error()
+  def overlap_sma_state_init(_period, _nan_policy), do: error()
+  def overlap_sma_state_init_many(_periods), do: error()
   def overlap_sma_state_next(_state, _value, _is_new_bar), do: error()
+  def overlap_sma_state_next_ts(_state, _value, _timestamp), do: error()
+  def overlap_sma_state_value(_state), do: error()
+  def overlap_sma_state_is_ready(_state), do: error()
+  def overlap_sma_state_peek(_state, _value), do: error()
+  def overlap_sma_state_undo(_state), do: error()
   def overlap_ema_state_init(_period), do: error()
+  def overlap_ema_state_init(_period, _nan_policy_or_seed_mode), do: error()
+  def overlap_ema_state_init(_period, _nan_policy, _seed_mode), do: error()
   def overlap_ema_state_next(_state, _value, _is_new_bar), do: error()
+  def overlap_ema_state_value(_state), do: error()
+  def overlap_ema_state_is_ready(_state), do: error()
+  def overlap_ema_state_peek(_state, _value), do: error()
+  def overlap_ema_state_undo(_state), do: error()
   def overlap_wma_state_init(_period), do: error()
+  def overlap_wma_state_init(_period, _nan_policy), do: error()
   def overlap_wma_state_next(_state, _value, _is_new_bar), do: error()
   def overlap_dema_state_init(_period), do: error()
+  def overlap_dema_state_init(_period, _seed_mode), do: error()
   def overlap_dema_state_next(_state, _value, _is_new_bar), do: error()
   def overlap_tema_state_init(_period), do: error()
+  def overlap_tema_state_init(_period, _seed_mode), do: error()
   def overlap_tema_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_trix_state_init(_period), do: error()
+  def momentum_trix_state_next(_state, _value, _is_new_bar), do: error()
   def overlap_trima_state_init(_period), do: error()
   def overlap_trima_state_next(_state, _value, _is_new_bar), do: error()
   def overlap_t3_state_init(_period, _vfactor), do: error()
+  def overlap_t3_state_init(_period, _vfactor, _seed_mode), do: error()
   def overlap_t3_state_next(_state, _value, _is_new_bar), do: error()
   def overlap_midpoint_state_init(_period), do: error()
+  def overlap_midpoint_state_init(_period, _nan_policy), do: error()
   def overlap_midpoint_state_next(_state, _value, _is_new_bar), do: error()
+  def overlap_kama_state_init(_period), do: error()
+  def overlap_kama_state_init(_period, _fast_period, _slow_period), do: error()
+  def overlap_kama_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_rsi_state_init(_period), do: error()
+  def momentum_rsi_state_next(_state, _value, _is_new_bar), do: error()
+  def volatility_atr_state_init(_period), do: error()
+  def volatility_atr_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def volatility_natr_state_init(_period), do: error()
+  def volatility_natr_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def volatility_trange_state_init(), do: error()
+  def volatility_trange_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def momentum_adx_state_init(_period), do: error()
+  def momentum_adx_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def momentum_dx_state_init(_period), do: error()
+  def momentum_dx_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def stats_stddev_state_init(_period, _nbdev), do: error()
+  def stats_stddev_state_init(_period, _nbdev, _recompute_interval), do: error()
+  def stats_stddev_state_next(_state, _value, _is_new_bar), do: error()
+  def stats_var_state_init(_period, _nbdev), do: error()
+  def stats_var_state_init(_period, _nbdev, _recompute_interval), do: error()
+  def stats_var_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_mom_state_init(_period), do: error()
+  def momentum_mom_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_roc_state_init(_period), do: error()
+  def momentum_roc_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_rocp_state_init(_period), do: error()
+  def momentum_rocp_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_rocr_state_init(_period), do: error()
+  def momentum_rocr_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_rocr100_state_init(_period), do: error()
+  def momentum_rocr100_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_ultosc_state_init(_period1, _period2, _period3), do: error()
+  def momentum_ultosc_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def momentum_aroon_state_init(_period), do: error()
+  def momentum_aroon_state_next(_state, _high, _low, _is_new_bar), do: error()
+  def overlap_midprice_state_init(_period), do: error()
+  def overlap_midprice_state_next(_state, _high, _low, _is_new_bar), do: error()
+  def math_max_state_init(_period), do: error()
+  def math_max_state_next(_state, _value, _is_new_bar), do: error()
+  def math_min_state_init(_period), do: error()
+  def math_min_state_next(_state, _value, _is_new_bar), do: error()
+  def math_sum_state_init(_period), do: error()
+  def math_sum_state_init(_period, _recompute_interval), do: error()
+  def math_sum_state_next(_state, _value, _is_new_bar), do: error()
+  def stats_linearreg_state_init(_period), do: error()
+  def stats_linearreg_state_next(_state, _value, _is_new_bar), do: error()
+  def stats_tsf_state_init(_period), do: error()
+  def stats_tsf_state_next(_state, _value, _is_new_bar), do: error()
+  def stats_correl_state_init(_period), do: error()
+  def stats_correl_state_next(_state, _x, _y, _is_new_bar), do: error()
+  def stats_beta_state_init(_period), do: error()
+  def stats_beta_state_next(_state, _x, _y, _is_new_bar), do: error()
+  def volume_obv_state_init(), do: error()
+  def volume_obv_state_next(_state, _close, _volume, _is_new_bar), do: error()
+  def volume_ad_state_init(), do: error()
+  def volume_ad_state_next(_state, _high, _low, _close, _volume, _is_new_bar), do: error()
+  def volume_adosc_state_init(_fast_period, _slow_period), do: error()
+  def volume_adosc_state_next(_state, _high, _low, _close, _volume, _is_new_bar), do: error()
+  def volume_mfi_state_init(_period), do: error()
+  def volume_mfi_state_next(_state, _high, _low, _close, _volume, _is_new_bar), do: error()
+  def overlap_bbands_state_init(_period, _nb_dev_up, _nb_dev_dn, _ma_type), do: error()
+  def overlap_bbands_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_cci_state_init(_period), do: error()
+  def momentum_cci_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def momentum_willr_state_init(_period), do: error()
+  def momentum_willr_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def momentum_macd_state_init(_fast_period, _slow_period, _signal_period), do: error()
+  def momentum_macd_state_next(_state, _value, _is_new_bar), do: error()
+  def overlap_ma_state_init(_period, _ma_type), do: error()
+  def overlap_ma_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_apo_state_init(_fast_period, _slow_period, _ma_type), do: error()
+  def momentum_apo_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_ppo_state_init(_fast_period, _slow_period, _ma_type), do: error()
+  def momentum_ppo_state_next(_state, _value, _is_new_bar), do: error()
+  def overlap_supertrend_state_init(_period, _multiplier), do: error()
+  def overlap_supertrend_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def overlap_rma_state_init(_period), do: error()
+  def overlap_rma_state_next(_state, _value, _is_new_bar), do: error()
+  def overlap_zlema_state_init(_period), do: error()
+  def overlap_zlema_state_next(_state, _value, _is_new_bar), do: error()
+  def price_heikin_ashi_state_init(), do: error()
+  def price_heikin_ashi_state_next(_state, _open, _high, _low, _close, _is_new_bar), do: error()
+  def price_typprice_state_init(), do: error()
+  def price_typprice_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def price_medprice_state_init(), do: error()
+  def price_medprice_state_next(_state, _high, _low, _is_new_bar), do: error()
+  def price_wclprice_state_init(), do: error()
+  def price_wclprice_state_next(_state, _high, _low, _close, _is_new_bar), do: error()
+  def stats_median_state_init(_period), do: error()
+  def stats_median_state_next(_state, _value, _is_new_bar), do: error()
+  def momentum_cmo_state_init(_period), do: error()
+  def momentum_cmo_state_next(_state, _value, _is_new_bar), do: error()
+  def overlap_mcginley_state_init(_period), do: error()
+  def overlap_mcginley_state_next(_state, _value, _is_new_bar), do: error()
+
+  # Pipelines of state-based functions (each stage fed with the previous stage output)
+  def pipeline_init(_specs), do: error()
+  def pipeline_next(_state, _value, _is_new_bar), do: error()
+
+  # Diagnostics comparing state-based functions to their batch counterpart
+  def selftest_ema(_data, _period), do: error()
+  def selftest_ma(_data, _period, _ma_type), do: error()
+
+  # Utilities over input series
+  def analyze_gaps(_data), do: error()
+  def fill_forward(_data), do: error()
+  def fill_backward(_data), do: error()
+  def fill_linear(_data), do: error()
+
+  # Backend information
+  def ta_lib_version(), do: error()
+  def has_talib(), do: error()
 
   ## Private functions
 
@@ -0,0 +1,206 @@
+defmodule TheoryCraftTA.Overlap.BBANDS do
+  @moduledoc """
+  Bollinger Bands (BBANDS).
+
+  Bollinger Bands are a volatility envelope around a moving average: the upper and
+  lower bands are placed a number of standard deviations away from the middle band.
+
+  ## Calculation
+
+  Middle = MA(price, period)
+  Upper = Middle + nb_dev_up × StdDev(price, period)
+  Lower = Middle - nb_dev_dn × StdDev(price, period)
+
+  Where:
+  - StdDev is the population standard deviation over the last `period` values
+  - `ma_type` selects the moving average of the middle band, as TA-Lib's `TA_MAType`
+    (0 = SMA, 1 = EMA, 2 = WMA, 3 = DEMA, 4 = TEMA, 5 = TRIMA, 6 = KAMA, 7 = MAMA, 8 = T3)
+
+  The streaming state only supports the SMA middle band (`ma_type: 0`).
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`. Each value is a map with the `:upper`, `:middle`
+  and `:lower` bands, or `nil` during warmup:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.bbands(eurusd_m5[:close], 20, name: "bb20"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type bands :: %{upper: float(), middle: float(), lower: float()}
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          nb_dev_up: float(),
+          nb_dev_dn: float(),
+          ma_type: non_neg_integer(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :nb_dev_up, :nb_dev_dn, :ma_type, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Bollinger Bands (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of periods for the middle band and the deviation (must be >= 2)
+    - `nb_dev_up` - Number of standard deviations for the upper band
+    - `nb_dev_dn` - Number of standard deviations for the lower band
+    - `ma_type` - Moving average of the middle band (`TA_MAType`, 0 = SMA)
+
+  ## Returns
+
+    - `{:ok, {upper, middle, lower}}` where each band is the same type as input
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+      iex> {:ok, {upper, middle, lower}} = TheoryCraftTA.Overlap.BBANDS.bbands(data, 8, 2.0, 2.0, 0)
+      iex> {List.last(upper), List.last(middle), List.last(lower)}
+      {9.0, 5.0, 1.0}
+
+  """
+  @spec bbands(TheoryCraftTA.source(), pos_integer(), float(), float(), non_neg_integer()) ::
+          {:ok, {TheoryCraftTA.source(), TheoryCraftTA.source(), TheoryCraftTA.source()}}
+          | {:error, String.t()}
+  def bbands(data, period, nb_dev_up, nb_dev_dn, ma_type) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.overlap_bbands(list_data, period, nb_dev_up, nb_dev_dn, ma_type) do
+      {:ok, {upper, middle, lower}} ->
+        {:ok,
+         {Helpers.rebuild_same_type(data, upper), Helpers.rebuild_same_type(data, middle),
+          Helpers.rebuild_same_type(data, lower)}}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new BBANDS state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The BBANDS period (must be >= 2)
+    - `:nb_dev_up` (optional) - Standard deviations for the upper band (default: 2.0)
+    - `:nb_dev_dn` (optional) - Standard deviations for the lower band (default: 2.0)
+    - `:ma_type` (optional) - Moving average of the middle band, only SMA is
+      supported (default: 0)
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period or ma_type is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Overlap.BBANDS.init(period: 20, data: "eurusd", name: "bb20", source: :close)
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    nb_dev_up = Keyword.get(opts, :nb_dev_up, 2.0)
+    nb_dev_dn = Keyword.get(opts, :nb_dev_dn, 2.0)
+    ma_type = Keyword.get(opts, :ma_type, 0)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.overlap_bbands_state_init(period, nb_dev_up, nb_dev_dn, ma_type) do
+      {:ok, native_state} ->
+        state = %BBANDS{
+          period: period,
+          nb_dev_up: nb_dev_up,
+          nb_dev_dn: nb_dev_dn,
+          ma_type: ma_type,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next Bollinger Bands.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue whose value is a `t:bands/0`
+    map, or `nil` during warmup
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %BBANDS{} = state) do
+    %BBANDS{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {upper, middle, lower, new_native_state}} =
+      Native.overlap_bbands_state_next(native_state, value, is_new_bar)
+
+    new_state = %BBANDS{state | state: new_native_state}
+
+    bands =
+      case middle do
+        nil -> nil
+        _ -> %{upper: upper, middle: middle, lower: lower}
+      end
+
+    indicator_value = %IndicatorValue{
+      value: bands,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -90,11 +90,14 @@ defmodule TheoryCraftTA.Overlap.DEMA do
     - `:name` (required) - The output name for the indicator
     - `:source` (optional) - The field to extract from bar (default: `:close`).
       Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+    - `:seed_mode` (optional) - How the inner EMAs compute their first value
+      (default: `:sma`): `:sma` averages the first `period` values like ta-lib,
+      `:first` starts from the first value so the output is defined from the first bar.
 
   ## Returns
 
   - `{:ok, state}` - Initialized state
-  - `{:error, message}` - If period is invalid
+  - `{:error, message}` - If period or seed_mode is invalid
 
   ## Examples
 
@@ -106,9 +109,10 @@ defmodule TheoryCraftTA.Overlap.DEMA do
   def init(opts) when is_list(opts) do
     period = Keyword.fetch!(opts, :period)
     source = Keyword.get(opts, :source, :close)
+    seed_mode = Keyword.get(opts, :seed_mode, :sma)
     data_name = Keyword.fetch!(opts, :data)
 
-    case Native.overlap_dema_state_init(period) do
+    case Native.overlap_dema_state_init(period, seed_mode) do
       {:ok, native_state} ->
         state = %DEMA{
           period: period,
@@ -90,11 +90,17 @@ defmodule TheoryCraftTA.Overlap.EMA do
     - `:name` (required) - The output name for the indicator
     - `:source` (optional) - The field to extract from bar (default: `:close`).
       Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+    - `:nan_policy` (optional) - What to do with a missing input (default: `:skip`):
+      `:skip` returns `nil`, `:carry_forward` returns the current value and `:reject`
+      returns an error. The state is left unchanged in all cases.
+    - `:seed_mode` (optional) - How the first EMA value is computed (default: `:sma`):
+      `:sma` averages the first `period` values like ta-lib, `:first` starts from the
+      first value so the output is defined from the first bar.
 
   ## Returns
 
   - `{:ok, state}` - Initialized state
-  - `{:error, message}` - If period is invalid
+  - `{:error, message}` - If period, nan_policy or seed_mode is invalid
 
   ## Examples
 
@@ -106,9 +112,11 @@ defmodule TheoryCraftTA.Overlap.EMA do
   def init(opts) when is_list(opts) do
     period = Keyword.fetch!(opts, :period)
     source = Keyword.get(opts, :source, :close)
+    nan_policy = Keyword.get(opts, :nan_policy, :skip)
+    seed_mode = Keyword.get(opts, :seed_mode, :sma)
     data_name = Keyword.fetch!(opts, :data)
 
-    case Native.overlap_ema_state_init(period) do
+    case Native.overlap_ema_state_init(period, nan_policy, seed_mode) do
       {:ok, native_state} ->
         state = %EMA{
           period: period,
@@ -139,9 +147,10 @@ defmodule TheoryCraftTA.Overlap.EMA do
 
   ## Nil Handling
 
-  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
-  returns `nil` without modifying the state. This matches ta-lib behavior for chained
-  indicators during warmup.
+  If the input value is `nil` (e.g., upstream indicator not yet ready), the state is left
+  unchanged and the result depends on the `:nan_policy` given to `init/1`: `nil` by default,
+  which matches ta-lib behavior for chained indicators during warmup, the current value
+  with `:carry_forward`, or `{:error, message}` with `:reject`.
 
   ## Data Types
 
@@ -151,7 +160,7 @@ defmodule TheoryCraftTA.Overlap.EMA do
 
   """
   @impl true
-  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()} | {:error, String.t()}
   def next(%MarketEvent{} = event, %EMA{} = state) do
     %EMA{
       source: source,
@@ -162,16 +171,98 @@ defmodule TheoryCraftTA.Overlap.EMA do
     value = MarketEvent.extract_value(event, data_name, source)
     is_new_bar = MarketEvent.new_bar?(event, data_name)
 
-    {:ok, {ema_value, new_native_state}} =
-      Native.overlap_ema_state_next(native_state, value, is_new_bar)
+    case Native.overlap_ema_state_next(native_state, value, is_new_bar) do
+      {:ok, {ema_value, new_native_state}} ->
+        new_state = %EMA{state | state: new_native_state}
 
-    new_state = %EMA{state | state: new_native_state}
+        indicator_value = %IndicatorValue{
+          value: ema_value,
+          data_name: data_name
+        }
+
+        {:ok, indicator_value, new_state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Returns the latest EMA value, without processing a new event.
+
+  ## Parameters
+
+  - `state` - The indicator state (from `init/1` or `next/2`)
+
+  ## Returns
+
+  - The EMA of the current bar, or `nil` during warmup
+
+  """
+  @spec value(t()) :: float() | nil
+  def value(%EMA{state: native_state}) do
+    {:ok, ema_value} = Native.overlap_ema_state_value(native_state)
+    ema_value
+  end
+
+  @doc """
+  Returns whether the EMA is past its warmup, i.e. has received `period` bars.
+  """
+  @spec ready?(t()) :: boolean()
+  def ready?(%EMA{state: native_state}) do
+    {:ok, ready} = Native.overlap_ema_state_is_ready(native_state)
+    ready
+  end
+
+  @doc """
+  Computes the EMA the current bar would have if it closed at `value`.
 
-    indicator_value = %IndicatorValue{
-      value: ema_value,
-      data_name: data_name
-    }
+  The calculation is the same as an UPDATE in `next/2`, but the state is not advanced,
+  which makes it suitable for "what-if" scans on a live state.
 
-    {:ok, indicator_value, new_state}
+  ## Parameters
+
+  - `state` - The indicator state (from `init/1` or `next/2`)
+  - `value` - The hypothetical value of the current bar
+
+  ## Returns
+
+  - `{:ok, value}` - The EMA value, `nil` during warmup or if `value` is `nil`
+    (carried forward with `nan_policy: :carry_forward`)
+  - `{:error, message}` - If `value` is `nil` and the state has `nan_policy: :reject`
+
+  """
+  @spec peek(t(), float() | nil) :: {:ok, float() | nil} | {:error, String.t()}
+  def peek(%EMA{state: native_state}, value) do
+    Native.overlap_ema_state_peek(native_state, value)
+  end
+
+  @doc """
+  Reverts the last APPEND, as if its bar had never been received.
+
+  Use this when a committed bar has to be corrected: undo it, then send the corrected
+  bar again in APPEND mode. UPDATEs received since that APPEND are reverted too.
+
+  ## Parameters
+
+  - `state` - The indicator state (from `next/2`)
+
+  ## Returns
+
+  - `{:ok, new_state}` - The state before the last APPEND
+  - `{:error, message}` - If there is no APPEND to revert
+
+  Only the last APPEND is kept, so a state can't be undone twice in a row.
+
+  """
+  @spec undo(t()) :: {:ok, t()} | {:error, String.t()}
+  def undo(%EMA{state: native_state} = state) do
+    case Native.overlap_ema_state_undo(native_state) do
+      {:ok, new_native_state} ->
+        {:ok, %EMA{state | state: new_native_state}}
+
+      {:error, _reason} = error ->
+        error
+    end
   end
 end
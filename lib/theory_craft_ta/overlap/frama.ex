@@ -0,0 +1,60 @@
+defmodule TheoryCraftTA.Overlap.FRAMA do
+  @moduledoc """
+  Fractal Adaptive Moving Average (FRAMA).
+
+  John Ehlers' FRAMA is an EMA whose smoothing follows the fractal dimension of the
+  window: it tracks the price closely in a trend and barely moves in a choppy market.
+
+  ## Calculation
+
+  FRAMA = alpha × Price + (1 - alpha) × FRAMA[1 bar ago]
+
+  Where:
+  - alpha = exp(-4.6 × (D - 1)), clamped to [0.01, 1]
+  - D = (log(N1 + N2) - log(N3)) / log(2), with N1 and N2 the ranges of both halves of
+    the last `period` values divided by `period / 2`, and N3 the range of the whole
+    window divided by `period`
+  - The period must be even, and the first value is emitted on bar index `period - 1`
+
+  FRAMA is a batch calculation only: there is no streaming state for it yet, so it can't
+  be added to a `TheoryCraft.MarketSimulator`.
+
+  """
+
+  alias TheoryCraftTA.{Helpers, Native}
+
+  ## Public API
+
+  @doc """
+  Calculates Fractal Adaptive Moving Average (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of values in the window (must be even and >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with FRAMA values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> TheoryCraftTA.Overlap.FRAMA.frama([1.0, 2.0, 3.0, 4.0], 4)
+      {:ok, [nil, nil, nil, 4.0]}
+
+  """
+  @spec frama(TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def frama(data, period) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.overlap_frama(list_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+end
@@ -0,0 +1,59 @@
+defmodule TheoryCraftTA.Overlap.HMA do
+  @moduledoc """
+  Hull Moving Average (HMA).
+
+  The Hull Moving Average combines weighted moving averages of two lengths to cancel
+  most of the lag of a WMA, then smooths the result over the square root of the period.
+
+  ## Calculation
+
+  HMA = WMA(2 × WMA(price, period / 2) - WMA(price, period), √period)
+
+  Where:
+  - `period / 2` and `√period` are rounded down
+  - The first value is emitted on bar index `(period - 1) + (√period - 1)`
+
+  HMA is a batch calculation only: there is no streaming state for it yet, so it can't
+  be added to a `TheoryCraft.MarketSimulator`.
+
+  """
+
+  alias TheoryCraftTA.{Helpers, Native}
+
+  ## Public API
+
+  @doc """
+  Calculates Hull Moving Average (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of periods for the moving average (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with HMA values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+      iex> {:ok, result} = TheoryCraftTA.Overlap.HMA.hma(data, 4)
+      iex> Enum.map(result, &(&1 && Float.round(&1, 4)))
+      [nil, nil, nil, nil, 5.0, 6.0]
+
+  """
+  @spec hma(TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def hma(data, period) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.overlap_hma(list_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+end
@@ -0,0 +1,192 @@
+defmodule TheoryCraftTA.Overlap.KAMA do
+  @moduledoc """
+  Kaufman Adaptive Moving Average (KAMA).
+
+  The Kaufman Adaptive Moving Average adjusts its smoothing to market noise using an
+  efficiency ratio. It follows prices closely when they trend and flattens out when
+  they move sideways.
+
+  ## Calculation
+
+  ER = |P₁ - Pₙ₊₁| / Σ|Pᵢ - Pᵢ₊₁|
+  SC = (ER × (2/(2+1) - 2/(30+1)) + 2/(30+1))²
+  KAMA = KAMA_prev + SC × (P₁ - KAMA_prev)
+
+  Where:
+  - n = period
+  - P₁ = most recent price
+  - Pₙ₊₁ = price n bars ago
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.kama(eurusd_m5[:close], 10, name: "kama10"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          fast_period: pos_integer(),
+          slow_period: pos_integer(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :fast_period, :slow_period, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Kaufman Adaptive Moving Average (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of periods for the moving average (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with KAMA values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> {:ok, result} = TheoryCraftTA.Overlap.KAMA.kama([1.0, 2.0, 3.0, 4.0, 5.0], 3)
+      iex> Enum.take(result, 3)
+      [nil, nil, nil]
+
+  """
+  @spec kama(TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def kama(data, period) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.overlap_kama(list_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new KAMA state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The KAMA period (must be >= 2)
+    - `:fast_period` (optional) - Period of the fastest smoothing constant (default: 2)
+    - `:slow_period` (optional) - Period of the slowest smoothing constant (default: 30).
+      Must be greater than `:fast_period`.
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period, fast period or slow period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Overlap.KAMA.init(period: 10, data: "eurusd", name: "kama10", source: :close)
+
+      iex> {:ok, _state} = TheoryCraftTA.Overlap.KAMA.init(period: 10, fast_period: 3, slow_period: 20, data: "eurusd", name: "kama10")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    fast_period = Keyword.get(opts, :fast_period, 2)
+    slow_period = Keyword.get(opts, :slow_period, 30)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.overlap_kama_state_init(period, fast_period, slow_period) do
+      {:ok, native_state} ->
+        state = %KAMA{
+          period: period,
+          fast_period: fast_period,
+          slow_period: slow_period,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next KAMA value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with KAMA calculation
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  ## Data Types
+
+  The data extracted from `event.data[data_name]` can be:
+  - A bar/struct with fields like `:close`, `:high`, etc. - uses the `:source` field
+  - A float/nil value directly (e.g., from another indicator) - uses the value as-is
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %KAMA{} = state) do
+    %KAMA{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {kama_value, new_native_state}} =
+      Native.overlap_kama_state_next(native_state, value, is_new_bar)
+
+    new_state = %KAMA{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: kama_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,180 @@
+defmodule TheoryCraftTA.Overlap.MA do
+  @moduledoc """
+  Moving Average (MA).
+
+  A generic moving average whose kind is selected by `ma_type`, so any of the moving
+  averages can be streamed through one uniform indicator.
+
+  ## Calculation
+
+  MA = SMA | EMA | WMA | DEMA | TEMA | TRIMA | KAMA | T3 (price, period)
+
+  Where:
+  - `ma_type` selects the moving average, as TA-Lib's `TA_MAType`
+    (0 = SMA, 1 = EMA, 2 = WMA, 3 = DEMA, 4 = TEMA, 5 = TRIMA, 6 = KAMA, 7 = MAMA, 8 = T3)
+  - Like TA-Lib, KAMA uses its default fast/slow periods (2 and 30) and T3 a volume
+    factor of 0.7
+
+  The streaming state supports every `ma_type` except MAMA (`ma_type: 7`).
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.ma(eurusd_m5[:close], 20, 1, name: "ma20"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          ma_type: non_neg_integer(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :ma_type, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Moving Average (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of periods for the moving average (must be >= 2)
+    - `ma_type` - Moving average to compute (`TA_MAType`, 0 = SMA)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with MA values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> TheoryCraftTA.Overlap.MA.ma([1.0, 2.0, 3.0, 4.0, 5.0], 3, 0)
+      {:ok, [nil, nil, 2.0, 3.0, 4.0]}
+
+  """
+  @spec ma(TheoryCraftTA.source(), pos_integer(), non_neg_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def ma(data, period, ma_type) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.overlap_ma(list_data, period, ma_type) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new MA state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The MA period (must be >= 2)
+    - `:ma_type` (optional) - Moving average to compute, MAMA is not supported
+      (default: 0)
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period or ma_type is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Overlap.MA.init(period: 14, ma_type: 1, data: "eurusd", name: "ma14", source: :close)
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    ma_type = Keyword.get(opts, :ma_type, 0)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.overlap_ma_state_init(period, ma_type) do
+      {:ok, native_state} ->
+        state = %MA{
+          period: period,
+          ma_type: ma_type,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next MA value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with MA calculation
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %MA{} = state) do
+    %MA{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {ma_value, new_native_state}} =
+      Native.overlap_ma_state_next(native_state, value, is_new_bar)
+
+    new_state = %MA{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: ma_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -90,11 +90,14 @@ defmodule TheoryCraftTA.Overlap.MIDPOINT do
     - `:name` (required) - The output name for the indicator
     - `:source` (optional) - The field to extract from bar (default: `:close`).
       Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+    - `:nan_policy` (optional) - What to do with a missing input (default: `:skip`):
+      `:skip` returns `nil`, `:carry_forward` returns the current value and `:reject`
+      returns an error. The state is left unchanged in all cases.
 
   ## Returns
 
   - `{:ok, state}` - Initialized state
-  - `{:error, message}` - If period is invalid
+  - `{:error, message}` - If period or nan_policy is invalid
 
   ## Examples
 
@@ -106,9 +109,10 @@ defmodule TheoryCraftTA.Overlap.MIDPOINT do
   def init(opts) when is_list(opts) do
     period = Keyword.fetch!(opts, :period)
     source = Keyword.get(opts, :source, :close)
+    nan_policy = Keyword.get(opts, :nan_policy, :skip)
     data_name = Keyword.fetch!(opts, :data)
 
-    case Native.overlap_midpoint_state_init(period) do
+    case Native.overlap_midpoint_state_init(period, nan_policy) do
       {:ok, native_state} ->
         state = %MIDPOINT{
           period: period,
@@ -139,9 +143,10 @@ defmodule TheoryCraftTA.Overlap.MIDPOINT do
 
   ## Nil Handling
 
-  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
-  returns `nil` without modifying the state. This matches ta-lib behavior for chained
-  indicators during warmup.
+  If the input value is `nil` (e.g., upstream indicator not yet ready), the state is left
+  unchanged and the result depends on the `:nan_policy` given to `init/1`: `nil` by default,
+  which matches ta-lib behavior for chained indicators during warmup, the current value
+  with `:carry_forward`, or `{:error, message}` with `:reject`.
 
   ## Data Types
 
@@ -151,7 +156,7 @@ defmodule TheoryCraftTA.Overlap.MIDPOINT do
 
   """
   @impl true
-  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()} | {:error, String.t()}
   def next(%MarketEvent{} = event, %MIDPOINT{} = state) do
     %MIDPOINT{
       source: source,
@@ -162,16 +167,19 @@ defmodule TheoryCraftTA.Overlap.MIDPOINT do
     value = MarketEvent.extract_value(event, data_name, source)
     is_new_bar = MarketEvent.new_bar?(event, data_name)
 
-    {:ok, {midpoint_value, new_native_state}} =
-      Native.overlap_midpoint_state_next(native_state, value, is_new_bar)
+    case Native.overlap_midpoint_state_next(native_state, value, is_new_bar) do
+      {:ok, {midpoint_value, new_native_state}} ->
+        new_state = %MIDPOINT{state | state: new_native_state}
 
-    new_state = %MIDPOINT{state | state: new_native_state}
+        indicator_value = %IndicatorValue{
+          value: midpoint_value,
+          data_name: data_name
+        }
 
-    indicator_value = %IndicatorValue{
-      value: midpoint_value,
-      data_name: data_name
-    }
+        {:ok, indicator_value, new_state}
 
-    {:ok, indicator_value, new_state}
+      {:error, _reason} = error ->
+        error
+    end
   end
 end
@@ -0,0 +1,164 @@
+defmodule TheoryCraftTA.Overlap.MIDPRICE do
+  @moduledoc """
+  Midpoint Price over period (MIDPRICE).
+
+  The Midpoint Price is the average of the highest high and the lowest low over a
+  given number of bars. It needs the high and low of each bar.
+
+  ## Calculation
+
+  MIDPRICE = (Highest High + Lowest Low) / 2
+
+  Where:
+  - Highest High / Lowest Low are taken over the last `period` bars
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.midprice(eurusd_m5, 14, name: "midprice14"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Midpoint Price over period (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `period` - Number of bars in the window (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `low` with MIDPRICE values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [12.0, 12.5, 12.2, 13.0, 13.4]
+      iex> low = [11.0, 11.6, 11.4, 12.1, 12.6]
+      iex> TheoryCraftTA.Overlap.MIDPRICE.midprice(high, low, 3)
+      {:ok, [nil, nil, 11.75, 12.2, 12.4]}
+
+  """
+  @spec midprice(TheoryCraftTA.source(), TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def midprice(high, low, period) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+
+    case Native.overlap_midprice(high_data, low_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(low, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new MIDPRICE state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The MIDPRICE period (must be >= 2)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Overlap.MIDPRICE.init(period: 14, data: "eurusd", name: "midprice14")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.overlap_midprice_state_init(period) do
+      {:ok, native_state} ->
+        state = %MIDPRICE{
+          period: period,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next MIDPRICE value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with MIDPRICE calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), its high and low replace the previous
+  ones, so a lower high or a higher low than before is taken into account.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %MIDPRICE{} = state) do
+    %MIDPRICE{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {midprice_value, new_native_state}} =
+      Native.overlap_midprice_state_next(native_state, high, low, is_new_bar)
+
+    new_state = %MIDPRICE{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: midprice_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,181 @@
+defmodule TheoryCraftTA.Overlap.RMA do
+  @moduledoc """
+  Wilder's Moving Average (RMA, also called SMMA).
+
+  Wilder's smoothing is the moving average behind RSI and ATR. It is an exponential
+  moving average with a slower decay, seeded by the SMA of the first `period` values.
+
+  ## Calculation
+
+  RMA = α × P + (1 - α) × RMA_prev, where α = 1/period
+
+  Where:
+  - n = period
+  - P = most recent price
+  - The first RMA is the SMA of the first n prices
+
+  TA-Lib has no RMA, so the batch calculation is implemented in Rust and is
+  available without TA-Lib.
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.rma(eurusd_m5[:close], 20, name: "rma20"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Wilder's Moving Average (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of periods for the moving average (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with RMA values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> {:ok, result} = TheoryCraftTA.Overlap.RMA.rma([1.0, 2.0, 3.0, 4.0, 5.0], 3)
+      iex> Enum.map(result, &(&1 && Float.round(&1, 4)))
+      [nil, nil, 2.0, 2.6667, 3.4444]
+
+  """
+  @spec rma(TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def rma(data, period) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.overlap_rma(list_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new RMA state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The RMA period (must be >= 2)
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Overlap.RMA.init(period: 14, data: "eurusd", name: "rma14", source: :close)
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.overlap_rma_state_init(period) do
+      {:ok, native_state} ->
+        state = %RMA{
+          period: period,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next RMA value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with RMA calculation
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  ## Data Types
+
+  The data extracted from `event.data[data_name]` can be:
+  - A bar/struct with fields like `:close`, `:high`, etc. - uses the `:source` field
+  - A float/nil value directly (e.g., from another indicator) - uses the value as-is
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %RMA{} = state) do
+    %RMA{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {rma_value, new_native_state}} =
+      Native.overlap_rma_state_next(native_state, value, is_new_bar)
+
+    new_state = %RMA{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: rma_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
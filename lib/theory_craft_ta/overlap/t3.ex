@@ -54,6 +54,7 @@ defmodule TheoryCraftTA.Overlap.T3 do
 
     - `data` - Input data (list of floats, DataSeries, or TimeSeries)
     - `period` - Number of periods for the moving average (must be >= 2)
+    - `vfactor` - Volume factor (must be between 0.0 and 1.0)
 
   ## Returns
 
@@ -92,16 +93,19 @@ defmodule TheoryCraftTA.Overlap.T3 do
 
   - `opts` - Keyword list with:
     - `:period` (required) - The T3 period (must be >= 2)
-    - `:vfactor` (required) - Volume factor (typically 0.0 to 1.0)
+    - `:vfactor` (required) - Volume factor (must be between 0.0 and 1.0)
     - `:data` (required) - The name of the data stream to read from
     - `:name` (required) - The output name for the indicator
     - `:source` (optional) - The field to extract from bar (default: `:close`).
       Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+    - `:seed_mode` (optional) - How the inner EMAs compute their first value
+      (default: `:sma`): `:sma` averages the first `period` values like ta-lib,
+      `:first` starts from the first value so the output is defined from the first bar.
 
   ## Returns
 
   - `{:ok, state}` - Initialized state
-  - `{:error, message}` - If period is invalid
+  - `{:error, message}` - If period or seed_mode is invalid
 
   ## Examples
 
@@ -114,9 +118,10 @@ defmodule TheoryCraftTA.Overlap.T3 do
     period = Keyword.fetch!(opts, :period)
     vfactor = Keyword.fetch!(opts, :vfactor)
     source = Keyword.get(opts, :source, :close)
+    seed_mode = Keyword.get(opts, :seed_mode, :sma)
     data_name = Keyword.fetch!(opts, :data)
 
-    case Native.overlap_t3_state_init(period, vfactor) do
+    case Native.overlap_t3_state_init(period, vfactor, seed_mode) do
       {:ok, native_state} ->
         state = %T3{
           period: period,
@@ -0,0 +1,61 @@
+defmodule TheoryCraftTA.Overlap.VWMA do
+  @moduledoc """
+  Volume-Weighted Moving Average (VWMA).
+
+  The VWMA averages the close over a given number of bars, weighting each close by the
+  volume of its bar, so that the bars with the most activity weigh the most.
+
+  ## Calculation
+
+  VWMA = Σ(Close × Volume) / Σ(Volume)
+
+  Where:
+  - Both sums are taken over the last `period` bars
+  - The VWMA is `nil` for a window without volume
+
+  VWMA is a batch calculation only: there is no streaming state for it yet, so it can't
+  be added to a `TheoryCraft.MarketSimulator`.
+
+  """
+
+  alias TheoryCraftTA.{Helpers, Native}
+
+  ## Public API
+
+  @doc """
+  Calculates Volume-Weighted Moving Average (batch calculation).
+
+  ## Parameters
+
+    - `close` - Close prices (list of floats, DataSeries, or TimeSeries)
+    - `volume` - Volumes (same type and length as `close`)
+    - `period` - Number of bars in the window (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `close` with VWMA values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> close = [1.0, 2.0, 4.0, 3.0]
+      iex> volume = [1.0, 3.0, 1.0, 0.0]
+      iex> TheoryCraftTA.Overlap.VWMA.vwma(close, volume, 2)
+      {:ok, [nil, 1.75, 2.5, 4.0]}
+
+  """
+  @spec vwma(TheoryCraftTA.source(), TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def vwma(close, volume, period) do
+    close_data = Helpers.to_list_and_reverse(close)
+    volume_data = Helpers.to_list_and_reverse(volume)
+
+    case Native.overlap_vwma(close_data, volume_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(close, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+end
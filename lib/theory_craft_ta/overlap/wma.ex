@@ -93,11 +93,14 @@ defmodule TheoryCraftTA.Overlap.WMA do
     - `:name` (required) - The output name for the indicator
     - `:source` (optional) - The field to extract from bar (default: `:close`).
       Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+    - `:nan_policy` (optional) - What to do with a missing input (default: `:skip`):
+      `:skip` returns `nil`, `:carry_forward` returns the current value and `:reject`
+      returns an error. The state is left unchanged in all cases.
 
   ## Returns
 
   - `{:ok, state}` - Initialized state
-  - `{:error, message}` - If period is invalid
+  - `{:error, message}` - If period or nan_policy is invalid
 
   ## Examples
 
@@ -109,9 +112,10 @@ defmodule TheoryCraftTA.Overlap.WMA do
   def init(opts) when is_list(opts) do
     period = Keyword.fetch!(opts, :period)
     source = Keyword.get(opts, :source, :close)
+    nan_policy = Keyword.get(opts, :nan_policy, :skip)
     data_name = Keyword.fetch!(opts, :data)
 
-    case Native.overlap_wma_state_init(period) do
+    case Native.overlap_wma_state_init(period, nan_policy) do
       {:ok, native_state} ->
         state = %WMA{
           period: period,
@@ -142,9 +146,10 @@ defmodule TheoryCraftTA.Overlap.WMA do
 
   ## Nil Handling
 
-  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
-  returns `nil` without modifying the state. This matches ta-lib behavior for chained
-  indicators during warmup.
+  If the input value is `nil` (e.g., upstream indicator not yet ready), the state is left
+  unchanged and the result depends on the `:nan_policy` given to `init/1`: `nil` by default,
+  which matches ta-lib behavior for chained indicators during warmup, the current value
+  with `:carry_forward`, or `{:error, message}` with `:reject`.
 
   ## Data Types
 
@@ -154,7 +159,7 @@ defmodule TheoryCraftTA.Overlap.WMA do
 
   """
   @impl true
-  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()} | {:error, String.t()}
   def next(%MarketEvent{} = event, %WMA{} = state) do
     %WMA{
       source: source,
@@ -165,16 +170,19 @@ defmodule TheoryCraftTA.Overlap.WMA do
     value = MarketEvent.extract_value(event, data_name, source)
     is_new_bar = MarketEvent.new_bar?(event, data_name)
 
-    {:ok, {wma_value, new_native_state}} =
-      Native.overlap_wma_state_next(native_state, value, is_new_bar)
+    case Native.overlap_wma_state_next(native_state, value, is_new_bar) do
+      {:ok, {wma_value, new_native_state}} ->
+        new_state = %WMA{state | state: new_native_state}
 
-    new_state = %WMA{state | state: new_native_state}
+        indicator_value = %IndicatorValue{
+          value: wma_value,
+          data_name: data_name
+        }
 
-    indicator_value = %IndicatorValue{
-      value: wma_value,
-      data_name: data_name
-    }
+        {:ok, indicator_value, new_state}
 
-    {:ok, indicator_value, new_state}
+      {:error, _reason} = error ->
+        error
+    end
   end
 end
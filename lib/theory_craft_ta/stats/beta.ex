@@ -0,0 +1,193 @@
+defmodule TheoryCraftTA.Stats.BETA do
+  @moduledoc """
+  Beta (BETA).
+
+  The sensitivity of the returns of `y` to the returns of `x`: a beta of 2 means `y`
+  moves twice as much as `x`, in percent.
+
+  ## Calculation
+
+  BETA = Cov(rx, ry) / Var(rx)
+
+  Where:
+  - rx and ry are the 1-bar returns of `x` and `y`, so both must be positive
+  - The covariance and variance are taken over the last `period` returns
+  - The first value is emitted on bar index `period`
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`. It reads `x` from the `:data` stream and `y`
+  from the `:other` one:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(eurusd_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_data(gbpusd_stream, name: "gbpusd_m5")
+        |> MarketSimulator.add_indicator(
+          TA.beta(eurusd_m5[:close], gbpusd_m5[:close], 20, name: "beta20")
+        )
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          source: atom(),
+          other_source: atom(),
+          data_name: String.t(),
+          other_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :source, :other_source, :data_name, :other_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Beta (batch calculation).
+
+  ## Parameters
+
+    - `x` - First series (list of floats, DataSeries, or TimeSeries)
+    - `y` - Second series (same type and length as `x`)
+    - `period` - Number of bars in the window (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `x` with BETA values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> x = [100.0, 110.0, 99.0, 108.9]
+      iex> y = [50.0, 60.0, 48.0, 57.6]
+      iex> {:ok, [nil, nil | values]} = TheoryCraftTA.Stats.BETA.beta(x, y, 2)
+      iex> Enum.map(values, &Float.round(&1, 4))
+      [2.0, 2.0]
+
+  """
+  @spec beta(TheoryCraftTA.source(), TheoryCraftTA.source(), pos_integer()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def beta(x, y, period) do
+    x_data = Helpers.to_list_and_reverse(x)
+    y_data = Helpers.to_list_and_reverse(y)
+
+    case Native.stats_beta(x_data, y_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(x, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new BETA state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The BETA period (must be >= 2)
+    - `:data` (required) - The name of the data stream to read `x` from
+    - `:other` (required) - The name of the data stream to read `y` from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from the `:data` bar (default: `:close`)
+    - `:other_source` (optional) - The field to extract from the `:other` bar
+      (default: `:close`)
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Stats.BETA.init(period: 20, data: "eurusd", other: "gbpusd", name: "beta20")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    source = Keyword.get(opts, :source, :close)
+    other_source = Keyword.get(opts, :other_source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+    other_name = Keyword.fetch!(opts, :other)
+
+    case Native.stats_beta_state_init(period) do
+      {:ok, native_state} ->
+        state = %BETA{
+          period: period,
+          source: source,
+          other_source: other_source,
+          data_name: data_name,
+          other_name: other_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next BETA value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with BETA calculation
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If `x` or `y` is `nil` (e.g., one stream has no bar yet), this function returns `nil`
+  without modifying the state. The value is also `nil` when a variance of the window is
+  zero, where the batch calculation returns 0 like TA-Lib.
+
+  The bar is a new one when the `:data` stream says so.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %BETA{} = state) do
+    %BETA{
+      source: source,
+      other_source: other_source,
+      data_name: data_name,
+      other_name: other_name,
+      state: native_state
+    } = state
+
+    x = MarketEvent.extract_value(event, data_name, source)
+    y = MarketEvent.extract_value(event, other_name, other_source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {beta_value, new_native_state}} =
+      Native.stats_beta_state_next(native_state, x, y, is_new_bar)
+
+    new_state = %BETA{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: beta_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,194 @@
+defmodule TheoryCraftTA.Stats.STDDEV do
+  @moduledoc """
+  Standard Deviation (STDDEV).
+
+  The Standard Deviation measures the dispersion of values around their mean over a
+  rolling window. It is commonly used as a volatility measure (e.g. Bollinger Bands).
+
+  ## Calculation
+
+  STDDEV = nbdev × √(Σ(P²) / n - (Σ(P) / n)²)
+
+  Where:
+  - n = period
+  - nbdev = multiplier applied to the (population) standard deviation
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.stddev(eurusd_m5[:close], 20, 1.0, name: "stddev20"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          nbdev: float(),
+          source: atom(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :nbdev, :source, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Standard Deviation (batch calculation).
+
+  ## Parameters
+
+    - `data` - Input data (list of floats, DataSeries, or TimeSeries)
+    - `period` - Number of values in the rolling window (must be >= 2)
+    - `nbdev` - Multiplier applied to the standard deviation (e.g. 1.0)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as input with STDDEV values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+      iex> TheoryCraftTA.Stats.STDDEV.stddev(data, 8, 1.0)
+      {:ok, [nil, nil, nil, nil, nil, nil, nil, 2.0]}
+
+  """
+  @spec stddev(TheoryCraftTA.source(), pos_integer(), float()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def stddev(data, period, nbdev) do
+    list_data = Helpers.to_list_and_reverse(data)
+
+    case Native.stats_stddev(list_data, period, nbdev) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(data, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new STDDEV state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The STDDEV period (must be >= 2)
+    - `:nbdev` (required) - Multiplier applied to the standard deviation (e.g. 1.0)
+    - `:data` (required) - The name of the data stream to read from
+    - `:name` (required) - The output name for the indicator
+    - `:source` (optional) - The field to extract from bar (default: `:close`).
+      Only used if the data is a bar/struct. If the data is a float/nil, this is ignored.
+    - `:recompute_interval` (optional) - Number of bars between two rebuilds of the running
+      sums from the window (default: the native `DEFAULT_RECOMPUTE_INTERVAL`). A smaller
+      interval trades speed for accuracy on long streams.
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period or recompute_interval is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Stats.STDDEV.init(period: 14, nbdev: 1.0, data: "eurusd", name: "stddev14", source: :close)
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    nbdev = Keyword.fetch!(opts, :nbdev)
+    source = Keyword.get(opts, :source, :close)
+    data_name = Keyword.fetch!(opts, :data)
+
+    native_result =
+      case Keyword.fetch(opts, :recompute_interval) do
+        {:ok, recompute_interval} ->
+          Native.stats_stddev_state_init(period, nbdev, recompute_interval)
+
+        :error ->
+          Native.stats_stddev_state_init(period, nbdev)
+      end
+
+    case native_result do
+      {:ok, native_state} ->
+        state = %STDDEV{
+          period: period,
+          nbdev: nbdev,
+          source: source,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next STDDEV value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with STDDEV calculation
+  - `{:error, message}` on error
+
+  ## Nil Handling
+
+  If the input value is `nil` (e.g., upstream indicator not yet ready), this function
+  returns `nil` without modifying the state. This matches ta-lib behavior for chained
+  indicators during warmup.
+
+  ## Data Types
+
+  The data extracted from `event.data[data_name]` can be:
+  - A bar/struct with fields like `:close`, `:high`, etc. - uses the `:source` field
+  - A float/nil value directly (e.g., from another indicator) - uses the value as-is
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %STDDEV{} = state) do
+    %STDDEV{
+      source: source,
+      data_name: data_name,
+      state: native_state
+    } = state
+
+    value = MarketEvent.extract_value(event, data_name, source)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {stddev_value, new_native_state}} =
+      Native.stats_stddev_state_next(native_state, value, is_new_bar)
+
+    new_state = %STDDEV{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: stddev_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
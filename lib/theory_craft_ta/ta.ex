@@ -226,6 +226,956 @@ defmodule TheoryCraftTA.TA do
     end
   end
 
+  @doc """
+  Kaufman Adaptive Moving Average (KAMA).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of periods for the efficiency ratio
+  - `opts` - Additional options (e.g., `name: "kama10"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro kama(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Overlap.KAMA, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Midpoint Price over period (MIDPRICE).
+
+  Reads the high and low of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of bars in the window
+  - `opts` - Additional options (e.g., `name: "midprice14"`)
+
+  """
+  defmacro midprice(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Overlap.MIDPRICE, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Bollinger Bands (BBANDS).
+
+  The deviations and the middle band type are passed as options
+  (`:nb_dev_up`, `:nb_dev_dn` and `:ma_type`, see `TheoryCraftTA.Overlap.BBANDS.init/1`).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of periods for the middle band and the deviation
+  - `opts` - Additional options (e.g., `name: "bb20"`, `nb_dev_up: 2.0`)
+
+  """
+  defmacro bbands(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Overlap.BBANDS, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Moving Average (MA), of the kind selected by `ma_type`.
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of periods for the moving average
+  - `ma_type` - Moving average to compute (`TA_MAType`, 0 = SMA)
+  - `opts` - Additional options (e.g., `name: "ma20"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro ma(data_or_accessor, period, ma_type, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, ma_type: ma_type, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Overlap.MA, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Wilder's Moving Average (RMA) indicator.
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of periods for the moving average
+  - `opts` - Additional options (e.g., `name: "rma14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro rma(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Overlap.RMA, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Zero-Lag Exponential Moving Average (ZLEMA).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of periods for the EMA
+  - `opts` - Additional options (e.g., `name: "zlema14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro zlema(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Overlap.ZLEMA, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  McGinley Dynamic.
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of periods for the smoothing
+  - `opts` - Additional options (e.g., `name: "mcginley14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro mcginley(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Overlap.McGinley, unquote(keyword_list)}
+    end
+  end
+
+  ## Momentum indicators
+
+  @doc """
+  Relative Strength Index (RSI).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of periods for Wilder's smoothing
+  - `opts` - Additional options (e.g., `name: "rsi14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro rsi(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.RSI, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Momentum (MOM).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of bars to look back
+  - `opts` - Additional options (e.g., `name: "mom10"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro mom(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.MOM, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Rate of Change (ROC).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of bars to look back
+  - `opts` - Additional options (e.g., `name: "roc10"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro roc(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.ROC, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Commodity Channel Index (CCI).
+
+  Reads the high, low and close of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of bars for the average and the mean deviation
+  - `opts` - Additional options (e.g., `name: "cci14"`)
+
+  """
+  defmacro cci(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.CCI, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Williams' %R (WILLR).
+
+  Reads the high, low and close of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of bars in the window
+  - `opts` - Additional options (e.g., `name: "willr14"`)
+
+  """
+  defmacro willr(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.WILLR, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Moving Average Convergence/Divergence (MACD).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `fast_period` - Number of periods for the fast EMA
+  - `slow_period` - Number of periods for the slow EMA
+  - `signal_period` - Number of periods for the signal EMA
+  - `opts` - Additional options (e.g., `name: "macd"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro macd(data_or_accessor, fast_period, slow_period, signal_period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [
+      fast_period: fast_period,
+      slow_period: slow_period,
+      signal_period: signal_period,
+      data: data
+    ]
+
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.MACD, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Absolute Price Oscillator (APO).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `fast_period` - Number of periods for the fast MA
+  - `slow_period` - Number of periods for the slow MA
+  - `ma_type` - Type of the moving averages (see `TheoryCraftTA.Momentum.APO`)
+  - `opts` - Additional options (e.g., `name: "apo"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro apo(data_or_accessor, fast_period, slow_period, ma_type, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [
+      fast_period: fast_period,
+      slow_period: slow_period,
+      ma_type: ma_type,
+      data: data
+    ]
+
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.APO, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Percentage Price Oscillator (PPO).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `fast_period` - Number of periods for the fast MA
+  - `slow_period` - Number of periods for the slow MA
+  - `ma_type` - Type of the moving averages (see `TheoryCraftTA.Momentum.PPO`)
+  - `opts` - Additional options (e.g., `name: "ppo"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro ppo(data_or_accessor, fast_period, slow_period, ma_type, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [
+      fast_period: fast_period,
+      slow_period: slow_period,
+      ma_type: ma_type,
+      data: data
+    ]
+
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.PPO, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Triple Exponential Average (TRIX).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of periods for each of the three EMAs
+  - `opts` - Additional options (e.g., `name: "trix14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro trix(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.TRIX, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Chande Momentum Oscillator (CMO).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of price changes to sum
+  - `opts` - Additional options (e.g., `name: "cmo14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro cmo(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.CMO, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Average Directional Movement Index (ADX).
+
+  Reads the high, low and close of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of periods for the smoothing
+  - `opts` - Additional options (e.g., `name: "adx14"`)
+
+  """
+  defmacro adx(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.ADX, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Directional Movement Index (DX).
+
+  Reads the high, low and close of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of periods for the smoothing
+  - `opts` - Additional options (e.g., `name: "dx14"`)
+
+  """
+  defmacro dx(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.DX, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Rate of Change Percentage (ROCP).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of bars to look back
+  - `opts` - Additional options (e.g., `name: "rocp10"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro rocp(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.ROCP, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Rate of Change Ratio (ROCR).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of bars to look back
+  - `opts` - Additional options (e.g., `name: "rocr10"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro rocr(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.ROCR, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Rate of Change Ratio 100 scale (ROCR100).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of bars to look back
+  - `opts` - Additional options (e.g., `name: "rocr100_10"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro rocr100(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.ROCR100, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Ultimate Oscillator (ULTOSC).
+
+  Reads the high, low and close of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period1` - Number of bars of the first window
+  - `period2` - Number of bars of the second window
+  - `period3` - Number of bars of the third window
+  - `opts` - Additional options (e.g., `name: "ultosc"`)
+
+  """
+  defmacro ultosc(data_or_accessor, period1, period2, period3, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period1: period1, period2: period2, period3: period3, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.ULTOSC, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Aroon (AROON).
+
+  Reads the high and low of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of bars to look back
+  - `opts` - Additional options (e.g., `name: "aroon14"`)
+
+  """
+  defmacro aroon(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Momentum.AROON, unquote(keyword_list)}
+    end
+  end
+
+  ## Volatility indicators
+
+  @doc """
+  Average True Range (ATR).
+
+  Reads the high, low and close of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of periods for Wilder's smoothing
+  - `opts` - Additional options (e.g., `name: "atr14"`)
+
+  """
+  defmacro atr(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Volatility.ATR, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  True Range (TRANGE).
+
+  Reads the high, low and close of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `opts` - Additional options (e.g., `name: "trange"`)
+
+  """
+  defmacro trange(data_or_accessor, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Volatility.TRANGE, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Normalized Average True Range (NATR).
+
+  Reads the high, low and close of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of periods for Wilder's smoothing
+  - `opts` - Additional options (e.g., `name: "natr14"`)
+
+  """
+  defmacro natr(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Volatility.NATR, unquote(keyword_list)}
+    end
+  end
+
+  ## Statistic indicators
+
+  @doc """
+  Standard Deviation (STDDEV).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of values in the rolling window
+  - `nbdev` - Multiplier applied to the standard deviation
+  - `opts` - Additional options (e.g., `name: "stddev20"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro stddev(data_or_accessor, period, nbdev, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, nbdev: nbdev, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Stats.STDDEV, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Variance (VAR).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of values in the rolling window
+  - `nbdev` - Accepted for parity with TA-Lib, not applied
+  - `opts` - Additional options (e.g., `name: "var20"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro var(data_or_accessor, period, nbdev, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, nbdev: nbdev, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Stats.VAR, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Rolling Median.
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of bars in the window
+  - `opts` - Additional options (e.g., `name: "median14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro median(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Stats.Median, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Linear Regression (LINEARREG).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of values in the fitted window
+  - `opts` - Additional options (e.g., `name: "linearreg14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro linearreg(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Stats.LINEARREG, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Time Series Forecast (TSF).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of values in the fitted window
+  - `opts` - Additional options (e.g., `name: "tsf14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro tsf(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Stats.TSF, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Pearson's Correlation Coefficient (CORREL).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source of `x` (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `other_or_accessor` - Data source of `y` (e.g., `gbpusd[:close]` or `"gbpusd"`)
+  - `period` - Number of bars in the window
+  - `opts` - Additional options (e.g., `name: "correl20"`)
+
+  """
+  defmacro correl(data_or_accessor, other_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+    {other, other_source} = parse_data_accessor(other_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    base_opts = base_opts ++ [other: other]
+    base_opts = if other_source, do: base_opts ++ [other_source: other_source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Stats.CORREL, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Beta (BETA).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source of `x` (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `other_or_accessor` - Data source of `y` (e.g., `gbpusd[:close]` or `"gbpusd"`)
+  - `period` - Number of bars in the window
+  - `opts` - Additional options (e.g., `name: "beta20"`)
+
+  """
+  defmacro beta(data_or_accessor, other_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+    {other, other_source} = parse_data_accessor(other_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    base_opts = base_opts ++ [other: other]
+    base_opts = if other_source, do: base_opts ++ [other_source: other_source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Stats.BETA, unquote(keyword_list)}
+    end
+  end
+
+  ## Math indicators
+
+  @doc """
+  Highest Value (MAX).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of values in the window
+  - `opts` - Additional options (e.g., `name: "max14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro max(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Math.MAX, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Lowest Value (MIN).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of values in the window
+  - `opts` - Additional options (e.g., `name: "min14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro min(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Math.MIN, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Summation (SUM).
+
+  ## Parameters
+
+  - `data_or_accessor` - Data source (e.g., `eurusd[:close]` or `"eurusd"`)
+  - `period` - Number of values in the window
+  - `opts` - Additional options (e.g., `name: "sum14"`, `bar_name: "eurusd_m1"`)
+
+  """
+  defmacro sum(data_or_accessor, period, opts \\ []) do
+    {data, source} = parse_data_accessor(data_or_accessor)
+
+    base_opts = [period: period, data: data]
+    base_opts = if source, do: base_opts ++ [source: source], else: base_opts
+    keyword_list = base_opts ++ opts
+
+    quote do
+      {TheoryCraftTA.Math.SUM, unquote(keyword_list)}
+    end
+  end
+
+  ## Volume indicators
+
+  @doc """
+  On Balance Volume (OBV).
+
+  Reads the close and volume of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `opts` - Additional options (e.g., `name: "obv"`)
+
+  """
+  defmacro obv(data_or_accessor, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Volume.OBV, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Chaikin A/D Line (AD).
+
+  Reads the high, low, close and volume of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `opts` - Additional options (e.g., `name: "ad"`)
+
+  """
+  defmacro ad(data_or_accessor, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Volume.AD, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Chaikin A/D Oscillator (ADOSC).
+
+  Reads the high, low, close and volume of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `fast_period` - Period of the fast EMA of the A/D line
+  - `slow_period` - Period of the slow EMA of the A/D line
+  - `opts` - Additional options (e.g., `name: "adosc"`)
+
+  """
+  defmacro adosc(data_or_accessor, fast_period, slow_period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [fast_period: fast_period, slow_period: slow_period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Volume.ADOSC, unquote(keyword_list)}
+    end
+  end
+
+  @doc """
+  Money Flow Index (MFI).
+
+  Reads the high, low, close and volume of the bar stream, so no source field is needed.
+
+  ## Parameters
+
+  - `data_or_accessor` - Bar stream (e.g., `eurusd` or `"eurusd"`)
+  - `period` - Number of bars to sum the money flows over
+  - `opts` - Additional options (e.g., `name: "mfi14"`)
+
+  """
+  defmacro mfi(data_or_accessor, period, opts \\ []) do
+    {data, _source} = parse_data_accessor(data_or_accessor)
+
+    keyword_list = [period: period, data: data] ++ opts
+
+    quote do
+      {TheoryCraftTA.Volume.MFI, unquote(keyword_list)}
+    end
+  end
+
   ## Private functions
 
   defp parse_data_accessor({{:., _, [Access, :get]}, _, [var, source]}) do
@@ -0,0 +1,175 @@
+defmodule TheoryCraftTA.Volatility.ATR do
+  @moduledoc """
+  Average True Range (ATR).
+
+  The Average True Range measures market volatility by smoothing the True Range of
+  each bar with Wilder's method. It needs the high, low and close of each bar.
+
+  ## Calculation
+
+  TR = max(High - Low, |High - Close_prev|, |Low - Close_prev|)
+
+  ATR = (ATR_prev × (period - 1) + TR) / period
+
+  Where:
+  - The first ATR is the simple average of the first `period` true ranges
+  - The first bar has no previous close, so the first value is emitted on bar index `period`
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.atr(eurusd_m5, 14, name: "atr14"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates Average True Range (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+    - `period` - Number of periods for Wilder's smoothing (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `close` with ATR values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [12.0, 12.5, 12.2, 13.0, 13.4]
+      iex> low = [11.0, 11.6, 11.4, 12.1, 12.6]
+      iex> close = [11.5, 12.3, 11.9, 12.8, 13.2]
+      iex> TheoryCraftTA.Volatility.ATR.atr(high, low, close, 3)
+      {:ok, [nil, nil, nil, 1.0, 0.9333333333333336]}
+
+  """
+  @spec atr(
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          pos_integer()
+        ) :: {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def atr(high, low, close, period) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+
+    case Native.volatility_atr(high_data, low_data, close_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(close, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new ATR state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The ATR period (must be >= 2)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Volatility.ATR.init(period: 14, data: "eurusd", name: "atr14")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.volatility_atr_state_init(period) do
+      {:ok, native_state} ->
+        state = %ATR{
+          period: period,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next ATR value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with ATR calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), the True Range is recomputed from the
+  close of the previous (closed) bar, not from the close of the bar being updated.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %ATR{} = state) do
+    %ATR{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {atr_value, new_native_state}} =
+      Native.volatility_atr_state_next(native_state, high, low, close, is_new_bar)
+
+    new_state = %ATR{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: atr_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
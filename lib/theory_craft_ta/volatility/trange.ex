@@ -0,0 +1,162 @@
+defmodule TheoryCraftTA.Volatility.TRANGE do
+  @moduledoc """
+  True Range (TRANGE).
+
+  The True Range is the greatest of the bar's range and the gaps from the previous
+  close. It is the raw, unsmoothed input of the Average True Range (ATR).
+
+  ## Calculation
+
+  TR = max(High - Low, |High - Close_prev|, |Low - Close_prev|)
+
+  The first bar has no previous close, so the first value is emitted on bar index 1.
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.trange(eurusd_m5, name: "trange"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates True Range (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `close` with TRANGE values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [10.0, 12.0, 11.0]
+      iex> low = [8.0, 9.0, 9.5]
+      iex> close = [9.0, 11.0, 10.0]
+      iex> TheoryCraftTA.Volatility.TRANGE.trange(high, low, close)
+      {:ok, [nil, 3.0, 1.5]}
+
+  """
+  @spec trange(TheoryCraftTA.source(), TheoryCraftTA.source(), TheoryCraftTA.source()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def trange(high, low, close) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+
+    case Native.volatility_trange(high_data, low_data, close_data) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(close, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new TRANGE state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Volatility.TRANGE.init(data: "eurusd", name: "trange")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.volatility_trange_state_init() do
+      {:ok, native_state} ->
+        state = %TRANGE{
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next TRANGE value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with TRANGE calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), the True Range is recomputed from the
+  close of the previous (closed) bar, not from the close of the bar being updated.
+  The first bar always yields `nil` as it has no previous close.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %TRANGE{} = state) do
+    %TRANGE{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {trange_value, new_native_state}} =
+      Native.volatility_trange_state_next(native_state, high, low, close, is_new_bar)
+
+    new_state = %TRANGE{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: trange_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,173 @@
+defmodule TheoryCraftTA.Volume.AD do
+  @moduledoc """
+  Chaikin Accumulation/Distribution Line (AD).
+
+  The Accumulation/Distribution Line is a cumulative total of the volume, weighted by
+  where the close lands in the range of the bar. It needs the high, low, close and
+  volume of each bar.
+
+  ## Calculation
+
+  CLV = ((Close - Low) - (High - Close)) / (High - Low)
+  AD = AD_prev + CLV × Volume
+
+  Where:
+  - A bar with no range (High == Low) leaves the line unchanged
+  - The first AD is the money flow of the first bar, so there is no warmup
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.ad(eurusd_m5, name: "ad"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates the Chaikin A/D Line (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+    - `volume` - Volumes (same type and length as `high`)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `high` with AD values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [10.0, 11.0, 12.0]
+      iex> low = [8.0, 9.0, 10.0]
+      iex> close = [9.0, 11.0, 10.0]
+      iex> volume = [100.0, 200.0, 300.0]
+      iex> TheoryCraftTA.Volume.AD.ad(high, low, close, volume)
+      {:ok, [0.0, 200.0, -100.0]}
+
+  """
+  @spec ad(
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source()
+        ) :: {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def ad(high, low, close, volume) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+    volume_data = Helpers.to_list_and_reverse(volume)
+
+    case Native.volume_ad(high_data, low_data, close_data, volume_data) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(high, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new AD state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Volume.AD.init(data: "eurusd", name: "ad")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.volume_ad_state_init() do
+      {:ok, native_state} ->
+        state = %AD{
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next AD value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with AD calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), the total up to the previous (closed)
+  bar is kept and only the contribution of the bar being updated is recomputed.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %AD{} = state) do
+    %AD{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    volume = MarketEvent.extract_value(event, data_name, :volume)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {ad_value, new_native_state}} =
+      Native.volume_ad_state_next(native_state, high, low, close, volume, is_new_bar)
+
+    new_state = %AD{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: ad_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,191 @@
+defmodule TheoryCraftTA.Volume.ADOSC do
+  @moduledoc """
+  Chaikin A/D Oscillator (ADOSC).
+
+  The Chaikin Oscillator is the difference between a fast and a slow EMA of the
+  Accumulation/Distribution Line (see `TheoryCraftTA.Volume.AD`). It needs the high,
+  low, close and volume of each bar.
+
+  ## Calculation
+
+  ADOSC = EMA(AD, fast_period) - EMA(AD, slow_period)
+
+  Where:
+  - Both EMAs are seeded with the first AD value, as ta-lib does
+  - The first value is emitted once the slowest EMA has seen `max(fast, slow)` bars
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.adosc(eurusd_m5, 3, 10, name: "adosc"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          fast_period: pos_integer(),
+          slow_period: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:fast_period, :slow_period, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates the Chaikin A/D Oscillator (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+    - `volume` - Volumes (same type and length as `high`)
+    - `fast_period` - Period of the fast EMA (must be >= 2)
+    - `slow_period` - Period of the slow EMA (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `high` with ADOSC values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [10.0, 11.0, 12.0, 13.0]
+      iex> low = [8.0, 9.0, 10.0, 11.0]
+      iex> close = [9.0, 11.0, 10.0, 13.0]
+      iex> volume = [100.0, 200.0, 300.0, 100.0]
+      iex> {:ok, [nil, nil, _, _]} = TheoryCraftTA.Volume.ADOSC.adosc(high, low, close, volume, 2, 3)
+
+  """
+  @spec adosc(
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          pos_integer(),
+          pos_integer()
+        ) :: {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def adosc(high, low, close, volume, fast_period, slow_period) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+    volume_data = Helpers.to_list_and_reverse(volume)
+
+    case Native.volume_adosc(
+           high_data,
+           low_data,
+           close_data,
+           volume_data,
+           fast_period,
+           slow_period
+         ) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(high, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new ADOSC state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:fast_period` (required) - Period of the fast EMA (must be >= 2)
+    - `:slow_period` (required) - Period of the slow EMA (must be >= 2)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If a period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Volume.ADOSC.init(fast_period: 3, slow_period: 10, data: "eurusd", name: "adosc")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()} | {:error, String.t()}
+  def init(opts) when is_list(opts) do
+    fast_period = Keyword.fetch!(opts, :fast_period)
+    slow_period = Keyword.fetch!(opts, :slow_period)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.volume_adosc_state_init(fast_period, slow_period) do
+      {:ok, native_state} ->
+        state = %ADOSC{
+          fast_period: fast_period,
+          slow_period: slow_period,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next ADOSC value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with ADOSC calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), the A/D total and both EMAs up to the
+  previous (closed) bar are kept and only the bar being updated is recomputed.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %ADOSC{} = state) do
+    %ADOSC{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    volume = MarketEvent.extract_value(event, data_name, :volume)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {adosc_value, new_native_state}} =
+      Native.volume_adosc_state_next(native_state, high, low, close, volume, is_new_bar)
+
+    new_state = %ADOSC{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: adosc_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
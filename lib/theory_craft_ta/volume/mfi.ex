@@ -0,0 +1,185 @@
+defmodule TheoryCraftTA.Volume.MFI do
+  @moduledoc """
+  Money Flow Index (MFI).
+
+  The Money Flow Index is a volume-weighted RSI: it compares the money flowing in on
+  rising bars with the money flowing out on falling bars. It needs the high, low, close
+  and volume of each bar.
+
+  ## Calculation
+
+  Typical Price = (high + low + close) / 3
+  Money Flow = Typical Price × volume
+
+  MFI = 100 × Positive Flow / (Positive Flow + Negative Flow)
+
+  Where:
+  - A bar's flow is positive when its typical price is above the previous one, negative
+    when it is below, and ignored when it is unchanged
+  - Both flows are summed over the last `period` bars
+  - The first value is emitted on bar index `period`
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.mfi(eurusd_m5, 14, name: "mfi14"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          period: pos_integer(),
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:period, :data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates the Money Flow Index (batch calculation).
+
+  ## Parameters
+
+    - `high` - High prices (list of floats, DataSeries, or TimeSeries)
+    - `low` - Low prices (same type and length as `high`)
+    - `close` - Close prices (same type and length as `high`)
+    - `volume` - Volumes (same type and length as `high`)
+    - `period` - Number of bars to sum the money flows over (must be >= 2)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `high` with MFI values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> high = [10.0, 11.0, 12.0, 11.0, 13.0]
+      iex> low = [8.0, 9.0, 10.0, 9.0, 11.0]
+      iex> close = [9.0, 10.0, 11.0, 10.0, 12.0]
+      iex> volume = [100.0, 200.0, 150.0, 300.0, 100.0]
+      iex> {:ok, result} = TheoryCraftTA.Volume.MFI.mfi(high, low, close, volume, 2)
+      iex> Enum.map(result, &(&1 && Float.round(&1, 4)))
+      [nil, nil, 100.0, 35.4839, 28.5714]
+
+  """
+  @spec mfi(
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          TheoryCraftTA.source(),
+          pos_integer()
+        ) :: {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def mfi(high, low, close, volume, period) do
+    high_data = Helpers.to_list_and_reverse(high)
+    low_data = Helpers.to_list_and_reverse(low)
+    close_data = Helpers.to_list_and_reverse(close)
+    volume_data = Helpers.to_list_and_reverse(volume)
+
+    case Native.volume_mfi(high_data, low_data, close_data, volume_data, period) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(high, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new MFI state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:period` (required) - The MFI period (must be >= 2)
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+  - `{:error, message}` - If period is invalid
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Volume.MFI.init(period: 14, data: "eurusd", name: "mfi14")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()} | {:error, String.t()}
+  def init(opts) when is_list(opts) do
+    period = Keyword.fetch!(opts, :period)
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.volume_mfi_state_init(period) do
+      {:ok, native_state} ->
+        state = %MFI{
+          period: period,
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next MFI value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with MFI calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), the typical price of the previous (closed)
+  bar is kept and only the money flow of the bar being updated is recomputed.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %MFI{} = state) do
+    %MFI{data_name: data_name, state: native_state} = state
+
+    high = MarketEvent.extract_value(event, data_name, :high)
+    low = MarketEvent.extract_value(event, data_name, :low)
+    close = MarketEvent.extract_value(event, data_name, :close)
+    volume = MarketEvent.extract_value(event, data_name, :volume)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {mfi_value, new_native_state}} =
+      Native.volume_mfi_state_next(native_state, high, low, close, volume, is_new_bar)
+
+    new_state = %MFI{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: mfi_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
@@ -0,0 +1,160 @@
+defmodule TheoryCraftTA.Volume.OBV do
+  @moduledoc """
+  On Balance Volume (OBV).
+
+  The On Balance Volume is a cumulative total of the volume, added on up bars and
+  subtracted on down bars. It needs the close and volume of each bar.
+
+  ## Calculation
+
+  OBV = OBV_prev + Volume   if Close > Close_prev
+  OBV = OBV_prev - Volume   if Close < Close_prev
+  OBV = OBV_prev            otherwise
+
+  Where:
+  - The first OBV is the volume of the first bar, so there is no warmup
+
+  ## Usage with TheoryCraft
+
+  This module implements the `TheoryCraft.Indicator` behaviour and can be used
+  with `TheoryCraft.MarketSimulator`:
+
+      require TheoryCraftTA.TA, as: TA
+
+      simulator =
+        %MarketSimulator{}
+        |> MarketSimulator.add_data(bar_stream, name: "eurusd_m5")
+        |> MarketSimulator.add_indicator(TA.obv(eurusd_m5, name: "obv"))
+        |> MarketSimulator.stream()
+
+  """
+
+  alias __MODULE__
+  alias TheoryCraft.MarketSource.{IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.{Helpers, Native}
+
+  @behaviour TheoryCraft.MarketSource.Indicator
+
+  @type t :: %__MODULE__{
+          data_name: String.t(),
+          state: reference()
+        }
+
+  defstruct [:data_name, :state]
+
+  ## Public API
+
+  @doc """
+  Calculates On Balance Volume (batch calculation).
+
+  ## Parameters
+
+    - `close` - Close prices (list of floats, DataSeries, or TimeSeries)
+    - `volume` - Volumes (same type and length as `close`)
+
+  ## Returns
+
+    - `{:ok, result}` where result is the same type as `close` with OBV values
+    - `{:error, reason}` if validation fails or calculation error occurs
+
+  ## Examples
+
+      iex> close = [10.0, 11.0, 10.5, 10.5, 12.0]
+      iex> volume = [100.0, 200.0, 150.0, 50.0, 300.0]
+      iex> TheoryCraftTA.Volume.OBV.obv(close, volume)
+      {:ok, [100.0, 300.0, 150.0, 150.0, 450.0]}
+
+  """
+  @spec obv(TheoryCraftTA.source(), TheoryCraftTA.source()) ::
+          {:ok, TheoryCraftTA.source()} | {:error, String.t()}
+  def obv(close, volume) do
+    close_data = Helpers.to_list_and_reverse(close)
+    volume_data = Helpers.to_list_and_reverse(volume)
+
+    case Native.volume_obv(close_data, volume_data) do
+      {:ok, result_list} ->
+        {:ok, Helpers.rebuild_same_type(close, result_list)}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Initializes a new OBV state for streaming calculation.
+
+  ## Parameters
+
+  - `opts` - Keyword list with:
+    - `:data` (required) - The name of the bar stream to read from
+    - `:name` (required) - The output name for the indicator
+
+  ## Returns
+
+  - `{:ok, state}` - Initialized state
+
+  ## Examples
+
+      iex> {:ok, _state} = TheoryCraftTA.Volume.OBV.init(data: "eurusd", name: "obv")
+
+  """
+  @impl true
+  @spec init(Keyword.t()) :: {:ok, t()}
+  def init(opts) when is_list(opts) do
+    data_name = Keyword.fetch!(opts, :data)
+
+    case Native.volume_obv_state_init() do
+      {:ok, native_state} ->
+        state = %OBV{
+          data_name: data_name,
+          state: native_state
+        }
+
+        {:ok, state}
+
+      {:error, _reason} = error ->
+        error
+    end
+  end
+
+  @doc """
+  Processes a MarketEvent and calculates the next OBV value.
+
+  ## Parameters
+
+  - `event` - The `MarketEvent` to process
+  - `state` - The indicator state (from `init/1` or previous `next/2`)
+
+  ## Returns
+
+  - `{:ok, indicator_value, new_state}` - IndicatorValue with OBV calculation
+  - `{:error, message}` on error
+
+  ## UPDATE mode
+
+  When the bar is updated (`new_bar?: false`), the total up to the previous (closed)
+  bar is kept and only the contribution of the bar being updated is recomputed.
+
+  """
+  @impl true
+  @spec next(MarketEvent.t(), t()) :: {:ok, IndicatorValue.t(), t()}
+  def next(%MarketEvent{} = event, %OBV{} = state) do
+    %OBV{data_name: data_name, state: native_state} = state
+
+    close = MarketEvent.extract_value(event, data_name, :close)
+    volume = MarketEvent.extract_value(event, data_name, :volume)
+    is_new_bar = MarketEvent.new_bar?(event, data_name)
+
+    {:ok, {obv_value, new_native_state}} =
+      Native.volume_obv_state_next(native_state, close, volume, is_new_bar)
+
+    new_state = %OBV{state | state: new_native_state}
+
+    indicator_value = %IndicatorValue{
+      value: obv_value,
+      data_name: data_name
+    }
+
+    {:ok, indicator_value, new_state}
+  end
+end
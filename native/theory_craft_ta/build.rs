@@ -14,6 +14,157 @@ fn main() {
     // Declare the custom cfg for conditional compilation
     println!("cargo:rustc-check-cfg=cfg(has_talib)");
 
+    // TA-Lib is looked up in this order:
+    // - TA_LIB_PATH: install prefix of a system TA-Lib (with lib/ and include/ta-lib/)
+    // - pkg-config, when `pkg-config --exists ta-lib` succeeds
+    // - otherwise ta-lib is built from source with tools/build_talib.sh (or .cmd),
+    //   unless TA_LIB_SKIP_BUILD=1 asks to reuse an existing ta-lib-install
+    println!("cargo:rerun-if-env-changed=TA_LIB_PATH");
+    println!("cargo:rerun-if-env-changed=PKG_CONFIG_PATH");
+    println!("cargo:rerun-if-env-changed=CMAKE_ARGS");
+    println!("cargo:rerun-if-env-changed=TA_LIB_SKIP_BUILD");
+
+    // TA_LIB_LINK selects how ta-lib is linked: "static" (default) or "dylib",
+    // for distros that only ship the shared library
+    println!("cargo:rerun-if-env-changed=TA_LIB_LINK");
+
+    // TA_LIB_OPTIONAL=1 turns a failed source build (or bindgen run) into a warning: the
+    // crate is then compiled without has_talib, so the NIFs return errors and the Elixir
+    // backend is used
+    println!("cargo:rerun-if-env-changed=TA_LIB_OPTIONAL");
+
+    let link_kind = env::var("TA_LIB_LINK").unwrap_or_else(|_| "static".to_string());
+
+    if link_kind != "static" && link_kind != "dylib" {
+        panic!(
+            "Invalid TA_LIB_LINK value {:?}: expected \"static\" (default) or \"dylib\"",
+            link_kind
+        );
+    }
+
+    let (lib_dir, include_dir) = match find_system_ta_lib() {
+        Some(dirs) => dirs,
+        None => match build_ta_lib_from_source() {
+            Ok(dirs) => dirs,
+            Err(reason) if ta_lib_optional() => {
+                eprintln!("=== TA-LIB BUILD FAILED: {} ===", reason);
+                println!(
+                    "cargo:warning=TA-Lib could not be built, the NIFs are compiled without it \
+                     (TA_LIB_OPTIONAL=1): use the Elixir backend"
+                );
+                return;
+            }
+            Err(reason) => panic!("Failed to build ta-lib: {}", reason),
+        },
+    };
+
+    // Generate the FFI bindings from the installed headers, before enabling has_talib:
+    // bindgen needs libclang, which may be missing even when ta-lib is found
+    match generate_bindings(&include_dir) {
+        Ok(()) => {}
+        Err(reason) if ta_lib_optional() => {
+            eprintln!("=== TA-LIB BINDINGS FAILED: {} ===", reason);
+            println!(
+                "cargo:warning=TA-Lib bindings could not be generated, the NIFs are compiled \
+                 without it (TA_LIB_OPTIONAL=1): use the Elixir backend"
+            );
+            return;
+        }
+        Err(reason) => panic!("Failed to generate TA-Lib bindings: {}", reason),
+    }
+
+    eprintln!("=== TA-Lib FOUND - CONTINUING WITH LINKING ===");
+
+    // Enable the has_talib cfg flag for conditional compilation
+    println!("cargo:rustc-cfg=has_talib");
+
+    // Configure library search path
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+
+    // Link ta-lib statically, unless TA_LIB_LINK=dylib asks for the shared library
+    if link_kind == "dylib" {
+        println!("cargo:rustc-link-lib=dylib=ta-lib");
+
+        // Let the NIF find libta-lib at runtime without LD_LIBRARY_PATH
+        if env::var("CARGO_CFG_TARGET_OS").as_deref() != Ok("windows") {
+            println!("cargo:rustc-link-arg=-Wl,-rpath,{}", lib_dir.display());
+        }
+    } else {
+        println!("cargo:rustc-link-lib=static=ta-lib");
+    }
+
+    // Add include path for bindgen or manual FFI
+    println!("cargo:include={}", include_dir.display());
+}
+
+/// Whether TA_LIB_OPTIONAL=1 asks to compile the stubs when ta-lib can't be used
+fn ta_lib_optional() -> bool {
+    env::var("TA_LIB_OPTIONAL").as_deref() == Ok("1")
+}
+
+/// Returns the lib and include directories of a system-installed TA-Lib, if any
+fn find_system_ta_lib() -> Option<(PathBuf, PathBuf)> {
+    if let Ok(prefix) = env::var("TA_LIB_PATH") {
+        let prefix = PathBuf::from(prefix);
+
+        eprintln!(
+            "=== Using TA-Lib from TA_LIB_PATH: {} ===",
+            prefix.display()
+        );
+
+        if !prefix.join("lib").exists() {
+            panic!(
+                "TA_LIB_PATH is set but {} does not exist",
+                prefix.join("lib").display()
+            );
+        }
+
+        return Some((prefix.join("lib"), prefix.join("include")));
+    }
+
+    // pkg-config describes the host system: ignore it when cross-compiling
+    if env::var("TARGET").ok() != env::var("HOST").ok() {
+        return None;
+    }
+
+    let exists = Command::new("pkg-config")
+        .args(["--exists", "ta-lib"])
+        .status();
+
+    if !matches!(exists, Ok(status) if status.success()) {
+        return None;
+    }
+
+    let lib_dir = pkg_config_variable("libdir")?;
+    let include_dir = pkg_config_variable("includedir")?;
+
+    eprintln!(
+        "=== Using TA-Lib from pkg-config: {} ===",
+        lib_dir.display()
+    );
+
+    Some((lib_dir, include_dir))
+}
+
+fn pkg_config_variable(name: &str) -> Option<PathBuf> {
+    let output = Command::new("pkg-config")
+        .arg(format!("--variable={}", name))
+        .arg("ta-lib")
+        .output()
+        .ok()?;
+
+    let value = String::from_utf8(output.stdout).ok()?;
+    let value = value.trim();
+
+    if !output.status.success() || value.is_empty() {
+        return None;
+    }
+
+    Some(PathBuf::from(value))
+}
+
+/// Builds ta-lib into OUT_DIR (once) and returns its lib and include directories
+fn build_ta_lib_from_source() -> Result<(PathBuf, PathBuf), String> {
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
     let manifest_path = PathBuf::from(&manifest_dir);
     let project_root = manifest_path
@@ -31,34 +182,64 @@ fn main() {
     );
     eprintln!("=== Exists: {} ===", ta_lib_install.exists());
 
+    if env::var("TA_LIB_SKIP_BUILD").as_deref() == Ok("1") {
+        let local_install = project_root.join("ta-lib-install");
+        return find_prebuilt_ta_lib(&[ta_lib_install, local_install]);
+    }
+
     // Check if ta-lib is already built
     if !ta_lib_install.exists() {
         eprintln!("=== TA-Lib NOT FOUND - ATTEMPTING TO BUILD ===");
 
-        // Build ta-lib automatically - panic if it fails
-        build_ta_lib(project_root, &ta_lib_install).expect("Failed to build ta-lib");
+        // Build ta-lib automatically - the caller decides if a failure is fatal
+        build_ta_lib(project_root, &ta_lib_install)?;
 
         eprintln!("=== TA-LIB BUILD SUCCESSFUL ===");
     }
 
-    eprintln!("=== TA-Lib FOUND - CONTINUING WITH LINKING ===");
+    // Rerun if ta-lib changes
+    println!("cargo:rerun-if-changed={}", ta_lib_install.display());
 
-    // Enable the has_talib cfg flag for conditional compilation
-    println!("cargo:rustc-cfg=has_talib");
+    Ok((ta_lib_install.join("lib"), ta_lib_install.join("include")))
+}
 
-    // Configure library search path
-    let lib_dir = ta_lib_install.join("lib");
-    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+/// Returns the lib and include directories of the first complete ta-lib-install
+///
+/// With TA_LIB_SKIP_BUILD=1 the source build never runs: a ta-lib-install restored
+/// from a cache (or unpacked from a release) must already be in OUT_DIR or at the
+/// project root (where tools/build_talib.sh installs when run by hand), laid out as:
+///
+/// ```text
+/// ta-lib-install/
+/// ├── lib/libta-lib.a         (or the shared library with TA_LIB_LINK=dylib)
+/// └── include/ta-lib/ta_libc.h (and the other ta-lib headers)
+/// ```
+fn find_prebuilt_ta_lib(candidates: &[PathBuf]) -> Result<(PathBuf, PathBuf), String> {
+    for install_dir in candidates {
+        let lib_dir = install_dir.join("lib");
+        let include_dir = install_dir.join("include");
 
-    // Link ta-lib statically
-    println!("cargo:rustc-link-lib=static=ta-lib");
+        if lib_dir.is_dir() && include_dir.join("ta-lib").join("ta_libc.h").is_file() {
+            eprintln!(
+                "=== Using prebuilt TA-Lib (TA_LIB_SKIP_BUILD=1): {} ===",
+                install_dir.display()
+            );
 
-    // Add include path for bindgen or manual FFI
-    let include_dir = ta_lib_install.join("include");
-    println!("cargo:include={}", include_dir.display());
+            println!("cargo:rerun-if-changed={}", install_dir.display());
+            return Ok((lib_dir, include_dir));
+        }
+    }
 
-    // Rerun if ta-lib changes
-    println!("cargo:rerun-if-changed={}", ta_lib_install.display());
+    let searched: Vec<String> = candidates
+        .iter()
+        .map(|dir| dir.display().to_string())
+        .collect();
+
+    Err(format!(
+        "TA_LIB_SKIP_BUILD=1 but no ta-lib-install with lib/ and include/ta-lib/ta_libc.h \
+         was found (searched: {})",
+        searched.join(", ")
+    ))
 }
 
 fn build_ta_lib(
@@ -91,6 +272,8 @@ fn build_ta_lib(
     );
     eprintln!("=== Install directory: {} ===", install_dir.display());
 
+    let cross_env = cross_compile_env()?;
+
     let output = if cfg!(target_os = "windows") {
         Command::new("cmd")
             .arg("/C")
@@ -104,6 +287,7 @@ fn build_ta_lib(
             .arg(&build_script)
             .current_dir(project_root)
             .env("TALIB_INSTALL_DIR", install_dir)
+            .envs(cross_env)
             .output()
             .map_err(|e| format!("Failed to execute build script: {}", e))?
     };
@@ -120,3 +304,91 @@ fn build_ta_lib(
     println!("cargo:warning=TA-Lib built successfully");
     Ok(())
 }
+
+/// Returns the environment pointing the ta-lib build script at the target's C compiler
+///
+/// Nothing is needed for native builds, or when CMAKE_ARGS already configures the
+/// toolchain (as the release workflow does). Otherwise the compiler is taken from
+/// `CC_<target>` or `TARGET_CC`, the variables the cc crate and cross images use.
+fn cross_compile_env() -> Result<Vec<(String, String)>, String> {
+    let target = env::var("TARGET").unwrap();
+    let host = env::var("HOST").unwrap();
+
+    if target == host || env::var("CMAKE_ARGS").is_ok() {
+        return Ok(Vec::new());
+    }
+
+    let target_cc_var = format!("CC_{}", target.replace('-', "_"));
+    println!("cargo:rerun-if-env-changed={}", target_cc_var);
+    println!("cargo:rerun-if-env-changed=TARGET_CC");
+
+    let compiler = match env::var(&target_cc_var).or_else(|_| env::var("TARGET_CC")) {
+        Ok(compiler) => compiler,
+        // musl can't be built with the host (glibc) toolchain
+        Err(_) if env::var("CARGO_CFG_TARGET_ENV").as_deref() == Ok("musl") => {
+            return Err(format!(
+                "Cannot build ta-lib from source for {target} without a musl C compiler.\n\
+                 Either set TA_LIB_PATH to the install prefix of a ta-lib cross-compiled for \
+                 {target} (with lib/libta-lib.a and include/ta-lib/), or set {target_cc_var} \
+                 (or CMAKE_ARGS) to point at a musl cross-compiler, e.g. \
+                 {target_cc_var}=x86_64-linux-musl-gcc"
+            ));
+        }
+        Err(_) => return Ok(Vec::new()),
+    };
+
+    let mut cmake_args = format!("-DCMAKE_BUILD_TYPE=Release -DCMAKE_C_COMPILER={}", compiler);
+
+    let system_name = match env::var("CARGO_CFG_TARGET_OS").as_deref() {
+        Ok("linux") => Some("Linux"),
+        Ok("freebsd") => Some("FreeBSD"),
+        _ => None,
+    };
+
+    if let Some(system_name) = system_name {
+        cmake_args.push_str(&format!(" -DCMAKE_SYSTEM_NAME={}", system_name));
+    }
+
+    eprintln!(
+        "=== Cross-compiling ta-lib for {} with {} ===",
+        target, compiler
+    );
+
+    Ok(vec![
+        ("CC".to_string(), compiler),
+        ("CMAKE_ARGS".to_string(), cmake_args),
+    ])
+}
+
+fn generate_bindings(include_dir: &std::path::Path) -> Result<(), String> {
+    // TA-Lib installs (and the build scripts copy) its headers to include/ta-lib/
+    let header_dir = include_dir.join("ta-lib");
+    let header = header_dir.join("ta_libc.h");
+
+    eprintln!("=== Generating bindings from: {} ===", header.display());
+
+    let bindings = bindgen::Builder::default()
+        .header(header.to_string_lossy())
+        .clang_arg(format!("-I{}", header_dir.display()))
+        .allowlist_function("TA_.*")
+        .allowlist_type("TA_RetCode")
+        .default_enum_style(bindgen::EnumVariation::Consts)
+        .prepend_enum_name(false)
+        // C enums are unsigned on some targets and signed on others: keep passing
+        // the MA type as a plain int, like the safe wrappers do
+        .blocklist_type("TA_MAType")
+        .raw_line("pub type TA_MAType = i32;")
+        .layout_tests(false)
+        .generate()
+        .map_err(|e| format!("Failed to run bindgen: {}", e))?;
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let bindings_path = PathBuf::from(out_dir).join("ta_lib_bindings.rs");
+
+    bindings
+        .write_to_file(&bindings_path)
+        .map_err(|e| format!("Failed to write bindings: {}", e))?;
+
+    println!("cargo:rerun-if-changed={}", header.display());
+    Ok(())
+}
@@ -0,0 +1,138 @@
+// Several indicators computed on the same OHLC series in a single NIF call
+
+/// OHLC series shared by the indicators of a `compute_batch` call
+///
+/// Decoded from a map with the `:open`, `:high`, `:low` and `:close` keys.
+#[derive(rustler::NifMap)]
+pub struct Ohlc {
+    pub open: Vec<Option<f64>>,
+    pub high: Vec<Option<f64>>,
+    pub low: Vec<Option<f64>>,
+    pub close: Vec<Option<f64>>,
+}
+
+/// Series of the OHLC an indicator with a single input is computed on
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
+pub enum Column {
+    Open,
+    High,
+    Low,
+    Close,
+}
+
+/// Indicator of a `compute_batch` call, with its parameters
+///
+/// Decoded from `{indicator, params}` tuples, e.g. `{:sma, %{period: 20, source: :close}}`
+/// or `{:atr, %{period: 14}}`.
+#[derive(rustler::NifTaggedEnum)]
+pub enum IndicatorSpec {
+    Sma { period: i32, source: Column },
+    Ema { period: i32, source: Column },
+    Wma { period: i32, source: Column },
+    Rsi { period: i32, source: Column },
+    Atr { period: i32 },
+}
+
+impl Ohlc {
+    fn column(&self, column: Column) -> &[Option<f64>] {
+        match column {
+            Column::Open => &self.open,
+            Column::High => &self.high,
+            Column::Low => &self.low,
+            Column::Close => &self.close,
+        }
+    }
+}
+
+/// Compute one indicator of the batch with its batch function
+#[cfg(has_talib)]
+fn compute_spec(ohlc: &Ohlc, spec: &IndicatorSpec) -> Result<Vec<Option<f64>>, String> {
+    use crate::momentum::calculate_rsi;
+    use crate::overlap::{calculate_ema, calculate_sma, calculate_wma};
+    use crate::volatility::calculate_atr;
+
+    match *spec {
+        IndicatorSpec::Sma { period, source } => {
+            calculate_sma(ohlc.column(source).to_vec(), period)
+        }
+        IndicatorSpec::Ema { period, source } => {
+            calculate_ema(ohlc.column(source).to_vec(), period)
+        }
+        IndicatorSpec::Wma { period, source } => {
+            calculate_wma(ohlc.column(source).to_vec(), period)
+        }
+        IndicatorSpec::Rsi { period, source } => {
+            calculate_rsi(ohlc.column(source).to_vec(), period)
+        }
+        IndicatorSpec::Atr { period } => calculate_atr(&ohlc.high, &ohlc.low, &ohlc.close, period),
+    }
+}
+
+/// Several indicators on the same OHLC, e.g. the features of a model
+///
+/// The OHLC is decoded once and the NIF boundary crossed once for all of them.
+/// Each spec gets its own result, in order, so an invalid spec doesn't fail the
+/// others. An unknown indicator or a malformed spec raises an `ArgumentError`.
+#[cfg(has_talib)]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn compute_batch(
+    ohlc: Ohlc,
+    specs: Vec<IndicatorSpec>,
+) -> Vec<Result<Vec<Option<f64>>, String>> {
+    specs.iter().map(|spec| compute_spec(&ohlc, spec)).collect()
+}
+
+/// TA-Lib lookback of one indicator of the batch: the index of its first value
+#[cfg(has_talib)]
+fn spec_lookback(spec: &IndicatorSpec) -> Result<i32, String> {
+    use crate::momentum_ffi::TA_RSI_Lookback;
+    use crate::overlap_ffi::{TA_EMA_Lookback, TA_SMA_Lookback, TA_WMA_Lookback};
+    use crate::volatility_ffi::TA_ATR_Lookback;
+
+    let (name, period, lookback): (&str, i32, unsafe extern "C" fn(i32) -> i32) = match *spec {
+        IndicatorSpec::Sma { period, .. } => ("SMA", period, TA_SMA_Lookback),
+        IndicatorSpec::Ema { period, .. } => ("EMA", period, TA_EMA_Lookback),
+        IndicatorSpec::Wma { period, .. } => ("WMA", period, TA_WMA_Lookback),
+        IndicatorSpec::Rsi { period, .. } => ("RSI", period, TA_RSI_Lookback),
+        IndicatorSpec::Atr { period } => ("ATR", period, TA_ATR_Lookback),
+    };
+
+    if period < 2 {
+        return Err(format!("{}: period must be >= 2", name));
+    }
+
+    Ok(unsafe { lookback(period) })
+}
+
+/// Largest lookback of several indicators, without computing them
+///
+/// The rows of a feature matrix built from these specs are all valid from this
+/// index on (past any leading missing value), so every column can be trimmed to
+/// a common start. 0 for no specs; an invalid spec fails the whole call.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn max_lookback(specs: Vec<IndicatorSpec>) -> Result<i32, String> {
+    specs
+        .iter()
+        .map(spec_lookback)
+        .try_fold(0, |max, lookback| Ok(max.max(lookback?)))
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn compute_batch(
+    _ohlc: Ohlc,
+    specs: Vec<IndicatorSpec>,
+) -> Vec<Result<Vec<Option<f64>>, String>> {
+    specs
+        .iter()
+        .map(|_| Err("TA-Lib not available. Please use the Elixir backend.".to_string()))
+        .collect()
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn max_lookback(_specs: Vec<IndicatorSpec>) -> Result<i32, String> {
+    Err("TA-Lib not available. Please use the Elixir backend.".to_string())
+}
@@ -0,0 +1,86 @@
+// FFI bindings to the TA-Lib C library
+//
+// The raw declarations are generated by bindgen from the installed ta-lib headers
+// (see build.rs). Only compiled when ta-lib is available (has_talib cfg flag).
+
+mod bindings {
+    #![allow(
+        non_upper_case_globals,
+        non_camel_case_types,
+        non_snake_case,
+        dead_code
+    )]
+    #![allow(clippy::all)]
+
+    include!(concat!(env!("OUT_DIR"), "/ta_lib_bindings.rs"));
+}
+
+pub use bindings::*;
+
+#[repr(C)]
+#[derive(Debug, PartialEq)]
+#[allow(dead_code)]
+pub enum TARetCode {
+    Success = 0,
+    LibNotInitialize = 1,
+    BadParam = 2,
+    AllocErr = 3,
+    GroupNotFound = 4,
+    FuncNotFound = 5,
+    InvalidHandle = 6,
+    InvalidParamHolder = 7,
+    InvalidParamHolderType = 8,
+    InvalidParamFunction = 9,
+    InputNotAllInitialize = 10,
+    OutputNotAllInitialize = 11,
+    OutOfRangeStartIndex = 12,
+    OutOfRangeEndIndex = 13,
+    InvalidListType = 14,
+    BadObject = 15,
+    NotSupported = 16,
+    InternalError = 5000,
+    UnknownErr = 0xFFFF,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ret_codes_match_the_generated_bindings() {
+        let ret_codes = [
+            (TARetCode::Success, TA_SUCCESS),
+            (TARetCode::LibNotInitialize, TA_LIB_NOT_INITIALIZE),
+            (TARetCode::BadParam, TA_BAD_PARAM),
+            (TARetCode::AllocErr, TA_ALLOC_ERR),
+            (TARetCode::GroupNotFound, TA_GROUP_NOT_FOUND),
+            (TARetCode::FuncNotFound, TA_FUNC_NOT_FOUND),
+            (TARetCode::InvalidHandle, TA_INVALID_HANDLE),
+            (TARetCode::InvalidParamHolder, TA_INVALID_PARAM_HOLDER),
+            (
+                TARetCode::InvalidParamHolderType,
+                TA_INVALID_PARAM_HOLDER_TYPE,
+            ),
+            (TARetCode::InvalidParamFunction, TA_INVALID_PARAM_FUNCTION),
+            (
+                TARetCode::InputNotAllInitialize,
+                TA_INPUT_NOT_ALL_INITIALIZE,
+            ),
+            (
+                TARetCode::OutputNotAllInitialize,
+                TA_OUTPUT_NOT_ALL_INITIALIZE,
+            ),
+            (TARetCode::OutOfRangeStartIndex, TA_OUT_OF_RANGE_START_INDEX),
+            (TARetCode::OutOfRangeEndIndex, TA_OUT_OF_RANGE_END_INDEX),
+            (TARetCode::InvalidListType, TA_INVALID_LIST_TYPE),
+            (TARetCode::BadObject, TA_BAD_OBJECT),
+            (TARetCode::NotSupported, TA_NOT_SUPPORTED),
+            (TARetCode::InternalError, TA_INTERNAL_ERROR),
+            (TARetCode::UnknownErr, TA_UNKNOWN_ERR),
+        ];
+
+        for (ret_code, generated) in ret_codes {
+            assert_eq!(ret_code as i64, generated as i64);
+        }
+    }
+}
@@ -16,10 +16,14 @@
 #[macro_export]
 macro_rules! check_ret_code {
     ($ret_code:expr, $func_name:expr) => {{
-        use $crate::overlap_ffi::TARetCode;
+        use $crate::ffi::TARetCode;
 
-        if $ret_code != TARetCode::Success as i32 {
-            let error_msg = match $ret_code {
+        // The generated TA_RetCode is unsigned on some targets and signed on others
+        #[allow(clippy::unnecessary_cast)]
+        let ret_code = $ret_code as i32;
+
+        if ret_code != TARetCode::Success as i32 {
+            let error_msg = match ret_code {
                 x if x == TARetCode::LibNotInitialize as i32 => {
                     format!("{}: TA-Lib not initialized", $func_name)
                 }
@@ -41,6 +45,9 @@ macro_rules! check_ret_code {
                 x if x == TARetCode::InvalidParamHolder as i32 => {
                     format!("{}: Invalid parameter holder", $func_name)
                 }
+                x if x == TARetCode::InvalidParamHolderType as i32 => {
+                    format!("{}: Invalid parameter holder type", $func_name)
+                }
                 x if x == TARetCode::InvalidParamFunction as i32 => {
                     format!("{}: Invalid parameter function", $func_name)
                 }
@@ -66,18 +73,12 @@ macro_rules! check_ret_code {
                     format!("{}: Operation not supported", $func_name)
                 }
                 x if x == TARetCode::InternalError as i32 => {
-                    format!(
-                        "{}: TA-Lib internal error (code: {})",
-                        $func_name, $ret_code
-                    )
+                    format!("{}: TA-Lib internal error (code: {})", $func_name, ret_code)
                 }
                 x if x == TARetCode::UnknownErr as i32 => {
-                    format!("{}: Unknown error (code: {})", $func_name, $ret_code)
+                    format!("{}: Unknown error (code: {})", $func_name, ret_code)
                 }
-                _ => format!(
-                    "{}: TA-Lib internal error (code: {})",
-                    $func_name, $ret_code
-                ),
+                _ => format!("{}: TA-Lib internal error (code: {})", $func_name, ret_code),
             };
 
             return Err(error_msg);
@@ -99,57 +100,569 @@ pub fn options_to_nan(data: &[Option<f64>]) -> Vec<f64> {
     data.iter().map(|x| x.unwrap_or(f64::NAN)).collect()
 }
 
+/// Check that the series of a multi-input function all have the same length
+///
+/// Each series is given by name and length, in the order of the arguments. On
+/// a mismatch every length is reported, so the short series is obvious.
+///
+/// # Examples
+///
+/// ```
+/// validate_inputs("ATR", &[("high", 100), ("low", 100), ("close", 99)])?;
+/// // Err("ATR: high/low/close length mismatch (100/100/99)")
+/// ```
+pub fn validate_inputs(func_name: &str, series: &[(&str, usize)]) -> Result<(), String> {
+    let Some(&(_, expected)) = series.first() else {
+        return Ok(());
+    };
+
+    if series.iter().all(|&(_, length)| length == expected) {
+        return Ok(());
+    }
+
+    let names: Vec<&str> = series.iter().map(|&(name, _)| name).collect();
+    let lengths: Vec<String> = series
+        .iter()
+        .map(|&(_, length)| length.to_string())
+        .collect();
+
+    Err(format!(
+        "{}: {} length mismatch ({})",
+        func_name,
+        names.join("/"),
+        lengths.join("/")
+    ))
+}
+
 /// Find index of first non-NaN value in data, similar to Python ta-lib's check_begidx1
 ///
 /// This replicates the Python ta-lib behavior of skipping leading NaN values
 /// before calling ta-lib C functions.
 ///
+/// Returns `None` when the data contains no valid value at all, in which case
+/// callers should short-circuit to an all-`None` result.
+///
 /// # Examples
 ///
 /// ```
 /// let data = vec![f64::NAN, f64::NAN, 1.0, 2.0];
-/// assert_eq!(check_begidx(&data), 2);
+/// assert_eq!(check_begidx(&data), Some(2));
 ///
 /// let data = vec![1.0, 2.0, 3.0];
-/// assert_eq!(check_begidx(&data), 0);
+/// assert_eq!(check_begidx(&data), Some(0));
+///
+/// let data = vec![f64::NAN, f64::NAN];
+/// assert_eq!(check_begidx(&data), None);
 /// ```
 #[inline]
-pub fn check_begidx(data: &[f64]) -> usize {
-    for (i, &val) in data.iter().enumerate() {
-        if !val.is_nan() {
-            return i;
-        }
-    }
+pub fn check_begidx(data: &[f64]) -> Option<usize> {
+    data.iter().position(|val| !val.is_nan())
+}
 
-    data.len().saturating_sub(1)
+/// Find the first index where every series has a valid value
+///
+/// The multi-input counterpart of [`check_begidx`] (high/low/close, close/volume,
+/// ...): TA-Lib needs all of its inputs valid from the start index on. The
+/// series are scanned side by side, up to the end of the shortest one.
+///
+/// # Examples
+///
+/// ```
+/// let high = vec![f64::NAN, 2.0, 3.0];
+/// let low = vec![1.0, f64::NAN, 2.0];
+/// assert_eq!(check_begidx_multi(&[&high, &low]), Some(2));
+/// ```
+#[inline]
+pub fn check_begidx_multi(series: &[&[f64]]) -> Option<usize> {
+    let length = series.iter().map(|values| values.len()).min()?;
+    (0..length).find(|&i| series.iter().all(|values| !values[i].is_nan()))
 }
 
 /// Build result vector from ta-lib output array
 ///
-/// Creates a result vector with `total_lookback` None values at the beginning,
+/// Creates a result vector with `first_idx` None values at the beginning,
 /// followed by the values from `out_real`, converting NaN to None.
 ///
+/// `first_idx` is the index of the first output in the original input, i.e.
+/// `begidx + out_beg_idx` as reported by TA-Lib.
+///
+/// Returns an error instead of reading out of bounds if `out_nb_element` does
+/// not fit in `out_real` (a panic here would take down the scheduler).
+///
 /// # Examples
 ///
 /// ```
-/// let result = build_result(total_lookback, out_nb_element, &out_real);
+/// let result = build_result(begidx as i32 + out_beg_idx, out_nb_element, &out_real, "SMA")?;
 /// ```
 #[inline]
 pub fn build_result(
-    total_lookback: i32,
+    first_idx: i32,
     out_nb_element: i32,
     out_real: &[f64],
-) -> Vec<Option<f64>> {
-    let mut result = vec![None; total_lookback as usize];
-
-    for i in 0..out_nb_element {
-        let value = out_real[i as usize];
-        if value.is_nan() {
-            result.push(None);
-        } else {
-            result.push(Some(value));
-        }
+    func_name: &str,
+) -> Result<Vec<Option<f64>>, String> {
+    let out_real = checked_output(out_nb_element, out_real, func_name)?;
+
+    // Sized once for the padding and the outputs: no reallocation while filling
+    let mut result = Vec::with_capacity(first_idx as usize + out_real.len());
+    result.resize(first_idx as usize, None);
+    result.extend(out_real.iter().map(|&value| nan_to_none(value)));
+
+    Ok(result)
+}
+
+/// The `out_nb_element` values written by TA-Lib in `out`
+///
+/// Errors instead of slicing out of bounds when TA-Lib reports more elements
+/// than the buffer holds (or a negative count).
+#[inline]
+fn checked_output<'a, T>(
+    out_nb_element: i32,
+    out: &'a [T],
+    func_name: &str,
+) -> Result<&'a [T], String> {
+    if out_nb_element < 0 || out_nb_element as usize > out.len() {
+        return Err(format!(
+            "{}: TA-Lib returned {} elements but the output buffer holds {}",
+            func_name,
+            out_nb_element,
+            out.len()
+        ));
+    }
+
+    Ok(&out[..out_nb_element as usize])
+}
+
+#[inline]
+fn nan_to_none(value: f64) -> Option<f64> {
+    if value.is_nan() {
+        None
+    } else {
+        Some(value)
     }
+}
+
+/// Build result vector from a ta-lib integer output array
+///
+/// Same padding and bounds check as [`build_result`], for the functions whose
+/// outputs are integers (indices, pattern flags). Integers have no NaN, so every
+/// output value is kept.
+#[inline]
+pub fn build_result_i32(
+    first_idx: i32,
+    out_nb_element: i32,
+    out_integer: &[i32],
+    func_name: &str,
+) -> Result<Vec<Option<i32>>, String> {
+    let out_integer = checked_output(out_nb_element, out_integer, func_name)?;
+
+    let mut result = Vec::with_capacity(first_idx as usize + out_integer.len());
+    result.resize(first_idx as usize, None);
+    result.extend(out_integer.iter().map(|&value| Some(value)));
+
+    Ok(result)
+}
+
+/// Result of a batch function without its leading padding
+///
+/// The index of the first output in the input (the total lookback, leading NaN
+/// included) and the outputs from there on. Interior NaN are still returned as
+/// `None` since they cannot be encoded as Erlang floats.
+pub type TrimmedOutput = (i32, Vec<Option<f64>>);
+
+/// Build a trimmed result from ta-lib output array
+///
+/// Same as [`build_result`] without allocating the `first_idx` leading None values.
+#[inline]
+pub fn build_trimmed_result(
+    first_idx: i32,
+    out_nb_element: i32,
+    out_real: &[f64],
+    func_name: &str,
+) -> Result<TrimmedOutput, String> {
+    let values = checked_output(out_nb_element, out_real, func_name)?
+        .iter()
+        .map(|&value| nan_to_none(value))
+        .collect();
+
+    Ok((first_idx, values))
+}
 
+/// Pad a trimmed result back to the full-length series returned by the padded API
+pub fn pad_trimmed((first_idx, values): TrimmedOutput) -> Vec<Option<f64>> {
+    let mut result = Vec::with_capacity(first_idx as usize + values.len());
+    result.resize(first_idx as usize, None);
+    result.extend(values);
     result
 }
+
+/// Convert a series using `sentinel` for missing values to the NaN convention
+///
+/// The sentinel counterpart of [`options_to_nan`].
+#[inline]
+pub fn sentinel_to_nan(data: &[f64], sentinel: f64) -> Vec<f64> {
+    data.iter()
+        .map(|&value| if value == sentinel { f64::NAN } else { value })
+        .collect()
+}
+
+/// Pad a trimmed result to the full-length series, `sentinel` for missing values
+///
+/// The sentinel counterpart of [`pad_trimmed`]: the padding and the interior
+/// `None` both become `sentinel`.
+pub fn pad_trimmed_with_sentinel((first_idx, values): TrimmedOutput, sentinel: f64) -> Vec<f64> {
+    let mut result = Vec::with_capacity(first_idx as usize + values.len());
+    result.resize(first_idx as usize, sentinel);
+    result.extend(values.into_iter().map(|value| value.unwrap_or(sentinel)));
+    result
+}
+
+/// Result of a batch function with its trimmed values packed in a binary
+///
+/// The values are native-endian f64, missing ones as NaN: the layout of an Nx
+/// `{:f, 64}` tensor, so `Nx.from_binary/2` can use the binary as is.
+pub type BinaryOutput<'a> = (i32, rustler::Binary<'a>);
+
+/// Pack values as native-endian f64 into `out`, `None` as NaN
+#[inline]
+pub fn pack_f64_ne(values: &[Option<f64>], out: &mut [u8]) {
+    for (chunk, value) in out.chunks_exact_mut(8).zip(values) {
+        chunk.copy_from_slice(&value.unwrap_or(f64::NAN).to_ne_bytes());
+    }
+}
+
+/// Move a trimmed result into a binary
+///
+/// A list of floats costs 4 words per element on the process heap (a cons cell
+/// and a boxed float), 32 bytes on a 64-bit VM, and is copied on every message
+/// send. The binary costs 8 bytes per element, off-heap and reference-counted,
+/// leaving a handful of words on the process heap: for 10 million values, about
+/// 320 MB of heap become an 80 MB shared binary.
+pub fn trimmed_to_binary<'a>(
+    env: rustler::Env<'a>,
+    (first_idx, values): TrimmedOutput,
+    func_name: &str,
+) -> Result<BinaryOutput<'a>, String> {
+    let mut binary = rustler::OwnedBinary::new(values.len() * std::mem::size_of::<f64>())
+        .ok_or_else(|| format!("{}: could not allocate the output binary", func_name))?;
+
+    pack_f64_ne(&values, binary.as_mut_slice());
+
+    Ok((first_idx, binary.release(env)))
+}
+
+/// Run an f64 batch calculation on an f32 series
+///
+/// TA-Lib only computes in f64, so the input is widened, the calculation runs at
+/// full f64 precision and only the output is narrowed back to f32. The result is
+/// therefore the f64 result rounded to the nearest f32, not an f32 computation.
+///
+/// `None` and NaN inputs are both treated as missing values, and NaN outputs are
+/// returned as `None`, exactly like the f64 API.
+///
+/// # Examples
+///
+/// ```
+/// let result = with_f32_series(&data, |data| calculate_sma(data, period))?;
+/// ```
+pub fn with_f32_series<F>(data: &[Option<f32>], calculate: F) -> Result<Vec<Option<f32>>, String>
+where
+    F: FnOnce(Vec<Option<f64>>) -> Result<Vec<Option<f64>>, String>,
+{
+    let widened = data
+        .iter()
+        .map(|value| value.filter(|v| !v.is_nan()).map(f64::from))
+        .collect();
+
+    let result = calculate(widened)?;
+
+    Ok(result
+        .into_iter()
+        .map(|value| value.filter(|v| !v.is_nan()).map(|v| v as f32))
+        .collect())
+}
+
+/// Thread pool of the batch NIFs processing several series at once
+///
+/// Sized like the BEAM's default dirty CPU scheduler count (one thread per core)
+/// and shared by all callers, so concurrent multi-series calls queue on the same
+/// threads instead of oversubscribing the machine. Returns `None` if the threads
+/// can't be spawned: callers then run sequentially rather than panicking.
+#[cfg(has_talib)]
+pub fn thread_pool() -> Option<&'static rayon::ThreadPool> {
+    use std::sync::OnceLock;
+
+    static POOL: OnceLock<rayon::ThreadPool> = OnceLock::new();
+
+    if POOL.get().is_none() {
+        let num_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .thread_name(|i| format!("theory_craft_ta-{}", i))
+            .build()
+            .ok()?;
+
+        // Another caller may have won the race: its pool is kept, this one dropped
+        let _ = POOL.set(pool);
+    }
+
+    POOL.get()
+}
+
+/// Values of a rolling window kept in ascending order, for order statistics
+///
+/// Insertion and removal find their position by binary search. Values must not
+/// be NaN: callers skip or reject them before they reach the window.
+#[derive(Clone, Default)]
+pub struct SortedWindow {
+    values: Vec<f64>,
+}
+
+impl SortedWindow {
+    /// Insert a value at its sorted position
+    pub fn insert(&mut self, value: f64) {
+        let idx = self.values.partition_point(|v| *v < value);
+        self.values.insert(idx, value);
+    }
+
+    /// Remove one occurrence of a value, if present
+    pub fn remove(&mut self, value: f64) {
+        let idx = self.values.partition_point(|v| *v < value);
+        if self.values.get(idx) == Some(&value) {
+            self.values.remove(idx);
+        }
+    }
+
+    /// Number of values strictly less than `value`
+    pub fn count_below(&self, value: f64) -> usize {
+        self.values.partition_point(|v| *v < value)
+    }
+
+    /// Median of the window: the mean of the two middle values for an even size
+    pub fn median(&self) -> Option<f64> {
+        let len = self.values.len();
+
+        match len {
+            0 => None,
+            _ if len % 2 == 1 => Some(self.values[len / 2]),
+            _ => Some((self.values[len / 2 - 1] + self.values[len / 2]) / 2.0),
+        }
+    }
+}
+
+/// Series returned by batch functions with two outputs (e.g. fast %K and fast %D)
+pub type DoubleOutput = (Vec<Option<f64>>, Vec<Option<f64>>);
+
+/// Series returned by batch functions with three outputs (e.g. upper, middle and lower bands)
+pub type TripleOutput = (Vec<Option<f64>>, Vec<Option<f64>>, Vec<Option<f64>>);
+
+/// Series returned by batch functions with two integer outputs (e.g. min and max indices)
+pub type IndexPairOutput = (Vec<Option<i32>>, Vec<Option<i32>>);
+
+/// Series of `{value, final}` pairs, `final` being false for a value still forming
+pub type AnnotatedOutput = Vec<(Option<f64>, bool)>;
+
+/// Flag each value of a series as final, except the last one if its bar isn't closed
+///
+/// For incremental chart updates: every bar before the last one is complete, so
+/// only the last value can still change with the next tick.
+pub fn annotate_result(values: Vec<Option<f64>>, last_bar_closed: bool) -> AnnotatedOutput {
+    let last_idx = values.len().saturating_sub(1);
+
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| (value, last_bar_closed || idx != last_idx))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotate_result_only_flags_an_open_last_bar() {
+        let values = vec![None, Some(1.0), Some(2.0)];
+
+        assert_eq!(
+            annotate_result(values.clone(), false),
+            vec![(None, true), (Some(1.0), true), (Some(2.0), false)]
+        );
+        assert!(annotate_result(values, true).iter().all(|&(_, fin)| fin));
+        assert!(annotate_result(Vec::new(), false).is_empty());
+    }
+
+    #[test]
+    fn build_result_pads_lookback_and_maps_nan() {
+        let out_real = [1.0, f64::NAN, 3.0];
+        let result = build_result(2, 3, &out_real, "SMA").unwrap();
+        assert_eq!(result, vec![None, None, Some(1.0), None, Some(3.0)]);
+    }
+
+    #[test]
+    fn check_begidx_multi_waits_for_every_series() {
+        let nan = f64::NAN;
+
+        let high = [nan, 2.0, 3.0, 4.0];
+        let low = [1.0, nan, 2.0, 3.0];
+        assert_eq!(check_begidx_multi(&[&high, &low]), Some(2));
+
+        let close = [1.0, 2.0, nan, 3.0];
+        assert_eq!(check_begidx_multi(&[&high, &low, &close]), Some(3));
+        assert_eq!(check_begidx_multi(&[&close]), check_begidx(&close));
+
+        let never_together = [nan, nan, nan, 1.0];
+        let only_early = [1.0, 1.0, 1.0, nan];
+        assert_eq!(check_begidx_multi(&[&never_together, &only_early]), None);
+        assert_eq!(check_begidx_multi(&[]), None);
+    }
+
+    #[test]
+    fn validate_inputs_reports_every_length() {
+        assert!(validate_inputs("ATR", &[("high", 3), ("low", 3), ("close", 3)]).is_ok());
+        assert!(validate_inputs("ATR", &[]).is_ok());
+
+        assert_eq!(
+            validate_inputs("ATR", &[("high", 100), ("low", 100), ("close", 99)]),
+            Err("ATR: high/low/close length mismatch (100/100/99)".to_string())
+        );
+        assert_eq!(
+            validate_inputs("OBV", &[("close", 0), ("volume", 2)]),
+            Err("OBV: close/volume length mismatch (0/2)".to_string())
+        );
+    }
+
+    #[test]
+    fn build_result_allocates_the_final_length_once() {
+        let out_real: Vec<f64> = (0..1000).map(f64::from).collect();
+        let result = build_result(500, 1000, &out_real, "SMA").unwrap();
+
+        assert_eq!(result.len(), 1500);
+        assert_eq!(result.capacity(), 1500);
+        assert_eq!(result[499], None);
+        assert_eq!(result[500], Some(0.0));
+        assert_eq!(result[1499], Some(999.0));
+    }
+
+    #[test]
+    fn build_result_rejects_out_nb_element_larger_than_buffer() {
+        let out_real = [1.0, 2.0];
+        let err = build_result(0, 3, &out_real, "SMA").unwrap_err();
+        assert!(err.starts_with("SMA: "), "{}", err);
+    }
+
+    #[test]
+    fn build_result_i32_pads_lookback_and_checks_bounds() {
+        let out_integer = [4, 0, 7];
+        let result = build_result_i32(1, 2, &out_integer, "MINMAXINDEX").unwrap();
+        assert_eq!(result, vec![None, Some(4), Some(0)]);
+
+        assert!(build_result_i32(0, 4, &out_integer, "MINMAXINDEX").is_err());
+    }
+
+    #[test]
+    fn build_result_i32_keeps_zero_and_negative_values() {
+        // Candlestick patterns flag bars with -100, 0 or 100: unlike NaN for
+        // the f64 outputs, 0 is a value and must not become None
+        let out_integer = [0, 100, -100, 0];
+        let result = build_result_i32(2, 4, &out_integer, "CDLENGULFING").unwrap();
+        assert_eq!(
+            result,
+            vec![None, None, Some(0), Some(100), Some(-100), Some(0)]
+        );
+
+        assert!(build_result_i32(0, -1, &out_integer, "CDLENGULFING").is_err());
+    }
+
+    #[test]
+    fn build_result_rejects_negative_out_nb_element() {
+        assert!(build_result(0, -1, &[1.0], "SMA").is_err());
+    }
+
+    #[test]
+    fn padding_the_trimmed_result_reproduces_build_result() {
+        let out_real = [1.0, f64::NAN, 3.0, 0.0];
+        let trimmed = build_trimmed_result(4, 3, &out_real, "SMA").unwrap();
+        assert_eq!(trimmed, (4, vec![Some(1.0), None, Some(3.0)]));
+        assert_eq!(
+            pad_trimmed(trimmed),
+            build_result(4, 3, &out_real, "SMA").unwrap()
+        );
+    }
+
+    #[test]
+    fn sentinel_round_trip() {
+        let clean = sentinel_to_nan(&[-1.0, 2.0, -1.0, 4.0], -1.0);
+        assert!(clean[0].is_nan() && clean[2].is_nan());
+        assert_eq!((clean[1], clean[3]), (2.0, 4.0));
+
+        let trimmed = (2, vec![Some(1.5), None, Some(3.0)]);
+        assert_eq!(
+            pad_trimmed_with_sentinel(trimmed, -1.0),
+            vec![-1.0, -1.0, 1.5, -1.0, 3.0]
+        );
+    }
+
+    #[test]
+    fn pad_trimmed_without_values_is_all_none() {
+        assert_eq!(pad_trimmed((3, Vec::new())), vec![None, None, None]);
+        assert_eq!(pad_trimmed((0, Vec::new())), Vec::new());
+    }
+
+    #[test]
+    fn pack_f64_ne_writes_native_floats_and_nan_for_gaps() {
+        let mut out = [0u8; 24];
+        pack_f64_ne(&[Some(1.5), None, Some(-2.0)], &mut out);
+
+        assert_eq!(f64::from_ne_bytes(out[0..8].try_into().unwrap()), 1.5);
+        assert!(f64::from_ne_bytes(out[8..16].try_into().unwrap()).is_nan());
+        assert_eq!(f64::from_ne_bytes(out[16..24].try_into().unwrap()), -2.0);
+    }
+
+    #[test]
+    fn with_f32_series_widens_input_and_narrows_output() {
+        let data = [Some(1.5f32), None, Some(f32::NAN), Some(0.1)];
+
+        let result = with_f32_series(&data, |widened| {
+            assert_eq!(widened[0], Some(1.5));
+            assert_eq!(widened[1], None);
+            assert_eq!(widened[2], None);
+            assert_eq!(widened[3], Some(f64::from(0.1f32)));
+
+            Ok(vec![Some(1.0 / 3.0), None, Some(f64::NAN), Some(2.5)])
+        })
+        .unwrap();
+
+        assert_eq!(result, vec![Some(1.0f32 / 3.0), None, None, Some(2.5)]);
+    }
+
+    #[test]
+    fn with_f32_series_propagates_errors() {
+        let err = with_f32_series(
+            &[Some(1.0)],
+            |_| Err("SMA: period must be >= 2".to_string()),
+        );
+        assert_eq!(err.unwrap_err(), "SMA: period must be >= 2");
+    }
+
+    #[test]
+    fn sorted_window_tracks_the_median() {
+        let mut window = SortedWindow::default();
+        assert_eq!(window.median(), None);
+
+        for value in [3.0, 1.0, 2.0, 2.0] {
+            window.insert(value);
+        }
+        assert_eq!(window.median(), Some(2.0));
+
+        window.remove(2.0);
+        window.remove(5.0);
+        assert_eq!(window.median(), Some(2.0));
+
+        window.remove(1.0);
+        assert_eq!(window.median(), Some(2.5));
+        assert_eq!(window.count_below(2.0), 0);
+        assert_eq!(window.count_below(2.5), 1);
+        assert_eq!(window.count_below(4.0), 2);
+    }
+}
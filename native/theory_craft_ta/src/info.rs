@@ -0,0 +1,23 @@
+// Implementation when ta-lib is available
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn ta_lib_version() -> String {
+    use crate::ffi::TA_GetVersionString;
+    use std::ffi::CStr;
+
+    // TA-Lib returns a static NUL-terminated string, e.g. "0.6.4 (Jan  1 2025 00:00:00)"
+    let version = unsafe { CStr::from_ptr(TA_GetVersionString()) };
+    version.to_string_lossy().into_owned()
+}
+
+#[rustler::nif]
+pub fn has_talib() -> bool {
+    cfg!(has_talib)
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn ta_lib_version() -> String {
+    "unavailable".to_string()
+}
@@ -1,3 +1,6 @@
+// Without TA-Lib the NIFs are stubs, which leaves the states and helpers unused
+#![cfg_attr(not(has_talib), allow(dead_code))]
+
 use rustler::{Env, Term};
 
 // Common atoms used across all modules
@@ -5,29 +8,132 @@ mod atoms {
     rustler::atoms! {
         ok,
         error,
+        // Pipeline stages
+        sma,
+        ema,
+        wma,
+        dema,
+        tema,
+        trima,
+        kama,
+        t3,
+        rsi,
+        // Missing input policies of state-based functions
+        skip,
+        carry_forward,
+        reject,
+        // EMA seed modes, along with sma
+        first,
     }
 }
 
+#[cfg(has_talib)]
+mod ffi;
+#[cfg(has_talib)]
+mod math_ffi;
+#[cfg(has_talib)]
+mod momentum_ffi;
 #[cfg(has_talib)]
 mod overlap_ffi;
+#[cfg(has_talib)]
+mod stats_ffi;
+#[cfg(has_talib)]
+mod volatility_ffi;
+#[cfg(has_talib)]
+mod volume_ffi;
 
 #[macro_use]
 mod helpers;
 
+mod batch;
+mod info;
+mod math;
+mod math_state;
+mod momentum;
+mod momentum_state;
 mod overlap;
 mod overlap_state;
+mod price;
+mod price_state;
+mod selftest;
+mod series;
+mod stats;
+mod stats_state;
+mod volatility;
+mod volatility_state;
+mod volume;
+mod volume_state;
 
 rustler::init!("Elixir.TheoryCraftTA.Native", load = load);
 
 #[allow(non_local_definitions)]
 fn load(env: Env, _: Term) -> bool {
+    // TA-Lib must be initialized before any other call. Rustler has no unload
+    // hook, so TA_Shutdown is left to the process exit.
+    #[cfg(has_talib)]
+    {
+        #[allow(clippy::unnecessary_cast)]
+        let ret_code = unsafe { ffi::TA_Initialize() } as i32;
+
+        if ret_code != ffi::TARetCode::Success as i32 {
+            return false;
+        }
+    }
+
     let _ = rustler::resource!(overlap_state::SMAState, env);
     let _ = rustler::resource!(overlap_state::EMAState, env);
     let _ = rustler::resource!(overlap_state::WMAState, env);
     let _ = rustler::resource!(overlap_state::DEMAState, env);
     let _ = rustler::resource!(overlap_state::TEMAState, env);
+    let _ = rustler::resource!(momentum_state::TRIXState, env);
     let _ = rustler::resource!(overlap_state::TRIMAState, env);
     let _ = rustler::resource!(overlap_state::MIDPOINTState, env);
     let _ = rustler::resource!(overlap_state::T3State, env);
+    let _ = rustler::resource!(overlap_state::KAMAState, env);
+    let _ = rustler::resource!(momentum_state::RSIState, env);
+    let _ = rustler::resource!(volatility_state::ATRState, env);
+    let _ = rustler::resource!(volatility_state::NATRState, env);
+    let _ = rustler::resource!(volatility_state::TRANGEState, env);
+    let _ = rustler::resource!(momentum_state::ADXState, env);
+    let _ = rustler::resource!(momentum_state::DXState, env);
+    let _ = rustler::resource!(stats_state::STDDEVState, env);
+    let _ = rustler::resource!(stats_state::VARState, env);
+    let _ = rustler::resource!(momentum_state::MOMState, env);
+    let _ = rustler::resource!(momentum_state::ROCState, env);
+    let _ = rustler::resource!(momentum_state::ROCPState, env);
+    let _ = rustler::resource!(momentum_state::ROCRState, env);
+    let _ = rustler::resource!(momentum_state::ROCR100State, env);
+    let _ = rustler::resource!(momentum_state::ULTOSCState, env);
+    let _ = rustler::resource!(momentum_state::AROONState, env);
+    let _ = rustler::resource!(math_state::MAXState, env);
+    let _ = rustler::resource!(math_state::MINState, env);
+    let _ = rustler::resource!(math_state::SUMState, env);
+    let _ = rustler::resource!(stats_state::LINEARREGState, env);
+    let _ = rustler::resource!(stats_state::TSFState, env);
+    let _ = rustler::resource!(stats_state::CORRELState, env);
+    let _ = rustler::resource!(stats_state::BETAState, env);
+    let _ = rustler::resource!(overlap_state::MIDPRICEState, env);
+    let _ = rustler::resource!(volume_state::OBVState, env);
+    let _ = rustler::resource!(volume_state::ADState, env);
+    let _ = rustler::resource!(volume_state::ADOSCState, env);
+    let _ = rustler::resource!(volume_state::MFIState, env);
+    let _ = rustler::resource!(overlap_state::BBANDSState, env);
+    let _ = rustler::resource!(momentum_state::CCIState, env);
+    let _ = rustler::resource!(momentum_state::WILLRState, env);
+    let _ = rustler::resource!(momentum_state::MACDState, env);
+    let _ = rustler::resource!(overlap_state::MAState, env);
+    let _ = rustler::resource!(momentum_state::APOState, env);
+    let _ = rustler::resource!(momentum_state::PPOState, env);
+    let _ = rustler::resource!(overlap_state::RMAState, env);
+    let _ = rustler::resource!(overlap_state::ZLEMAState, env);
+    let _ = rustler::resource!(overlap_state::SuperTrendState, env);
+    let _ = rustler::resource!(price_state::HeikinAshiState, env);
+    let _ = rustler::resource!(price_state::TYPPRICEState, env);
+    let _ = rustler::resource!(price_state::MEDPRICEState, env);
+    let _ = rustler::resource!(price_state::WCLPRICEState, env);
+    let _ = rustler::resource!(stats_state::MedianState, env);
+    let _ = rustler::resource!(momentum_state::CMOState, env);
+    let _ = rustler::resource!(overlap_state::McGinleyState, env);
+    let _ = rustler::resource!(overlap_state::PipelineState, env);
     true
 }
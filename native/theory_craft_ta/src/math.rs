@@ -0,0 +1,241 @@
+// Implementation when ta-lib is available
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_minmaxindex(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<crate::helpers::IndexPairOutput, String> {
+    use crate::helpers::{build_result_i32, check_begidx, options_to_nan};
+    use crate::math_ffi::{TA_MINMAXINDEX_Lookback, TA_MINMAXINDEX};
+
+    if period < 2 {
+        return Err("MINMAXINDEX: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok((vec![None; length], vec![None; length])),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_MINMAXINDEX_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok((vec![None; length], vec![None; length]));
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_min_idx: Vec<i32> = vec![0; valid_data_len];
+    let mut out_max_idx: Vec<i32> = vec![0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_MINMAXINDEX(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_min_idx.as_mut_ptr(),
+            out_max_idx.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "MINMAXINDEX");
+
+    // The indices are relative to the sliced input: shift them back onto the
+    // original series so they can be used to index it directly
+    for index in out_min_idx.iter_mut().chain(out_max_idx.iter_mut()) {
+        *index += begidx as i32;
+    }
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let min_idx = build_result_i32(first_idx, out_nb_element, &out_min_idx, "MINMAXINDEX")?;
+    let max_idx = build_result_i32(first_idx, out_nb_element, &out_max_idx, "MINMAXINDEX")?;
+
+    Ok((min_idx, max_idx))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_sum(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::math_ffi::{TA_SUM_Lookback, TA_SUM};
+
+    if period < 2 {
+        return Err("SUM: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_SUM_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_SUM(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "SUM");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "SUM")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_max(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::math_ffi::{TA_MAX_Lookback, TA_MAX};
+
+    calculate_extremum(data, period, "MAX", TA_MAX, TA_MAX_Lookback)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_min(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::math_ffi::{TA_MIN_Lookback, TA_MIN};
+
+    calculate_extremum(data, period, "MIN", TA_MIN, TA_MIN_Lookback)
+}
+
+/// Signature of the TA-Lib functions taking a single series and a period
+#[cfg(has_talib)]
+type PeriodFn = unsafe extern "C" fn(
+    i32,
+    i32,
+    *const f64,
+    i32,
+    *mut i32,
+    *mut i32,
+    *mut f64,
+) -> crate::ffi::TA_RetCode;
+
+/// Shared body of MAX and MIN, which only differ by their TA-Lib function
+#[cfg(has_talib)]
+fn calculate_extremum(
+    data: Vec<Option<f64>>,
+    period: i32,
+    func_name: &str,
+    ta_func: PeriodFn,
+    ta_lookback: unsafe extern "C" fn(i32) -> i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+
+    if period < 2 {
+        return Err(format!("{}: period must be >= 2", func_name));
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { ta_lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        ta_func(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, func_name);
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, func_name)?;
+
+    Ok(result)
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_minmaxindex(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<crate::helpers::IndexPairOutput, String> {
+    Err("MINMAXINDEX: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_sum(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("SUM: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_max(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("MAX: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_min(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("MIN: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
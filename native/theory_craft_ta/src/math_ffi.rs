@@ -0,0 +1,9 @@
+// FFI declarations for TA-Lib math operator functions
+//
+// Re-exports the bindings generated from the TA-Lib headers (see ffi.rs).
+// Only compiled when ta-lib is available (has_talib cfg flag).
+
+pub use crate::ffi::{
+    TA_MAX_Lookback, TA_MINMAXINDEX_Lookback, TA_MIN_Lookback, TA_SUM_Lookback, TA_MAX, TA_MIN,
+    TA_MINMAXINDEX, TA_SUM,
+};
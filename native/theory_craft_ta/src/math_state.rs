@@ -0,0 +1,354 @@
+use crate::overlap_state::MonotonicDeque;
+#[cfg(has_talib)]
+use crate::overlap_state::{monotonic_evict, monotonic_push, validate_recompute_interval};
+use rustler::ResourceArc;
+#[cfg(not(has_talib))]
+use rustler::Term;
+use std::collections::VecDeque;
+
+/// Rolling extremum window shared by MAX and MIN
+#[derive(Clone)]
+struct ExtremumWindow {
+    period: i32,
+    lookback_count: i32,
+    current: f64,            // Value of current bar (can change in UPDATE mode)
+    extrema: MonotonicDeque, // Dominating values of the closed bars in the window
+    dominates: fn(f64, f64) -> bool,
+}
+
+/// State for MAX calculation
+pub struct MAXState {
+    window: ExtremumWindow,
+}
+
+/// State for MIN calculation
+pub struct MINState {
+    window: ExtremumWindow,
+}
+
+/// State for SUM calculation
+pub struct SUMState {
+    period: i32,
+    lookback_count: i32,
+    sum: f64,                // Sum of the window, current bar excluded
+    buffer: VecDeque<f64>,   // Last 'period' values, current bar included
+    recompute_interval: i32, // Bars between two rebuilds of the sum from the buffer
+}
+
+/// Creates an extremum window, `dominates(a, b)` telling whether `a` replaces `b`
+#[cfg(has_talib)]
+fn new_extremum_window(period: i32, dominates: fn(f64, f64) -> bool) -> ExtremumWindow {
+    ExtremumWindow {
+        period,
+        lookback_count: 0,
+        current: f64::NAN,
+        extrema: VecDeque::with_capacity(period as usize),
+        dominates,
+    }
+}
+
+/// Advances a rolling extremum window by one value
+///
+/// Same scheme as the highest high / lowest low window: the deque only holds
+/// closed bars, so UPDATE mode just replaces the current value. On ties the
+/// newest bar is kept, as it stays in the window the longest.
+#[cfg(has_talib)]
+fn extremum_window_next(
+    window: &ExtremumWindow,
+    value: f64,
+    is_new_bar: bool,
+) -> (Option<f64>, ExtremumWindow) {
+    let mut new_window = window.clone();
+
+    // A value set by an UPDATE before the first bar is not a bar and is dropped
+    if is_new_bar {
+        if window.lookback_count > 0 {
+            monotonic_push(
+                &mut new_window.extrema,
+                window.lookback_count,
+                window.current,
+                window.dominates,
+            );
+        }
+
+        // Closed bars still in the window: the 'period - 1' before the new one
+        let oldest_index = window.lookback_count + 2 - window.period;
+        monotonic_evict(&mut new_window.extrema, oldest_index);
+
+        new_window.lookback_count += 1;
+    }
+
+    new_window.current = value;
+
+    // Warmup phase: need 'period' bars
+    if new_window.lookback_count < window.period {
+        return (None, new_window);
+    }
+
+    let extremum = match new_window.extrema.front() {
+        Some(&(_, front)) if !(window.dominates)(value, front) => front,
+        _ => value,
+    };
+
+    (Some(extremum), new_window)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_max_state_init(period: i32) -> Result<ResourceArc<MAXState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for MAX".to_string());
+    }
+
+    let state = MAXState {
+        window: new_extremum_window(period, |v, back| v >= back),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_max_state_next(
+    state_arc: ResourceArc<MAXState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MAXState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (max, new_window) = extremum_window_next(&state.window, value, is_new_bar);
+
+    let new_state = MAXState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((max, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_min_state_init(period: i32) -> Result<ResourceArc<MINState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for MIN".to_string());
+    }
+
+    let state = MINState {
+        window: new_extremum_window(period, |v, back| v <= back),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_min_state_next(
+    state_arc: ResourceArc<MINState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MINState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (min, new_window) = extremum_window_next(&state.window, value, is_new_bar);
+
+    let new_state = MINState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((min, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_sum_state_init(period: i32) -> Result<ResourceArc<SUMState>, String> {
+    // Rebuild the sum once per window turnover by default
+    let state = new_sum_state(period, period)?;
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif(name = "math_sum_state_init")]
+pub fn math_sum_state_init_with_recompute_interval(
+    period: i32,
+    recompute_interval: i32,
+) -> Result<ResourceArc<SUMState>, String> {
+    let state = new_sum_state(period, recompute_interval)?;
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+fn new_sum_state(period: i32, recompute_interval: i32) -> Result<SUMState, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for SUM".to_string());
+    }
+
+    validate_recompute_interval(recompute_interval, "SUM")?;
+
+    Ok(SUMState {
+        period,
+        lookback_count: 0,
+        sum: 0.0,
+        buffer: VecDeque::with_capacity(period as usize),
+        recompute_interval,
+    })
+}
+
+/// Advances a rolling sum by one value
+///
+/// Same scheme as the variance window: the running sum excludes the current
+/// bar, so UPDATE mode just replaces it. Adding and subtracting values lets
+/// rounding errors pile up, so the running sum is recomputed from the buffer
+/// every `recompute_interval` bars, once per window unless set at init: a
+/// smaller interval trades speed for accuracy.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn math_sum_state_next(
+    state_arc: ResourceArc<SUMState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<SUMState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let mut sum = state.sum;
+    let mut buffer = state.buffer.clone();
+    let mut lookback_count = state.lookback_count;
+
+    if is_new_bar {
+        if lookback_count == 0 {
+            // A value pushed by an UPDATE before the first bar is not a bar
+            buffer.clear();
+        } else if let Some(&current) = buffer.back() {
+            // The current bar is closed: move it into the running sum
+            sum += current;
+
+            // Drop the trailing value once the window is full
+            if buffer.len() == state.period as usize {
+                sum -= buffer.pop_front().unwrap_or_default();
+            }
+
+            // Drift correction, once every 'recompute_interval' bars
+            if lookback_count % state.recompute_interval == 0 {
+                sum = buffer.iter().sum();
+            }
+        }
+
+        buffer.push_back(value);
+        lookback_count += 1;
+    } else if let Some(last) = buffer.back_mut() {
+        // UPDATE mode: replace the current bar, the sum doesn't include it
+        *last = value;
+    } else {
+        buffer.push_back(value);
+    }
+
+    // Warmup phase: need 'period' values
+    let total = if lookback_count < state.period {
+        None
+    } else {
+        Some(sum + value)
+    };
+
+    let new_state = SUMState {
+        period: state.period,
+        lookback_count,
+        sum,
+        buffer,
+        recompute_interval: state.recompute_interval,
+    };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((total, new_resource))
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_max_state_init(_period: i32) -> Result<ResourceArc<MAXState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_max_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MAXState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_min_state_init(_period: i32) -> Result<ResourceArc<MINState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_min_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MINState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_sum_state_init(_period: i32) -> Result<ResourceArc<SUMState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "math_sum_state_init")]
+pub fn math_sum_state_init_with_recompute_interval(
+    _period: i32,
+    _recompute_interval: i32,
+) -> Result<ResourceArc<SUMState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn math_sum_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<SUMState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
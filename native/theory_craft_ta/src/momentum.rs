@@ -0,0 +1,1359 @@
+// Implementation when ta-lib is available
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_stochf(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    fastk_period: i32,
+    fastd_period: i32,
+    fastd_ma_type: i32,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::momentum_ffi::{TA_STOCHF_Lookback, TA_STOCHF};
+
+    if fastk_period < 1 || fastd_period < 1 {
+        return Err("STOCHF: fastk_period and fastd_period must be >= 1".to_string());
+    }
+
+    if !(0..=8).contains(&fastd_ma_type) {
+        return Err("STOCHF: fastd_ma_type must be between 0 and 8".to_string());
+    }
+
+    validate_inputs(
+        "STOCHF",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok((vec![None; length], vec![None; length])),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_STOCHF_Lookback(fastk_period, fastd_period, fastd_ma_type) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok((vec![None; length], vec![None; length]));
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_fastk: Vec<f64> = vec![0.0; valid_data_len];
+    let mut out_fastd: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_STOCHF(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            fastk_period,
+            fastd_period,
+            fastd_ma_type,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_fastk.as_mut_ptr(),
+            out_fastd.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "STOCHF");
+
+    // Both outputs share the lookback of %D: %K values before it are not returned
+    let first_idx = begidx as i32 + out_beg_idx;
+    let fastk = build_result(first_idx, out_nb_element, &out_fastk, "STOCHF")?;
+    let fastd = build_result(first_idx, out_nb_element, &out_fastd, "STOCHF")?;
+
+    Ok((fastk, fastd))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_stochrsi(
+    data: Vec<Option<f64>>,
+    period: i32,
+    fastk_period: i32,
+    fastd_period: i32,
+    fastd_ma_type: i32,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::momentum_ffi::{TA_STOCHRSI_Lookback, TA_STOCHRSI};
+
+    if period < 2 {
+        return Err("STOCHRSI: period must be >= 2".to_string());
+    }
+
+    if fastk_period < 1 || fastd_period < 1 {
+        return Err("STOCHRSI: fastk_period and fastd_period must be >= 1".to_string());
+    }
+
+    if !(0..=8).contains(&fastd_ma_type) {
+        return Err("STOCHRSI: fastd_ma_type must be between 0 and 8".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok((vec![None; length], vec![None; length])),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    // RSI lookback, then the %K window over the RSI, then the %D smoothing
+    let lookback =
+        unsafe { TA_STOCHRSI_Lookback(period, fastk_period, fastd_period, fastd_ma_type) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok((vec![None; length], vec![None; length]));
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_fastk: Vec<f64> = vec![0.0; valid_data_len];
+    let mut out_fastd: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_STOCHRSI(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            fastk_period,
+            fastd_period,
+            fastd_ma_type,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_fastk.as_mut_ptr(),
+            out_fastd.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "STOCHRSI");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let fastk = build_result(first_idx, out_nb_element, &out_fastk, "STOCHRSI")?;
+    let fastd = build_result(first_idx, out_nb_element, &out_fastd, "STOCHRSI")?;
+
+    Ok((fastk, fastd))
+}
+
+/// Aroon down and up: how recent the lowest low and the highest high are
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_aroon(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    period: i32,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::momentum_ffi::{TA_AROON_Lookback, TA_AROON};
+
+    if period < 2 {
+        return Err("AROON: period must be >= 2".to_string());
+    }
+
+    validate_inputs("AROON", &[("high", high.len()), ("low", low.len())])?;
+
+    if high.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let length = clean_high.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low]) {
+        Some(idx) => idx,
+        None => return Ok((vec![None; length], vec![None; length])),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_AROON_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok((vec![None; length], vec![None; length]));
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_down: Vec<f64> = vec![0.0; valid_data_len];
+    let mut out_up: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_AROON(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_down.as_mut_ptr(),
+            out_up.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "AROON");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let down = build_result(first_idx, out_nb_element, &out_down, "AROON")?;
+    let up = build_result(first_idx, out_nb_element, &out_up, "AROON")?;
+
+    Ok((down, up))
+}
+
+/// Ultimate Oscillator: weighted average of the buying pressure over three windows
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_ultosc(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period1: i32,
+    period2: i32,
+    period3: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::momentum_ffi::{TA_ULTOSC_Lookback, TA_ULTOSC};
+
+    if period1 < 1 || period2 < 1 || period3 < 1 {
+        return Err("ULTOSC: periods must be >= 1".to_string());
+    }
+
+    validate_inputs(
+        "ULTOSC",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_ULTOSC_Lookback(period1, period2, period3) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_ULTOSC(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            period1,
+            period2,
+            period3,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "ULTOSC");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "ULTOSC")?;
+
+    Ok(result)
+}
+
+/// Signature of the TA-Lib functions taking a single series and a period
+#[cfg(has_talib)]
+type PeriodFn = unsafe extern "C" fn(
+    i32,
+    i32,
+    *const f64,
+    i32,
+    *mut i32,
+    *mut i32,
+    *mut f64,
+) -> crate::ffi::TA_RetCode;
+
+/// Rate of change as a proportion: (price - prev) / prev
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocp(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::momentum_ffi::{TA_ROCP_Lookback, TA_ROCP};
+
+    calculate_rate_of_change(data, period, "ROCP", TA_ROCP, TA_ROCP_Lookback)
+}
+
+/// Rate of change as a ratio: price / prev
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocr(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::momentum_ffi::{TA_ROCR_Lookback, TA_ROCR};
+
+    calculate_rate_of_change(data, period, "ROCR", TA_ROCR, TA_ROCR_Lookback)
+}
+
+/// Rate of change as a ratio in percent: price / prev * 100
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocr100(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::momentum_ffi::{TA_ROCR100_Lookback, TA_ROCR100};
+
+    calculate_rate_of_change(data, period, "ROCR100", TA_ROCR100, TA_ROCR100_Lookback)
+}
+
+/// Shared body of the ROC variants, which only differ by their TA-Lib function
+#[cfg(has_talib)]
+fn calculate_rate_of_change(
+    data: Vec<Option<f64>>,
+    period: i32,
+    func_name: &str,
+    ta_func: PeriodFn,
+    ta_lookback: unsafe extern "C" fn(i32) -> i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+
+    if period < 1 {
+        return Err(format!("{}: period must be >= 1", func_name));
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { ta_lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        ta_func(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, func_name);
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, func_name)?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rsi(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    calculate_rsi(data, period)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rsi_f32(data: Vec<Option<f32>>, period: i32) -> Result<Vec<Option<f32>>, String> {
+    crate::helpers::with_f32_series(&data, |data| calculate_rsi(data, period))
+}
+
+#[cfg(has_talib)]
+pub(crate) fn calculate_rsi(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::momentum_ffi::{TA_RSI_Lookback, TA_RSI};
+
+    if period < 2 {
+        return Err("RSI: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_RSI_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_RSI(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "RSI");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "RSI")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_mom(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::momentum_ffi::{TA_MOM_Lookback, TA_MOM};
+
+    if period < 1 {
+        return Err("MOM: period must be >= 1".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_MOM_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_MOM(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "MOM");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "MOM")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_roc(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::momentum_ffi::{TA_ROC_Lookback, TA_ROC};
+
+    if period < 1 {
+        return Err("ROC: period must be >= 1".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_ROC_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_ROC(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "ROC");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "ROC")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_cci(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::momentum_ffi::{TA_CCI_Lookback, TA_CCI};
+
+    if period < 2 {
+        return Err("CCI: period must be >= 2".to_string());
+    }
+
+    validate_inputs(
+        "CCI",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_CCI_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_CCI(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "CCI");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "CCI")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_willr(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::momentum_ffi::{TA_WILLR_Lookback, TA_WILLR};
+
+    if period < 2 {
+        return Err("WILLR: period must be >= 2".to_string());
+    }
+
+    validate_inputs(
+        "WILLR",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_WILLR_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_WILLR(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "WILLR");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "WILLR")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_macd(
+    data: Vec<Option<f64>>,
+    fast_period: i32,
+    slow_period: i32,
+    signal_period: i32,
+) -> Result<crate::helpers::TripleOutput, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::momentum_ffi::{TA_MACD_Lookback, TA_MACD};
+
+    if fast_period < 2 || slow_period < 2 {
+        return Err("MACD: fast_period and slow_period must be >= 2".to_string());
+    }
+
+    if signal_period < 1 {
+        return Err("MACD: signal_period must be >= 1".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok((Vec::new(), Vec::new(), Vec::new()));
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok((vec![None; length], vec![None; length], vec![None; length])),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_MACD_Lookback(fast_period, slow_period, signal_period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok((vec![None; length], vec![None; length], vec![None; length]));
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_macd: Vec<f64> = vec![0.0; valid_data_len];
+    let mut out_signal: Vec<f64> = vec![0.0; valid_data_len];
+    let mut out_hist: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_MACD(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            fast_period,
+            slow_period,
+            signal_period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_macd.as_mut_ptr(),
+            out_signal.as_mut_ptr(),
+            out_hist.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "MACD");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let macd = build_result(first_idx, out_nb_element, &out_macd, "MACD")?;
+    let signal = build_result(first_idx, out_nb_element, &out_signal, "MACD")?;
+    let hist = build_result(first_idx, out_nb_element, &out_hist, "MACD")?;
+
+    Ok((macd, signal, hist))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_apo(
+    data: Vec<Option<f64>>,
+    fast_period: i32,
+    slow_period: i32,
+    ma_type: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::momentum_ffi::{TA_APO_Lookback, TA_APO};
+
+    if fast_period < 2 || slow_period < 2 {
+        return Err("APO: fast_period and slow_period must be >= 2".to_string());
+    }
+
+    // TA_MAType: SMA, EMA, WMA, DEMA, TEMA, TRIMA, KAMA, MAMA, T3
+    if !(0..=8).contains(&ma_type) {
+        return Err("APO: ma_type must be between 0 and 8".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_APO_Lookback(fast_period, slow_period, ma_type) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_APO(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            fast_period,
+            slow_period,
+            ma_type,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "APO");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "APO")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_ppo(
+    data: Vec<Option<f64>>,
+    fast_period: i32,
+    slow_period: i32,
+    ma_type: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::momentum_ffi::{TA_PPO_Lookback, TA_PPO};
+
+    if fast_period < 2 || slow_period < 2 {
+        return Err("PPO: fast_period and slow_period must be >= 2".to_string());
+    }
+
+    // TA_MAType: SMA, EMA, WMA, DEMA, TEMA, TRIMA, KAMA, MAMA, T3
+    if !(0..=8).contains(&ma_type) {
+        return Err("PPO: ma_type must be between 0 and 8".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_PPO_Lookback(fast_period, slow_period, ma_type) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_PPO(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            fast_period,
+            slow_period,
+            ma_type,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "PPO");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "PPO")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_trix(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::momentum_ffi::{TA_TRIX_Lookback, TA_TRIX};
+
+    if period < 2 {
+        return Err("TRIX: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_TRIX_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_TRIX(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "TRIX");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "TRIX")?;
+
+    Ok(result)
+}
+
+/// Chande Momentum Oscillator (CMO)
+///
+/// `100 * (su - sd) / (su + sd)` with the sums of the up and down moves over the
+/// last `period` deltas. TA-Lib's CMO smooths the moves like RSI instead, so
+/// this one runs in Rust on the same steps as the streaming state.
+#[rustler::nif]
+pub fn momentum_cmo(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::momentum_state::{new_cmo_state, step_cmo};
+
+    let mut state = new_cmo_state(period).map_err(|_| "CMO: period must be >= 2".to_string())?;
+
+    // Missing values are skipped like nil in the streaming state
+    let result = data
+        .into_iter()
+        .map(|value| {
+            let value = value.filter(|v| !v.is_nan())?;
+            let (cmo, new_state) = step_cmo(&state, value, true);
+            state = new_state;
+            cmo
+        })
+        .collect();
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_adx(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::momentum_ffi::{TA_ADX_Lookback, TA_ADX};
+
+    if period < 2 {
+        return Err("ADX: period must be >= 2".to_string());
+    }
+
+    validate_inputs(
+        "ADX",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_ADX_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_ADX(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "ADX");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "ADX")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_dx(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::momentum_ffi::{TA_DX_Lookback, TA_DX};
+
+    if period < 2 {
+        return Err("DX: period must be >= 2".to_string());
+    }
+
+    validate_inputs(
+        "DX",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_DX_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_DX(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "DX");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "DX")?;
+
+    Ok(result)
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_stochf(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _fastk_period: i32,
+    _fastd_period: i32,
+    _fastd_ma_type: i32,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    Err("STOCHF: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_stochrsi(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+    _fastk_period: i32,
+    _fastd_period: i32,
+    _fastd_ma_type: i32,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    Err("STOCHRSI: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_aroon(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    Err("AROON: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_ultosc(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period1: i32,
+    _period2: i32,
+    _period3: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("ULTOSC: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocp(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("ROCP: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocr(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("ROCR: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocr100(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("ROCR100: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rsi(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("RSI: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rsi_f32(_data: Vec<Option<f32>>, _period: i32) -> Result<Vec<Option<f32>>, String> {
+    Err("RSI: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_mom(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("MOM: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_roc(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("ROC: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_cci(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("CCI: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_willr(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("WILLR: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_macd(
+    _data: Vec<Option<f64>>,
+    _fast_period: i32,
+    _slow_period: i32,
+    _signal_period: i32,
+) -> Result<crate::helpers::TripleOutput, String> {
+    Err("MACD: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_apo(
+    _data: Vec<Option<f64>>,
+    _fast_period: i32,
+    _slow_period: i32,
+    _ma_type: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("APO: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_ppo(
+    _data: Vec<Option<f64>>,
+    _fast_period: i32,
+    _slow_period: i32,
+    _ma_type: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("PPO: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_trix(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("TRIX: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_adx(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("ADX: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_dx(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("DX: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
@@ -0,0 +1,13 @@
+// FFI declarations for TA-Lib momentum functions
+//
+// Re-exports the bindings generated from the TA-Lib headers (see ffi.rs).
+// Only compiled when ta-lib is available (has_talib cfg flag).
+
+pub use crate::ffi::{
+    TA_ADX_Lookback, TA_APO_Lookback, TA_AROON_Lookback, TA_CCI_Lookback, TA_DX_Lookback,
+    TA_MACD_Lookback, TA_MOM_Lookback, TA_PPO_Lookback, TA_ROCP_Lookback, TA_ROCR100_Lookback,
+    TA_ROCR_Lookback, TA_ROC_Lookback, TA_RSI_Lookback, TA_STOCHF_Lookback, TA_STOCHRSI_Lookback,
+    TA_TRIX_Lookback, TA_ULTOSC_Lookback, TA_WILLR_Lookback, TA_ADX, TA_APO, TA_AROON, TA_CCI,
+    TA_DX, TA_MACD, TA_MOM, TA_PPO, TA_ROC, TA_ROCP, TA_ROCR, TA_ROCR100, TA_RSI, TA_STOCHF,
+    TA_STOCHRSI, TA_TRIX, TA_ULTOSC, TA_WILLR,
+};
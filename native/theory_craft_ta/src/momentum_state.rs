@@ -0,0 +1,1994 @@
+#[cfg(has_talib)]
+use crate::overlap_state::{
+    high_low_window_next, new_ema_state, new_high_low_window, new_ma_state, step_ma,
+    step_nested_ema, DEFAULT_RECOMPUTE_INTERVAL,
+};
+use crate::overlap_state::{EMAState, HighLowWindow, MAState};
+#[cfg(has_talib)]
+use crate::volatility_state::true_range;
+use rustler::ResourceArc;
+#[cfg(not(has_talib))]
+use rustler::Term;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// State for TRIX calculation
+pub struct TRIXState {
+    ema1_state: Arc<EMAState>,
+    ema2_state: Arc<EMAState>,
+    ema3_state: Arc<EMAState>,
+    current_ema3: Option<f64>, // Triple EMA of current bar (can change in UPDATE mode)
+    prev_ema3: Option<f64>,    // Triple EMA of previous bar (persisted in APPEND mode)
+}
+
+/// State for RSI calculation
+#[derive(Clone)]
+pub struct RSIState {
+    period: i32,
+    lookback_count: i32,
+    current_avg_gain: Option<f64>, // Averages of current bar (can change in UPDATE mode)
+    current_avg_loss: Option<f64>,
+    prev_avg_gain: Option<f64>, // Averages of previous bar (persisted in APPEND mode)
+    prev_avg_loss: Option<f64>,
+    current_value: Option<f64>, // Value of current bar (can change in UPDATE mode)
+    prev_value: Option<f64>,    // Value of previous bar (persisted in APPEND mode)
+    buffer: Vec<f64>,
+}
+
+/// Wilder accumulators of ADX as of the end of a bar
+#[derive(Clone, Copy)]
+struct ADXAccumulators {
+    bars: i32,        // Number of bars accumulated, including this one
+    high: f64,        // High of this bar
+    low: f64,         // Low of this bar
+    close: f64,       // Close of this bar
+    plus_dm: f64,     // Smoothed +DM
+    minus_dm: f64,    // Smoothed -DM
+    tr: f64,          // Smoothed true range
+    sum_dx: f64,      // Sum of the DX seeding the first ADX
+    dx: Option<f64>,  // DX of this bar, None during warmup
+    adx: Option<f64>, // ADX of this bar, None during warmup
+}
+
+/// State for ADX calculation
+pub struct ADXState {
+    period: i32,
+    current: Option<ADXAccumulators>, // Accumulators of current bar (can change in UPDATE mode)
+    prev: Option<ADXAccumulators>,    // Accumulators of previous bar (persisted in APPEND mode)
+}
+
+/// State for DX calculation (ADX before the final smoothing)
+pub struct DXState {
+    period: i32,
+    current: Option<ADXAccumulators>, // Accumulators of current bar (can change in UPDATE mode)
+    prev: Option<ADXAccumulators>,    // Accumulators of previous bar (persisted in APPEND mode)
+}
+
+/// Rolling window shared by MOM and ROC
+#[derive(Clone)]
+pub(crate) struct MomentumWindow {
+    period: i32,
+    lookback_count: i32,
+    buffer: VecDeque<f64>, // Last 'period + 1' values, current bar included
+}
+
+/// State for MOM calculation
+pub struct MOMState {
+    window: MomentumWindow,
+}
+
+/// State for ROC calculation
+pub struct ROCState {
+    window: MomentumWindow,
+}
+
+/// State for ROCP calculation
+pub struct ROCPState {
+    window: MomentumWindow,
+}
+
+/// State for ROCR calculation
+pub struct ROCRState {
+    window: MomentumWindow,
+}
+
+/// State for ROCR100 calculation
+pub struct ROCR100State {
+    window: MomentumWindow,
+}
+
+/// Buying pressure (close - true low) and true range of a bar
+type ULTOSCTerm = (f64, f64);
+
+/// State for ULTOSC calculation
+pub struct ULTOSCState {
+    periods: [usize; 3], // Sorted from shortest to longest, as TA-Lib does
+    lookback_count: i32,
+    prev_close: Option<f64>,          // Close of the last closed bar
+    current_close: f64,               // Close of current bar (can change in UPDATE mode)
+    current_term: Option<ULTOSCTerm>, // Term of current bar, None without a previous close
+    terms: VecDeque<ULTOSCTerm>,      // Terms of the closed bars in the longest window
+    sums: [ULTOSCTerm; 3],            // Sums of each window, current bar excluded
+}
+
+/// State for CCI calculation
+pub struct CCIState {
+    period: i32,
+    lookback_count: i32,
+    buffer: VecDeque<f64>, // Typical prices of the last 'period' bars, current bar included
+}
+
+/// State for WILLR calculation
+pub struct WILLRState {
+    window: HighLowWindow,
+}
+
+/// State for AROON calculation
+pub struct AROONState {
+    period: i32,
+    window: HighLowWindow, // Spans 'period + 1' bars, as TA-Lib does
+}
+
+/// State for MACD calculation
+pub struct MACDState {
+    fast_period: i32,
+    slow_period: i32,
+    signal_period: i32,
+    lookback_count: i32,
+    fast_ema_state: Arc<EMAState>,
+    slow_ema_state: Arc<EMAState>,
+    signal_ema_state: Arc<EMAState>, // EMA of the MACD line
+}
+
+/// Fast and slow moving averages shared by APO and PPO
+struct PriceOscillator {
+    fast_skip: i32, // Bars before the fast MA starts, so both MAs get ready on the same bar
+    lookback_count: i32,
+    fast_ma_state: MAState,
+    slow_ma_state: MAState,
+}
+
+/// State for APO calculation
+pub struct APOState {
+    oscillator: PriceOscillator,
+}
+
+/// State for PPO calculation
+pub struct PPOState {
+    oscillator: PriceOscillator,
+}
+
+/// State for CMO calculation
+#[derive(Clone)]
+pub struct CMOState {
+    period: i32,
+    deltas: VecDeque<f64>,      // Closed deltas, the last 'period - 1' ones
+    sum_up: f64,                // Sum of the up-moves of the closed deltas
+    sum_down: f64,              // Sum of the down-moves of the closed deltas
+    current_delta: Option<f64>, // Delta of current bar (can change in UPDATE mode)
+    current_value: Option<f64>, // Value of current bar (can change in UPDATE mode)
+    prev_value: Option<f64>,    // Value of previous bar (persisted in APPEND mode)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_trix_state_init(period: i32) -> Result<ResourceArc<TRIXState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for TRIX".to_string());
+    }
+
+    let state = TRIXState {
+        ema1_state: Arc::new(new_ema_state(period)),
+        ema2_state: Arc::new(new_ema_state(period)),
+        ema3_state: Arc::new(new_ema_state(period)),
+        current_ema3: None,
+        prev_ema3: None,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_trix_state_next(
+    state_arc: ResourceArc<TRIXState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TRIXState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    // Each EMA is only fed once the previous one is past its warmup
+    let (ema1_value, new_ema1_state) = step_nested_ema(&state.ema1_state, Some(value), is_new_bar);
+
+    let (ema2_value, new_ema2_state) = step_nested_ema(&state.ema2_state, ema1_value, is_new_bar);
+
+    let (ema3_value, new_ema3_state) = step_nested_ema(&state.ema3_state, ema2_value, is_new_bar);
+
+    // In APPEND mode the current triple EMA becomes the previous one,
+    // in UPDATE mode the previous one stays the same
+    let new_prev_ema3 = if is_new_bar {
+        state.current_ema3
+    } else {
+        state.prev_ema3
+    };
+
+    // 1-bar ROC of the triple EMA, which needs one more bar after its warmup.
+    // Same as TA-Lib: a zero previous value gives 0 instead of dividing by zero
+    let trix = match (ema3_value, new_prev_ema3) {
+        (Some(ema3), Some(prev)) if prev != 0.0 => Some(((ema3 / prev) - 1.0) * 100.0),
+        (Some(_), Some(_)) => Some(0.0),
+        _ => None,
+    };
+
+    let new_state = TRIXState {
+        ema1_state: new_ema1_state,
+        ema2_state: new_ema2_state,
+        ema3_state: new_ema3_state,
+        current_ema3: ema3_value,
+        prev_ema3: new_prev_ema3,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((trix, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rsi_state_init(period: i32) -> Result<ResourceArc<RSIState>, String> {
+    let state = new_rsi_state(period)?;
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+pub(crate) fn new_rsi_state(period: i32) -> Result<RSIState, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for RSI".to_string());
+    }
+
+    Ok(RSIState {
+        period,
+        lookback_count: 0,
+        current_avg_gain: None,
+        current_avg_loss: None,
+        prev_avg_gain: None,
+        prev_avg_loss: None,
+        current_value: None,
+        prev_value: None,
+        buffer: Vec::new(),
+    })
+}
+
+/// Wilder seed for the first RSI value
+///
+/// Averages the gains and losses of the last `period` deltas of the warmup
+/// buffer (i.e. its last `period + 1` values), like TA-Lib does.
+#[cfg(has_talib)]
+#[inline]
+fn rsi_seed(buffer: &[f64], period: i32) -> (f64, f64) {
+    let start = buffer.len().saturating_sub(period as usize + 1);
+    let (gain, loss) = buffer[start..]
+        .windows(2)
+        .fold((0.0, 0.0), |(gain, loss), w| {
+            let delta = w[1] - w[0];
+            if delta < 0.0 {
+                (gain, loss - delta)
+            } else {
+                (gain + delta, loss)
+            }
+        });
+
+    (gain / period as f64, loss / period as f64)
+}
+
+/// RSI from the smoothed averages (TA-Lib returns 0 when both are ~0)
+#[cfg(has_talib)]
+#[inline]
+fn rsi_from_averages(avg_gain: f64, avg_loss: f64) -> f64 {
+    let total = avg_gain + avg_loss;
+    if total.abs() < 1e-8 {
+        0.0
+    } else {
+        100.0 * (avg_gain / total)
+    }
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rsi_state_next(
+    state_arc: ResourceArc<RSIState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<RSIState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (rsi, new_state) = step_rsi(state, value, is_new_bar);
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((rsi, new_resource))
+}
+
+/// Advances an RSI state by one value
+///
+/// APPEND mode persists the current averages as the previous ones; UPDATE mode
+/// recomputes the current averages from the previous ones.
+#[cfg(has_talib)]
+pub(crate) fn step_rsi(state: &RSIState, value: f64, is_new_bar: bool) -> (Option<f64>, RSIState) {
+    let new_lookback = if is_new_bar {
+        state.lookback_count + 1
+    } else {
+        state.lookback_count
+    };
+
+    // In APPEND mode the current value becomes the previous one,
+    // in UPDATE mode the previous value stays the same
+    let new_prev_value = if is_new_bar {
+        state.current_value
+    } else {
+        state.prev_value
+    };
+
+    // Keep the buffer until both averages are populated (needed to reseed in UPDATE mode)
+    let new_buffer = if new_lookback <= state.period || state.prev_avg_gain.is_none() {
+        let mut buf = state.buffer.clone();
+        if is_new_bar || buf.is_empty() {
+            buf.push(value);
+        } else {
+            let last_idx = buf.len() - 1;
+            buf[last_idx] = value;
+        }
+        buf
+    } else {
+        // After warmup AND both averages calculated - clear buffer to save memory
+        Vec::new()
+    };
+
+    // Warmup phase: need 'period' deltas (period + 1 bars) before the first RSI
+    if new_lookback <= state.period {
+        let new_state = RSIState {
+            period: state.period,
+            lookback_count: new_lookback,
+            current_avg_gain: state.current_avg_gain,
+            current_avg_loss: state.current_avg_loss,
+            prev_avg_gain: state.prev_avg_gain,
+            prev_avg_loss: state.prev_avg_loss,
+            current_value: Some(value),
+            prev_value: new_prev_value,
+            buffer: new_buffer,
+        };
+        return (None, new_state);
+    }
+
+    // Base averages: the ones of the bar before the one being computed
+    let (base_gain, base_loss) = if is_new_bar {
+        (state.current_avg_gain, state.current_avg_loss)
+    } else {
+        (state.prev_avg_gain, state.prev_avg_loss)
+    };
+
+    let (avg_gain, avg_loss) = match (base_gain, base_loss, new_prev_value) {
+        // Wilder smoothing: avg = (prev_avg * (period - 1) + current) / period
+        (Some(gain), Some(loss), Some(prev_value)) => {
+            let period = state.period as f64;
+            let delta = value - prev_value;
+            let (up, down) = if delta < 0.0 {
+                (0.0, -delta)
+            } else {
+                (delta, 0.0)
+            };
+            (
+                (gain * (period - 1.0) + up) / period,
+                (loss * (period - 1.0) + down) / period,
+            )
+        }
+        // First RSI: use the simple averages of the warmup deltas as seed
+        _ => rsi_seed(&new_buffer, state.period),
+    };
+
+    let (new_prev_gain, new_prev_loss) = if is_new_bar {
+        // In APPEND: current averages become prev averages for next iteration
+        (state.current_avg_gain, state.current_avg_loss)
+    } else {
+        // In UPDATE: prev averages stay the same
+        (state.prev_avg_gain, state.prev_avg_loss)
+    };
+
+    let rsi = rsi_from_averages(avg_gain, avg_loss);
+
+    let new_state = RSIState {
+        period: state.period,
+        lookback_count: new_lookback,
+        current_avg_gain: Some(avg_gain),
+        current_avg_loss: Some(avg_loss),
+        prev_avg_gain: new_prev_gain,
+        prev_avg_loss: new_prev_loss,
+        current_value: Some(value),
+        prev_value: new_prev_value,
+        buffer: new_buffer,
+    };
+
+    (Some(rsi), new_state)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_adx_state_init(period: i32) -> Result<ResourceArc<ADXState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for ADX".to_string());
+    }
+
+    let state = ADXState {
+        period,
+        current: None,
+        prev: None,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+/// Accumulates one bar on top of the accumulators of the previous bar
+///
+/// Follows the steps of TA-Lib's ADX: the directional movements and true
+/// ranges of the first 'period - 1' deltas are summed, then Wilder-smoothed.
+/// From the 'period'-th delta on, the DX are summed and their average seeds
+/// the ADX at the '2 * period - 1'-th delta, which is then Wilder-smoothed too.
+///
+/// Returns the new accumulators with the +DI and -DI, None during warmup.
+#[cfg(has_talib)]
+fn adx_accumulate(
+    base: Option<&ADXAccumulators>,
+    period: i32,
+    high: f64,
+    low: f64,
+    close: f64,
+) -> (ADXAccumulators, Option<(f64, f64)>) {
+    let is_zero = |value: f64| value.abs() < 1e-8;
+
+    // The first bar only seeds the previous high, low and close
+    let base = match base {
+        Some(base) => base,
+        None => {
+            let accumulators = ADXAccumulators {
+                bars: 1,
+                high,
+                low,
+                close,
+                plus_dm: 0.0,
+                minus_dm: 0.0,
+                tr: 0.0,
+                sum_dx: 0.0,
+                dx: None,
+                adx: None,
+            };
+            return (accumulators, None);
+        }
+    };
+
+    let mut acc = ADXAccumulators {
+        bars: base.bars + 1,
+        high,
+        low,
+        close,
+        ..*base
+    };
+
+    let deltas = base.bars;
+    let period_f = period as f64;
+
+    let diff_plus = high - base.high;
+    let diff_minus = base.low - low;
+    let (plus_dm, minus_dm) = if diff_minus > 0.0 && diff_plus < diff_minus {
+        (0.0, diff_minus)
+    } else if diff_plus > 0.0 && diff_plus > diff_minus {
+        (diff_plus, 0.0)
+    } else {
+        (0.0, 0.0)
+    };
+    let tr = true_range(high, low, base.close);
+
+    if deltas < period {
+        acc.plus_dm += plus_dm;
+        acc.minus_dm += minus_dm;
+        acc.tr += tr;
+    } else {
+        acc.plus_dm = acc.plus_dm - acc.plus_dm / period_f + plus_dm;
+        acc.minus_dm = acc.minus_dm - acc.minus_dm / period_f + minus_dm;
+        acc.tr = acc.tr - acc.tr / period_f + tr;
+    }
+
+    // Warmup phase: need 'period' deltas before the first DI
+    if deltas < period {
+        return (acc, None);
+    }
+
+    let (plus_di, minus_di) = if is_zero(acc.tr) {
+        (0.0, 0.0)
+    } else {
+        (100.0 * acc.plus_dm / acc.tr, 100.0 * acc.minus_dm / acc.tr)
+    };
+
+    // DX is undefined without range or directional movement: ADX is carried over
+    let dx = if is_zero(acc.tr) || is_zero(plus_di + minus_di) {
+        None
+    } else {
+        Some(100.0 * (minus_di - plus_di).abs() / (plus_di + minus_di))
+    };
+
+    // Like TA-Lib's DX, an undefined DX repeats the previous one (0 for the first)
+    acc.dx = Some(dx.or(base.dx).unwrap_or(0.0));
+
+    if deltas < 2 * period - 1 {
+        acc.sum_dx += dx.unwrap_or(0.0);
+    } else if deltas == 2 * period - 1 {
+        acc.sum_dx += dx.unwrap_or(0.0);
+        acc.adx = Some(acc.sum_dx / period_f);
+    } else {
+        acc.adx = match (base.adx, dx) {
+            (Some(adx), Some(dx)) => Some((adx * (period_f - 1.0) + dx) / period_f),
+            (adx, _) => adx,
+        };
+    }
+
+    (acc, Some((plus_di, minus_di)))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn momentum_adx_state_next(
+    state_arc: ResourceArc<ADXState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, Option<f64>, Option<f64>, ResourceArc<ADXState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => (high, low, close),
+        _ => return Ok((None, None, None, state_arc)),
+    };
+
+    // Base accumulators: the ones of the bar before the one being computed
+    let base = if is_new_bar {
+        state.current
+    } else {
+        state.prev
+    };
+
+    let (accumulators, di) = adx_accumulate(base.as_ref(), state.period, high, low, close);
+    let (plus_di, minus_di) = di.unzip();
+
+    let new_state = ADXState {
+        period: state.period,
+        current: Some(accumulators),
+        prev: base,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((accumulators.adx, plus_di, minus_di, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_dx_state_init(period: i32) -> Result<ResourceArc<DXState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for DX".to_string());
+    }
+
+    let state = DXState {
+        period,
+        current: None,
+        prev: None,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_dx_state_next(
+    state_arc: ResourceArc<DXState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<DXState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => (high, low, close),
+        _ => return Ok((None, state_arc)),
+    };
+
+    // Base accumulators: the ones of the bar before the one being computed
+    let base = if is_new_bar {
+        state.current
+    } else {
+        state.prev
+    };
+
+    // Same accumulators as ADX, only the DX is returned
+    let (accumulators, _di) = adx_accumulate(base.as_ref(), state.period, high, low, close);
+
+    let new_state = DXState {
+        period: state.period,
+        current: Some(accumulators),
+        prev: base,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((accumulators.dx, new_resource))
+}
+
+#[cfg(has_talib)]
+pub(crate) fn new_momentum_window(period: i32) -> MomentumWindow {
+    MomentumWindow {
+        period,
+        lookback_count: 0,
+        buffer: VecDeque::with_capacity(period as usize + 1),
+    }
+}
+
+/// Advances a momentum window by one value
+///
+/// Returns the current value and the one `period` bars before it once the
+/// window holds `period + 1` bars. UPDATE mode only replaces the current value.
+#[cfg(has_talib)]
+pub(crate) fn momentum_window_next(
+    window: &MomentumWindow,
+    value: f64,
+    is_new_bar: bool,
+) -> (Option<(f64, f64)>, MomentumWindow) {
+    let mut new_window = window.clone();
+
+    if is_new_bar {
+        if window.lookback_count == 0 {
+            // A value pushed by an UPDATE before the first bar is not a bar
+            new_window.buffer.clear();
+        } else if window.buffer.len() > window.period as usize {
+            new_window.buffer.pop_front();
+        }
+
+        new_window.buffer.push_back(value);
+        new_window.lookback_count += 1;
+    } else if let Some(last) = new_window.buffer.back_mut() {
+        *last = value;
+    } else {
+        new_window.buffer.push_back(value);
+    }
+
+    // Warmup phase: need 'period + 1' values
+    if new_window.lookback_count <= window.period {
+        return (None, new_window);
+    }
+
+    let trailing = new_window.buffer.front().copied().unwrap_or(value);
+
+    (Some((value, trailing)), new_window)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_mom_state_init(period: i32) -> Result<ResourceArc<MOMState>, String> {
+    if period < 1 {
+        return Err("Invalid period: must be >= 1 for MOM".to_string());
+    }
+
+    let state = MOMState {
+        window: new_momentum_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_mom_state_next(
+    state_arc: ResourceArc<MOMState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MOMState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (values, new_window) = momentum_window_next(&state.window, value, is_new_bar);
+    let mom = values.map(|(value, trailing)| value - trailing);
+
+    let new_state = MOMState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((mom, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_roc_state_init(period: i32) -> Result<ResourceArc<ROCState>, String> {
+    if period < 1 {
+        return Err("Invalid period: must be >= 1 for ROC".to_string());
+    }
+
+    let state = ROCState {
+        window: new_momentum_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_roc_state_next(
+    state_arc: ResourceArc<ROCState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ROCState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (values, new_window) = momentum_window_next(&state.window, value, is_new_bar);
+
+    // Same as TA-Lib: a zero trailing value gives 0 instead of dividing by zero
+    let roc = values.map(|(value, trailing)| {
+        if trailing != 0.0 {
+            ((value / trailing) - 1.0) * 100.0
+        } else {
+            0.0
+        }
+    });
+
+    let new_state = ROCState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((roc, new_resource))
+}
+
+/// Advances the momentum window of a ROCP, ROCR or ROCR100 state
+///
+/// `rate` is applied to the current and trailing values. Unlike TA-Lib, which
+/// outputs 0, a zero trailing value gives nil: the rate is undefined.
+#[cfg(has_talib)]
+fn rate_of_change_next(
+    window: &MomentumWindow,
+    value: f64,
+    is_new_bar: bool,
+    rate: fn(f64, f64) -> f64,
+) -> (Option<f64>, MomentumWindow) {
+    let (values, new_window) = momentum_window_next(window, value, is_new_bar);
+
+    let result = values
+        .filter(|&(_, trailing)| trailing != 0.0)
+        .map(|(value, trailing)| rate(value, trailing));
+
+    (result, new_window)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocp_state_init(period: i32) -> Result<ResourceArc<ROCPState>, String> {
+    if period < 1 {
+        return Err("Invalid period: must be >= 1 for ROCP".to_string());
+    }
+
+    let state = ROCPState {
+        window: new_momentum_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocp_state_next(
+    state_arc: ResourceArc<ROCPState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ROCPState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (rate, new_window) =
+        rate_of_change_next(&state.window, value, is_new_bar, |value, trailing| {
+            (value - trailing) / trailing
+        });
+
+    let new_state = ROCPState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((rate, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocr_state_init(period: i32) -> Result<ResourceArc<ROCRState>, String> {
+    if period < 1 {
+        return Err("Invalid period: must be >= 1 for ROCR".to_string());
+    }
+
+    let state = ROCRState {
+        window: new_momentum_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocr_state_next(
+    state_arc: ResourceArc<ROCRState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ROCRState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (rate, new_window) =
+        rate_of_change_next(&state.window, value, is_new_bar, |value, trailing| {
+            value / trailing
+        });
+
+    let new_state = ROCRState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((rate, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocr100_state_init(period: i32) -> Result<ResourceArc<ROCR100State>, String> {
+    if period < 1 {
+        return Err("Invalid period: must be >= 1 for ROCR100".to_string());
+    }
+
+    let state = ROCR100State {
+        window: new_momentum_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_rocr100_state_next(
+    state_arc: ResourceArc<ROCR100State>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ROCR100State>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (rate, new_window) =
+        rate_of_change_next(&state.window, value, is_new_bar, |value, trailing| {
+            value / trailing * 100.0
+        });
+
+    let new_state = ROCR100State { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((rate, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_ultosc_state_init(
+    period1: i32,
+    period2: i32,
+    period3: i32,
+) -> Result<ResourceArc<ULTOSCState>, String> {
+    if period1 < 1 || period2 < 1 || period3 < 1 {
+        return Err("Invalid period: must be >= 1 for ULTOSC".to_string());
+    }
+
+    // The 4/2/1 weights go to the shortest, middle and longest window
+    let mut periods = [period1 as usize, period2 as usize, period3 as usize];
+    periods.sort_unstable();
+
+    let state = ULTOSCState {
+        periods,
+        lookback_count: 0,
+        prev_close: None,
+        current_close: 0.0,
+        current_term: None,
+        terms: VecDeque::with_capacity(periods[2]),
+        sums: [(0.0, 0.0); 3],
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+/// Advances the Ultimate Oscillator by one bar
+///
+/// The window sums exclude the current bar, whose term is recomputed from the
+/// same previous close in UPDATE mode. An APPEND moves the term of the closed
+/// bar into the sums and drops the terms that left each window.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_ultosc_state_next(
+    state_arc: ResourceArc<ULTOSCState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ULTOSCState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => (high, low, close),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let periods = state.periods;
+    let mut lookback_count = state.lookback_count;
+    let mut prev_close = state.prev_close;
+    let mut terms = state.terms.clone();
+    let mut sums = state.sums;
+
+    // A bar given by an UPDATE before the first bar is not a bar
+    if is_new_bar && lookback_count > 0 {
+        // The current bar is closed: move its term into the window sums
+        if let Some(term) = state.current_term {
+            terms.push_back(term);
+
+            for (sum, &period) in sums.iter_mut().zip(&periods) {
+                sum.0 += term.0;
+                sum.1 += term.1;
+
+                // Each window holds 'period - 1' closed bars besides the current one
+                if terms.len() >= period {
+                    let (pressure, range) = terms[terms.len() - period];
+                    sum.0 -= pressure;
+                    sum.1 -= range;
+                }
+            }
+
+            while terms.len() >= periods[2] {
+                terms.pop_front();
+            }
+
+            // Drift correction, once every DEFAULT_RECOMPUTE_INTERVAL bars
+            if lookback_count % DEFAULT_RECOMPUTE_INTERVAL == 0 {
+                for (sum, &period) in sums.iter_mut().zip(&periods) {
+                    let skip = (terms.len() + 1).saturating_sub(period);
+                    *sum = terms
+                        .iter()
+                        .skip(skip)
+                        .fold((0.0, 0.0), |acc, t| (acc.0 + t.0, acc.1 + t.1));
+                }
+            }
+        }
+
+        prev_close = Some(state.current_close);
+    }
+
+    if is_new_bar {
+        lookback_count += 1;
+    }
+
+    let current_term = prev_close.map(|prev_close| {
+        let true_low = low.min(prev_close);
+        (close - true_low, true_range(high, low, prev_close))
+    });
+
+    // Warmup phase: the longest window needs 'period' bars with a previous close
+    let ultosc = match current_term {
+        Some(term) if lookback_count > periods[2] as i32 => {
+            let weighted: f64 = sums
+                .iter()
+                .zip([4.0, 2.0, 1.0])
+                .map(|(sum, weight)| {
+                    let range = sum.1 + term.1;
+
+                    // Same as TA-Lib: a window without any range doesn't contribute
+                    if range.abs() < 1e-8 {
+                        0.0
+                    } else {
+                        weight * (sum.0 + term.0) / range
+                    }
+                })
+                .sum();
+
+            Some(100.0 * (weighted / 7.0))
+        }
+        _ => None,
+    };
+
+    let new_state = ULTOSCState {
+        periods,
+        lookback_count,
+        prev_close,
+        current_close: close,
+        current_term,
+        terms,
+        sums,
+    };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((ultosc, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_aroon_state_init(period: i32) -> Result<ResourceArc<AROONState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for AROON".to_string());
+    }
+
+    let state = AROONState {
+        period,
+        window: new_high_low_window(period + 1),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+/// Advances Aroon down and up by one bar
+///
+/// The monotonic deques of the window keep the index of each extremum, so the
+/// number of bars since the highest high and the lowest low is read from their
+/// fronts. On ties the newest bar wins, as in TA-Lib.
+#[cfg(has_talib)]
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn momentum_aroon_state_next(
+    state_arc: ResourceArc<AROONState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, Option<f64>, ResourceArc<AROONState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low) = match (high, low) {
+        (Some(high), Some(low)) => (high, low),
+        _ => return Ok((None, None, state_arc)),
+    };
+
+    let (extremes, new_window) = high_low_window_next(&state.window, high, low, is_new_bar);
+
+    // Bars are indexed by the window count, the current one included
+    let current_index = new_window.lookback_count;
+    let since_highest = match new_window.highs.front() {
+        Some(&(index, front)) if front > high => current_index - index,
+        _ => 0,
+    };
+    let since_lowest = match new_window.lows.front() {
+        Some(&(index, front)) if front < low => current_index - index,
+        _ => 0,
+    };
+
+    let factor = 100.0 / state.period as f64;
+    let aroon = extremes.map(|_| {
+        (
+            factor * (state.period - since_lowest) as f64,
+            factor * (state.period - since_highest) as f64,
+        )
+    });
+
+    let new_state = AROONState {
+        period: state.period,
+        window: new_window,
+    };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((
+        aroon.map(|(down, _)| down),
+        aroon.map(|(_, up)| up),
+        new_resource,
+    ))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_cci_state_init(period: i32) -> Result<ResourceArc<CCIState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for CCI".to_string());
+    }
+
+    let state = CCIState {
+        period,
+        lookback_count: 0,
+        buffer: VecDeque::with_capacity(period as usize),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_cci_state_next(
+    state_arc: ResourceArc<CCIState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<CCIState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => (high, low, close),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let typical_price = (high + low + close) / 3.0;
+
+    let mut new_buffer = state.buffer.clone();
+    let new_lookback = if is_new_bar {
+        if state.lookback_count == 0 {
+            // A value pushed by an UPDATE before the first bar is not a bar
+            new_buffer.clear();
+        } else if new_buffer.len() == state.period as usize {
+            new_buffer.pop_front();
+        }
+
+        new_buffer.push_back(typical_price);
+        state.lookback_count + 1
+    } else {
+        // UPDATE mode: replace the typical price of the current bar
+        match new_buffer.back_mut() {
+            Some(last) => *last = typical_price,
+            None => new_buffer.push_back(typical_price),
+        }
+        state.lookback_count
+    };
+
+    let new_state = CCIState {
+        period: state.period,
+        lookback_count: new_lookback,
+        buffer: new_buffer,
+    };
+
+    // Warmup phase: need 'period' bars
+    if new_lookback < state.period {
+        let new_resource = ResourceArc::new(new_state);
+        return Ok((None, new_resource));
+    }
+
+    // The mean deviation depends on the average of the whole window, so unlike a
+    // variance it can't be maintained with running sums: recompute it in O(period)
+    let period = state.period as f64;
+    let average = new_state.buffer.iter().sum::<f64>() / period;
+    let mean_deviation = new_state
+        .buffer
+        .iter()
+        .map(|tp| (tp - average).abs())
+        .sum::<f64>()
+        / period;
+
+    // Same as TA-Lib: 0 when the price sits on its average or doesn't move at all
+    let deviation = typical_price - average;
+    let cci = if deviation != 0.0 && mean_deviation != 0.0 {
+        deviation / (0.015 * mean_deviation)
+    } else {
+        0.0
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((Some(cci), new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_willr_state_init(period: i32) -> Result<ResourceArc<WILLRState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for WILLR".to_string());
+    }
+
+    let state = WILLRState {
+        window: new_high_low_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_willr_state_next(
+    state_arc: ResourceArc<WILLRState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<WILLRState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => (high, low, close),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let (extremes, new_window) = high_low_window_next(&state.window, high, low, is_new_bar);
+
+    // Same as TA-Lib: 0 when the window has no range
+    let willr = extremes.map(|(max_high, min_low)| {
+        let diff = (max_high - min_low) / -100.0;
+
+        if diff != 0.0 {
+            (max_high - close) / diff
+        } else {
+            0.0
+        }
+    });
+
+    let new_state = WILLRState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((willr, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_macd_state_init(
+    fast_period: i32,
+    slow_period: i32,
+    signal_period: i32,
+) -> Result<ResourceArc<MACDState>, String> {
+    if fast_period < 2 || slow_period < 2 {
+        return Err("Invalid period: fast and slow periods must be >= 2 for MACD".to_string());
+    }
+
+    if signal_period < 1 {
+        return Err("Invalid signal period: must be >= 1 for MACD".to_string());
+    }
+
+    // Same as TA-Lib: the periods are swapped when the slow one is shorter
+    let (fast_period, slow_period) = if slow_period < fast_period {
+        (slow_period, fast_period)
+    } else {
+        (fast_period, slow_period)
+    };
+
+    let state = MACDState {
+        fast_period,
+        slow_period,
+        signal_period,
+        lookback_count: 0,
+        fast_ema_state: Arc::new(new_ema_state(fast_period)),
+        slow_ema_state: Arc::new(new_ema_state(slow_period)),
+        signal_ema_state: Arc::new(new_ema_state(signal_period)),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn momentum_macd_state_next(
+    state_arc: ResourceArc<MACDState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<
+    (
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        ResourceArc<MACDState>,
+    ),
+    String,
+> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, None, None, state_arc)),
+    };
+
+    // Update lookback count
+    let new_lookback = if is_new_bar {
+        state.lookback_count + 1
+    } else {
+        state.lookback_count
+    };
+
+    // TA-Lib seeds the fast EMA on the same bar as the slow one, with the SMA of the
+    // 'fast_period' values before it, so the fast EMA skips the first bars
+    let fast_input = (new_lookback > state.slow_period - state.fast_period).then_some(value);
+    let (fast_value, new_fast_ema_state) =
+        step_nested_ema(&state.fast_ema_state, fast_input, is_new_bar);
+
+    let (slow_value, new_slow_ema_state) =
+        step_nested_ema(&state.slow_ema_state, Some(value), is_new_bar);
+
+    let macd = match (fast_value, slow_value) {
+        (Some(fast), Some(slow)) => Some(fast - slow),
+        _ => None,
+    };
+
+    // The signal EMA only starts once the MACD line is available
+    let (signal, new_signal_ema_state) = step_nested_ema(&state.signal_ema_state, macd, is_new_bar);
+
+    let hist = match (macd, signal) {
+        (Some(macd_val), Some(signal_val)) => Some(macd_val - signal_val),
+        _ => None,
+    };
+
+    let new_state = MACDState {
+        fast_period: state.fast_period,
+        slow_period: state.slow_period,
+        signal_period: state.signal_period,
+        lookback_count: new_lookback,
+        fast_ema_state: new_fast_ema_state,
+        slow_ema_state: new_slow_ema_state,
+        signal_ema_state: new_signal_ema_state,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((macd, signal, hist, new_resource))
+}
+
+#[cfg(has_talib)]
+fn new_price_oscillator(
+    fast_period: i32,
+    slow_period: i32,
+    ma_type: i32,
+    name: &str,
+) -> Result<PriceOscillator, String> {
+    use crate::overlap_ffi::TA_MA_Lookback;
+
+    if fast_period < 2 || slow_period < 2 {
+        return Err(format!(
+            "Invalid period: fast and slow periods must be >= 2 for {}",
+            name
+        ));
+    }
+
+    // Same as TA-Lib: the periods are swapped when the slow one is shorter
+    let (fast_period, slow_period) = if slow_period < fast_period {
+        (slow_period, fast_period)
+    } else {
+        (fast_period, slow_period)
+    };
+
+    let fast_ma_state = new_ma_state(fast_period, ma_type)?;
+    let slow_ma_state = new_ma_state(slow_period, ma_type)?;
+
+    // TA-Lib computes the fast MA from the first bar of the output, looking back
+    // only its own lookback: its warmup starts later than the slow one's
+    let fast_skip =
+        unsafe { TA_MA_Lookback(slow_period, ma_type) - TA_MA_Lookback(fast_period, ma_type) };
+
+    Ok(PriceOscillator {
+        fast_skip,
+        lookback_count: 0,
+        fast_ma_state,
+        slow_ma_state,
+    })
+}
+
+/// Advances the fast and slow MAs of a price oscillator by one value
+///
+/// Returns both MA values once they are past their warmup.
+#[cfg(has_talib)]
+fn price_oscillator_next(
+    oscillator: &PriceOscillator,
+    value: f64,
+    is_new_bar: bool,
+) -> (Option<(f64, f64)>, PriceOscillator) {
+    let new_lookback = if is_new_bar {
+        oscillator.lookback_count + 1
+    } else {
+        oscillator.lookback_count
+    };
+
+    let (fast_value, new_fast_ma_state) = if new_lookback > oscillator.fast_skip {
+        step_ma(&oscillator.fast_ma_state, value, is_new_bar)
+    } else {
+        (None, oscillator.fast_ma_state.clone())
+    };
+
+    let (slow_value, new_slow_ma_state) = step_ma(&oscillator.slow_ma_state, value, is_new_bar);
+
+    let new_oscillator = PriceOscillator {
+        fast_skip: oscillator.fast_skip,
+        lookback_count: new_lookback,
+        fast_ma_state: new_fast_ma_state,
+        slow_ma_state: new_slow_ma_state,
+    };
+
+    (fast_value.zip(slow_value), new_oscillator)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_apo_state_init(
+    fast_period: i32,
+    slow_period: i32,
+    ma_type: i32,
+) -> Result<ResourceArc<APOState>, String> {
+    let state = APOState {
+        oscillator: new_price_oscillator(fast_period, slow_period, ma_type, "APO")?,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_apo_state_next(
+    state_arc: ResourceArc<APOState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<APOState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (values, new_oscillator) = price_oscillator_next(&state.oscillator, value, is_new_bar);
+    let apo = values.map(|(fast, slow)| fast - slow);
+
+    let new_state = APOState {
+        oscillator: new_oscillator,
+    };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((apo, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_ppo_state_init(
+    fast_period: i32,
+    slow_period: i32,
+    ma_type: i32,
+) -> Result<ResourceArc<PPOState>, String> {
+    let state = PPOState {
+        oscillator: new_price_oscillator(fast_period, slow_period, ma_type, "PPO")?,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn momentum_ppo_state_next(
+    state_arc: ResourceArc<PPOState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<PPOState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (values, new_oscillator) = price_oscillator_next(&state.oscillator, value, is_new_bar);
+
+    // Same as TA-Lib: a (nearly) zero slow MA gives 0 instead of dividing by zero
+    let ppo = values.map(|(fast, slow)| {
+        if slow.abs() < 1e-8 {
+            0.0
+        } else {
+            ((fast - slow) / slow) * 100.0
+        }
+    });
+
+    let new_state = PPOState {
+        oscillator: new_oscillator,
+    };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((ppo, new_resource))
+}
+
+// CMO does not use TA-Lib (whose CMO smooths like RSI), so its state is always available
+pub(crate) fn new_cmo_state(period: i32) -> Result<CMOState, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for CMO".to_string());
+    }
+
+    Ok(CMOState {
+        period,
+        deltas: VecDeque::with_capacity(period as usize),
+        sum_up: 0.0,
+        sum_down: 0.0,
+        current_delta: None,
+        current_value: None,
+        prev_value: None,
+    })
+}
+
+/// Advances a CMO state by one value
+///
+/// The running sums only hold the closed deltas: APPEND moves the current
+/// delta into them (dropping the trailing one), UPDATE only replaces the
+/// current delta, so both are O(1).
+pub(crate) fn step_cmo(state: &CMOState, value: f64, is_new_bar: bool) -> (Option<f64>, CMOState) {
+    let up = |delta: f64| delta.max(0.0);
+    let down = |delta: f64| (-delta).max(0.0);
+
+    let mut new_state = state.clone();
+
+    if is_new_bar {
+        if let Some(delta) = state.current_delta {
+            new_state.deltas.push_back(delta);
+            new_state.sum_up += up(delta);
+            new_state.sum_down += down(delta);
+
+            if new_state.deltas.len() >= state.period as usize {
+                let trailing = new_state.deltas.pop_front().unwrap_or_default();
+                new_state.sum_up -= up(trailing);
+                new_state.sum_down -= down(trailing);
+            }
+        }
+
+        new_state.prev_value = state.current_value;
+    }
+
+    new_state.current_delta = new_state.prev_value.map(|prev| value - prev);
+    new_state.current_value = Some(value);
+
+    // Warmup phase: need 'period' deltas (period + 1 bars)
+    let delta = match new_state.current_delta {
+        Some(delta) if new_state.deltas.len() + 1 >= state.period as usize => delta,
+        _ => return (None, new_state),
+    };
+
+    let sum_up = new_state.sum_up + up(delta);
+    let sum_down = new_state.sum_down + down(delta);
+    let total = sum_up + sum_down;
+
+    // A flat window has no momentum (the sums can drift around zero)
+    let cmo = if total.abs() < 1e-8 {
+        0.0
+    } else {
+        100.0 * (sum_up - sum_down) / total
+    };
+
+    (Some(cmo), new_state)
+}
+
+#[rustler::nif]
+pub fn momentum_cmo_state_init(period: i32) -> Result<ResourceArc<CMOState>, String> {
+    let state = new_cmo_state(period)?;
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[rustler::nif]
+pub fn momentum_cmo_state_next(
+    state_arc: ResourceArc<CMOState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<CMOState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (cmo, new_state) = step_cmo(state, value, is_new_bar);
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((cmo, new_resource))
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_trix_state_init(_period: i32) -> Result<ResourceArc<TRIXState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_trix_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TRIXState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rsi_state_init(_period: i32) -> Result<ResourceArc<RSIState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rsi_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<RSIState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_dx_state_init(_period: i32) -> Result<ResourceArc<DXState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_dx_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<DXState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_adx_state_init(_period: i32) -> Result<ResourceArc<ADXState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn momentum_adx_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, Option<f64>, Option<f64>, ResourceArc<ADXState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_mom_state_init(_period: i32) -> Result<ResourceArc<MOMState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_mom_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MOMState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_roc_state_init(_period: i32) -> Result<ResourceArc<ROCState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_roc_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ROCState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocp_state_init(_period: i32) -> Result<ResourceArc<ROCPState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocp_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ROCPState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocr_state_init(_period: i32) -> Result<ResourceArc<ROCRState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocr_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ROCRState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocr100_state_init(_period: i32) -> Result<ResourceArc<ROCR100State>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_rocr100_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ROCR100State>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_ultosc_state_init(
+    _period1: i32,
+    _period2: i32,
+    _period3: i32,
+) -> Result<ResourceArc<ULTOSCState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_ultosc_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ULTOSCState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_aroon_state_init(_period: i32) -> Result<ResourceArc<AROONState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn momentum_aroon_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, Option<f64>, ResourceArc<AROONState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_cci_state_init(_period: i32) -> Result<ResourceArc<CCIState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_cci_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<CCIState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_willr_state_init(_period: i32) -> Result<ResourceArc<WILLRState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_willr_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<WILLRState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_macd_state_init(
+    _fast_period: i32,
+    _slow_period: i32,
+    _signal_period: i32,
+) -> Result<ResourceArc<MACDState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn momentum_macd_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<
+    (
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        ResourceArc<MACDState>,
+    ),
+    String,
+> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_apo_state_init(
+    _fast_period: i32,
+    _slow_period: i32,
+    _ma_type: i32,
+) -> Result<ResourceArc<APOState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_apo_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<APOState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_ppo_state_init(
+    _fast_period: i32,
+    _slow_period: i32,
+    _ma_type: i32,
+) -> Result<ResourceArc<PPOState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn momentum_ppo_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<PPOState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
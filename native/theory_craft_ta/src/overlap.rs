@@ -2,18 +2,210 @@
 #[cfg(has_talib)]
 #[rustler::nif]
 pub fn overlap_sma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
-    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    calculate_sma(data, period)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_f32(data: Vec<Option<f32>>, period: i32) -> Result<Vec<Option<f32>>, String> {
+    crate::helpers::with_f32_series(&data, |data| calculate_sma(data, period))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_trimmed(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<crate::helpers::TrimmedOutput, String> {
+    calculate_sma_trimmed(data, period)
+}
+
+/// SMA without its leading padding, the values packed in a binary
+///
+/// Meant for very large series: see [`crate::helpers::trimmed_to_binary`] for
+/// the layout and the memory saved over the list returned by `overlap_sma/2`.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_iodata<'a>(
+    env: rustler::Env<'a>,
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<crate::helpers::BinaryOutput<'a>, String> {
+    let trimmed = calculate_sma_trimmed(data, period)?;
+    crate::helpers::trimmed_to_binary(env, trimmed, "SMA")
+}
+
+/// SMA with a sentinel value standing for missing values, in and out
+///
+/// For callers whose series use a sentinel (e.g. -1.0) rather than nil: the
+/// series is a plain list of floats both ways, with no nil to encode or decode.
+/// Inputs equal to `sentinel` are missing, and so are the outputs set to it.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_with_sentinel(
+    data: Vec<f64>,
+    period: i32,
+    sentinel: f64,
+) -> Result<Vec<f64>, String> {
+    use crate::helpers::{pad_trimmed_with_sentinel, sentinel_to_nan};
+
+    let trimmed = calculate_sma_trimmed_on_clean(&sentinel_to_nan(&data, sentinel), period)?;
+    Ok(pad_trimmed_with_sentinel(trimmed, sentinel))
+}
+
+/// SMA of several series at once, computed in parallel
+///
+/// Runs on a dirty CPU scheduler and spreads the series over the shared thread
+/// pool. Each series gets its own result, so an invalid series doesn't fail the
+/// others.
+#[cfg(has_talib)]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn overlap_sma_multi(
+    series: Vec<Vec<Option<f64>>>,
+    period: i32,
+) -> Vec<Result<Vec<Option<f64>>, String>> {
+    use rayon::prelude::*;
+
+    let calculate = |data| calculate_sma(data, period);
+
+    match crate::helpers::thread_pool() {
+        Some(pool) => pool.install(|| series.into_par_iter().map(calculate).collect()),
+        None => series.into_iter().map(calculate).collect(),
+    }
+}
+
+/// SMA of each sliding window of `data`, for walk-forward studies
+///
+/// Windows are `window` values long and start every `step` values; a trailing
+/// window shorter than `window` is dropped. The input is decoded once and each
+/// window is computed independently, so its warmup restarts at its first value.
+#[cfg(has_talib)]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn overlap_sma_windows(
+    data: Vec<Option<f64>>,
+    period: i32,
+    window: usize,
+    step: usize,
+) -> Result<Vec<Vec<Option<f64>>>, String> {
+    if period < 2 {
+        return Err("SMA: period must be >= 2".to_string());
+    }
+
+    if window < period as usize {
+        return Err("SMA: window must be >= period".to_string());
+    }
+
+    if step < 1 {
+        return Err("SMA: step must be >= 1".to_string());
+    }
+
+    data.windows(window)
+        .step_by(step)
+        .map(|slice| calculate_sma(slice.to_vec(), period))
+        .collect()
+}
+
+/// SMA with each value flagged as final or not, for incremental chart updates
+///
+/// Returns `{value, final}` pairs: `final` is true for every bar but the last
+/// one, which is only final when `last_bar_closed` is true.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_annotated(
+    data: Vec<Option<f64>>,
+    period: i32,
+    last_bar_closed: bool,
+) -> Result<crate::helpers::AnnotatedOutput, String> {
+    let values = calculate_sma(data, period)?;
+    Ok(crate::helpers::annotate_result(values, last_bar_closed))
+}
+
+/// SMA with its warmup filled by the mean of the values seen so far
+///
+/// Until the first full window, each bar gets the average of the valid values
+/// from the start of the series (missing values are skipped) instead of `None`,
+/// then it is the regular SMA. Leading missing values are still `None`.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_partial(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    let clean_data = crate::helpers::options_to_nan(&data);
+    let (first_idx, values) = calculate_sma_trimmed_on_clean(&clean_data, period)?;
+
+    let mut result = Vec::with_capacity(data.len());
+    let mut sum = 0.0;
+    let mut count = 0;
+
+    for value in &data[..first_idx as usize] {
+        if let Some(value) = value {
+            sum += value;
+            count += 1;
+        }
+
+        result.push((count > 0).then(|| sum / count as f64));
+    }
+
+    result.extend(values);
+    Ok(result)
+}
+
+/// SMA of a price derived from the OHLC, e.g. the typical price with `:hlc3`
+///
+/// Saves the caller from computing the derived series first: see
+/// [`crate::price::PriceSource`] for the prices available.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_source(
+    ohlc: crate::batch::Ohlc,
+    period: i32,
+    source: crate::price::PriceSource,
+) -> Result<Vec<Option<f64>>, String> {
+    calculate_sma(source.series(&ohlc, "SMA")?, period)
+}
+
+#[cfg(has_talib)]
+pub(crate) fn calculate_sma(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    calculate_sma_trimmed(data, period).map(crate::helpers::pad_trimmed)
+}
+
+#[cfg(has_talib)]
+fn calculate_sma_trimmed(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<crate::helpers::TrimmedOutput, String> {
+    calculate_sma_trimmed_on_clean(&crate::helpers::options_to_nan(&data), period)
+}
+
+/// SMA of data with its missing values already converted to NaN
+#[cfg(has_talib)]
+fn calculate_sma_trimmed_on_clean(
+    clean_data: &[f64],
+    period: i32,
+) -> Result<crate::helpers::TrimmedOutput, String> {
+    use crate::helpers::{build_trimmed_result, check_begidx};
     use crate::overlap_ffi::{TA_SMA_Lookback, TA_SMA};
 
-    if data.is_empty() {
-        return Ok(Vec::new());
+    if period < 2 {
+        return Err("SMA: period must be >= 2".to_string());
+    }
+
+    if clean_data.is_empty() {
+        return Ok((0, Vec::new()));
     }
 
-    let clean_data = options_to_nan(&data);
     let length = clean_data.len();
 
     // Python ta-lib pattern: skip leading NaN values
-    let begidx = check_begidx(&clean_data);
+    let begidx = match check_begidx(clean_data) {
+        Some(idx) => idx,
+        // No valid value at all: nothing to compute
+        None => return Ok((length as i32, Vec::new())),
+    };
     let endidx = (length - begidx - 1) as i32;
 
     // Calculate lookback from the beginning of valid data
@@ -22,7 +214,7 @@ pub fn overlap_sma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64
 
     // If not enough valid data, return all None
     if total_lookback >= length as i32 {
-        return Ok(vec![None; length]);
+        return Ok((length as i32, Vec::new()));
     }
 
     let mut out_beg_idx: i32 = 0;
@@ -45,7 +237,12 @@ pub fn overlap_sma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64
 
     check_ret_code!(ret_code, "SMA");
 
-    let result = build_result(total_lookback, out_nb_element, &out_real);
+    // SMA has a fixed lookback, TA-Lib must start exactly where we expect
+    debug_assert_eq!(total_lookback, begidx as i32 + out_beg_idx);
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_trimmed_result(first_idx, out_nb_element, &out_real, "SMA")?;
 
     Ok(result)
 }
@@ -53,24 +250,95 @@ pub fn overlap_sma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64
 #[cfg(has_talib)]
 #[rustler::nif]
 pub fn overlap_ema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
-    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    calculate_ema(data, period)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ema_f32(data: Vec<Option<f32>>, period: i32) -> Result<Vec<Option<f32>>, String> {
+    crate::helpers::with_f32_series(&data, |data| calculate_ema(data, period))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ema_trimmed(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<crate::helpers::TrimmedOutput, String> {
+    calculate_ema_trimmed(data, period)
+}
+
+/// EMA of the same series for several periods (e.g. an EMA ribbon)
+///
+/// The NaN conversion and the leading NaN skip are done once for all periods.
+/// Each period gets its own result, so an invalid period doesn't fail the others.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ema_multi(
+    data: Vec<Option<f64>>,
+    periods: Vec<i32>,
+) -> Vec<Result<Vec<Option<f64>>, String>> {
+    use crate::helpers::{check_begidx, options_to_nan, pad_trimmed};
+
+    let clean_data = options_to_nan(&data);
+    let begidx = check_begidx(&clean_data);
+
+    periods
+        .into_iter()
+        .map(|period| calculate_ema_trimmed_on_clean(&clean_data, begidx, period).map(pad_trimmed))
+        .collect()
+}
+
+#[cfg(has_talib)]
+pub(crate) fn calculate_ema(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    calculate_ema_trimmed(data, period).map(crate::helpers::pad_trimmed)
+}
+
+#[cfg(has_talib)]
+fn calculate_ema_trimmed(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<crate::helpers::TrimmedOutput, String> {
+    use crate::helpers::{check_begidx, options_to_nan};
+
+    let clean_data = options_to_nan(&data);
+    let begidx = check_begidx(&clean_data);
+
+    calculate_ema_trimmed_on_clean(&clean_data, begidx, period)
+}
+
+/// EMA of data already converted by `options_to_nan`, `begidx` being the
+/// result of `check_begidx` on it, so several periods can share that work
+#[cfg(has_talib)]
+fn calculate_ema_trimmed_on_clean(
+    clean_data: &[f64],
+    begidx: Option<usize>,
+    period: i32,
+) -> Result<crate::helpers::TrimmedOutput, String> {
+    use crate::helpers::build_trimmed_result;
     use crate::overlap_ffi::{TA_EMA_Lookback, TA_EMA};
 
-    if data.is_empty() {
-        return Ok(Vec::new());
+    if period < 2 {
+        return Err("EMA: period must be >= 2".to_string());
     }
 
-    let clean_data = options_to_nan(&data);
     let length = clean_data.len();
 
-    let begidx = check_begidx(&clean_data);
+    // Empty or all-NaN data
+    let begidx = match begidx {
+        Some(idx) => idx,
+        None => return Ok((length as i32, Vec::new())),
+    };
     let endidx = (length - begidx - 1) as i32;
 
     let lookback = unsafe { TA_EMA_Lookback(period) };
     let total_lookback = begidx as i32 + lookback;
 
     if total_lookback >= length as i32 {
-        return Ok(vec![None; length]);
+        return Ok((length as i32, Vec::new()));
     }
 
     let mut out_beg_idx: i32 = 0;
@@ -92,7 +360,206 @@ pub fn overlap_ema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64
 
     check_ret_code!(ret_code, "EMA");
 
-    let result = build_result(total_lookback, out_nb_element, &out_real);
+    // EMA has a fixed lookback, TA-Lib must start exactly where we expect
+    debug_assert_eq!(total_lookback, begidx as i32 + out_beg_idx);
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_trimmed_result(first_idx, out_nb_element, &out_real, "EMA")?;
+
+    Ok(result)
+}
+
+/// Wilder's moving average (RMA, also called SMMA)
+///
+/// TA-Lib has no RMA, so it runs in Rust with the recurrence of TA-Lib's EMA
+/// and `k = 1 / period`, seeded by the SMA of the first `period` values.
+#[rustler::nif]
+pub fn overlap_rma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    if period < 2 {
+        return Err("RMA: period must be >= 2".to_string());
+    }
+
+    let length = data.len();
+
+    // Skip leading NaN values
+    let begidx = match data
+        .iter()
+        .position(|value| value.is_some_and(|v| !v.is_nan()))
+    {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+
+    let total_lookback = begidx + period as usize - 1;
+
+    if total_lookback >= length {
+        return Ok(vec![None; length]);
+    }
+
+    // Missing values after begidx poison the average, as in TA-Lib
+    let value_at = |i: usize| data[i].unwrap_or(f64::NAN);
+    let k = 1.0 / period as f64;
+
+    let mut result = vec![None; total_lookback];
+
+    // First RMA: SMA of the first 'period' values
+    let mut rma = (begidx..=total_lookback).map(value_at).sum::<f64>() / period as f64;
+    result.push(Some(rma).filter(|v| !v.is_nan()));
+
+    for i in (total_lookback + 1)..length {
+        rma = (value_at(i) - rma) * k + rma;
+        result.push(Some(rma).filter(|v| !v.is_nan()));
+    }
+
+    Ok(result)
+}
+
+/// Volume-weighted moving average (VWMA)
+///
+/// TA-Lib has no VWMA, so it runs in Rust with rolling sums of `close * volume`
+/// and of `volume` over the last `period` bars.
+#[rustler::nif]
+pub fn overlap_vwma(
+    close: Vec<Option<f64>>,
+    volume: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    if period < 2 {
+        return Err("VWMA: period must be >= 2".to_string());
+    }
+
+    crate::helpers::validate_inputs("VWMA", &[("close", close.len()), ("volume", volume.len())])?;
+
+    let length = close.len();
+    let period = period as usize;
+
+    // Missing values after begidx poison the sums, as in TA-Lib
+    let close_at = |i: usize| close[i].unwrap_or(f64::NAN);
+    let volume_at = |i: usize| volume[i].unwrap_or(f64::NAN);
+
+    // Skip leading bars where any of the inputs is NaN
+    let is_valid = |i: usize| !close_at(i).is_nan() && !volume_at(i).is_nan();
+    let begidx = match (0..length).find(|&i| is_valid(i)) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+
+    let total_lookback = begidx + period - 1;
+
+    if total_lookback >= length {
+        return Ok(vec![None; length]);
+    }
+
+    let mut result = vec![None; total_lookback];
+
+    let mut weighted_sum: f64 = (begidx..total_lookback)
+        .map(|i| close_at(i) * volume_at(i))
+        .sum();
+    let mut volume_sum: f64 = (begidx..total_lookback).map(volume_at).sum();
+
+    // Counted apart: the rolling sum can drift away from an exact zero
+    let mut traded_bars = (begidx..total_lookback)
+        .filter(|&i| volume_at(i) != 0.0)
+        .count();
+
+    for i in total_lookback..length {
+        weighted_sum += close_at(i) * volume_at(i);
+        volume_sum += volume_at(i);
+        traded_bars += (volume_at(i) != 0.0) as usize;
+
+        // A window without volume has no weighted average
+        let vwma = Some(weighted_sum / volume_sum).filter(|_| traded_bars > 0);
+        result.push(vwma.filter(|v| !v.is_nan()));
+
+        let trailing = i + 1 - period;
+        weighted_sum -= close_at(trailing) * volume_at(trailing);
+        volume_sum -= volume_at(trailing);
+        traded_bars -= (volume_at(trailing) != 0.0) as usize;
+    }
+
+    Ok(result)
+}
+
+/// McGinley Dynamic
+///
+/// Adaptive moving average which speeds up when the price runs away from it
+/// and slows down when the price comes back. Not in TA-Lib, so it runs in Rust
+/// on the same steps as the streaming state.
+#[rustler::nif]
+pub fn overlap_mcginley(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::overlap_state::{new_mcginley_state, step_mcginley};
+
+    let mut state =
+        new_mcginley_state(period).map_err(|_| "MCGINLEY: period must be >= 2".to_string())?;
+
+    // Missing values are skipped like nil in the streaming state
+    let result = data
+        .into_iter()
+        .map(|value| {
+            let value = value.filter(|v| !v.is_nan())?;
+            let (md, new_state) = step_mcginley(&state, value, true);
+            state = new_state;
+            Some(md)
+        })
+        .collect();
+
+    Ok(result)
+}
+
+/// Fractal Adaptive Moving Average (FRAMA)
+///
+/// Ehlers' EMA whose alpha follows the fractal dimension D of the last `period`
+/// values: `alpha = exp(-4.6 * (D - 1))`, clamped to [0.01, 1]. D compares the
+/// ranges of both halves of the window to the range of the whole window, so the
+/// period must be even. Not in TA-Lib, so it runs in Rust.
+#[rustler::nif]
+pub fn overlap_frama(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    if period < 2 || period % 2 != 0 {
+        return Err("FRAMA: period must be even and >= 2".to_string());
+    }
+
+    let period = period as usize;
+    let half = period / 2;
+
+    // Missing values are skipped, the windows are made of the valid ones
+    let valid: Vec<(usize, f64)> = data
+        .iter()
+        .enumerate()
+        .filter_map(|(i, value)| value.filter(|v| !v.is_nan()).map(|v| (i, v)))
+        .collect();
+
+    let range = |window: &[f64]| {
+        let high = window.iter().copied().fold(f64::MIN, f64::max);
+        let low = window.iter().copied().fold(f64::MAX, f64::min);
+        high - low
+    };
+
+    let mut result = vec![None; data.len()];
+    let mut frama: Option<f64> = None;
+
+    for end in period..=valid.len() {
+        let window: Vec<f64> = valid[end - period..end].iter().map(|&(_, v)| v).collect();
+
+        let n1 = range(&window[..half]) / half as f64;
+        let n2 = range(&window[half..]) / half as f64;
+        let n3 = range(&window) / period as f64;
+
+        // A window without range in either half just follows the price
+        let alpha = if n1 + n2 > 0.0 && n3 > 0.0 {
+            let dimension = ((n1 + n2).ln() - n3.ln()) / std::f64::consts::LN_2;
+            (-4.6 * (dimension - 1.0)).exp().clamp(0.01, 1.0)
+        } else {
+            1.0
+        };
+
+        // Seeded with the previous value, as Ehlers does before the first full window
+        let prev = frama.unwrap_or(window[period - 2]);
+        let value = alpha * window[period - 1] + (1.0 - alpha) * prev;
+
+        frama = Some(value);
+        result[valid[end - 1].0] = Some(value);
+    }
 
     Ok(result)
 }
@@ -100,9 +567,102 @@ pub fn overlap_ema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64
 #[cfg(has_talib)]
 #[rustler::nif]
 pub fn overlap_wma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    calculate_wma(data, period)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_hma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    if period < 2 {
+        return Err("HMA: period must be >= 2".to_string());
+    }
+
+    let half_period = period / 2;
+    let sqrt_period = (period as f64).sqrt().floor() as i32;
+
+    if half_period < 1 {
+        return Err("HMA: period / 2 must be >= 1".to_string());
+    }
+
+    // A 1-period WMA is the series itself (TA-Lib's WMA requires period >= 2)
+    let wma = |data: Vec<Option<f64>>, period: i32| {
+        if period == 1 {
+            Ok(data)
+        } else {
+            calculate_wma(data, period)
+        }
+    };
+
+    let half_wma = wma(data.clone(), half_period)?;
+    let full_wma = calculate_wma(data, period)?;
+
+    // The raw series starts with the full WMA (the longest lookback): leading
+    // None values are skipped by the final WMA, so the total lookback is
+    // begidx + (period - 1) + (sqrt_period - 1)
+    let raw: Vec<Option<f64>> = half_wma
+        .into_iter()
+        .zip(full_wma)
+        .map(|(half, full)| match (half, full) {
+            (Some(half), Some(full)) => Some(2.0 * half - full),
+            _ => None,
+        })
+        .collect();
+
+    if raw.iter().all(Option::is_none) {
+        return Ok(raw);
+    }
+
+    wma(raw, sqrt_period)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_zlema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{check_begidx, options_to_nan};
+
+    if period < 2 {
+        return Err("ZLEMA: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+    let lag = ((period - 1) / 2) as usize;
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+
+    // The lag term is only available 'lag' bars after the first valid value
+    let adjusted: Vec<Option<f64>> = (0..length)
+        .map(|i| {
+            if i < begidx + lag {
+                None
+            } else {
+                Some(clean_data[i] + (clean_data[i] - clean_data[i - lag]))
+            }
+        })
+        .collect();
+
+    calculate_ema(adjusted, period)
+}
+
+#[cfg(has_talib)]
+pub(crate) fn calculate_wma(
+    data: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
     use crate::helpers::{build_result, check_begidx, options_to_nan};
     use crate::overlap_ffi::{TA_WMA_Lookback, TA_WMA};
 
+    if period < 2 {
+        return Err("WMA: period must be >= 2".to_string());
+    }
+
     if data.is_empty() {
         return Ok(Vec::new());
     }
@@ -110,7 +670,10 @@ pub fn overlap_wma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64
     let clean_data = options_to_nan(&data);
     let length = clean_data.len();
 
-    let begidx = check_begidx(&clean_data);
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
     let endidx = (length - begidx - 1) as i32;
 
     let lookback = unsafe { TA_WMA_Lookback(period) };
@@ -139,7 +702,9 @@ pub fn overlap_wma(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64
 
     check_ret_code!(ret_code, "WMA");
 
-    let result = build_result(total_lookback, out_nb_element, &out_real);
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "WMA")?;
 
     Ok(result)
 }
@@ -150,6 +715,10 @@ pub fn overlap_dema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f6
     use crate::helpers::{build_result, check_begidx, options_to_nan};
     use crate::overlap_ffi::{TA_DEMA_Lookback, TA_DEMA};
 
+    if period < 2 {
+        return Err("DEMA: period must be >= 2".to_string());
+    }
+
     if data.is_empty() {
         return Ok(Vec::new());
     }
@@ -157,7 +726,10 @@ pub fn overlap_dema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f6
     let clean_data = options_to_nan(&data);
     let length = clean_data.len();
 
-    let begidx = check_begidx(&clean_data);
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
     let endidx = (length - begidx - 1) as i32;
 
     let lookback = unsafe { TA_DEMA_Lookback(period) };
@@ -186,7 +758,9 @@ pub fn overlap_dema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f6
 
     check_ret_code!(ret_code, "DEMA");
 
-    let result = build_result(total_lookback, out_nb_element, &out_real);
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "DEMA")?;
 
     Ok(result)
 }
@@ -197,6 +771,10 @@ pub fn overlap_tema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f6
     use crate::helpers::{build_result, check_begidx, options_to_nan};
     use crate::overlap_ffi::{TA_TEMA_Lookback, TA_TEMA};
 
+    if period < 2 {
+        return Err("TEMA: period must be >= 2".to_string());
+    }
+
     if data.is_empty() {
         return Ok(Vec::new());
     }
@@ -204,7 +782,10 @@ pub fn overlap_tema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f6
     let clean_data = options_to_nan(&data);
     let length = clean_data.len();
 
-    let begidx = check_begidx(&clean_data);
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
     let endidx = (length - begidx - 1) as i32;
 
     let lookback = unsafe { TA_TEMA_Lookback(period) };
@@ -233,7 +814,9 @@ pub fn overlap_tema(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f6
 
     check_ret_code!(ret_code, "TEMA");
 
-    let result = build_result(total_lookback, out_nb_element, &out_real);
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "TEMA")?;
 
     Ok(result)
 }
@@ -244,6 +827,10 @@ pub fn overlap_trima(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f
     use crate::helpers::{build_result, check_begidx, options_to_nan};
     use crate::overlap_ffi::{TA_TRIMA_Lookback, TA_TRIMA};
 
+    if period < 2 {
+        return Err("TRIMA: period must be >= 2".to_string());
+    }
+
     if data.is_empty() {
         return Ok(Vec::new());
     }
@@ -251,7 +838,10 @@ pub fn overlap_trima(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f
     let clean_data = options_to_nan(&data);
     let length = clean_data.len();
 
-    let begidx = check_begidx(&clean_data);
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
     let endidx = (length - begidx - 1) as i32;
 
     let lookback = unsafe { TA_TRIMA_Lookback(period) };
@@ -280,7 +870,9 @@ pub fn overlap_trima(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f
 
     check_ret_code!(ret_code, "TRIMA");
 
-    let result = build_result(total_lookback, out_nb_element, &out_real);
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "TRIMA")?;
 
     Ok(result)
 }
@@ -291,6 +883,10 @@ pub fn overlap_midpoint(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Optio
     use crate::helpers::{build_result, check_begidx, options_to_nan};
     use crate::overlap_ffi::{TA_MIDPOINT_Lookback, TA_MIDPOINT};
 
+    if period < 2 {
+        return Err("MIDPOINT: period must be >= 2".to_string());
+    }
+
     if data.is_empty() {
         return Ok(Vec::new());
     }
@@ -298,7 +894,10 @@ pub fn overlap_midpoint(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Optio
     let clean_data = options_to_nan(&data);
     let length = clean_data.len();
 
-    let begidx = check_begidx(&clean_data);
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
     let endidx = (length - begidx - 1) as i32;
 
     let lookback = unsafe { TA_MIDPOINT_Lookback(period) };
@@ -327,7 +926,9 @@ pub fn overlap_midpoint(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Optio
 
     check_ret_code!(ret_code, "MIDPOINT");
 
-    let result = build_result(total_lookback, out_nb_element, &out_real);
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "MIDPOINT")?;
 
     Ok(result)
 }
@@ -342,14 +943,25 @@ pub fn overlap_t3(
     use crate::helpers::{build_result, check_begidx, options_to_nan};
     use crate::overlap_ffi::{TA_T3_Lookback, TA_T3};
 
-    if data.is_empty() {
+    if period < 2 {
+        return Err("T3: period must be >= 2".to_string());
+    }
+
+    if !(0.0..=1.0).contains(&vfactor) {
+        return Err("T3: vfactor must be between 0.0 and 1.0".to_string());
+    }
+
+    if data.is_empty() {
         return Ok(Vec::new());
     }
 
     let clean_data = options_to_nan(&data);
     let length = clean_data.len();
 
-    let begidx = check_begidx(&clean_data);
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
     let endidx = (length - begidx - 1) as i32;
 
     let lookback = unsafe { TA_T3_Lookback(period, vfactor) };
@@ -379,7 +991,789 @@ pub fn overlap_t3(
 
     check_ret_code!(ret_code, "T3");
 
-    let result = build_result(total_lookback, out_nb_element, &out_real);
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "T3")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_kama(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::overlap_ffi::{TA_KAMA_Lookback, TA_KAMA};
+
+    if period < 2 {
+        return Err("KAMA: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_KAMA_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_KAMA(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "KAMA");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "KAMA")?;
+
+    Ok(result)
+}
+
+/// SuperTrend line and trend direction (+1 up, -1 down) series
+pub type SuperTrendOutput = (Vec<Option<f64>>, Vec<Option<i32>>);
+
+/// Final SuperTrend bands and trend direction of one bar
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SuperTrendBar {
+    pub upper: f64,
+    pub lower: f64,
+    pub direction: i32,
+}
+
+impl SuperTrendBar {
+    /// The SuperTrend line: the lower band in an uptrend, the upper band in a downtrend
+    #[inline]
+    pub fn line(&self) -> f64 {
+        if self.direction > 0 {
+            self.lower
+        } else {
+            self.upper
+        }
+    }
+}
+
+/// Compute the SuperTrend of one bar from its ATR
+///
+/// `previous` is the SuperTrend and the close of the previous bar, or `None` for
+/// the first bar, which starts in an uptrend with the basic bands.
+///
+/// The final bands only tighten: the upper band can't rise (nor the lower band
+/// fall) unless the previous close crossed it. The trend flips when the close
+/// crosses the band of the opposite side.
+#[cfg(has_talib)]
+pub fn supertrend_bar(
+    previous: Option<(SuperTrendBar, f64)>,
+    high: f64,
+    low: f64,
+    close: f64,
+    atr: f64,
+    multiplier: f64,
+) -> SuperTrendBar {
+    let hl2 = (high + low) / 2.0;
+    let basic_upper = hl2 + multiplier * atr;
+    let basic_lower = hl2 - multiplier * atr;
+
+    let (upper, lower, prev_direction) = match previous {
+        Some((prev, prev_close)) => {
+            let upper = if basic_upper < prev.upper || prev_close > prev.upper {
+                basic_upper
+            } else {
+                prev.upper
+            };
+
+            let lower = if basic_lower > prev.lower || prev_close < prev.lower {
+                basic_lower
+            } else {
+                prev.lower
+            };
+
+            (upper, lower, prev.direction)
+        }
+        None => (basic_upper, basic_lower, 1),
+    };
+
+    let direction = if prev_direction > 0 && close < lower {
+        -1
+    } else if prev_direction < 0 && close > upper {
+        1
+    } else {
+        prev_direction
+    };
+
+    SuperTrendBar {
+        upper,
+        lower,
+        direction,
+    }
+}
+
+/// Chain [`supertrend_bar`] over a series given its ATR
+///
+/// Bars without an ATR (leading NaN and warmup) or with a missing input are
+/// returned as `None` and skipped: the next complete bar chains on the last one.
+#[cfg(has_talib)]
+fn supertrend_from_atr(
+    high: &[Option<f64>],
+    low: &[Option<f64>],
+    close: &[Option<f64>],
+    atr: &[Option<f64>],
+    multiplier: f64,
+) -> SuperTrendOutput {
+    let length = close.len();
+    let mut line = Vec::with_capacity(length);
+    let mut direction = Vec::with_capacity(length);
+    let mut previous = None;
+
+    for i in 0..length {
+        match (high[i], low[i], close[i], atr[i]) {
+            (Some(h), Some(l), Some(c), Some(atr)) if !(h.is_nan() || l.is_nan() || c.is_nan()) => {
+                let bar = supertrend_bar(previous, h, l, c, atr, multiplier);
+                previous = Some((bar, c));
+
+                line.push(Some(bar.line()));
+                direction.push(Some(bar.direction));
+            }
+            _ => {
+                line.push(None);
+                direction.push(None);
+            }
+        }
+    }
+
+    (line, direction)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_supertrend(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period: i32,
+    multiplier: f64,
+) -> Result<SuperTrendOutput, String> {
+    use crate::volatility::calculate_atr;
+
+    if period < 2 {
+        return Err("SUPERTREND: period must be >= 2".to_string());
+    }
+
+    if multiplier.is_nan() || multiplier <= 0.0 {
+        return Err("SUPERTREND: multiplier must be > 0".to_string());
+    }
+
+    crate::helpers::validate_inputs(
+        "SUPERTREND",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    let atr = calculate_atr(&high, &low, &close, period)?;
+
+    Ok(supertrend_from_atr(&high, &low, &close, &atr, multiplier))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_keltner(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    ema_period: i32,
+    atr_period: i32,
+    multiplier: f64,
+) -> Result<crate::helpers::TripleOutput, String> {
+    use crate::volatility::calculate_atr;
+
+    if ema_period < 2 {
+        return Err("KELTNER: ema_period must be >= 2".to_string());
+    }
+
+    if atr_period < 2 {
+        return Err("KELTNER: atr_period must be >= 2".to_string());
+    }
+
+    if multiplier.is_nan() || multiplier <= 0.0 {
+        return Err("KELTNER: multiplier must be > 0".to_string());
+    }
+
+    crate::helpers::validate_inputs(
+        "KELTNER",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    let length = close.len();
+
+    // Shared begidx: the first bar where high, low and close are all valid
+    let is_valid = |value: Option<f64>| value.is_some_and(|v| !v.is_nan());
+    let begidx =
+        match (0..length).find(|&i| is_valid(high[i]) && is_valid(low[i]) && is_valid(close[i])) {
+            Some(idx) => idx,
+            None => return Ok((vec![None; length], vec![None; length], vec![None; length])),
+        };
+
+    // Hide the closes before the shared begidx so that the EMA starts with the ATR
+    let ema_input: Vec<Option<f64>> = close
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| if i < begidx { None } else { value })
+        .collect();
+
+    let ema = calculate_ema(ema_input, ema_period)?;
+    let atr = calculate_atr(&high, &low, &close, atr_period)?;
+
+    let mut upper = Vec::with_capacity(length);
+    let mut middle = Vec::with_capacity(length);
+    let mut lower = Vec::with_capacity(length);
+
+    // All three outputs start at the later of the EMA and ATR warmups
+    for (ema, atr) in ema.into_iter().zip(atr) {
+        match (ema, atr) {
+            (Some(ema), Some(atr)) => {
+                upper.push(Some(ema + multiplier * atr));
+                middle.push(Some(ema));
+                lower.push(Some(ema - multiplier * atr));
+            }
+            _ => {
+                upper.push(None);
+                middle.push(None);
+                lower.push(None);
+            }
+        }
+    }
+
+    Ok((upper, middle, lower))
+}
+
+/// Chandelier Exit
+///
+/// The long exit hangs `multiplier` ATRs below the highest high of the last
+/// `period` bars, the short exit as far above the lowest low. Both start with
+/// the ATR, whose warmup is one bar longer than the rolling high and low.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_chandelier(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period: i32,
+    multiplier: f64,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    use crate::volatility::calculate_atr;
+
+    if period < 2 {
+        return Err("CHANDELIER: period must be >= 2".to_string());
+    }
+
+    if multiplier.is_nan() || multiplier <= 0.0 {
+        return Err("CHANDELIER: multiplier must be > 0".to_string());
+    }
+
+    crate::helpers::validate_inputs(
+        "CHANDELIER",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    let (highest, lowest) = calculate_high_low_bands(&high, &low, period, "CHANDELIER")?;
+    let atr = calculate_atr(&high, &low, &close, period)?;
+
+    let mut long_exit = Vec::with_capacity(atr.len());
+    let mut short_exit = Vec::with_capacity(atr.len());
+
+    for ((highest, lowest), atr) in highest.into_iter().zip(lowest).zip(atr) {
+        match (highest, lowest, atr) {
+            (Some(highest), Some(lowest), Some(atr)) => {
+                long_exit.push(Some(highest - multiplier * atr));
+                short_exit.push(Some(lowest + multiplier * atr));
+            }
+            _ => {
+                long_exit.push(None);
+                short_exit.push(None);
+            }
+        }
+    }
+
+    Ok((long_exit, short_exit))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_midprice(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::overlap_ffi::{TA_MIDPRICE_Lookback, TA_MIDPRICE};
+
+    if period < 2 {
+        return Err("MIDPRICE: period must be >= 2".to_string());
+    }
+
+    validate_inputs("MIDPRICE", &[("high", high.len()), ("low", low.len())])?;
+
+    if low.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let length = clean_low.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_MIDPRICE_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_MIDPRICE(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "MIDPRICE");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "MIDPRICE")?;
+
+    Ok(result)
+}
+
+/// Parabolic SAR with every TA-Lib parameter exposed
+///
+/// Short positions are returned as negative values, as TA-Lib encodes them: the
+/// absolute value is the SAR and the sign is the side of the trade.
+#[cfg(has_talib)]
+#[rustler::nif]
+#[allow(clippy::too_many_arguments)]
+pub fn overlap_sarext(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    start_value: f64,
+    offset_on_reverse: f64,
+    accel_init_long: f64,
+    accel_long: f64,
+    accel_max_long: f64,
+    accel_init_short: f64,
+    accel_short: f64,
+    accel_max_short: f64,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::overlap_ffi::{TA_SAREXT_Lookback, TA_SAREXT};
+
+    if offset_on_reverse < 0.0 {
+        return Err("SAREXT: offset_on_reverse must be >= 0".to_string());
+    }
+
+    let accelerations = [
+        accel_init_long,
+        accel_long,
+        accel_max_long,
+        accel_init_short,
+        accel_short,
+        accel_max_short,
+    ];
+
+    if accelerations.iter().any(|&accel| accel < 0.0) {
+        return Err("SAREXT: acceleration factors must be >= 0".to_string());
+    }
+
+    validate_inputs("SAREXT", &[("high", high.len()), ("low", low.len())])?;
+
+    if low.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let length = clean_low.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe {
+        TA_SAREXT_Lookback(
+            start_value,
+            offset_on_reverse,
+            accel_init_long,
+            accel_long,
+            accel_max_long,
+            accel_init_short,
+            accel_short,
+            accel_max_short,
+        )
+    };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_SAREXT(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            start_value,
+            offset_on_reverse,
+            accel_init_long,
+            accel_long,
+            accel_max_long,
+            accel_init_short,
+            accel_short,
+            accel_max_short,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "SAREXT");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "SAREXT")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_donchian(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    period: i32,
+) -> Result<crate::helpers::TripleOutput, String> {
+    if period < 2 {
+        return Err("DONCHIAN: period must be >= 2".to_string());
+    }
+
+    crate::helpers::validate_inputs("DONCHIAN", &[("high", high.len()), ("low", low.len())])?;
+
+    let (upper, lower) = calculate_high_low_bands(&high, &low, period, "DONCHIAN")?;
+
+    let middle = upper
+        .iter()
+        .zip(&lower)
+        .map(|(upper, lower)| match (upper, lower) {
+            (Some(upper), Some(lower)) => Some((upper + lower) / 2.0),
+            _ => None,
+        })
+        .collect();
+
+    Ok((upper, middle, lower))
+}
+
+/// Highest high and lowest low over the last `period` bars
+///
+/// Both inputs must have the same length (validated by the caller).
+#[cfg(has_talib)]
+pub(crate) fn calculate_high_low_bands(
+    high: &[Option<f64>],
+    low: &[Option<f64>],
+    period: i32,
+    func_name: &str,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan};
+    use crate::overlap_ffi::{TA_MAX_Lookback, TA_MAX, TA_MIN};
+
+    if low.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let clean_high = options_to_nan(high);
+    let clean_low = options_to_nan(low);
+    let length = clean_low.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low]) {
+        Some(idx) => idx,
+        None => return Ok((vec![None; length], vec![None; length])),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    // MAX and MIN share the same lookback
+    let lookback = unsafe { TA_MAX_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok((vec![None; length], vec![None; length]));
+    }
+
+    let valid_data_len = length - begidx;
+
+    let mut upper_beg_idx: i32 = 0;
+    let mut upper_nb_element: i32 = 0;
+    let mut out_upper: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_MAX(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            period,
+            &mut upper_beg_idx as *mut i32,
+            &mut upper_nb_element as *mut i32,
+            out_upper.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, func_name);
+
+    let mut lower_beg_idx: i32 = 0;
+    let mut lower_nb_element: i32 = 0;
+    let mut out_lower: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_MIN(
+            0,
+            endidx,
+            clean_low[begidx..].as_ptr(),
+            period,
+            &mut lower_beg_idx as *mut i32,
+            &mut lower_nb_element as *mut i32,
+            out_lower.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, func_name);
+
+    // Both bands are computed over the same bars, TA-Lib must align them
+    debug_assert_eq!(upper_beg_idx, lower_beg_idx);
+    debug_assert_eq!(upper_nb_element, lower_nb_element);
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let upper = build_result(
+        begidx as i32 + upper_beg_idx,
+        upper_nb_element,
+        &out_upper,
+        func_name,
+    )?;
+    let lower = build_result(
+        begidx as i32 + lower_beg_idx,
+        lower_nb_element,
+        &out_lower,
+        func_name,
+    )?;
+
+    Ok((upper, lower))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_bbands(
+    data: Vec<Option<f64>>,
+    period: i32,
+    nb_dev_up: f64,
+    nb_dev_dn: f64,
+    ma_type: i32,
+) -> Result<crate::helpers::TripleOutput, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::overlap_ffi::{TA_BBANDS_Lookback, TA_BBANDS};
+
+    if period < 2 {
+        return Err("BBANDS: period must be >= 2".to_string());
+    }
+
+    // TA_MAType: SMA, EMA, WMA, DEMA, TEMA, TRIMA, KAMA, MAMA, T3
+    if !(0..=8).contains(&ma_type) {
+        return Err("BBANDS: ma_type must be between 0 and 8".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok((Vec::new(), Vec::new(), Vec::new()));
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok((vec![None; length], vec![None; length], vec![None; length])),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_BBANDS_Lookback(period, nb_dev_up, nb_dev_dn, ma_type) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok((vec![None; length], vec![None; length], vec![None; length]));
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_upper: Vec<f64> = vec![0.0; valid_data_len];
+    let mut out_middle: Vec<f64> = vec![0.0; valid_data_len];
+    let mut out_lower: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_BBANDS(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            nb_dev_up,
+            nb_dev_dn,
+            ma_type,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_upper.as_mut_ptr(),
+            out_middle.as_mut_ptr(),
+            out_lower.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "BBANDS");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let upper = build_result(first_idx, out_nb_element, &out_upper, "BBANDS")?;
+    let middle = build_result(first_idx, out_nb_element, &out_middle, "BBANDS")?;
+    let lower = build_result(first_idx, out_nb_element, &out_lower, "BBANDS")?;
+
+    Ok((upper, middle, lower))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ma(
+    data: Vec<Option<f64>>,
+    period: i32,
+    ma_type: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    calculate_ma(&data, period, ma_type)
+}
+
+#[cfg(has_talib)]
+pub(crate) fn calculate_ma(
+    data: &[Option<f64>],
+    period: i32,
+    ma_type: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::overlap_ffi::{TA_MA_Lookback, TA_MA};
+
+    if period < 2 {
+        return Err("MA: period must be >= 2".to_string());
+    }
+
+    // TA_MAType: SMA, EMA, WMA, DEMA, TEMA, TRIMA, KAMA, MAMA, T3
+    if !(0..=8).contains(&ma_type) {
+        return Err("MA: ma_type must be between 0 and 8".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_MA_Lookback(period, ma_type) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_MA(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            ma_type,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "MA");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "MA")?;
 
     Ok(result)
 }
@@ -391,18 +1785,144 @@ pub fn overlap_sma(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f
     Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
 }
 
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_source(
+    _ohlc: crate::batch::Ohlc,
+    _period: i32,
+    _source: crate::price::PriceSource,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_iodata<'a>(
+    _env: rustler::Env<'a>,
+    _data: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<crate::helpers::BinaryOutput<'a>, String> {
+    Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_with_sentinel(
+    _data: Vec<f64>,
+    _period: i32,
+    _sentinel: f64,
+) -> Result<Vec<f64>, String> {
+    Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_annotated(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+    _last_bar_closed: bool,
+) -> Result<crate::helpers::AnnotatedOutput, String> {
+    Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_partial(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn overlap_sma_windows(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+    _window: usize,
+    _step: usize,
+) -> Result<Vec<Vec<Option<f64>>>, String> {
+    Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn overlap_sma_multi(
+    series: Vec<Vec<Option<f64>>>,
+    _period: i32,
+) -> Vec<Result<Vec<Option<f64>>, String>> {
+    series
+        .iter()
+        .map(|_| Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string()))
+        .collect()
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_f32(_data: Vec<Option<f32>>, _period: i32) -> Result<Vec<Option<f32>>, String> {
+    Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_trimmed(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<crate::helpers::TrimmedOutput, String> {
+    Err("SMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
 #[cfg(not(has_talib))]
 #[rustler::nif]
 pub fn overlap_ema(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
     Err("EMA: TA-Lib not available. Please use the Elixir backend.".to_string())
 }
 
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_multi(
+    _data: Vec<Option<f64>>,
+    periods: Vec<i32>,
+) -> Vec<Result<Vec<Option<f64>>, String>> {
+    periods
+        .iter()
+        .map(|_| Err("EMA: TA-Lib not available. Please use the Elixir backend.".to_string()))
+        .collect()
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_f32(_data: Vec<Option<f32>>, _period: i32) -> Result<Vec<Option<f32>>, String> {
+    Err("EMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_trimmed(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<crate::helpers::TrimmedOutput, String> {
+    Err("EMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
 #[cfg(not(has_talib))]
 #[rustler::nif]
 pub fn overlap_wma(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
     Err("WMA: TA-Lib not available. Please use the Elixir backend.".to_string())
 }
 
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_hma(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("HMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_zlema(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("ZLEMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
 #[cfg(not(has_talib))]
 #[rustler::nif]
 pub fn overlap_dema(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
@@ -436,3 +1956,167 @@ pub fn overlap_t3(
 ) -> Result<Vec<Option<f64>>, String> {
     Err("T3: TA-Lib not available. Please use the Elixir backend.".to_string())
 }
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_kama(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("KAMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_supertrend(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period: i32,
+    _multiplier: f64,
+) -> Result<SuperTrendOutput, String> {
+    Err("SUPERTREND: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_keltner(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _ema_period: i32,
+    _atr_period: i32,
+    _multiplier: f64,
+) -> Result<crate::helpers::TripleOutput, String> {
+    Err("KELTNER: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_midprice(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("MIDPRICE: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+#[allow(clippy::too_many_arguments)]
+pub fn overlap_sarext(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _start_value: f64,
+    _offset_on_reverse: f64,
+    _accel_init_long: f64,
+    _accel_long: f64,
+    _accel_max_long: f64,
+    _accel_init_short: f64,
+    _accel_short: f64,
+    _accel_max_short: f64,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("SAREXT: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_chandelier(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period: i32,
+    _multiplier: f64,
+) -> Result<crate::helpers::DoubleOutput, String> {
+    Err("CHANDELIER: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_donchian(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<crate::helpers::TripleOutput, String> {
+    Err("DONCHIAN: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_bbands(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+    _nb_dev_up: f64,
+    _nb_dev_dn: f64,
+    _ma_type: i32,
+) -> Result<crate::helpers::TripleOutput, String> {
+    Err("BBANDS: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ma(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+    _ma_type: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("MA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(all(test, has_talib))]
+mod tests {
+    use super::*;
+
+    fn bars(ohlc: &[(f64, f64, f64)]) -> [Vec<Option<f64>>; 3] {
+        [
+            ohlc.iter().map(|&(h, _, _)| Some(h)).collect(),
+            ohlc.iter().map(|&(_, l, _)| Some(l)).collect(),
+            ohlc.iter().map(|&(_, _, c)| Some(c)).collect(),
+        ]
+    }
+
+    #[test]
+    fn supertrend_follows_a_clean_uptrend() {
+        let [high, low, close] = bars(&[
+            (11.0, 9.0, 10.5),
+            (12.0, 10.0, 11.5),
+            (13.0, 11.0, 12.5),
+            (14.0, 12.0, 13.5),
+        ]);
+        let atr = [None, Some(1.0), Some(1.0), Some(1.0)];
+
+        let (line, direction) = supertrend_from_atr(&high, &low, &close, &atr, 2.0);
+
+        // Warmup without ATR, then the lower band (hl2 - 2 * ATR) rises with the bars
+        assert_eq!(line, vec![None, Some(9.0), Some(10.0), Some(11.0)]);
+        assert_eq!(direction, vec![None, Some(1), Some(1), Some(1)]);
+    }
+
+    #[test]
+    fn supertrend_flips_on_a_whipsaw() {
+        let [high, low, close] = bars(&[
+            (11.0, 9.0, 10.0),
+            (12.0, 10.0, 11.5),
+            (10.0, 8.0, 8.5),
+            (12.0, 10.0, 11.5),
+        ]);
+        let atr = [Some(1.0); 4];
+
+        let (line, direction) = supertrend_from_atr(&high, &low, &close, &atr, 1.0);
+
+        // Bar 2 closes below the lower band (10) and switches to the upper band (10),
+        // bar 3 closes above it and switches back to the lower band, held at 10
+        assert_eq!(line, vec![Some(9.0), Some(10.0), Some(10.0), Some(10.0)]);
+        assert_eq!(direction, vec![Some(1), Some(1), Some(-1), Some(1)]);
+    }
+
+    #[test]
+    fn supertrend_skips_incomplete_bars() {
+        let [high, low, mut close] =
+            bars(&[(11.0, 9.0, 10.5), (12.0, 10.0, 11.5), (13.0, 11.0, 12.5)]);
+        close[1] = None;
+        let atr = [Some(1.0); 3];
+
+        let (line, direction) = supertrend_from_atr(&high, &low, &close, &atr, 2.0);
+
+        assert_eq!(line, vec![Some(8.0), None, Some(10.0)]);
+        assert_eq!(direction, vec![Some(1), None, Some(1)]);
+    }
+}
@@ -1,48 +1,105 @@
+#[cfg(has_talib)]
+use crate::momentum_state::{momentum_window_next, new_momentum_window, new_rsi_state, step_rsi};
+use crate::momentum_state::{MomentumWindow, RSIState};
+use crate::overlap::SuperTrendBar;
+use crate::stats_state::VARWindow;
+#[cfg(has_talib)]
+use crate::stats_state::{new_var_window, var_window_next};
+use crate::volatility_state::ATRState;
+#[cfg(has_talib)]
+use crate::volatility_state::{new_atr_state, step_atr};
 use rustler::ResourceArc;
+#[cfg(not(has_talib))]
+use rustler::Term;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// What a state does with a missing input (nil, or NaN)
+///
+/// The state is never modified by a missing input, so it can't be poisoned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NanPolicy {
+    /// Return nil
+    Skip,
+    /// Return the current value of the state
+    CarryForward,
+    /// Return an error
+    Reject,
+}
+
+/// Default number of bars between two rebuilds of the running sums from the buffer
+///
+/// Adding and subtracting values lets rounding errors pile up in a running sum:
+/// each bar adds a couple of roundings, i.e. a few 1e-16 of the window sum, so
+/// on price-like data even 100_000 bars without a rebuild drift by less than
+/// 1e-6. Rebuilding costs O(period), so doing it every 8192 bars keeps the
+/// overhead negligible while bounding the drift.
+pub const DEFAULT_RECOMPUTE_INTERVAL: i32 = 8192;
+
+/// How an EMA state computes its first value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SeedMode {
+    /// SMA of the first `period` values, as TA-Lib does
+    Sma,
+    /// First value as is, so the EMA is defined from the first bar
+    First,
+}
 
 /// State for EMA calculation
 #[derive(Clone)]
 pub struct EMAState {
-    period: i32,
-    k: f64,
-    current_ema: Option<f64>, // EMA of current bar (can change in UPDATE mode)
-    prev_ema: Option<f64>,    // EMA of previous bar (persisted in APPEND mode)
-    lookback_count: i32,
-    buffer: Vec<f64>,
+    pub(crate) period: i32,
+    pub(crate) k: f64,
+    pub(crate) current_ema: Option<f64>, // EMA of current bar (can change in UPDATE mode)
+    pub(crate) prev_ema: Option<f64>,    // EMA of previous bar (persisted in APPEND mode)
+    pub(crate) lookback_count: i32,
+    pub(crate) buffer: Vec<f64>,
+    pub(crate) previous: Option<Box<EMAState>>, // State before the last APPEND (for undo)
+    pub(crate) nan_policy: NanPolicy,
+    pub(crate) seed_mode: SeedMode,
 }
 
 /// State for SMA calculation
+#[derive(Clone)]
 pub struct SMAState {
     period: i32,
     buffer: Vec<f64>,
     lookback_count: i32,
+    previous: Option<Box<SMAState>>, // State before the last APPEND (for undo)
+    nan_policy: NanPolicy,
+    last_ts: Option<i64>, // Timestamp of the last bar given to state_next_ts
 }
 
 /// State for WMA calculation
+#[derive(Clone)]
 pub struct WMAState {
     period: i32,
     buffer: Vec<f64>,
     lookback_count: i32,
+    nan_policy: NanPolicy,
 }
 
 /// State for DEMA calculation
+#[derive(Clone)]
 pub struct DEMAState {
     period: i32,
     lookback_count: i32,
-    ema1_state: Box<EMAState>,
-    ema2_state: Box<EMAState>,
+    ema1_state: Arc<EMAState>,
+    ema2_state: Arc<EMAState>,
 }
 
 /// State for TEMA calculation
+#[derive(Clone)]
 pub struct TEMAState {
     period: i32,
     lookback_count: i32,
-    ema1_state: Box<EMAState>,
-    ema2_state: Box<EMAState>,
-    ema3_state: Box<EMAState>,
+    ema1_state: Arc<EMAState>,
+    ema2_state: Arc<EMAState>,
+    ema3_state: Arc<EMAState>,
 }
 
 /// State for TRIMA calculation
+#[derive(Clone)]
 pub struct TRIMAState {
     period: i32,
     first_period: i32,
@@ -57,75 +114,335 @@ pub struct MIDPOINTState {
     period: i32,
     buffer: Vec<f64>,
     lookback_count: i32,
+    nan_policy: NanPolicy,
 }
 
 /// State for T3 calculation
+#[derive(Clone)]
 pub struct T3State {
     period: i32,
     vfactor: f64,
     lookback_count: i32,
-    ema1_state: Box<EMAState>,
-    ema2_state: Box<EMAState>,
-    ema3_state: Box<EMAState>,
-    ema4_state: Box<EMAState>,
-    ema5_state: Box<EMAState>,
-    ema6_state: Box<EMAState>,
+    ema1_state: Arc<EMAState>,
+    ema2_state: Arc<EMAState>,
+    ema3_state: Arc<EMAState>,
+    ema4_state: Arc<EMAState>,
+    ema5_state: Arc<EMAState>,
+    ema6_state: Arc<EMAState>,
+}
+
+/// State for KAMA calculation
+#[derive(Clone)]
+pub struct KAMAState {
+    period: i32,
+    fastest_sc: f64,
+    slowest_sc: f64,
+    lookback_count: i32,
+    current_kama: Option<f64>, // KAMA of current bar (can change in UPDATE mode)
+    prev_kama: Option<f64>,    // KAMA of previous bar (persisted in APPEND mode)
+    buffer: Vec<f64>,          // Last period + 1 values
+}
+
+/// Monotonic deque of (bar index, value) pairs, with the extremum at the front
+pub(crate) type MonotonicDeque = VecDeque<(i32, f64)>;
+
+/// Rolling highest high and lowest low window shared by MIDPRICE, WILLR and AROON
+#[derive(Clone)]
+pub(crate) struct HighLowWindow {
+    pub(crate) period: i32,
+    pub(crate) lookback_count: i32,
+    pub(crate) current_high: f64, // High of current bar (can change in UPDATE mode)
+    pub(crate) current_low: f64,  // Low of current bar (can change in UPDATE mode)
+    pub(crate) highs: MonotonicDeque, // Decreasing highs of the closed bars in the window
+    pub(crate) lows: MonotonicDeque, // Increasing lows of the closed bars in the window
+}
+
+/// State for MIDPRICE calculation
+pub struct MIDPRICEState {
+    window: HighLowWindow,
+}
+
+/// State for BBANDS calculation
+pub struct BBANDSState {
+    nb_dev_up: f64,
+    nb_dev_dn: f64,
+    ma_type: i32,
+    window: VARWindow, // Gives both the SMA middle band and the standard deviation
+}
+
+/// State for MA calculation, wrapping the state of the selected `ma_type`
+#[derive(Clone)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum MAState {
+    SMA(SMAState),
+    EMA(EMAState),
+    WMA(WMAState),
+    DEMA(DEMAState),
+    TEMA(TEMAState),
+    TRIMA(TRIMAState),
+    KAMA(KAMAState),
+    T3(T3State),
 }
 
+/// State for RMA calculation: an EMA state with `k = 1 / period`
+pub struct RMAState(EMAState);
+
+/// State for ZLEMA calculation
+pub struct ZLEMAState {
+    window: MomentumWindow, // Last 'lag + 1' values, for the lag term
+    ema: EMAState,
+}
+
+/// State for SuperTrend calculation
+pub struct SuperTrendState {
+    multiplier: f64,
+    atr: ATRState,
+    current: Option<(SuperTrendBar, f64)>, // Current bar and its close (can change in UPDATE mode)
+    prev: Option<(SuperTrendBar, f64)>,    // Previous bar and its close (persisted in APPEND mode)
+}
+
+/// State for McGinley Dynamic calculation
+pub struct McGinleyState {
+    period: i32,
+    current_md: Option<f64>, // MD of current bar (can change in UPDATE mode)
+    prev_md: Option<f64>,    // MD of previous bar (persisted in APPEND mode)
+}
+
+/// Stage of a pipeline, fed with the output of the previous stage
+#[allow(clippy::upper_case_acronyms)]
+enum PipelineStage {
+    MA(MAState),
+    RSI(RSIState),
+}
+
+/// State for a pipeline of indicators (e.g. the SMA of an RSI)
+pub struct PipelineState {
+    stages: Vec<PipelineStage>,
+}
+
+/// Validate the `recompute_interval` argument of the state init NIFs
 #[cfg(has_talib)]
-#[rustler::nif]
-pub fn overlap_ema_state_init(period: i32) -> Result<ResourceArc<EMAState>, String> {
-    if period < 2 {
-        return Err("Invalid period: must be >= 2 for EMA".to_string());
+pub(crate) fn validate_recompute_interval(
+    recompute_interval: i32,
+    name: &str,
+) -> Result<(), String> {
+    if recompute_interval < 1 {
+        return Err(format!(
+            "Invalid recompute_interval: must be >= 1 for {}",
+            name
+        ));
     }
 
-    let k = 2.0 / (period as f64 + 1.0);
-    let state = EMAState {
+    Ok(())
+}
+
+/// Decode the `nan_policy` argument of the state init NIFs
+#[cfg(has_talib)]
+fn decode_nan_policy(nan_policy: rustler::Atom, name: &str) -> Result<NanPolicy, String> {
+    use crate::atoms;
+
+    if nan_policy == atoms::skip() {
+        Ok(NanPolicy::Skip)
+    } else if nan_policy == atoms::carry_forward() {
+        Ok(NanPolicy::CarryForward)
+    } else if nan_policy == atoms::reject() {
+        Ok(NanPolicy::Reject)
+    } else {
+        Err(format!(
+            "Invalid nan_policy: must be :skip, :carry_forward or :reject for {}",
+            name
+        ))
+    }
+}
+
+/// Decode the `seed_mode` argument of the EMA-based state init NIFs
+#[cfg(has_talib)]
+fn decode_seed_mode(seed_mode: rustler::Atom, name: &str) -> Result<SeedMode, String> {
+    use crate::atoms;
+
+    if seed_mode == atoms::sma() {
+        Ok(SeedMode::Sma)
+    } else if seed_mode == atoms::first() {
+        Ok(SeedMode::First)
+    } else {
+        Err(format!(
+            "Invalid seed_mode: must be :sma or :first for {}",
+            name
+        ))
+    }
+}
+
+/// Output of a state for a missing input, according to its policy
+///
+/// `current` is the value the state currently reports, carried forward as is.
+#[cfg(has_talib)]
+fn missing_input_result(
+    nan_policy: NanPolicy,
+    current: Option<f64>,
+    name: &str,
+) -> Result<Option<f64>, String> {
+    match nan_policy {
+        NanPolicy::Skip => Ok(None),
+        NanPolicy::CarryForward => Ok(current),
+        NanPolicy::Reject => Err(format!("Invalid value: missing input rejected by {}", name)),
+    }
+}
+
+#[cfg(has_talib)]
+pub(crate) fn new_ema_state(period: i32) -> EMAState {
+    EMAState {
         period,
-        k,
+        k: 2.0 / (period as f64 + 1.0),
         current_ema: None,
         prev_ema: None,
         lookback_count: 0,
         buffer: Vec::new(),
+        previous: None,
+        nan_policy: NanPolicy::Skip,
+        seed_mode: SeedMode::Sma,
+    }
+}
+
+#[cfg(has_talib)]
+impl EMAState {
+    /// Number of bars before the first EMA value: `period` with an SMA seed, 1 otherwise
+    fn warmup(&self) -> i32 {
+        match self.seed_mode {
+            SeedMode::Sma => self.period,
+            SeedMode::First => 1,
+        }
+    }
+}
+
+/// Decode the option of the 2-arity EMA init, either a `nan_policy` or a `seed_mode`
+///
+/// The two sets of atoms are disjoint; the other setting keeps its default.
+#[cfg(has_talib)]
+fn decode_ema_option(option: rustler::Atom) -> Result<(NanPolicy, SeedMode), String> {
+    if let Ok(nan_policy) = decode_nan_policy(option, "EMA") {
+        return Ok((nan_policy, SeedMode::Sma));
+    }
+
+    if let Ok(seed_mode) = decode_seed_mode(option, "EMA") {
+        return Ok((NanPolicy::Skip, seed_mode));
+    }
+
+    Err(
+        "Invalid option: must be :skip, :carry_forward, :reject, :sma or :first for EMA"
+            .to_string(),
+    )
+}
+
+/// `overlap_ema_state_init(period)`: skips missing values and seeds with an SMA
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ema_state_init(period: i32) -> Result<ResourceArc<EMAState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for EMA".to_string());
+    }
+
+    let state = new_ema_state(period);
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+/// `overlap_ema_state_init(period, nan_policy_or_seed_mode)`
+///
+/// Takes either a `nan_policy` (`:skip`, `:carry_forward` or `:reject`) or a
+/// `seed_mode` (`:sma` or `:first`), the other one keeping its default.
+#[cfg(has_talib)]
+#[rustler::nif(name = "overlap_ema_state_init")]
+pub fn overlap_ema_state_init_with_option(
+    period: i32,
+    option: rustler::Atom,
+) -> Result<ResourceArc<EMAState>, String> {
+    let (nan_policy, seed_mode) = decode_ema_option(option)?;
+
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for EMA".to_string());
+    }
+
+    let state = EMAState {
+        nan_policy,
+        seed_mode,
+        ..new_ema_state(period)
     };
 
     let resource = ResourceArc::new(state);
     Ok(resource)
 }
 
+/// `overlap_ema_state_init(period, nan_policy, seed_mode)`
 #[cfg(has_talib)]
-#[rustler::nif]
-pub fn overlap_ema_state_next(
-    state_arc: ResourceArc<EMAState>,
-    value: Option<f64>,
-    is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<EMAState>), String> {
-    let state = &*state_arc;
+#[rustler::nif(name = "overlap_ema_state_init")]
+pub fn overlap_ema_state_init_with_seed_mode(
+    period: i32,
+    nan_policy: rustler::Atom,
+    seed_mode: rustler::Atom,
+) -> Result<ResourceArc<EMAState>, String> {
+    let nan_policy = decode_nan_policy(nan_policy, "EMA")?;
+    let seed_mode = decode_seed_mode(seed_mode, "EMA")?;
 
-    // Handle nil input: return nil without modifying state
-    if value.is_none() {
-        return Ok((None, state_arc));
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for EMA".to_string());
     }
 
-    let value = value.unwrap();
+    let state = EMAState {
+        nan_policy,
+        seed_mode,
+        ..new_ema_state(period)
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+/// Seed for the first EMA value
+///
+/// Averages the last `warmup` values of the warmup buffer, dividing by the
+/// number of values actually used. With a warmup of 1 (`SeedMode::First`),
+/// this is the latest value itself. The buffer can hold an extra leading value
+/// when an UPDATE arrives before the first bar (it is pushed without opening
+/// a bar), and that value must not leak into the seed.
+///
+/// Missing values never reach the buffer, so after leading nil the seed is the
+/// average of the first `period` valid values, like TA-Lib's seed at
+/// `begidx + period - 1` in the batch EMA.
+#[cfg(has_talib)]
+#[inline]
+fn ema_seed(buffer: &[f64], warmup: i32) -> f64 {
+    let start = buffer.len().saturating_sub(warmup as usize);
+    let window = &buffer[start..];
+    let sum: f64 = window.iter().sum();
+    sum / (window.len() as f64)
+}
 
+/// Advances an EMA state by one value
+///
+/// Shared by EMA and the composite indicators (DEMA, TEMA, T3, MACD) so they
+/// all run the same recurrence. APPEND mode persists the current EMA as the
+/// previous one; UPDATE mode recomputes the current EMA from the previous one.
+#[cfg(has_talib)]
+pub(crate) fn step_ema(state: &EMAState, input: f64, is_new_bar: bool) -> (Option<f64>, EMAState) {
     let new_lookback = if is_new_bar {
         state.lookback_count + 1
     } else {
         state.lookback_count
     };
+    let warmup = state.warmup();
 
     // Update buffer ONLY during warmup OR if we need it for SMA calculation
     // We need buffer until both current_ema AND prev_ema are populated
     // (prev_ema is None until second bar after warmup)
-    let new_buffer = if new_lookback < state.period || state.prev_ema.is_none() {
+    let new_buffer = if new_lookback < warmup || state.prev_ema.is_none() {
         // Still in warmup or might need buffer for SMA in UPDATE mode
         let mut buf = state.buffer.clone();
         if is_new_bar || buf.is_empty() {
-            buf.push(value);
+            buf.push(input);
         } else {
             let last_idx = buf.len() - 1;
-            buf[last_idx] = value;
+            buf[last_idx] = input;
         }
         buf
     } else {
@@ -133,8 +450,8 @@ pub fn overlap_ema_state_next(
         Vec::new()
     };
 
-    // Warmup phase: need 'period' bars before we can calculate EMA
-    if new_lookback < state.period {
+    // Warmup phase: need 'warmup' bars before we can calculate EMA
+    if new_lookback < warmup {
         let new_state = EMAState {
             period: state.period,
             k: state.k,
@@ -142,36 +459,29 @@ pub fn overlap_ema_state_next(
             prev_ema: state.prev_ema,
             lookback_count: new_lookback,
             buffer: new_buffer,
+            previous: None,
+            nan_policy: state.nan_policy,
+            seed_mode: state.seed_mode,
         };
-        let new_resource = ResourceArc::new(new_state);
-        let result = (None, new_resource);
-        return Ok(result);
+        return (None, new_state);
     }
 
     // Calculate new EMA
     let (new_ema, new_prev_ema) = if is_new_bar {
         // APPEND mode: calculate new EMA and persist previous one
         let ema = match state.current_ema {
-            None => {
-                // First EMA: use SMA as seed (average of all values in buffer)
-                // Buffer should have exactly 'period' values at this point
-                let sum: f64 = new_buffer.iter().sum();
-                sum / (state.period as f64)
-            }
-            Some(current) => (value - current) * state.k + current,
+            // First EMA: use the seed
+            None => ema_seed(&new_buffer, warmup),
+            Some(current) => (input - current) * state.k + current,
         };
         // In APPEND: current_ema becomes prev_ema for next iteration
         (ema, state.current_ema)
     } else {
         // UPDATE mode: only recalculate last value using prev_ema
         let ema = match state.prev_ema {
-            None => {
-                // First bar being updated: use SMA
-                // Buffer should have exactly 'period' values at this point
-                let sum: f64 = new_buffer.iter().sum();
-                sum / (state.period as f64)
-            }
-            Some(prev) => (value - prev) * state.k + prev,
+            // First bar being updated: use the seed
+            None => ema_seed(&new_buffer, warmup),
+            Some(prev) => (input - prev) * state.k + prev,
         };
         // In UPDATE: prev_ema stays the same
         (ema, state.prev_ema)
@@ -184,24 +494,160 @@ pub fn overlap_ema_state_next(
         prev_ema: new_prev_ema,
         lookback_count: new_lookback,
         buffer: new_buffer,
+        previous: None,
+        nan_policy: state.nan_policy,
+        seed_mode: state.seed_mode,
+    };
+
+    (Some(new_ema), new_state)
+}
+
+/// Advances an EMA nested in a composite state (DEMA, TEMA, TRIX, T3, MACD)
+///
+/// `None` means the stage is not fed on this bar, because the stage before it
+/// is still warming up. It is then shared with the new state instead of being
+/// copied, so a long warmup doesn't deep-copy the idle stages on every bar.
+#[cfg(has_talib)]
+pub(crate) fn step_nested_ema(
+    state: &Arc<EMAState>,
+    input: Option<f64>,
+    is_new_bar: bool,
+) -> (Option<f64>, Arc<EMAState>) {
+    match input {
+        Some(input) => {
+            let (ema, new_state) = step_ema(state, input, is_new_bar);
+            (ema, Arc::new(new_state))
+        }
+        None => (None, Arc::clone(state)),
+    }
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ema_state_next(
+    state_arc: ResourceArc<EMAState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<EMAState>), String> {
+    let state = &*state_arc;
+
+    // Handle missing input (nil or NaN) as chosen at init, without modifying state
+    let value = match value.filter(|v| !v.is_nan()) {
+        Some(value) => value,
+        None => {
+            let ema = missing_input_result(state.nan_policy, state.current_ema, "EMA")?;
+            return Ok((ema, state_arc));
+        }
+    };
+
+    let (ema, mut new_state) = step_ema(state, value, is_new_bar);
+
+    // Keep the state before an APPEND so that it can be undone
+    new_state.previous = if is_new_bar {
+        Some(Box::new(EMAState {
+            previous: None,
+            ..state.clone()
+        }))
+    } else {
+        state.previous.clone()
     };
 
     let new_resource = ResourceArc::new(new_state);
 
-    Ok((Some(new_ema), new_resource))
+    Ok((ema, new_resource))
 }
 
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_sma_state_init(period: i32) -> Result<ResourceArc<SMAState>, String> {
+pub fn overlap_ema_state_value(state_arc: ResourceArc<EMAState>) -> Result<Option<f64>, String> {
+    Ok(state_arc.current_ema)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ema_state_is_ready(state_arc: ResourceArc<EMAState>) -> Result<bool, String> {
+    Ok(state_arc.lookback_count >= state_arc.warmup())
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ema_state_peek(
+    state_arc: ResourceArc<EMAState>,
+    value: Option<f64>,
+) -> Result<Option<f64>, String> {
+    // Handle missing input (nil or NaN): same policy as state_next
+    let value = match value.filter(|v| !v.is_nan()) {
+        Some(value) => value,
+        None => return missing_input_result(state_arc.nan_policy, state_arc.current_ema, "EMA"),
+    };
+
+    // Same computation as an UPDATE, but the derived state is discarded
+    let (ema, _new_state) = step_ema(&state_arc, value, false);
+
+    Ok(ema)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ema_state_undo(
+    state_arc: ResourceArc<EMAState>,
+) -> Result<ResourceArc<EMAState>, String> {
+    // Only one APPEND is kept: the restored state has nothing left to undo
+    match &state_arc.previous {
+        Some(previous) => Ok(ResourceArc::new((**previous).clone())),
+        None => Err("Nothing to undo: no previous APPEND for EMA".to_string()),
+    }
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_rma_state_init(period: i32) -> Result<ResourceArc<RMAState>, String> {
     if period < 2 {
-        return Err("Invalid period: must be >= 2 for SMA".to_string());
+        return Err("Invalid period: must be >= 2 for RMA".to_string());
     }
 
-    let state = SMAState {
-        period,
-        buffer: Vec::new(),
-        lookback_count: 0,
+    // Wilder's smoothing is the EMA recurrence with k = 1 / period
+    let state = RMAState(EMAState {
+        k: 1.0 / period as f64,
+        ..new_ema_state(period)
+    });
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_rma_state_next(
+    state_arc: ResourceArc<RMAState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<RMAState>), String> {
+    let RMAState(state) = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (rma, new_state) = step_ema(state, value, is_new_bar);
+
+    let new_resource = ResourceArc::new(RMAState(new_state));
+
+    Ok((rma, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_zlema_state_init(period: i32) -> Result<ResourceArc<ZLEMAState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for ZLEMA".to_string());
+    }
+
+    let state = ZLEMAState {
+        window: new_momentum_window((period - 1) / 2),
+        ema: new_ema_state(period),
     };
 
     let resource = ResourceArc::new(state);
@@ -210,20 +656,51 @@ pub fn overlap_sma_state_init(period: i32) -> Result<ResourceArc<SMAState>, Stri
 
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_sma_state_next(
-    state_arc: ResourceArc<SMAState>,
+pub fn overlap_zlema_state_next(
+    state_arc: ResourceArc<ZLEMAState>,
     value: Option<f64>,
     is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<SMAState>), String> {
+) -> Result<(Option<f64>, ResourceArc<ZLEMAState>), String> {
     let state = &*state_arc;
 
     // Handle nil input: return nil without modifying state
-    if value.is_none() {
-        return Ok((None, state_arc));
-    }
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
 
-    let value = value.unwrap();
+    let (values, new_window) = momentum_window_next(&state.window, value, is_new_bar);
+
+    // Warmup phase: the EMA is not fed until the lag term is available
+    let (zlema, new_ema) = match values {
+        Some((value, lagged)) => step_ema(&state.ema, value + (value - lagged), is_new_bar),
+        None => (None, state.ema.clone()),
+    };
+
+    let new_state = ZLEMAState {
+        window: new_window,
+        ema: new_ema,
+    };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((zlema, new_resource))
+}
 
+#[cfg(has_talib)]
+fn new_sma_state(period: i32) -> SMAState {
+    SMAState {
+        period,
+        buffer: Vec::new(),
+        lookback_count: 0,
+        previous: None,
+        nan_policy: NanPolicy::Skip,
+        last_ts: None,
+    }
+}
+
+/// Advances an SMA state by one value
+#[cfg(has_talib)]
+fn step_sma(state: &SMAState, value: f64, is_new_bar: bool) -> (Option<f64>, SMAState) {
     let mut new_buffer = state.buffer.clone();
     let new_lookback = if is_new_bar {
         state.lookback_count + 1
@@ -254,10 +731,11 @@ pub fn overlap_sma_state_next(
             period: state.period,
             buffer: new_buffer,
             lookback_count: new_lookback,
+            previous: None,
+            nan_policy: state.nan_policy,
+            last_ts: state.last_ts,
         };
-        let new_resource = ResourceArc::new(new_state);
-        let result = (None, new_resource);
-        return Ok(result);
+        return (None, new_state);
     }
 
     // Calculate SMA
@@ -268,46 +746,243 @@ pub fn overlap_sma_state_next(
         period: state.period,
         buffer: new_buffer,
         lookback_count: new_lookback,
+        previous: None,
+        nan_policy: state.nan_policy,
+        last_ts: state.last_ts,
     };
 
-    let new_resource = ResourceArc::new(new_state);
-
-    Ok((Some(sma), new_resource))
+    (Some(sma), new_state)
 }
 
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_wma_state_init(period: i32) -> Result<ResourceArc<WMAState>, String> {
+pub fn overlap_sma_state_init(period: i32) -> Result<ResourceArc<SMAState>, String> {
     if period < 2 {
-        return Err("Invalid period: must be >= 2 for WMA".to_string());
+        return Err("Invalid period: must be >= 2 for SMA".to_string());
     }
 
-    let state = WMAState {
-        period,
-        buffer: Vec::new(),
-        lookback_count: 0,
-    };
+    let state = new_sma_state(period);
 
     let resource = ResourceArc::new(state);
     Ok(resource)
 }
 
+/// One SMA state per period, e.g. one per symbol, in a single call
+///
+/// Every period is validated first: an invalid one fails the whole call,
+/// reporting its index, and no state is built.
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_wma_state_next(
-    state_arc: ResourceArc<WMAState>,
-    value: Option<f64>,
-    is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<WMAState>), String> {
-    let state = &*state_arc;
-
-    // Handle nil input: return nil without modifying state
-    if value.is_none() {
-        return Ok((None, state_arc));
+pub fn overlap_sma_state_init_many(
+    periods: Vec<i32>,
+) -> Result<Vec<ResourceArc<SMAState>>, String> {
+    if let Some(index) = periods.iter().position(|&period| period < 2) {
+        return Err(format!(
+            "Invalid period at index {}: must be >= 2 for SMA",
+            index
+        ));
     }
 
-    let value = value.unwrap();
+    let states = periods
+        .into_iter()
+        .map(|period| ResourceArc::new(new_sma_state(period)))
+        .collect();
+
+    Ok(states)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif(name = "overlap_sma_state_init")]
+pub fn overlap_sma_state_init_with_nan_policy(
+    period: i32,
+    nan_policy: rustler::Atom,
+) -> Result<ResourceArc<SMAState>, String> {
+    let nan_policy = decode_nan_policy(nan_policy, "SMA")?;
+
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for SMA".to_string());
+    }
+
+    let state = SMAState {
+        nan_policy,
+        ..new_sma_state(period)
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_state_next(
+    state_arc: ResourceArc<SMAState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<SMAState>), String> {
+    advance_sma_state(state_arc, value, is_new_bar, None)
+}
+
+/// Like `overlap_sma_state_next`, new bar or update told apart by the bar timestamp
+///
+/// A timestamp greater than the last one starts a new bar, the same timestamp
+/// updates the current bar and an older one is an error. A missing value leaves
+/// the state untouched, its timestamp included.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_state_next_ts(
+    state_arc: ResourceArc<SMAState>,
+    value: Option<f64>,
+    timestamp: i64,
+) -> Result<(Option<f64>, ResourceArc<SMAState>), String> {
+    let is_new_bar = match state_arc.last_ts {
+        None => true,
+        Some(last_ts) if timestamp < last_ts => {
+            return Err(format!(
+                "SMA: timestamp {} is older than the last bar ({})",
+                timestamp, last_ts
+            ));
+        }
+        Some(last_ts) => timestamp > last_ts,
+    };
+
+    advance_sma_state(state_arc, value, is_new_bar, Some(timestamp))
+}
+
+/// Shared body of the SMA state_next NIFs, recording `timestamp` when given
+#[cfg(has_talib)]
+fn advance_sma_state(
+    state_arc: ResourceArc<SMAState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+    timestamp: Option<i64>,
+) -> Result<(Option<f64>, ResourceArc<SMAState>), String> {
+    let state = &*state_arc;
+
+    // Handle missing input (nil or NaN) as chosen at init, without modifying state
+    let value = match value.filter(|v| !v.is_nan()) {
+        Some(value) => value,
+        None => {
+            let sma = missing_input_result(state.nan_policy, sma_value(state), "SMA")?;
+            return Ok((sma, state_arc));
+        }
+    };
+
+    let (sma, mut new_state) = step_sma(state, value, is_new_bar);
+
+    // Keep the state before an APPEND so that it can be undone
+    new_state.previous = if is_new_bar {
+        Some(Box::new(SMAState {
+            previous: None,
+            ..state.clone()
+        }))
+    } else {
+        state.previous.clone()
+    };
+
+    if timestamp.is_some() {
+        new_state.last_ts = timestamp;
+    }
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((sma, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_state_value(state_arc: ResourceArc<SMAState>) -> Result<Option<f64>, String> {
+    Ok(sma_value(&state_arc))
+}
+
+/// Current SMA of a state, None during warmup
+#[cfg(has_talib)]
+fn sma_value(state: &SMAState) -> Option<f64> {
+    if state.lookback_count < state.period {
+        return None;
+    }
+
+    let sum: f64 = state.buffer.iter().sum();
+    Some(sum / (state.period as f64))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_state_is_ready(state_arc: ResourceArc<SMAState>) -> Result<bool, String> {
+    Ok(state_arc.lookback_count >= state_arc.period)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_state_peek(
+    state_arc: ResourceArc<SMAState>,
+    value: Option<f64>,
+) -> Result<Option<f64>, String> {
+    // Handle missing input (nil or NaN): same policy as state_next
+    let value = match value.filter(|v| !v.is_nan()) {
+        Some(value) => value,
+        None => return missing_input_result(state_arc.nan_policy, sma_value(&state_arc), "SMA"),
+    };
+
+    // Same computation as an UPDATE, but the derived state is discarded
+    let (sma, _new_state) = step_sma(&state_arc, value, false);
+
+    Ok(sma)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_sma_state_undo(
+    state_arc: ResourceArc<SMAState>,
+) -> Result<ResourceArc<SMAState>, String> {
+    // Only one APPEND is kept: the restored state has nothing left to undo
+    match &state_arc.previous {
+        Some(previous) => Ok(ResourceArc::new((**previous).clone())),
+        None => Err("Nothing to undo: no previous APPEND for SMA".to_string()),
+    }
+}
+
+#[cfg(has_talib)]
+fn new_wma_state(period: i32) -> WMAState {
+    WMAState {
+        period,
+        buffer: Vec::new(),
+        lookback_count: 0,
+        nan_policy: NanPolicy::Skip,
+    }
+}
+
+/// Linearly weighted average of a WMA buffer, the last value weighing the most
+///
+/// Past warmup every APPEND has pushed a value, so the buffer always holds
+/// exactly 'period' values (UPDATE only replaces the last one). Weights are
+/// still derived from the actual buffer length so a short buffer can never be
+/// divided by a too-large denominator.
+#[cfg(has_talib)]
+fn weighted_average(buffer: &[f64]) -> f64 {
+    let n = buffer.len();
+
+    // Sum of weights: 1 + 2 + ... + n = n * (n + 1) / 2
+    let sum_weights = (n * (n + 1)) as f64 / 2.0;
+
+    // Weighted sum: buffer[0] * 1 + buffer[1] * 2 + ... + buffer[n-1] * n
+    let weighted_sum: f64 = buffer
+        .iter()
+        .enumerate()
+        .map(|(i, &val)| val * (i + 1) as f64)
+        .sum();
+
+    weighted_sum / sum_weights
+}
+
+/// Current WMA of a state, None during warmup
+#[cfg(has_talib)]
+fn wma_value(state: &WMAState) -> Option<f64> {
+    (state.lookback_count >= state.period).then(|| weighted_average(&state.buffer))
+}
 
+/// Advances a WMA state by one value
+#[cfg(has_talib)]
+fn step_wma(state: &WMAState, value: f64, is_new_bar: bool) -> (Option<f64>, WMAState) {
     let mut new_buffer = state.buffer.clone();
     let new_lookback = if is_new_bar {
         state.lookback_count + 1
@@ -338,67 +1013,51 @@ pub fn overlap_wma_state_next(
             period: state.period,
             buffer: new_buffer,
             lookback_count: new_lookback,
+            nan_policy: state.nan_policy,
         };
-        let new_resource = ResourceArc::new(new_state);
-        let result = (None, new_resource);
-        return Ok(result);
+        return (None, new_state);
     }
 
-    // Calculate WMA
-    // Sum of weights: 1 + 2 + ... + period = period * (period + 1) / 2
-    let sum_weights = (state.period * (state.period + 1)) as f64 / 2.0;
-
-    // Weighted sum: buffer[0] * 1 + buffer[1] * 2 + ... + buffer[period-1] * period
-    let weighted_sum: f64 = new_buffer
-        .iter()
-        .enumerate()
-        .map(|(i, &val)| val * (i + 1) as f64)
-        .sum();
-
-    let wma = weighted_sum / sum_weights;
+    let wma = weighted_average(&new_buffer);
 
     let new_state = WMAState {
         period: state.period,
         buffer: new_buffer,
         lookback_count: new_lookback,
+        nan_policy: state.nan_policy,
     };
 
-    let new_resource = ResourceArc::new(new_state);
-
-    Ok((Some(wma), new_resource))
+    (Some(wma), new_state)
 }
 
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_dema_state_init(period: i32) -> Result<ResourceArc<DEMAState>, String> {
+pub fn overlap_wma_state_init(period: i32) -> Result<ResourceArc<WMAState>, String> {
     if period < 2 {
-        return Err("Invalid period: must be >= 2 for DEMA".to_string());
+        return Err("Invalid period: must be >= 2 for WMA".to_string());
     }
 
-    let k = 2.0 / (period as f64 + 1.0);
-    let ema1_state = Box::new(EMAState {
-        period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+    let state = new_wma_state(period);
 
-    let ema2_state = Box::new(EMAState {
-        period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
 
-    let state = DEMAState {
-        period,
-        lookback_count: 0,
-        ema1_state,
-        ema2_state,
+#[cfg(has_talib)]
+#[rustler::nif(name = "overlap_wma_state_init")]
+pub fn overlap_wma_state_init_with_nan_policy(
+    period: i32,
+    nan_policy: rustler::Atom,
+) -> Result<ResourceArc<WMAState>, String> {
+    let nan_policy = decode_nan_policy(nan_policy, "WMA")?;
+
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for WMA".to_string());
+    }
+
+    let state = WMAState {
+        nan_policy,
+        ..new_wma_state(period)
     };
 
     let resource = ResourceArc::new(state);
@@ -407,20 +1066,46 @@ pub fn overlap_dema_state_init(period: i32) -> Result<ResourceArc<DEMAState>, St
 
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_dema_state_next(
-    state_arc: ResourceArc<DEMAState>,
+pub fn overlap_wma_state_next(
+    state_arc: ResourceArc<WMAState>,
     value: Option<f64>,
     is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<DEMAState>), String> {
+) -> Result<(Option<f64>, ResourceArc<WMAState>), String> {
     let state = &*state_arc;
 
-    // Handle nil input: return nil without modifying state
-    if value.is_none() {
-        return Ok((None, state_arc));
-    }
+    // Handle missing input (nil or NaN) as chosen at init, without modifying state
+    let value = match value.filter(|v| !v.is_nan()) {
+        Some(value) => value,
+        None => {
+            let wma = missing_input_result(state.nan_policy, wma_value(state), "WMA")?;
+            return Ok((wma, state_arc));
+        }
+    };
 
-    let value = value.unwrap();
+    let (wma, new_state) = step_wma(state, value, is_new_bar);
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((wma, new_resource))
+}
+
+#[cfg(has_talib)]
+fn new_dema_state(period: i32, seed_mode: SeedMode) -> DEMAState {
+    let ema_state = Arc::new(EMAState {
+        seed_mode,
+        ..new_ema_state(period)
+    });
+
+    DEMAState {
+        period,
+        lookback_count: 0,
+        ema1_state: Arc::clone(&ema_state),
+        ema2_state: ema_state,
+    }
+}
 
+/// Advances a DEMA state by one value
+#[cfg(has_talib)]
+fn step_dema(state: &DEMAState, value: f64, is_new_bar: bool) -> (Option<f64>, DEMAState) {
     // Update lookback count
     let new_lookback = if is_new_bar {
         state.lookback_count + 1
@@ -429,131 +1114,10 @@ pub fn overlap_dema_state_next(
     };
 
     // Calculate first EMA
-    let ema1_state = &*state.ema1_state;
-    let new_lookback_ema1 = if is_new_bar {
-        ema1_state.lookback_count + 1
-    } else {
-        ema1_state.lookback_count
-    };
-
-    // Update buffer for EMA1 ONLY during warmup
-    let new_buffer_ema1 = if new_lookback_ema1 < ema1_state.period || ema1_state.prev_ema.is_none()
-    {
-        let mut buf = ema1_state.buffer.clone();
-        if is_new_bar || buf.is_empty() {
-            buf.push(value);
-        } else {
-            let last_idx = buf.len() - 1;
-            buf[last_idx] = value;
-        }
-        buf
-    } else {
-        Vec::new()
-    };
-
-    // Calculate EMA1 value
-    let (ema1_value, new_ema1_current, new_ema1_prev) = if new_lookback_ema1 < ema1_state.period {
-        (None, ema1_state.current_ema, ema1_state.prev_ema)
-    } else {
-        let (ema, prev) = if is_new_bar {
-            // APPEND mode: calculate new EMA and persist previous one
-            let e = match ema1_state.current_ema {
-                None => {
-                    let sum: f64 = new_buffer_ema1.iter().sum();
-                    sum / (ema1_state.period as f64)
-                }
-                Some(current) => (value - current) * ema1_state.k + current,
-            };
-            (e, ema1_state.current_ema)
-        } else {
-            // UPDATE mode: only recalculate last value using prev_ema
-            let e = match ema1_state.prev_ema {
-                None => {
-                    let sum: f64 = new_buffer_ema1.iter().sum();
-                    sum / (ema1_state.period as f64)
-                }
-                Some(prev) => (value - prev) * ema1_state.k + prev,
-            };
-            (e, ema1_state.prev_ema)
-        };
-        (Some(ema), Some(ema), prev)
-    };
-
-    let new_ema1_state = Box::new(EMAState {
-        period: ema1_state.period,
-        k: ema1_state.k,
-        current_ema: new_ema1_current,
-        prev_ema: new_ema1_prev,
-        lookback_count: new_lookback_ema1,
-        buffer: new_buffer_ema1,
-    });
+    let (ema1_value, new_ema1_state) = step_nested_ema(&state.ema1_state, Some(value), is_new_bar);
 
     // Calculate second EMA (EMA of EMA1)
-    let (ema2_value, new_ema2_state) = if let Some(ema1_val) = ema1_value {
-        let ema2_state = &*state.ema2_state;
-        let new_lookback_ema2 = if is_new_bar {
-            ema2_state.lookback_count + 1
-        } else {
-            ema2_state.lookback_count
-        };
-
-        // Update buffer for EMA2 ONLY during warmup
-        let new_buffer_ema2 =
-            if new_lookback_ema2 < ema2_state.period || ema2_state.prev_ema.is_none() {
-                let mut buf = ema2_state.buffer.clone();
-                if is_new_bar || buf.is_empty() {
-                    buf.push(ema1_val);
-                } else {
-                    let last_idx = buf.len() - 1;
-                    buf[last_idx] = ema1_val;
-                }
-                buf
-            } else {
-                Vec::new()
-            };
-
-        // Calculate EMA2 value
-        let (ema2_val, new_ema2_current, new_ema2_prev) = if new_lookback_ema2 < ema2_state.period {
-            (None, ema2_state.current_ema, ema2_state.prev_ema)
-        } else {
-            let (ema, prev) = if is_new_bar {
-                // APPEND mode: calculate new EMA and persist previous one
-                let e = match ema2_state.current_ema {
-                    None => {
-                        let sum: f64 = new_buffer_ema2.iter().sum();
-                        sum / (ema2_state.period as f64)
-                    }
-                    Some(current) => (ema1_val - current) * ema2_state.k + current,
-                };
-                (e, ema2_state.current_ema)
-            } else {
-                // UPDATE mode: only recalculate last value using prev_ema
-                let e = match ema2_state.prev_ema {
-                    None => {
-                        let sum: f64 = new_buffer_ema2.iter().sum();
-                        sum / (ema2_state.period as f64)
-                    }
-                    Some(prev) => (ema1_val - prev) * ema2_state.k + prev,
-                };
-                (e, ema2_state.prev_ema)
-            };
-            (Some(ema), Some(ema), prev)
-        };
-
-        let new_state = Box::new(EMAState {
-            period: ema2_state.period,
-            k: ema2_state.k,
-            current_ema: new_ema2_current,
-            prev_ema: new_ema2_prev,
-            lookback_count: new_lookback_ema2,
-            buffer: new_buffer_ema2,
-        });
-
-        (ema2_val, new_state)
-    } else {
-        // During warmup of first EMA, don't update second EMA
-        (None, state.ema2_state.clone())
-    };
+    let (ema2_value, new_ema2_state) = step_nested_ema(&state.ema2_state, ema1_value, is_new_bar);
 
     let new_state = DEMAState {
         period: state.period,
@@ -562,61 +1126,49 @@ pub fn overlap_dema_state_next(
         ema2_state: new_ema2_state,
     };
 
-    let new_resource = ResourceArc::new(new_state);
+    // TA-Lib's DEMA lookback is 2 * (period - 1): the first value lands on the
+    // bar where EMA2 completes its own warmup over EMA1's output
+    if new_lookback <= 2 * (state.ema1_state.warmup() - 1) {
+        return (None, new_state);
+    }
 
     // Calculate DEMA = 2 * EMA1 - EMA2
     match (ema1_value, ema2_value) {
         (Some(e1), Some(e2)) => {
             let dema = 2.0 * e1 - e2;
 
-            Ok((Some(dema), new_resource))
+            (Some(dema), new_state)
         }
-        _ => Ok((None, new_resource)),
+        _ => (None, new_state),
     }
 }
 
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_tema_state_init(period: i32) -> Result<ResourceArc<TEMAState>, String> {
+pub fn overlap_dema_state_init(period: i32) -> Result<ResourceArc<DEMAState>, String> {
     if period < 2 {
-        return Err("Invalid period: must be >= 2 for TEMA".to_string());
+        return Err("Invalid period: must be >= 2 for DEMA".to_string());
     }
 
-    let k = 2.0 / (period as f64 + 1.0);
-    let ema1_state = Box::new(EMAState {
-        period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+    let state = new_dema_state(period, SeedMode::Sma);
 
-    let ema2_state = Box::new(EMAState {
-        period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
 
-    let ema3_state = Box::new(EMAState {
-        period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+#[cfg(has_talib)]
+#[rustler::nif(name = "overlap_dema_state_init")]
+pub fn overlap_dema_state_init_with_seed_mode(
+    period: i32,
+    seed_mode: rustler::Atom,
+) -> Result<ResourceArc<DEMAState>, String> {
+    let seed_mode = decode_seed_mode(seed_mode, "DEMA")?;
 
-    let state = TEMAState {
-        period,
-        lookback_count: 0,
-        ema1_state,
-        ema2_state,
-        ema3_state,
-    };
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for DEMA".to_string());
+    }
+
+    let state = new_dema_state(period, seed_mode);
 
     let resource = ResourceArc::new(state);
     Ok(resource)
@@ -624,11 +1176,11 @@ pub fn overlap_tema_state_init(period: i32) -> Result<ResourceArc<TEMAState>, St
 
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_tema_state_next(
-    state_arc: ResourceArc<TEMAState>,
+pub fn overlap_dema_state_next(
+    state_arc: ResourceArc<DEMAState>,
     value: Option<f64>,
     is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<TEMAState>), String> {
+) -> Result<(Option<f64>, ResourceArc<DEMAState>), String> {
     let state = &*state_arc;
 
     // Handle nil input: return nil without modifying state
@@ -638,206 +1190,46 @@ pub fn overlap_tema_state_next(
 
     let value = value.unwrap();
 
-    // Update lookback count
-    let new_lookback = if is_new_bar {
-        state.lookback_count + 1
-    } else {
-        state.lookback_count
-    };
+    let (dema, new_state) = step_dema(state, value, is_new_bar);
+    let new_resource = ResourceArc::new(new_state);
 
-    // Calculate first EMA
-    let ema1_state = &*state.ema1_state;
-    let new_lookback_ema1 = if is_new_bar {
-        ema1_state.lookback_count + 1
-    } else {
-        ema1_state.lookback_count
-    };
-
-    // Update buffer for EMA1 ONLY during warmup
-    let new_buffer_ema1 = if new_lookback_ema1 < ema1_state.period || ema1_state.prev_ema.is_none()
-    {
-        let mut buf = ema1_state.buffer.clone();
-        if is_new_bar || buf.is_empty() {
-            buf.push(value);
-        } else {
-            let last_idx = buf.len() - 1;
-            buf[last_idx] = value;
-        }
-        buf
-    } else {
-        Vec::new()
-    };
-
-    // Calculate EMA1 value
-    let (ema1_value, new_ema1_current, new_ema1_prev) = if new_lookback_ema1 < ema1_state.period {
-        (None, ema1_state.current_ema, ema1_state.prev_ema)
-    } else {
-        let (ema, prev) = if is_new_bar {
-            // APPEND mode: calculate new EMA and persist previous one
-            let e = match ema1_state.current_ema {
-                None => {
-                    let sum: f64 = new_buffer_ema1.iter().sum();
-                    sum / (ema1_state.period as f64)
-                }
-                Some(current) => (value - current) * ema1_state.k + current,
-            };
-            (e, ema1_state.current_ema)
-        } else {
-            // UPDATE mode: only recalculate last value using prev_ema
-            let e = match ema1_state.prev_ema {
-                None => {
-                    let sum: f64 = new_buffer_ema1.iter().sum();
-                    sum / (ema1_state.period as f64)
-                }
-                Some(prev) => (value - prev) * ema1_state.k + prev,
-            };
-            (e, ema1_state.prev_ema)
-        };
-        (Some(ema), Some(ema), prev)
-    };
+    Ok((dema, new_resource))
+}
 
-    let new_ema1_state = Box::new(EMAState {
-        period: ema1_state.period,
-        k: ema1_state.k,
-        current_ema: new_ema1_current,
-        prev_ema: new_ema1_prev,
-        lookback_count: new_lookback_ema1,
-        buffer: new_buffer_ema1,
+#[cfg(has_talib)]
+fn new_tema_state(period: i32, seed_mode: SeedMode) -> TEMAState {
+    let ema_state = Arc::new(EMAState {
+        seed_mode,
+        ..new_ema_state(period)
     });
 
-    // Calculate second EMA (EMA of EMA1)
-    let (ema2_value, new_ema2_state) = if let Some(ema1_val) = ema1_value {
-        let ema2_state = &*state.ema2_state;
-        let new_lookback_ema2 = if is_new_bar {
-            ema2_state.lookback_count + 1
-        } else {
-            ema2_state.lookback_count
-        };
-
-        // Update buffer for EMA2 ONLY during warmup
-        let new_buffer_ema2 =
-            if new_lookback_ema2 < ema2_state.period || ema2_state.prev_ema.is_none() {
-                let mut buf = ema2_state.buffer.clone();
-                if is_new_bar || buf.is_empty() {
-                    buf.push(ema1_val);
-                } else {
-                    let last_idx = buf.len() - 1;
-                    buf[last_idx] = ema1_val;
-                }
-                buf
-            } else {
-                Vec::new()
-            };
-
-        // Calculate EMA2 value
-        let (ema2_val, new_ema2_current, new_ema2_prev) = if new_lookback_ema2 < ema2_state.period {
-            (None, ema2_state.current_ema, ema2_state.prev_ema)
-        } else {
-            let (ema, prev) = if is_new_bar {
-                // APPEND mode: calculate new EMA and persist previous one
-                let e = match ema2_state.current_ema {
-                    None => {
-                        let sum: f64 = new_buffer_ema2.iter().sum();
-                        sum / (ema2_state.period as f64)
-                    }
-                    Some(current) => (ema1_val - current) * ema2_state.k + current,
-                };
-                (e, ema2_state.current_ema)
-            } else {
-                // UPDATE mode: only recalculate last value using prev_ema
-                let e = match ema2_state.prev_ema {
-                    None => {
-                        let sum: f64 = new_buffer_ema2.iter().sum();
-                        sum / (ema2_state.period as f64)
-                    }
-                    Some(prev) => (ema1_val - prev) * ema2_state.k + prev,
-                };
-                (e, ema2_state.prev_ema)
-            };
-            (Some(ema), Some(ema), prev)
-        };
-
-        let new_state = Box::new(EMAState {
-            period: ema2_state.period,
-            k: ema2_state.k,
-            current_ema: new_ema2_current,
-            prev_ema: new_ema2_prev,
-            lookback_count: new_lookback_ema2,
-            buffer: new_buffer_ema2,
-        });
+    TEMAState {
+        period,
+        lookback_count: 0,
+        ema1_state: Arc::clone(&ema_state),
+        ema2_state: Arc::clone(&ema_state),
+        ema3_state: ema_state,
+    }
+}
 
-        (ema2_val, new_state)
+/// Advances a TEMA state by one value
+#[cfg(has_talib)]
+fn step_tema(state: &TEMAState, value: f64, is_new_bar: bool) -> (Option<f64>, TEMAState) {
+    // Update lookback count
+    let new_lookback = if is_new_bar {
+        state.lookback_count + 1
     } else {
-        // During warmup of first EMA, don't update second EMA
-        (None, state.ema2_state.clone())
+        state.lookback_count
     };
 
-    // Calculate third EMA (EMA of EMA2)
-    let (ema3_value, new_ema3_state) = if let Some(ema2_val) = ema2_value {
-        let ema3_state = &*state.ema3_state;
-        let new_lookback_ema3 = if is_new_bar {
-            ema3_state.lookback_count + 1
-        } else {
-            ema3_state.lookback_count
-        };
-
-        // Update buffer for EMA3 ONLY during warmup
-        let new_buffer_ema3 =
-            if new_lookback_ema3 < ema3_state.period || ema3_state.prev_ema.is_none() {
-                let mut buf = ema3_state.buffer.clone();
-                if is_new_bar || buf.is_empty() {
-                    buf.push(ema2_val);
-                } else {
-                    let last_idx = buf.len() - 1;
-                    buf[last_idx] = ema2_val;
-                }
-                buf
-            } else {
-                Vec::new()
-            };
-
-        // Calculate EMA3 value
-        let (ema3_val, new_ema3_current, new_ema3_prev) = if new_lookback_ema3 < ema3_state.period {
-            (None, ema3_state.current_ema, ema3_state.prev_ema)
-        } else {
-            let (ema, prev) = if is_new_bar {
-                // APPEND mode: calculate new EMA and persist previous one
-                let e = match ema3_state.current_ema {
-                    None => {
-                        let sum: f64 = new_buffer_ema3.iter().sum();
-                        sum / (ema3_state.period as f64)
-                    }
-                    Some(current) => (ema2_val - current) * ema3_state.k + current,
-                };
-                (e, ema3_state.current_ema)
-            } else {
-                // UPDATE mode: only recalculate last value using prev_ema
-                let e = match ema3_state.prev_ema {
-                    None => {
-                        let sum: f64 = new_buffer_ema3.iter().sum();
-                        sum / (ema3_state.period as f64)
-                    }
-                    Some(prev) => (ema2_val - prev) * ema3_state.k + prev,
-                };
-                (e, ema3_state.prev_ema)
-            };
-            (Some(ema), Some(ema), prev)
-        };
+    // Calculate first EMA
+    let (ema1_value, new_ema1_state) = step_nested_ema(&state.ema1_state, Some(value), is_new_bar);
 
-        let new_state = Box::new(EMAState {
-            period: ema3_state.period,
-            k: ema3_state.k,
-            current_ema: new_ema3_current,
-            prev_ema: new_ema3_prev,
-            lookback_count: new_lookback_ema3,
-            buffer: new_buffer_ema3,
-        });
+    // Calculate second EMA (EMA of EMA1)
+    let (ema2_value, new_ema2_state) = step_nested_ema(&state.ema2_state, ema1_value, is_new_bar);
 
-        (ema3_val, new_state)
-    } else {
-        // During warmup of second EMA, don't update third EMA
-        (None, state.ema3_state.clone())
-    };
+    // Calculate third EMA (EMA of EMA2)
+    let (ema3_value, new_ema3_state) = step_nested_ema(&state.ema3_state, ema2_value, is_new_bar);
 
     let new_state = TEMAState {
         period: state.period,
@@ -847,27 +1239,86 @@ pub fn overlap_tema_state_next(
         ema3_state: new_ema3_state,
     };
 
-    let new_resource = ResourceArc::new(new_state);
+    // TA-Lib's TEMA lookback is 3 * (period - 1), one EMA warmup per layer
+    if new_lookback <= 3 * (state.ema1_state.warmup() - 1) {
+        return (None, new_state);
+    }
 
     // Calculate TEMA = 3 * EMA1 - 3 * EMA2 + EMA3
     match (ema1_value, ema2_value, ema3_value) {
         (Some(e1), Some(e2), Some(e3)) => {
             let tema = 3.0 * e1 - 3.0 * e2 + e3;
 
-            Ok((Some(tema), new_resource))
+            (Some(tema), new_state)
         }
-        _ => Ok((None, new_resource)),
+        _ => (None, new_state),
     }
 }
 
 #[cfg(has_talib)]
 #[rustler::nif]
-pub fn overlap_trima_state_init(period: i32) -> Result<ResourceArc<TRIMAState>, String> {
+pub fn overlap_tema_state_init(period: i32) -> Result<ResourceArc<TEMAState>, String> {
     if period < 2 {
-        return Err("Invalid period: must be >= 2 for TRIMA".to_string());
+        return Err("Invalid period: must be >= 2 for TEMA".to_string());
+    }
+
+    let state = new_tema_state(period, SeedMode::Sma);
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif(name = "overlap_tema_state_init")]
+pub fn overlap_tema_state_init_with_seed_mode(
+    period: i32,
+    seed_mode: rustler::Atom,
+) -> Result<ResourceArc<TEMAState>, String> {
+    let seed_mode = decode_seed_mode(seed_mode, "TEMA")?;
+
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for TEMA".to_string());
+    }
+
+    let state = new_tema_state(period, seed_mode);
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_tema_state_next(
+    state_arc: ResourceArc<TEMAState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TEMAState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    if value.is_none() {
+        return Ok((None, state_arc));
     }
 
+    let value = value.unwrap();
+
+    let (tema, new_state) = step_tema(state, value, is_new_bar);
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((tema, new_resource))
+}
+
+#[cfg(has_talib)]
+fn new_trima_state(period: i32) -> TRIMAState {
     // Calculate periods for double smoothing
+    //
+    // TRIMA is a triangular-weighted average, which is exactly the convolution
+    // of two SMAs (the weights of SMA(SMA(x, n1), n2) are [1..n1] * [1..n2]):
+    // - Odd period (e.g. 5): n1 = n2 = (period + 1) / 2
+    //   weights 1,2,3,2,1 / 9, same as TA-Lib
+    // - Even period (e.g. 4): n1 = period / 2, n2 = period / 2 + 1
+    //   weights 1,2,2,1 / 6, same as TA-Lib (the order of n1/n2 doesn't matter)
+    // In both cases the lookback (n1 - 1) + (n2 - 1) equals TA-Lib's period - 1.
     let (first_period, second_period) = if period < 3 {
         // For period < 3, TRIMA = SMA
         (period, period)
@@ -881,35 +1332,19 @@ pub fn overlap_trima_state_init(period: i32) -> Result<ResourceArc<TRIMAState>,
         (half, half + 1)
     };
 
-    let state = TRIMAState {
+    TRIMAState {
         period,
         first_period,
         second_period,
         lookback_count: 0,
         first_sma_buffer: Vec::new(),
         second_sma_buffer: Vec::new(),
-    };
-
-    let resource = ResourceArc::new(state);
-    Ok(resource)
+    }
 }
 
+/// Advances a TRIMA state by one value
 #[cfg(has_talib)]
-#[rustler::nif]
-pub fn overlap_trima_state_next(
-    state_arc: ResourceArc<TRIMAState>,
-    value: Option<f64>,
-    is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<TRIMAState>), String> {
-    let state = &*state_arc;
-
-    // Handle nil input: return nil without modifying state
-    if value.is_none() {
-        return Ok((None, state_arc));
-    }
-
-    let value = value.unwrap();
-
+fn step_trima(state: &TRIMAState, value: f64, is_new_bar: bool) -> (Option<f64>, TRIMAState) {
     let new_lookback = if is_new_bar {
         state.lookback_count + 1
     } else {
@@ -974,29 +1409,92 @@ pub fn overlap_trima_state_next(
         second_sma_buffer: new_second_buffer,
     };
 
-    let new_resource = ResourceArc::new(new_state);
+    (trima, new_state)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_trima_state_init(period: i32) -> Result<ResourceArc<TRIMAState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for TRIMA".to_string());
+    }
 
-    match trima {
-        Some(value) => Ok((Some(value), new_resource)),
-        None => Ok((None, new_resource)),
+    let state = new_trima_state(period);
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_trima_state_next(
+    state_arc: ResourceArc<TRIMAState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TRIMAState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    if value.is_none() {
+        return Ok((None, state_arc));
     }
+
+    let value = value.unwrap();
+
+    let (trima, new_state) = step_trima(state, value, is_new_bar);
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((trima, new_resource))
 }
 
 #[cfg(has_talib)]
 #[rustler::nif]
 pub fn overlap_midpoint_state_init(period: i32) -> Result<ResourceArc<MIDPOINTState>, String> {
+    let state = new_midpoint_state(period, NanPolicy::Skip)?;
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif(name = "overlap_midpoint_state_init")]
+pub fn overlap_midpoint_state_init_with_nan_policy(
+    period: i32,
+    nan_policy: rustler::Atom,
+) -> Result<ResourceArc<MIDPOINTState>, String> {
+    let nan_policy = decode_nan_policy(nan_policy, "MIDPOINT")?;
+    let state = new_midpoint_state(period, nan_policy)?;
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+fn new_midpoint_state(period: i32, nan_policy: NanPolicy) -> Result<MIDPOINTState, String> {
     if period < 2 {
         return Err("Invalid period: must be >= 2 for MIDPOINT".to_string());
     }
 
-    let state = MIDPOINTState {
+    Ok(MIDPOINTState {
         period,
         buffer: Vec::new(),
         lookback_count: 0,
-    };
+        nan_policy,
+    })
+}
 
-    let resource = ResourceArc::new(state);
-    Ok(resource)
+/// MIDPOINT of a buffer: (MAX + MIN) / 2
+#[cfg(has_talib)]
+fn midpoint_of(buffer: &[f64]) -> f64 {
+    let max_val = buffer.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    let min_val = buffer.iter().cloned().fold(f64::INFINITY, f64::min);
+    (max_val + min_val) / 2.0
+}
+
+/// Current MIDPOINT of a state, None during warmup
+#[cfg(has_talib)]
+fn midpoint_value(state: &MIDPOINTState) -> Option<f64> {
+    (state.lookback_count >= state.period).then(|| midpoint_of(&state.buffer))
 }
 
 #[cfg(has_talib)]
@@ -1008,12 +1506,15 @@ pub fn overlap_midpoint_state_next(
 ) -> Result<(Option<f64>, ResourceArc<MIDPOINTState>), String> {
     let state = &*state_arc;
 
-    // Handle nil input: return nil without modifying state
-    if value.is_none() {
-        return Ok((None, state_arc));
-    }
-
-    let value = value.unwrap();
+    // Handle missing input (nil or NaN) as chosen at init, without modifying state
+    let value = match value.filter(|v| !v.is_nan()) {
+        Some(value) => value,
+        None => {
+            let midpoint =
+                missing_input_result(state.nan_policy, midpoint_value(state), "MIDPOINT")?;
+            return Ok((midpoint, state_arc));
+        }
+    };
 
     let mut new_buffer = state.buffer.clone();
     let new_lookback = if is_new_bar {
@@ -1045,21 +1546,20 @@ pub fn overlap_midpoint_state_next(
             period: state.period,
             buffer: new_buffer,
             lookback_count: new_lookback,
+            nan_policy: state.nan_policy,
         };
         let new_resource = ResourceArc::new(new_state);
         let result = (None, new_resource);
         return Ok(result);
     }
 
-    // Calculate MIDPOINT = (MAX + MIN) / 2
-    let max_val = new_buffer.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
-    let min_val = new_buffer.iter().cloned().fold(f64::INFINITY, f64::min);
-    let midpoint = (max_val + min_val) / 2.0;
+    let midpoint = midpoint_of(&new_buffer);
 
     let new_state = MIDPOINTState {
         period: state.period,
         buffer: new_buffer,
         lookback_count: new_lookback,
+        nan_policy: state.nan_policy,
     };
 
     let new_resource = ResourceArc::new(new_state);
@@ -1067,6 +1567,107 @@ pub fn overlap_midpoint_state_next(
     Ok((Some(midpoint), new_resource))
 }
 
+#[cfg(has_talib)]
+fn new_t3_state(period: i32, vfactor: f64, seed_mode: SeedMode) -> T3State {
+    let ema_state = Arc::new(EMAState {
+        seed_mode,
+        ..new_ema_state(period)
+    });
+
+    T3State {
+        period,
+        vfactor,
+        lookback_count: 0,
+        ema1_state: Arc::clone(&ema_state),
+        ema2_state: Arc::clone(&ema_state),
+        ema3_state: Arc::clone(&ema_state),
+        ema4_state: Arc::clone(&ema_state),
+        ema5_state: Arc::clone(&ema_state),
+        ema6_state: ema_state,
+    }
+}
+
+/// Coefficients of the 6th, 5th, 4th and 3rd chained EMAs in T3
+///
+/// T3 applies the generalized DEMA three times, GD = (1 + v) * E - v * E^2 with
+/// E the EMA operator and v the volume factor. Expanding
+/// ((1 + v) * E - v * E^2)^3 with the binomial theorem gives:
+///
+/// - E^6: c1 = -v^3
+/// - E^5: c2 = 3 * v^2 * (1 + v) = 3v^2 + 3v^3
+/// - E^4: c3 = -3 * v * (1 + v)^2 = -3v - 6v^2 - 3v^3
+/// - E^3: c4 = (1 + v)^3 = 1 + 3v + 3v^2 + v^3
+///
+/// TA-Lib's `TA_T3` computes the same values from `c1` (c2 = 3 * (v^2 - c1), ...).
+/// They sum to 1, but outside `[0, 1]` they grow with v^3 and T3 stops being a
+/// smoothing, hence the validation at init.
+#[cfg(has_talib)]
+fn t3_coefficients(vfactor: f64) -> (f64, f64, f64, f64) {
+    let v = vfactor;
+    let v2 = v * v;
+    let v3 = v2 * v;
+
+    let c1 = -v3;
+    let c2 = 3.0 * v2 + 3.0 * v3;
+    let c3 = -6.0 * v2 - 3.0 * v - 3.0 * v3;
+    let c4 = 1.0 + 3.0 * v + v3 + 3.0 * v2;
+
+    (c1, c2, c3, c4)
+}
+
+/// Advances a T3 state by one value
+#[cfg(has_talib)]
+fn step_t3(state: &T3State, value: f64, is_new_bar: bool) -> (Option<f64>, T3State) {
+    // Update lookback count
+    let new_lookback = if is_new_bar {
+        state.lookback_count + 1
+    } else {
+        state.lookback_count
+    };
+
+    // Process EMA1
+    let (ema1_value, new_ema1_state) = step_nested_ema(&state.ema1_state, Some(value), is_new_bar);
+
+    // Process EMA2 (EMA of EMA1)
+    let (ema2_value, new_ema2_state) = step_nested_ema(&state.ema2_state, ema1_value, is_new_bar);
+
+    // Process EMA3 (EMA of EMA2)
+    let (ema3_value, new_ema3_state) = step_nested_ema(&state.ema3_state, ema2_value, is_new_bar);
+
+    // Process EMA4 (EMA of EMA3)
+    let (ema4_value, new_ema4_state) = step_nested_ema(&state.ema4_state, ema3_value, is_new_bar);
+
+    // Process EMA5 (EMA of EMA4)
+    let (ema5_value, new_ema5_state) = step_nested_ema(&state.ema5_state, ema4_value, is_new_bar);
+
+    // Process EMA6 (EMA of EMA5)
+    let (ema6_value, new_ema6_state) = step_nested_ema(&state.ema6_state, ema5_value, is_new_bar);
+
+    let new_state = T3State {
+        period: state.period,
+        vfactor: state.vfactor,
+        lookback_count: new_lookback,
+        ema1_state: new_ema1_state,
+        ema2_state: new_ema2_state,
+        ema3_state: new_ema3_state,
+        ema4_state: new_ema4_state,
+        ema5_state: new_ema5_state,
+        ema6_state: new_ema6_state,
+    };
+
+    // Calculate T3 = c1*e6 + c2*e5 + c3*e4 + c4*e3
+    match (ema3_value, ema4_value, ema5_value, ema6_value) {
+        (Some(e3), Some(e4), Some(e5), Some(e6)) => {
+            let (c1, c2, c3, c4) = t3_coefficients(state.vfactor);
+
+            let t3 = c1 * e6 + c2 * e5 + c3 * e4 + c4 * e3;
+
+            (Some(t3), new_state)
+        }
+        _ => (None, new_state),
+    }
+}
+
 #[cfg(has_talib)]
 #[rustler::nif]
 pub fn overlap_t3_state_init(period: i32, vfactor: f64) -> Result<ResourceArc<T3State>, String> {
@@ -1074,241 +1675,1087 @@ pub fn overlap_t3_state_init(period: i32, vfactor: f64) -> Result<ResourceArc<T3
         return Err("Invalid period: must be >= 2 for T3".to_string());
     }
 
-    let k = 2.0 / (period as f64 + 1.0);
+    if !(0.0..=1.0).contains(&vfactor) {
+        return Err("Invalid vfactor: must be between 0.0 and 1.0 for T3".to_string());
+    }
 
-    let ema1_state = Box::new(EMAState {
-        period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+    let state = new_t3_state(period, vfactor, SeedMode::Sma);
 
-    let ema2_state = Box::new(EMAState {
-        period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
 
-    let ema3_state = Box::new(EMAState {
-        period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+#[cfg(has_talib)]
+#[rustler::nif(name = "overlap_t3_state_init")]
+pub fn overlap_t3_state_init_with_seed_mode(
+    period: i32,
+    vfactor: f64,
+    seed_mode: rustler::Atom,
+) -> Result<ResourceArc<T3State>, String> {
+    let seed_mode = decode_seed_mode(seed_mode, "T3")?;
+
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for T3".to_string());
+    }
+
+    if !(0.0..=1.0).contains(&vfactor) {
+        return Err("Invalid vfactor: must be between 0.0 and 1.0 for T3".to_string());
+    }
+
+    let state = new_t3_state(period, vfactor, seed_mode);
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_t3_state_next(
+    state_arc: ResourceArc<T3State>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<T3State>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    if value.is_none() {
+        return Ok((None, state_arc));
+    }
+
+    let value = value.unwrap();
+
+    let (t3, new_state) = step_t3(state, value, is_new_bar);
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((t3, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_kama_state_init(period: i32) -> Result<ResourceArc<KAMAState>, String> {
+    let state = new_kama_state(period, 2, 30)?;
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif(name = "overlap_kama_state_init")]
+pub fn overlap_kama_state_init_with_sc(
+    period: i32,
+    fast_period: i32,
+    slow_period: i32,
+) -> Result<ResourceArc<KAMAState>, String> {
+    let state = new_kama_state(period, fast_period, slow_period)?;
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+fn new_kama_state(period: i32, fast_period: i32, slow_period: i32) -> Result<KAMAState, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for KAMA".to_string());
+    }
+
+    if fast_period < 1 {
+        return Err("Invalid fast period: must be >= 1 for KAMA".to_string());
+    }
+
+    if fast_period >= slow_period {
+        return Err("Invalid periods: fast period must be < slow period for KAMA".to_string());
+    }
 
-    let ema4_state = Box::new(EMAState {
+    Ok(KAMAState {
         period,
-        k,
-        current_ema: None,
-        prev_ema: None,
+        fastest_sc: 2.0 / (fast_period as f64 + 1.0),
+        slowest_sc: 2.0 / (slow_period as f64 + 1.0),
         lookback_count: 0,
+        current_kama: None,
+        prev_kama: None,
         buffer: Vec::new(),
-    });
+    })
+}
+
+/// Advances a KAMA state by one value
+#[cfg(has_talib)]
+fn step_kama(state: &KAMAState, value: f64, is_new_bar: bool) -> (Option<f64>, KAMAState) {
+    let mut new_buffer = state.buffer.clone();
+    let new_lookback = if is_new_bar {
+        state.lookback_count + 1
+    } else {
+        state.lookback_count
+    };
+
+    // Update buffer (KAMA needs the current value plus the 'period' previous ones)
+    if is_new_bar {
+        new_buffer.push(value);
+        if new_buffer.len() > (state.period + 1) as usize {
+            new_buffer.remove(0);
+        }
+    } else if !new_buffer.is_empty() {
+        // UPDATE mode: replace last value
+        let last_idx = new_buffer.len() - 1;
+        new_buffer[last_idx] = value;
+    } else {
+        // First value in first bar
+        new_buffer.push(value);
+    }
+
+    // Warmup phase: TA-Lib's KAMA lookback is 'period', so the first value
+    // is emitted on bar index 'period' (i.e. after period + 1 bars)
+    if new_lookback <= state.period {
+        let new_state = KAMAState {
+            period: state.period,
+            fastest_sc: state.fastest_sc,
+            slowest_sc: state.slowest_sc,
+            lookback_count: new_lookback,
+            current_kama: state.current_kama,
+            prev_kama: state.prev_kama,
+            buffer: new_buffer,
+        };
+        return (None, new_state);
+    }
+
+    // Efficiency ratio = |net change| / sum of absolute 1-bar changes over the window
+    let last_idx = new_buffer.len() - 1;
+    let period_roc = new_buffer[last_idx] - new_buffer[0];
+    let sum_roc: f64 = new_buffer.windows(2).map(|w| (w[1] - w[0]).abs()).sum();
+
+    // Same test as TA-Lib (note: compares against the signed net change)
+    let er = if sum_roc <= period_roc || sum_roc == 0.0 {
+        1.0
+    } else {
+        (period_roc / sum_roc).abs()
+    };
+
+    // Smoothing constant, bounded by the fast and slow EMA constants (fast = 2 and
+    // slow = 30 are only the TA-Lib defaults, used by the 1-arity init and new_ma_state)
+    let sc = er * (state.fastest_sc - state.slowest_sc) + state.slowest_sc;
+    let sc = sc * sc;
+
+    // TA-Lib seeds the recursion with the value right before the first output bar
+    let seed = new_buffer[last_idx - 1];
+
+    let (new_kama, new_prev_kama) = if is_new_bar {
+        // APPEND mode: current_kama becomes prev_kama for next iteration
+        let base = state.current_kama.unwrap_or(seed);
+        ((value - base) * sc + base, state.current_kama)
+    } else {
+        // UPDATE mode: only recalculate last value using prev_kama
+        let base = state.prev_kama.unwrap_or(seed);
+        ((value - base) * sc + base, state.prev_kama)
+    };
+
+    let new_state = KAMAState {
+        period: state.period,
+        fastest_sc: state.fastest_sc,
+        slowest_sc: state.slowest_sc,
+        lookback_count: new_lookback,
+        current_kama: Some(new_kama),
+        prev_kama: new_prev_kama,
+        buffer: new_buffer,
+    };
+
+    (Some(new_kama), new_state)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_kama_state_next(
+    state_arc: ResourceArc<KAMAState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<KAMAState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    if value.is_none() {
+        return Ok((None, state_arc));
+    }
+
+    let value = value.unwrap();
 
-    let ema5_state = Box::new(EMAState {
+    let (kama, new_state) = step_kama(state, value, is_new_bar);
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((kama, new_resource))
+}
+
+/// Pushes a closed bar into a monotonic deque
+///
+/// Drops the values at the back that can no longer be the extremum, i.e. the
+/// ones for which `dominates(value, back)` holds.
+#[cfg(has_talib)]
+pub(crate) fn monotonic_push(
+    deque: &mut MonotonicDeque,
+    index: i32,
+    value: f64,
+    dominates: fn(f64, f64) -> bool,
+) {
+    while matches!(deque.back(), Some(&(_, back)) if dominates(value, back)) {
+        deque.pop_back();
+    }
+
+    deque.push_back((index, value));
+}
+
+/// Drops the bars older than `oldest_index` from the front of a monotonic deque
+#[cfg(has_talib)]
+pub(crate) fn monotonic_evict(deque: &mut MonotonicDeque, oldest_index: i32) {
+    while matches!(deque.front(), Some(&(index, _)) if index < oldest_index) {
+        deque.pop_front();
+    }
+}
+
+#[cfg(has_talib)]
+pub(crate) fn new_high_low_window(period: i32) -> HighLowWindow {
+    HighLowWindow {
         period,
-        k,
-        current_ema: None,
-        prev_ema: None,
         lookback_count: 0,
-        buffer: Vec::new(),
+        current_high: f64::NAN,
+        current_low: f64::NAN,
+        highs: VecDeque::with_capacity(period as usize),
+        lows: VecDeque::with_capacity(period as usize),
+    }
+}
+
+/// Advances a highest high / lowest low window by one bar
+///
+/// Returns the highest high and the lowest low of the window once it holds
+/// `period` bars. The deques only hold closed bars, so UPDATE mode just replaces
+/// the current bar, which is combined with the deque fronts at read time.
+#[cfg(has_talib)]
+pub(crate) fn high_low_window_next(
+    window: &HighLowWindow,
+    high: f64,
+    low: f64,
+    is_new_bar: bool,
+) -> (Option<(f64, f64)>, HighLowWindow) {
+    let mut new_window = window.clone();
+
+    // A value set by an UPDATE before the first bar is not a bar and is dropped
+    if is_new_bar {
+        if window.lookback_count > 0 {
+            monotonic_push(
+                &mut new_window.highs,
+                window.lookback_count,
+                window.current_high,
+                |v, back| v >= back,
+            );
+            monotonic_push(
+                &mut new_window.lows,
+                window.lookback_count,
+                window.current_low,
+                |v, back| v <= back,
+            );
+        }
+
+        // Closed bars still in the window: the 'period - 1' before the new one
+        let oldest_index = window.lookback_count + 2 - window.period;
+        monotonic_evict(&mut new_window.highs, oldest_index);
+        monotonic_evict(&mut new_window.lows, oldest_index);
+
+        new_window.lookback_count += 1;
+    }
+
+    new_window.current_high = high;
+    new_window.current_low = low;
+
+    // Warmup phase: need 'period' bars
+    if new_window.lookback_count < window.period {
+        return (None, new_window);
+    }
+
+    let max_high = new_window.highs.front().map_or(high, |&(_, h)| h.max(high));
+    let min_low = new_window.lows.front().map_or(low, |&(_, l)| l.min(low));
+
+    (Some((max_high, min_low)), new_window)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_midprice_state_init(period: i32) -> Result<ResourceArc<MIDPRICEState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for MIDPRICE".to_string());
+    }
+
+    let state = MIDPRICEState {
+        window: new_high_low_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_midprice_state_next(
+    state_arc: ResourceArc<MIDPRICEState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MIDPRICEState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low) = match (high, low) {
+        (Some(high), Some(low)) => (high, low),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let (extremes, new_window) = high_low_window_next(&state.window, high, low, is_new_bar);
+    let midprice = extremes.map(|(max_high, min_low)| (max_high + min_low) / 2.0);
+
+    let new_state = MIDPRICEState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((midprice, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_bbands_state_init(
+    period: i32,
+    nb_dev_up: f64,
+    nb_dev_dn: f64,
+    ma_type: i32,
+) -> Result<ResourceArc<BBANDSState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for BBANDS".to_string());
+    }
+
+    // Only the SMA middle band can be derived from the variance window
+    if ma_type != 0 {
+        return Err("Invalid ma_type: only SMA (0) is supported for BBANDS".to_string());
+    }
+
+    let state = BBANDSState {
+        nb_dev_up,
+        nb_dev_dn,
+        ma_type,
+        window: new_var_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn overlap_bbands_state_next(
+    state_arc: ResourceArc<BBANDSState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<
+    (
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        ResourceArc<BBANDSState>,
+    ),
+    String,
+> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, None, None, state_arc)),
+    };
+
+    let (stats, new_window) = var_window_next(&state.window, value, is_new_bar);
+
+    let new_state = BBANDSState {
+        nb_dev_up: state.nb_dev_up,
+        nb_dev_dn: state.nb_dev_dn,
+        ma_type: state.ma_type,
+        window: new_window,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    let (middle, variance) = match stats {
+        Some(stats) => stats,
+        None => return Ok((None, None, None, new_resource)),
+    };
+
+    // Same as TA-Lib: a (nearly) zero or negative variance gives 0
+    let stddev = if variance < 1e-8 {
+        0.0
+    } else {
+        variance.sqrt()
+    };
+
+    let upper = middle + stddev * state.nb_dev_up;
+    let lower = middle - stddev * state.nb_dev_dn;
+
+    Ok((Some(upper), Some(middle), Some(lower), new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ma_state_init(period: i32, ma_type: i32) -> Result<ResourceArc<MAState>, String> {
+    let state = new_ma_state(period, ma_type)?;
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+pub(crate) fn new_ma_state(period: i32, ma_type: i32) -> Result<MAState, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for MA".to_string());
+    }
+
+    // TA_MAType: SMA, EMA, WMA, DEMA, TEMA, TRIMA, KAMA, MAMA, T3
+    let state = match ma_type {
+        0 => MAState::SMA(new_sma_state(period)),
+        1 => MAState::EMA(new_ema_state(period)),
+        2 => MAState::WMA(new_wma_state(period)),
+        3 => MAState::DEMA(new_dema_state(period, SeedMode::Sma)),
+        4 => MAState::TEMA(new_tema_state(period, SeedMode::Sma)),
+        5 => MAState::TRIMA(new_trima_state(period)),
+        // TA-Lib's MA uses the default KAMA fast/slow periods and T3 volume factor
+        6 => MAState::KAMA(new_kama_state(period, 2, 30)?),
+        7 => return Err("Invalid ma_type: MAMA (7) is not supported for MA".to_string()),
+        8 => MAState::T3(new_t3_state(period, 0.7, SeedMode::Sma)),
+        _ => return Err("Invalid ma_type: must be between 0 and 8 for MA".to_string()),
+    };
+
+    Ok(state)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_ma_state_next(
+    state_arc: ResourceArc<MAState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MAState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (ma, new_state) = step_ma(state, value, is_new_bar);
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((ma, new_resource))
+}
+
+/// Advances an MA state by one value, with the step of its `ma_type`
+#[cfg(has_talib)]
+pub(crate) fn step_ma(state: &MAState, value: f64, is_new_bar: bool) -> (Option<f64>, MAState) {
+    match state {
+        MAState::SMA(inner) => {
+            let (ma, s) = step_sma(inner, value, is_new_bar);
+            (ma, MAState::SMA(s))
+        }
+        MAState::EMA(inner) => {
+            let (ma, s) = step_ema(inner, value, is_new_bar);
+            (ma, MAState::EMA(s))
+        }
+        MAState::WMA(inner) => {
+            let (ma, s) = step_wma(inner, value, is_new_bar);
+            (ma, MAState::WMA(s))
+        }
+        MAState::DEMA(inner) => {
+            let (ma, s) = step_dema(inner, value, is_new_bar);
+            (ma, MAState::DEMA(s))
+        }
+        MAState::TEMA(inner) => {
+            let (ma, s) = step_tema(inner, value, is_new_bar);
+            (ma, MAState::TEMA(s))
+        }
+        MAState::TRIMA(inner) => {
+            let (ma, s) = step_trima(inner, value, is_new_bar);
+            (ma, MAState::TRIMA(s))
+        }
+        MAState::KAMA(inner) => {
+            let (ma, s) = step_kama(inner, value, is_new_bar);
+            (ma, MAState::KAMA(s))
+        }
+        MAState::T3(inner) => {
+            let (ma, s) = step_t3(inner, value, is_new_bar);
+            (ma, MAState::T3(s))
+        }
+    }
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn pipeline_init(
+    specs: Vec<(rustler::Atom, i32)>,
+) -> Result<ResourceArc<PipelineState>, String> {
+    use crate::atoms;
+
+    if specs.is_empty() {
+        return Err("Invalid pipeline: at least one stage is required".to_string());
+    }
+
+    let stages = specs
+        .into_iter()
+        .map(|(indicator, period)| {
+            // Moving averages reuse the MA state with the matching TA_MAType
+            let ma_type = match indicator {
+                i if i == atoms::sma() => 0,
+                i if i == atoms::ema() => 1,
+                i if i == atoms::wma() => 2,
+                i if i == atoms::dema() => 3,
+                i if i == atoms::tema() => 4,
+                i if i == atoms::trima() => 5,
+                i if i == atoms::kama() => 6,
+                i if i == atoms::t3() => 8,
+                i if i == atoms::rsi() => return new_rsi_state(period).map(PipelineStage::RSI),
+                _ => return Err("Invalid pipeline stage: unsupported indicator".to_string()),
+            };
+
+            new_ma_state(period, ma_type).map(PipelineStage::MA)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let state = PipelineState { stages };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn pipeline_next(
+    state_arc: ResourceArc<PipelineState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<PipelineState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let mut output = Some(value);
+    let mut stages = Vec::with_capacity(state.stages.len());
+
+    for stage in &state.stages {
+        // A stage still in warmup gives nil: the next stages are not fed
+        let new_stage = match (stage, output) {
+            (PipelineStage::MA(inner), Some(input)) => {
+                let (ma, s) = step_ma(inner, input, is_new_bar);
+                output = ma;
+                PipelineStage::MA(s)
+            }
+            (PipelineStage::RSI(inner), Some(input)) => {
+                let (rsi, s) = step_rsi(inner, input, is_new_bar);
+                output = rsi;
+                PipelineStage::RSI(s)
+            }
+            (PipelineStage::MA(inner), None) => PipelineStage::MA(inner.clone()),
+            (PipelineStage::RSI(inner), None) => PipelineStage::RSI(inner.clone()),
+        };
+
+        stages.push(new_stage);
+    }
+
+    let new_state = PipelineState { stages };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((output, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn overlap_supertrend_state_init(
+    period: i32,
+    multiplier: f64,
+) -> Result<ResourceArc<SuperTrendState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for SUPERTREND".to_string());
+    }
+
+    if multiplier.is_nan() || multiplier <= 0.0 {
+        return Err("Invalid multiplier: must be > 0 for SUPERTREND".to_string());
+    }
+
+    let state = SuperTrendState {
+        multiplier,
+        atr: new_atr_state(period)?,
+        current: None,
+        prev: None,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn overlap_supertrend_state_next(
+    state_arc: ResourceArc<SuperTrendState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, Option<i32>, ResourceArc<SuperTrendState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => (high, low, close),
+        _ => return Ok((None, None, state_arc)),
+    };
+
+    use crate::overlap::supertrend_bar;
+
+    let (atr, new_atr) = step_atr(&state.atr, high, low, close, is_new_bar);
+
+    // In UPDATE mode the previous bands are fixed: the forming bar can flip the
+    // trend, but only against the bar before it
+    let new_prev = if is_new_bar {
+        state.current
+    } else {
+        state.prev
+    };
+
+    // Bars before the first ATR have no SuperTrend
+    let current = atr.map(|atr| {
+        let bar = supertrend_bar(new_prev, high, low, close, atr, state.multiplier);
+        (bar, close)
     });
 
-    let ema6_state = Box::new(EMAState {
+    let new_state = SuperTrendState {
+        multiplier: state.multiplier,
+        atr: new_atr,
+        current,
+        prev: new_prev,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    let line = current.map(|(bar, _close)| bar.line());
+    let direction = current.map(|(bar, _close)| bar.direction);
+
+    Ok((line, direction, new_resource))
+}
+
+// McGinley Dynamic is not in TA-Lib, so its state is always available
+pub(crate) fn new_mcginley_state(period: i32) -> Result<McGinleyState, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for MCGINLEY".to_string());
+    }
+
+    Ok(McGinleyState {
         period,
-        k,
-        current_ema: None,
-        prev_ema: None,
-        lookback_count: 0,
-        buffer: Vec::new(),
-    });
+        current_md: None,
+        prev_md: None,
+    })
+}
+
+/// Advances a McGinley Dynamic state by one value
+///
+/// `md = md_prev + (price - md_prev) / (period * (price / md_prev)^4)`, seeded
+/// with the first price so that it is defined from the first bar.
+pub(crate) fn step_mcginley(
+    state: &McGinleyState,
+    value: f64,
+    is_new_bar: bool,
+) -> (f64, McGinleyState) {
+    let prev_md = if is_new_bar {
+        state.current_md
+    } else {
+        state.prev_md
+    };
+
+    let md = match prev_md {
+        Some(prev) => {
+            let ratio = value / prev;
+            let divisor = state.period as f64 * ratio.powi(4);
+
+            // A price or MD at zero has no ratio: restart from the price
+            if prev.abs() < 1e-8 || divisor < 1e-8 {
+                value
+            } else {
+                // A sharp drop can overshoot out of range: restart there too
+                Some(prev + (value - prev) / divisor)
+                    .filter(|md| md.is_finite())
+                    .unwrap_or(value)
+            }
+        }
+        None => value,
+    };
+
+    let new_state = McGinleyState {
+        period: state.period,
+        current_md: Some(md),
+        prev_md,
+    };
+
+    (md, new_state)
+}
+
+#[rustler::nif]
+pub fn overlap_mcginley_state_init(period: i32) -> Result<ResourceArc<McGinleyState>, String> {
+    let state = new_mcginley_state(period)?;
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[rustler::nif]
+pub fn overlap_mcginley_state_next(
+    state_arc: ResourceArc<McGinleyState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<McGinleyState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (md, new_state) = step_mcginley(state, value, is_new_bar);
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((Some(md), new_resource))
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_state_init(_period: i32) -> Result<ResourceArc<EMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_ema_state_init")]
+pub fn overlap_ema_state_init_with_option(
+    _period: i32,
+    _option: rustler::Atom,
+) -> Result<ResourceArc<EMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_ema_state_init")]
+pub fn overlap_ema_state_init_with_seed_mode(
+    _period: i32,
+    _nan_policy: rustler::Atom,
+    _seed_mode: rustler::Atom,
+) -> Result<ResourceArc<EMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<EMAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_state_value(_state: Term) -> Result<Option<f64>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_state_is_ready(_state: Term) -> Result<bool, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_state_peek(_state: Term, _value: Option<f64>) -> Result<Option<f64>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_ema_state_undo(_state: Term) -> Result<ResourceArc<EMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_rma_state_init(_period: i32) -> Result<ResourceArc<RMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_rma_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<RMAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_zlema_state_init(_period: i32) -> Result<ResourceArc<ZLEMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_zlema_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ZLEMAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    let state = T3State {
-        period,
-        vfactor,
-        lookback_count: 0,
-        ema1_state,
-        ema2_state,
-        ema3_state,
-        ema4_state,
-        ema5_state,
-        ema6_state,
-    };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_state_init(_period: i32) -> Result<ResourceArc<SMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    let resource = ResourceArc::new(state);
-    Ok(resource)
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_sma_state_init")]
+pub fn overlap_sma_state_init_with_nan_policy(
+    _period: i32,
+    _nan_policy: rustler::Atom,
+) -> Result<ResourceArc<SMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
 }
 
-#[cfg(has_talib)]
+#[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_t3_state_next(
-    state_arc: ResourceArc<T3State>,
-    value: Option<f64>,
-    is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<T3State>), String> {
-    let state = &*state_arc;
-
-    // Handle nil input: return nil without modifying state
-    if value.is_none() {
-        return Ok((None, state_arc));
-    }
+pub fn overlap_sma_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<SMAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    let value = value.unwrap();
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_state_init_many(
+    _periods: Vec<i32>,
+) -> Result<Vec<ResourceArc<SMAState>>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Update lookback count
-    let new_lookback = if is_new_bar {
-        state.lookback_count + 1
-    } else {
-        state.lookback_count
-    };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_state_next_ts(
+    _state: Term,
+    _value: Option<f64>,
+    _timestamp: i64,
+) -> Result<(Option<f64>, ResourceArc<SMAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Helper function to process EMA state
-    let process_ema_state =
-        |ema_state: &EMAState, input_value: f64, is_new: bool| -> (Option<f64>, Box<EMAState>) {
-            let new_lb = if is_new {
-                ema_state.lookback_count + 1
-            } else {
-                ema_state.lookback_count
-            };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_state_value(_state: Term) -> Result<Option<f64>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-            let new_buf = if new_lb < ema_state.period || ema_state.prev_ema.is_none() {
-                let mut buf = ema_state.buffer.clone();
-                if is_new || buf.is_empty() {
-                    buf.push(input_value);
-                } else {
-                    let last_idx = buf.len() - 1;
-                    buf[last_idx] = input_value;
-                }
-                buf
-            } else {
-                Vec::new()
-            };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_state_is_ready(_state: Term) -> Result<bool, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-            let (ema_val, new_current, new_prev) = if new_lb < ema_state.period {
-                (None, ema_state.current_ema, ema_state.prev_ema)
-            } else {
-                let (ema, prev) = if is_new {
-                    // APPEND mode: calculate new EMA and persist previous one
-                    let e = match ema_state.current_ema {
-                        None => {
-                            let sum: f64 = new_buf.iter().sum();
-                            sum / (ema_state.period as f64)
-                        }
-                        Some(current) => (input_value - current) * ema_state.k + current,
-                    };
-                    (e, ema_state.current_ema)
-                } else {
-                    // UPDATE mode: only recalculate last value using prev_ema
-                    let e = match ema_state.prev_ema {
-                        None => {
-                            let sum: f64 = new_buf.iter().sum();
-                            sum / (ema_state.period as f64)
-                        }
-                        Some(prev) => (input_value - prev) * ema_state.k + prev,
-                    };
-                    (e, ema_state.prev_ema)
-                };
-                (Some(ema), Some(ema), prev)
-            };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_state_peek(_state: Term, _value: Option<f64>) -> Result<Option<f64>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-            let new_state = Box::new(EMAState {
-                period: ema_state.period,
-                k: ema_state.k,
-                current_ema: new_current,
-                prev_ema: new_prev,
-                lookback_count: new_lb,
-                buffer: new_buf,
-            });
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_sma_state_undo(_state: Term) -> Result<ResourceArc<SMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-            (ema_val, new_state)
-        };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_wma_state_init(_period: i32) -> Result<ResourceArc<WMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Process EMA1
-    let (ema1_value, new_ema1_state) = process_ema_state(&state.ema1_state, value, is_new_bar);
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_wma_state_init")]
+pub fn overlap_wma_state_init_with_nan_policy(
+    _period: i32,
+    _nan_policy: rustler::Atom,
+) -> Result<ResourceArc<WMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Process EMA2 (EMA of EMA1)
-    let (ema2_value, new_ema2_state) = if let Some(ema1_val) = ema1_value {
-        process_ema_state(&state.ema2_state, ema1_val, is_new_bar)
-    } else {
-        (None, state.ema2_state.clone())
-    };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_wma_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<WMAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Process EMA3 (EMA of EMA2)
-    let (ema3_value, new_ema3_state) = if let Some(ema2_val) = ema2_value {
-        process_ema_state(&state.ema3_state, ema2_val, is_new_bar)
-    } else {
-        (None, state.ema3_state.clone())
-    };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_dema_state_init(_period: i32) -> Result<ResourceArc<DEMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Process EMA4 (EMA of EMA3)
-    let (ema4_value, new_ema4_state) = if let Some(ema3_val) = ema3_value {
-        process_ema_state(&state.ema4_state, ema3_val, is_new_bar)
-    } else {
-        (None, state.ema4_state.clone())
-    };
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_dema_state_init")]
+pub fn overlap_dema_state_init_with_seed_mode(
+    _period: i32,
+    _seed_mode: rustler::Atom,
+) -> Result<ResourceArc<DEMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Process EMA5 (EMA of EMA4)
-    let (ema5_value, new_ema5_state) = if let Some(ema4_val) = ema4_value {
-        process_ema_state(&state.ema5_state, ema4_val, is_new_bar)
-    } else {
-        (None, state.ema5_state.clone())
-    };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_dema_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<DEMAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Process EMA6 (EMA of EMA5)
-    let (ema6_value, new_ema6_state) = if let Some(ema5_val) = ema5_value {
-        process_ema_state(&state.ema6_state, ema5_val, is_new_bar)
-    } else {
-        (None, state.ema6_state.clone())
-    };
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_tema_state_init(_period: i32) -> Result<ResourceArc<TEMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    let new_state = T3State {
-        period: state.period,
-        vfactor: state.vfactor,
-        lookback_count: new_lookback,
-        ema1_state: new_ema1_state,
-        ema2_state: new_ema2_state,
-        ema3_state: new_ema3_state,
-        ema4_state: new_ema4_state,
-        ema5_state: new_ema5_state,
-        ema6_state: new_ema6_state,
-    };
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_tema_state_init")]
+pub fn overlap_tema_state_init_with_seed_mode(
+    _period: i32,
+    _seed_mode: rustler::Atom,
+) -> Result<ResourceArc<TEMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    let new_resource = ResourceArc::new(new_state);
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_tema_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TEMAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-    // Calculate T3 = c1*e6 + c2*e5 + c3*e4 + c4*e3
-    // where coefficients are based on vfactor
-    match (ema3_value, ema4_value, ema5_value, ema6_value) {
-        (Some(e3), Some(e4), Some(e5), Some(e6)) => {
-            let c1 = -state.vfactor * state.vfactor * state.vfactor;
-            let c2 = 3.0 * state.vfactor * state.vfactor
-                + 3.0 * state.vfactor * state.vfactor * state.vfactor;
-            let c3 = -6.0 * state.vfactor * state.vfactor
-                - 3.0 * state.vfactor
-                - 3.0 * state.vfactor * state.vfactor * state.vfactor;
-            let c4 = 1.0
-                + 3.0 * state.vfactor
-                + state.vfactor * state.vfactor * state.vfactor
-                + 3.0 * state.vfactor * state.vfactor;
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_midpoint_state_init(_period: i32) -> Result<ResourceArc<MIDPOINTState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-            let t3 = c1 * e6 + c2 * e5 + c3 * e4 + c4 * e3;
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_midpoint_state_init")]
+pub fn overlap_midpoint_state_init_with_nan_policy(
+    _period: i32,
+    _nan_policy: rustler::Atom,
+) -> Result<ResourceArc<MIDPOINTState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
 
-            Ok((Some(t3), new_resource))
-        }
-        _ => Ok((None, new_resource)),
-    }
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn overlap_midpoint_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MIDPOINTState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
 }
 
-// Stub implementations when ta-lib is not available
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_ema_state_init(period: i32) -> Result<ResourceArc<EMAState>, String> {
+pub fn overlap_trima_state_init(_period: i32) -> Result<ResourceArc<TRIMAState>, String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1316,11 +2763,11 @@ pub fn overlap_ema_state_init(period: i32) -> Result<ResourceArc<EMAState>, Stri
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_ema_state_next(
+pub fn overlap_trima_state_next(
     _state: Term,
     _value: Option<f64>,
     _is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<EMAState>), String> {
+) -> Result<(Option<f64>, ResourceArc<TRIMAState>), String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1328,7 +2775,19 @@ pub fn overlap_ema_state_next(
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_sma_state_init(period: i32) -> Result<ResourceArc<SMAState>, String> {
+pub fn overlap_t3_state_init(_period: i32, _vfactor: f64) -> Result<ResourceArc<T3State>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_t3_state_init")]
+pub fn overlap_t3_state_init_with_seed_mode(
+    _period: i32,
+    _vfactor: f64,
+    _seed_mode: rustler::Atom,
+) -> Result<ResourceArc<T3State>, String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1336,11 +2795,11 @@ pub fn overlap_sma_state_init(period: i32) -> Result<ResourceArc<SMAState>, Stri
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_sma_state_next(
+pub fn overlap_t3_state_next(
     _state: Term,
     _value: Option<f64>,
     _is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<SMAState>), String> {
+) -> Result<(Option<f64>, ResourceArc<T3State>), String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1348,7 +2807,19 @@ pub fn overlap_sma_state_next(
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_wma_state_init(period: i32) -> Result<ResourceArc<WMAState>, String> {
+pub fn overlap_kama_state_init(_period: i32) -> Result<ResourceArc<KAMAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "overlap_kama_state_init")]
+pub fn overlap_kama_state_init_with_sc(
+    _period: i32,
+    _fast_period: i32,
+    _slow_period: i32,
+) -> Result<ResourceArc<KAMAState>, String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1356,11 +2827,11 @@ pub fn overlap_wma_state_init(period: i32) -> Result<ResourceArc<WMAState>, Stri
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_wma_state_next(
+pub fn overlap_kama_state_next(
     _state: Term,
     _value: Option<f64>,
     _is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<WMAState>), String> {
+) -> Result<(Option<f64>, ResourceArc<KAMAState>), String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1368,7 +2839,10 @@ pub fn overlap_wma_state_next(
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_dema_state_init(period: i32) -> Result<ResourceArc<DEMAState>, String> {
+pub fn overlap_supertrend_state_init(
+    _period: i32,
+    _multiplier: f64,
+) -> Result<ResourceArc<SuperTrendState>, String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1376,11 +2850,14 @@ pub fn overlap_dema_state_init(period: i32) -> Result<ResourceArc<DEMAState>, St
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_dema_state_next(
+#[allow(clippy::type_complexity)]
+pub fn overlap_supertrend_state_next(
     _state: Term,
-    _value: Option<f64>,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
     _is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<DEMAState>), String> {
+) -> Result<(Option<f64>, Option<i32>, ResourceArc<SuperTrendState>), String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1388,7 +2865,7 @@ pub fn overlap_dema_state_next(
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_tema_state_init(period: i32) -> Result<ResourceArc<TEMAState>, String> {
+pub fn overlap_midprice_state_init(_period: i32) -> Result<ResourceArc<MIDPRICEState>, String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1396,11 +2873,12 @@ pub fn overlap_tema_state_init(period: i32) -> Result<ResourceArc<TEMAState>, St
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_tema_state_next(
+pub fn overlap_midprice_state_next(
     _state: Term,
-    _value: Option<f64>,
+    _high: Option<f64>,
+    _low: Option<f64>,
     _is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<TEMAState>), String> {
+) -> Result<(Option<f64>, ResourceArc<MIDPRICEState>), String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1408,7 +2886,12 @@ pub fn overlap_tema_state_next(
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_midpoint_state_init(period: i32) -> Result<ResourceArc<MIDPOINTState>, String> {
+pub fn overlap_bbands_state_init(
+    _period: i32,
+    _nb_dev_up: f64,
+    _nb_dev_dn: f64,
+    _ma_type: i32,
+) -> Result<ResourceArc<BBANDSState>, String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1416,11 +2899,20 @@ pub fn overlap_midpoint_state_init(period: i32) -> Result<ResourceArc<MIDPOINTSt
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_midpoint_state_next(
+#[allow(clippy::type_complexity)]
+pub fn overlap_bbands_state_next(
     _state: Term,
     _value: Option<f64>,
     _is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<MIDPOINTState>), String> {
+) -> Result<
+    (
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        ResourceArc<BBANDSState>,
+    ),
+    String,
+> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1428,7 +2920,7 @@ pub fn overlap_midpoint_state_next(
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_trima_state_init(period: i32) -> Result<ResourceArc<TRIMAState>, String> {
+pub fn overlap_ma_state_init(_period: i32, _ma_type: i32) -> Result<ResourceArc<MAState>, String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1436,11 +2928,11 @@ pub fn overlap_trima_state_init(period: i32) -> Result<ResourceArc<TRIMAState>,
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_trima_state_next(
+pub fn overlap_ma_state_next(
     _state: Term,
     _value: Option<f64>,
     _is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<TRIMAState>), String> {
+) -> Result<(Option<f64>, ResourceArc<MAState>), String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1448,7 +2940,9 @@ pub fn overlap_trima_state_next(
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_t3_state_init(period: i32, vfactor: f64) -> Result<ResourceArc<T3State>, String> {
+pub fn pipeline_init(
+    _specs: Vec<(rustler::Atom, i32)>,
+) -> Result<ResourceArc<PipelineState>, String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
@@ -1456,11 +2950,11 @@ pub fn overlap_t3_state_init(period: i32, vfactor: f64) -> Result<ResourceArc<T3
 
 #[cfg(not(has_talib))]
 #[rustler::nif]
-pub fn overlap_t3_state_next(
+pub fn pipeline_next(
     _state: Term,
     _value: Option<f64>,
     _is_new_bar: bool,
-) -> Result<(Option<f64>, ResourceArc<T3State>), String> {
+) -> Result<(Option<f64>, ResourceArc<PipelineState>), String> {
     Err(
         "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
     .to_string())
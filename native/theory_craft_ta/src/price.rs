@@ -0,0 +1,446 @@
+// Price transforms not provided by TA-Lib, implemented in Rust (always available)
+
+/// Heikin-Ashi open, high, low and close series
+pub type HeikinAshiOutput = (
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+);
+
+/// Compute one Heikin-Ashi bar
+///
+/// `previous` is the HA (open, close) of the previous bar, or `None` for the
+/// first bar, in which case the HA open is seeded from the raw open.
+///
+/// Returns the HA (open, high, low, close).
+#[inline]
+pub fn heikin_ashi_bar(
+    previous: Option<(f64, f64)>,
+    open: f64,
+    high: f64,
+    low: f64,
+    close: f64,
+) -> (f64, f64, f64, f64) {
+    let ha_close = (open + high + low + close) / 4.0;
+
+    let ha_open = match previous {
+        Some((prev_open, prev_close)) => (prev_open + prev_close) / 2.0,
+        None => open,
+    };
+
+    let ha_high = high.max(ha_open).max(ha_close);
+    let ha_low = low.min(ha_open).min(ha_close);
+
+    (ha_open, ha_high, ha_low, ha_close)
+}
+
+#[rustler::nif]
+pub fn price_heikin_ashi(
+    open: Vec<Option<f64>>,
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+) -> Result<HeikinAshiOutput, String> {
+    heikin_ashi(&open, &high, &low, &close)
+}
+
+fn heikin_ashi(
+    open: &[Option<f64>],
+    high: &[Option<f64>],
+    low: &[Option<f64>],
+    close: &[Option<f64>],
+) -> Result<HeikinAshiOutput, String> {
+    let length = open.len();
+
+    crate::helpers::validate_inputs(
+        "HEIKIN_ASHI",
+        &[
+            ("open", open.len()),
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    let mut ha_open = Vec::with_capacity(length);
+    let mut ha_high = Vec::with_capacity(length);
+    let mut ha_low = Vec::with_capacity(length);
+    let mut ha_close = Vec::with_capacity(length);
+
+    // HA open/close of the last complete bar, None until the first one
+    let mut previous = None;
+
+    for i in 0..length {
+        let valid = |value: Option<f64>| value.filter(|v| !v.is_nan());
+
+        match (
+            valid(open[i]),
+            valid(high[i]),
+            valid(low[i]),
+            valid(close[i]),
+        ) {
+            (Some(o), Some(h), Some(l), Some(c)) => {
+                let (bar_open, bar_high, bar_low, bar_close) =
+                    heikin_ashi_bar(previous, o, h, l, c);
+                previous = Some((bar_open, bar_close));
+
+                ha_open.push(Some(bar_open));
+                ha_high.push(Some(bar_high));
+                ha_low.push(Some(bar_low));
+                ha_close.push(Some(bar_close));
+            }
+            // Incomplete bar (leading NaN or gap): no output, the chain skips it
+            _ => {
+                ha_open.push(None);
+                ha_high.push(None);
+                ha_low.push(None);
+                ha_close.push(None);
+            }
+        }
+    }
+
+    Ok((ha_open, ha_high, ha_low, ha_close))
+}
+
+/// Pivot point, resistances R1 to R3 and supports S1 to S3 series
+pub type PivotsOutput = (
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+    Vec<Option<f64>>,
+);
+
+/// Compute the classic floor-trader pivots of one bar
+///
+/// Returns the (pivot, r1, r2, r3, s1, s2, s3) levels for the next bar.
+#[inline]
+pub fn pivots_bar(high: f64, low: f64, close: f64) -> [f64; 7] {
+    let pivot = (high + low + close) / 3.0;
+    let range = high - low;
+
+    [
+        pivot,
+        2.0 * pivot - low,
+        pivot + range,
+        high + 2.0 * (pivot - low),
+        2.0 * pivot - high,
+        pivot - range,
+        low - 2.0 * (high - pivot),
+    ]
+}
+
+#[rustler::nif]
+pub fn price_pivots(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+) -> Result<PivotsOutput, String> {
+    pivots(&high, &low, &close)
+}
+
+fn pivots(
+    high: &[Option<f64>],
+    low: &[Option<f64>],
+    close: &[Option<f64>],
+) -> Result<PivotsOutput, String> {
+    let length = high.len();
+
+    crate::helpers::validate_inputs(
+        "PIVOTS",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    let mut levels: [Vec<Option<f64>>; 7] = Default::default();
+
+    for i in 0..length {
+        let valid = |value: Option<f64>| value.filter(|v| !v.is_nan());
+
+        // The levels of a bar come from the previous one: none for the first bar
+        let bar = match i.checked_sub(1) {
+            Some(prev) => match (valid(high[prev]), valid(low[prev]), valid(close[prev])) {
+                (Some(h), Some(l), Some(c)) => Some(pivots_bar(h, l, c)),
+                // Incomplete previous bar (leading NaN or gap): no levels
+                _ => None,
+            },
+            None => None,
+        };
+
+        for (index, level) in levels.iter_mut().enumerate() {
+            level.push(bar.map(|values| values[index]));
+        }
+    }
+
+    let [pivot, r1, r2, r3, s1, s2, s3] = levels;
+
+    Ok((pivot, r1, r2, r3, s1, s2, s3))
+}
+
+/// Typical price of one bar: (high + low + close) / 3
+#[inline]
+pub fn typprice_bar(high: f64, low: f64, close: f64) -> f64 {
+    (high + low + close) / 3.0
+}
+
+/// Median price of one bar: (high + low) / 2
+#[inline]
+pub fn medprice_bar(high: f64, low: f64) -> f64 {
+    (high + low) / 2.0
+}
+
+/// Weighted close price of one bar: (high + low + 2 * close) / 4
+#[inline]
+pub fn wclprice_bar(high: f64, low: f64, close: f64) -> f64 {
+    (high + low + close * 2.0) / 4.0
+}
+
+#[rustler::nif]
+pub fn price_typprice(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    hlc_transform("TYPPRICE", &high, &low, &close, typprice_bar)
+}
+
+#[rustler::nif]
+pub fn price_medprice(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    hl_transform("MEDPRICE", &high, &low, medprice_bar)
+}
+
+#[rustler::nif]
+pub fn price_wclprice(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    hlc_transform("WCLPRICE", &high, &low, &close, wclprice_bar)
+}
+
+/// Average price of one bar: (open + high + low + close) / 4
+#[inline]
+pub fn avgprice_bar(open: f64, high: f64, low: f64, close: f64) -> f64 {
+    (open + high + low + close) / 4.0
+}
+
+/// Price an indicator with a single input is computed on, derived from the OHLC
+///
+/// Decoded from `:close`, `:hl2`, `:hlc3` or `:ohlc4`.
+#[derive(rustler::NifUnitEnum, Clone, Copy)]
+pub enum PriceSource {
+    /// Close price
+    Close,
+    /// Median price: (high + low) / 2
+    Hl2,
+    /// Typical price: (high + low + close) / 3
+    Hlc3,
+    /// Average price: (open + high + low + close) / 4
+    Ohlc4,
+}
+
+impl PriceSource {
+    /// Derive the series of this price, `None` for an incomplete bar
+    ///
+    /// Only the columns the price is made of must have the same length.
+    pub fn series(
+        self,
+        ohlc: &crate::batch::Ohlc,
+        func_name: &str,
+    ) -> Result<Vec<Option<f64>>, String> {
+        match self {
+            PriceSource::Close => Ok(ohlc.close.clone()),
+            PriceSource::Hl2 => hl_transform(func_name, &ohlc.high, &ohlc.low, medprice_bar),
+            PriceSource::Hlc3 => {
+                hlc_transform(func_name, &ohlc.high, &ohlc.low, &ohlc.close, typprice_bar)
+            }
+            PriceSource::Ohlc4 => {
+                crate::helpers::validate_inputs(
+                    func_name,
+                    &[
+                        ("open", ohlc.open.len()),
+                        ("high", ohlc.high.len()),
+                        ("low", ohlc.low.len()),
+                        ("close", ohlc.close.len()),
+                    ],
+                )?;
+
+                let valid = |value: Option<f64>| value.filter(|v| !v.is_nan());
+
+                let result = (0..ohlc.close.len())
+                    .map(|i| {
+                        Some(avgprice_bar(
+                            valid(ohlc.open[i])?,
+                            valid(ohlc.high[i])?,
+                            valid(ohlc.low[i])?,
+                            valid(ohlc.close[i])?,
+                        ))
+                    })
+                    .collect();
+
+                Ok(result)
+            }
+        }
+    }
+}
+
+/// Apply a per-bar transform of the high and low, `None` for an incomplete bar
+fn hl_transform(
+    func_name: &str,
+    high: &[Option<f64>],
+    low: &[Option<f64>],
+    transform: fn(f64, f64) -> f64,
+) -> Result<Vec<Option<f64>>, String> {
+    crate::helpers::validate_inputs(func_name, &[("high", high.len()), ("low", low.len())])?;
+
+    let valid = |value: Option<f64>| value.filter(|v| !v.is_nan());
+
+    let result = high
+        .iter()
+        .zip(low)
+        .map(|(&h, &l)| Some(transform(valid(h)?, valid(l)?)))
+        .collect();
+
+    Ok(result)
+}
+
+/// Apply a per-bar transform of the high, low and close, `None` for an incomplete bar
+fn hlc_transform(
+    func_name: &str,
+    high: &[Option<f64>],
+    low: &[Option<f64>],
+    close: &[Option<f64>],
+    transform: fn(f64, f64, f64) -> f64,
+) -> Result<Vec<Option<f64>>, String> {
+    crate::helpers::validate_inputs(
+        func_name,
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    let valid = |value: Option<f64>| value.filter(|v| !v.is_nan());
+
+    let result = (0..high.len())
+        .map(|i| Some(transform(valid(high[i])?, valid(low[i])?, valid(close[i])?)))
+        .collect();
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hlc_transform_skips_incomplete_bars() {
+        let high = [Some(12.0), None, Some(13.0)];
+        let low = [Some(9.0), Some(10.0), Some(f64::NAN)];
+        let close = [Some(12.0), Some(11.0), Some(12.0)];
+
+        let result = hlc_transform("TYPPRICE", &high, &low, &close, typprice_bar).unwrap();
+        assert_eq!(result, vec![Some(11.0), None, None]);
+
+        let result = hlc_transform("WCLPRICE", &high, &low, &close, wclprice_bar).unwrap();
+        assert_eq!(result, vec![Some(11.25), None, None]);
+    }
+
+    #[test]
+    fn heikin_ashi_matches_hand_computed_values() {
+        let open = [None, Some(10.0), Some(11.0), Some(12.0), Some(11.0)];
+        let high = [None, Some(12.0), Some(13.0), Some(12.5), Some(11.0)];
+        let low = [None, Some(9.0), Some(10.0), Some(10.5), Some(8.0)];
+        let close = [None, Some(11.0), Some(12.0), Some(11.0), Some(8.5)];
+
+        let (ha_open, ha_high, ha_low, ha_close) = heikin_ashi(&open, &high, &low, &close).unwrap();
+
+        // Bar 1 seeds the HA open from the raw open (10.0)
+        // Bar 2: open = (10 + 10.5) / 2, close = (11 + 13 + 10 + 12) / 4
+        // Bar 3: open = (10.25 + 11.5) / 2, close = (12 + 12.5 + 10.5 + 11) / 4
+        // Bar 4: open = (10.875 + 11.5) / 2 is above the raw high (11.0)
+        assert_eq!(
+            ha_open,
+            vec![None, Some(10.0), Some(10.25), Some(10.875), Some(11.1875)]
+        );
+        assert_eq!(
+            ha_close,
+            vec![None, Some(10.5), Some(11.5), Some(11.5), Some(9.625)]
+        );
+        assert_eq!(
+            ha_high,
+            vec![None, Some(12.0), Some(13.0), Some(12.5), Some(11.1875)]
+        );
+        assert_eq!(
+            ha_low,
+            vec![None, Some(9.0), Some(10.0), Some(10.5), Some(8.0)]
+        );
+    }
+
+    #[test]
+    fn heikin_ashi_skips_incomplete_bars() {
+        let open = [Some(f64::NAN), Some(10.0), Some(11.0), Some(11.0)];
+        let high = [Some(1.0), Some(12.0), None, Some(13.0)];
+        let low = [Some(1.0), Some(9.0), Some(10.0), Some(10.0)];
+        let close = [Some(1.0), Some(11.0), Some(12.0), Some(12.0)];
+
+        let (ha_open, _ha_high, _ha_low, ha_close) =
+            heikin_ashi(&open, &high, &low, &close).unwrap();
+
+        // The gap at index 2 is skipped: bar 3 chains on bar 1
+        assert_eq!(ha_open, vec![None, Some(10.0), None, Some(10.25)]);
+        assert_eq!(ha_close, vec![None, Some(10.5), None, Some(11.5)]);
+    }
+
+    #[test]
+    fn heikin_ashi_rejects_mismatched_lengths() {
+        let err = heikin_ashi(&[Some(1.0)], &[], &[], &[]).unwrap_err();
+        assert!(err.starts_with("HEIKIN_ASHI: "), "{}", err);
+    }
+
+    #[test]
+    fn pivots_match_hand_computed_values() {
+        let high = [Some(12.0), Some(13.0), Some(14.0)];
+        let low = [Some(9.0), Some(10.0), Some(11.0)];
+        let close = [Some(12.0), Some(11.5), Some(13.0)];
+
+        let (pivot, r1, r2, r3, s1, s2, s3) = pivots(&high, &low, &close).unwrap();
+
+        // Bar 1 uses bar 0: P = (12 + 9 + 12) / 3 = 11, range = 3
+        // Bar 2 uses bar 1: P = (13 + 10 + 11.5) / 3 = 11.5, range = 3
+        assert_eq!(pivot, vec![None, Some(11.0), Some(11.5)]);
+        assert_eq!(r1, vec![None, Some(13.0), Some(13.0)]);
+        assert_eq!(r2, vec![None, Some(14.0), Some(14.5)]);
+        assert_eq!(r3, vec![None, Some(16.0), Some(16.0)]);
+        assert_eq!(s1, vec![None, Some(10.0), Some(10.0)]);
+        assert_eq!(s2, vec![None, Some(8.0), Some(8.5)]);
+        assert_eq!(s3, vec![None, Some(7.0), Some(7.0)]);
+    }
+
+    #[test]
+    fn pivots_skip_bars_after_incomplete_ones() {
+        let high = [Some(12.0), None, Some(13.0), Some(14.0)];
+        let low = [Some(9.0), Some(10.0), Some(10.0), Some(11.0)];
+        let close = [Some(12.0), Some(11.0), Some(11.5), Some(13.0)];
+
+        let (pivot, ..) = pivots(&high, &low, &close).unwrap();
+
+        assert_eq!(pivot, vec![None, Some(11.0), None, Some(11.5)]);
+    }
+
+    #[test]
+    fn pivots_reject_mismatched_lengths() {
+        let err = pivots(&[Some(1.0)], &[], &[]).unwrap_err();
+        assert!(err.starts_with("PIVOTS: "), "{}", err);
+    }
+}
@@ -0,0 +1,158 @@
+use rustler::ResourceArc;
+
+/// State for Heikin-Ashi calculation
+pub struct HeikinAshiState {
+    current: Option<(f64, f64)>, // HA open/close of current bar (can change in UPDATE mode)
+    prev: Option<(f64, f64)>,    // HA open/close of previous bar (persisted in APPEND mode)
+}
+
+/// State for TYPPRICE calculation
+///
+/// The price transforms have no memory between bars: their states only give
+/// them the same interface as the other indicators.
+pub struct TYPPRICEState;
+
+/// State for MEDPRICE calculation (see [`TYPPRICEState`])
+pub struct MEDPRICEState;
+
+/// State for WCLPRICE calculation (see [`TYPPRICEState`])
+pub struct WCLPRICEState;
+
+// Heikin-Ashi does not use TA-Lib, so its state is always available
+#[rustler::nif]
+pub fn price_heikin_ashi_state_init() -> Result<ResourceArc<HeikinAshiState>, String> {
+    let state = HeikinAshiState {
+        current: None,
+        prev: None,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[rustler::nif]
+#[allow(clippy::type_complexity)]
+pub fn price_heikin_ashi_state_next(
+    state_arc: ResourceArc<HeikinAshiState>,
+    open: Option<f64>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<
+    (
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        Option<f64>,
+        ResourceArc<HeikinAshiState>,
+    ),
+    String,
+> {
+    use crate::price::heikin_ashi_bar;
+
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (open, high, low, close) = match (open, high, low, close) {
+        (Some(open), Some(high), Some(low), Some(close)) => (open, high, low, close),
+        _ => return Ok((None, None, None, None, state_arc)),
+    };
+
+    // In UPDATE mode the previous HA bar is fixed, only the current bar changes
+    let new_prev = if is_new_bar {
+        state.current
+    } else {
+        state.prev
+    };
+
+    // The first bar has no previous HA bar and seeds the HA open from the raw open
+    let (ha_open, ha_high, ha_low, ha_close) = heikin_ashi_bar(new_prev, open, high, low, close);
+
+    let new_state = HeikinAshiState {
+        current: Some((ha_open, ha_close)),
+        prev: new_prev,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((
+        Some(ha_open),
+        Some(ha_high),
+        Some(ha_low),
+        Some(ha_close),
+        new_resource,
+    ))
+}
+
+// The price transforms do not use TA-Lib, so their states are always available
+#[rustler::nif]
+pub fn price_typprice_state_init() -> Result<ResourceArc<TYPPRICEState>, String> {
+    Ok(ResourceArc::new(TYPPRICEState))
+}
+
+#[rustler::nif]
+pub fn price_typprice_state_next(
+    state_arc: ResourceArc<TYPPRICEState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TYPPRICEState>), String> {
+    use crate::price::typprice_bar;
+
+    // Nothing carries over between bars: an UPDATE is computed like an APPEND
+    let value = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => Some(typprice_bar(high, low, close)),
+        _ => None,
+    };
+
+    Ok((value, state_arc))
+}
+
+#[rustler::nif]
+pub fn price_medprice_state_init() -> Result<ResourceArc<MEDPRICEState>, String> {
+    Ok(ResourceArc::new(MEDPRICEState))
+}
+
+#[rustler::nif]
+pub fn price_medprice_state_next(
+    state_arc: ResourceArc<MEDPRICEState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MEDPRICEState>), String> {
+    use crate::price::medprice_bar;
+
+    // Nothing carries over between bars: an UPDATE is computed like an APPEND
+    let value = match (high, low) {
+        (Some(high), Some(low)) => Some(medprice_bar(high, low)),
+        _ => None,
+    };
+
+    Ok((value, state_arc))
+}
+
+#[rustler::nif]
+pub fn price_wclprice_state_init() -> Result<ResourceArc<WCLPRICEState>, String> {
+    Ok(ResourceArc::new(WCLPRICEState))
+}
+
+#[rustler::nif]
+pub fn price_wclprice_state_next(
+    state_arc: ResourceArc<WCLPRICEState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<WCLPRICEState>), String> {
+    use crate::price::wclprice_bar;
+
+    // Nothing carries over between bars: an UPDATE is computed like an APPEND
+    let value = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => Some(wclprice_bar(high, low, close)),
+        _ => None,
+    };
+
+    Ok((value, state_arc))
+}
@@ -0,0 +1,126 @@
+// Diagnostics comparing the streaming states to their batch functions
+
+/// Where a streaming state diverges from its batch function
+#[derive(rustler::NifMap, Debug, PartialEq)]
+pub struct SelfTestReport {
+    /// Largest absolute difference where both produce a value
+    pub max_abs_diff: f64,
+    /// First index where the values differ beyond the tolerance, or where only
+    /// one of the two produces a value
+    pub first_divergence_index: Option<usize>,
+}
+
+/// Relative tolerance (absolute below 1.0) for two values to be considered equal
+const TOLERANCE: f64 = 1e-9;
+
+/// Compare a batch result to the outputs of a streaming state fed one value at a time
+pub fn compare_outputs(batch: &[Option<f64>], stream: &[Option<f64>]) -> SelfTestReport {
+    let mut max_abs_diff: f64 = 0.0;
+    let mut first_divergence_index = None;
+
+    for (i, (expected, value)) in batch.iter().zip(stream).enumerate() {
+        let diverges = match (expected, value) {
+            (None, None) => false,
+            (Some(expected), Some(value)) => {
+                let diff = (value - expected).abs();
+                max_abs_diff = max_abs_diff.max(diff);
+                diff > TOLERANCE * expected.abs().max(1.0)
+            }
+            _ => true,
+        };
+
+        if diverges && first_divergence_index.is_none() {
+            first_divergence_index = Some(i);
+        }
+    }
+
+    SelfTestReport {
+        max_abs_diff,
+        first_divergence_index,
+    }
+}
+
+/// Run `overlap_ma` and the MA streaming state (in APPEND mode) over the same data
+///
+/// NaN values are missing values: nil for the batch, skipped by the state.
+#[cfg(has_talib)]
+fn selftest_ma_type(data: &[f64], period: i32, ma_type: i32) -> Result<SelfTestReport, String> {
+    use crate::overlap::calculate_ma;
+    use crate::overlap_state::{new_ma_state, step_ma};
+
+    let series: Vec<Option<f64>> = data
+        .iter()
+        .map(|&value| (!value.is_nan()).then_some(value))
+        .collect();
+
+    let batch = calculate_ma(&series, period, ma_type)?;
+
+    let mut state = new_ma_state(period, ma_type)?;
+    let stream: Vec<Option<f64>> = series
+        .iter()
+        .map(|value| {
+            let (ma, new_state) = step_ma(&state, (*value)?, true);
+            state = new_state;
+            ma
+        })
+        .collect();
+
+    Ok(compare_outputs(&batch, &stream))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn selftest_ma(data: Vec<f64>, period: i32, ma_type: i32) -> Result<SelfTestReport, String> {
+    selftest_ma_type(&data, period, ma_type)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn selftest_ema(data: Vec<f64>, period: i32) -> Result<SelfTestReport, String> {
+    selftest_ma_type(&data, period, 1)
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn selftest_ma(_data: Vec<f64>, _period: i32, _ma_type: i32) -> Result<SelfTestReport, String> {
+    Err("MA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn selftest_ema(_data: Vec<f64>, _period: i32) -> Result<SelfTestReport, String> {
+    Err("EMA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compare_outputs_reports_no_divergence_on_equal_series() {
+        let batch = [None, Some(1.0), Some(2.0)];
+        let stream = [None, Some(1.0), Some(2.0 + 1e-12)];
+
+        let report = compare_outputs(&batch, &stream);
+        assert_eq!(report.first_divergence_index, None);
+        assert!(report.max_abs_diff < 1e-11);
+    }
+
+    #[test]
+    fn compare_outputs_reports_the_first_divergence() {
+        let batch = [None, Some(1.0), Some(2.0), Some(3.0)];
+        let stream = [None, Some(1.0), Some(2.5), Some(4.0)];
+
+        let report = compare_outputs(&batch, &stream);
+        assert_eq!(report.first_divergence_index, Some(2));
+        assert_eq!(report.max_abs_diff, 1.0);
+    }
+
+    #[test]
+    fn compare_outputs_treats_a_warmup_mismatch_as_divergence() {
+        let report = compare_outputs(&[None, Some(1.0)], &[Some(1.0), Some(1.0)]);
+        assert_eq!(report.first_divergence_index, Some(0));
+        assert_eq!(report.max_abs_diff, 0.0);
+    }
+}
@@ -0,0 +1,163 @@
+// Utilities over input series, independent of TA-Lib
+
+/// Run of consecutive missing values in a series
+#[derive(rustler::NifMap, Debug, PartialEq)]
+pub struct Gap {
+    /// Index of the first missing value
+    pub start: usize,
+    /// Number of consecutive missing values
+    pub len: usize,
+}
+
+/// Value of the series, `None` for a missing value (nil or NaN)
+#[inline]
+fn valid(value: Option<f64>) -> Option<f64> {
+    value.filter(|v| !v.is_nan())
+}
+
+/// Find the runs of missing values (nil or NaN), in order
+pub fn find_gaps(data: &[Option<f64>]) -> Vec<Gap> {
+    let mut gaps: Vec<Gap> = Vec::new();
+
+    for (i, &value) in data.iter().enumerate() {
+        if valid(value).is_some() {
+            continue;
+        }
+
+        match gaps.last_mut() {
+            Some(gap) if gap.start + gap.len == i => gap.len += 1,
+            _ => gaps.push(Gap { start: i, len: 1 }),
+        }
+    }
+
+    gaps
+}
+
+/// Replace each missing value with the last value before it
+///
+/// Missing values before the first value are left as `None`.
+pub fn forward_filled(data: &[Option<f64>]) -> Vec<Option<f64>> {
+    let mut last = None;
+
+    data.iter()
+        .map(|&value| {
+            last = valid(value).or(last);
+            last
+        })
+        .collect()
+}
+
+/// Replace each missing value with the first value after it
+///
+/// Missing values after the last value are left as `None`.
+pub fn backward_filled(data: &[Option<f64>]) -> Vec<Option<f64>> {
+    let reversed: Vec<Option<f64>> = data.iter().rev().copied().collect();
+
+    forward_filled(&reversed).into_iter().rev().collect()
+}
+
+/// Replace the missing values between two values with their linear interpolation
+///
+/// The values are evenly spaced: the interpolation is on the index. Missing
+/// values before the first or after the last value are left as `None`.
+pub fn linear_filled(data: &[Option<f64>]) -> Vec<Option<f64>> {
+    let mut result: Vec<Option<f64>> = data.iter().map(|&value| valid(value)).collect();
+
+    for gap in find_gaps(data) {
+        let end = gap.start + gap.len;
+        let (Some(before), Some(after)) = (
+            gap.start.checked_sub(1).and_then(|i| result[i]),
+            result.get(end).copied().flatten(),
+        ) else {
+            continue;
+        };
+
+        let step = (after - before) / (gap.len + 1) as f64;
+
+        for (offset, value) in result[gap.start..end].iter_mut().enumerate() {
+            *value = Some(before + step * (offset + 1) as f64);
+        }
+    }
+
+    result
+}
+
+#[rustler::nif]
+pub fn analyze_gaps(data: Vec<Option<f64>>) -> Vec<Gap> {
+    find_gaps(&data)
+}
+
+#[rustler::nif]
+pub fn fill_forward(data: Vec<Option<f64>>) -> Vec<Option<f64>> {
+    forward_filled(&data)
+}
+
+#[rustler::nif]
+pub fn fill_backward(data: Vec<Option<f64>>) -> Vec<Option<f64>> {
+    backward_filled(&data)
+}
+
+#[rustler::nif]
+pub fn fill_linear(data: Vec<Option<f64>>) -> Vec<Option<f64>> {
+    linear_filled(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_gaps_reports_leading_interior_and_trailing_gaps() {
+        let data = [
+            None,
+            None,
+            Some(1.0),
+            None,
+            Some(2.0),
+            Some(f64::NAN),
+            None,
+            Some(3.0),
+            None,
+        ];
+
+        assert_eq!(
+            find_gaps(&data),
+            vec![
+                Gap { start: 0, len: 2 },
+                Gap { start: 3, len: 1 },
+                Gap { start: 5, len: 2 },
+                Gap { start: 8, len: 1 },
+            ]
+        );
+    }
+
+    #[test]
+    fn find_gaps_handles_complete_and_empty_series() {
+        assert_eq!(find_gaps(&[Some(1.0), Some(2.0)]), vec![]);
+        assert_eq!(find_gaps(&[]), vec![]);
+        assert_eq!(find_gaps(&[None, None]), vec![Gap { start: 0, len: 2 }]);
+    }
+
+    #[test]
+    fn fills_leave_the_missing_values_without_a_known_neighbour() {
+        let data = [None, Some(1.0), None, None, Some(4.0), Some(f64::NAN)];
+
+        let forward = vec![None, Some(1.0), Some(1.0), Some(1.0), Some(4.0), Some(4.0)];
+        assert_eq!(forward_filled(&data), forward);
+
+        let backward = vec![Some(1.0), Some(1.0), Some(4.0), Some(4.0), Some(4.0), None];
+        assert_eq!(backward_filled(&data), backward);
+
+        let linear = vec![None, Some(1.0), Some(2.0), Some(3.0), Some(4.0), None];
+        assert_eq!(linear_filled(&data), linear);
+    }
+
+    #[test]
+    fn fills_keep_an_all_missing_series() {
+        let data = [None, Some(f64::NAN), None];
+
+        assert_eq!(forward_filled(&data), vec![None; 3]);
+        assert_eq!(backward_filled(&data), vec![None; 3]);
+        assert_eq!(linear_filled(&data), vec![None; 3]);
+    }
+}
@@ -0,0 +1,616 @@
+// Implementation when ta-lib is available
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_stddev(
+    data: Vec<Option<f64>>,
+    period: i32,
+    nbdev: f64,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::stats_ffi::{TA_STDDEV_Lookback, TA_STDDEV};
+
+    if period < 2 {
+        return Err("STDDEV: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_STDDEV_Lookback(period, nbdev) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_STDDEV(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            nbdev,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "STDDEV");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "STDDEV")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_var(
+    data: Vec<Option<f64>>,
+    period: i32,
+    nbdev: f64,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::stats_ffi::{TA_VAR_Lookback, TA_VAR};
+
+    if period < 1 {
+        return Err("VAR: period must be >= 1".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_VAR_Lookback(period, nbdev) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_VAR(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            nbdev,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "VAR");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "VAR")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_linearreg(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::stats_ffi::{TA_LINEARREG_Lookback, TA_LINEARREG};
+
+    if period < 2 {
+        return Err("LINEARREG: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_LINEARREG_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_LINEARREG(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "LINEARREG");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "LINEARREG")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_tsf(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx, options_to_nan};
+    use crate::stats_ffi::{TA_TSF_Lookback, TA_TSF};
+
+    if period < 2 {
+        return Err("TSF: period must be >= 2".to_string());
+    }
+
+    if data.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_data = options_to_nan(&data);
+    let length = clean_data.len();
+
+    let begidx = match check_begidx(&clean_data) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_TSF_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_TSF(
+            0,
+            endidx,
+            clean_data[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "TSF");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "TSF")?;
+
+    Ok(result)
+}
+
+/// Pearson correlation of x and y over the last `period` bars
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_correl(
+    x: Vec<Option<f64>>,
+    y: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::stats_ffi::{TA_CORREL_Lookback, TA_CORREL};
+
+    if period < 2 {
+        return Err("CORREL: period must be >= 2".to_string());
+    }
+
+    validate_inputs("CORREL", &[("x", x.len()), ("y", y.len())])?;
+
+    if x.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_x = options_to_nan(&x);
+    let clean_y = options_to_nan(&y);
+    let length = clean_x.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_x, &clean_y]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_CORREL_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_CORREL(
+            0,
+            endidx,
+            clean_x[begidx..].as_ptr(),
+            clean_y[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "CORREL");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "CORREL")?;
+
+    Ok(result)
+}
+
+/// Beta of y against x
+///
+/// As in TA-Lib, the regression runs on the bar-to-bar returns of both series,
+/// so the first value needs `period + 1` bars.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_beta(
+    x: Vec<Option<f64>>,
+    y: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::stats_ffi::{TA_BETA_Lookback, TA_BETA};
+
+    if period < 2 {
+        return Err("BETA: period must be >= 2".to_string());
+    }
+
+    validate_inputs("BETA", &[("x", x.len()), ("y", y.len())])?;
+
+    if x.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_x = options_to_nan(&x);
+    let clean_y = options_to_nan(&y);
+    let length = clean_x.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_x, &clean_y]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_BETA_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_BETA(
+            0,
+            endidx,
+            clean_x[begidx..].as_ptr(),
+            clean_y[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "BETA");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "BETA")?;
+
+    Ok(result)
+}
+
+/// Rolling median
+///
+/// TA-Lib has no MEDIAN, so it runs in Rust over a sorted copy of the window.
+#[rustler::nif]
+pub fn stats_median(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::SortedWindow;
+
+    if period < 2 {
+        return Err("MEDIAN: period must be >= 2".to_string());
+    }
+
+    let length = data.len();
+    let period = period as usize;
+
+    // Skip leading NaN values
+    let is_valid = |value: &Option<f64>| value.is_some_and(|v| !v.is_nan());
+    let begidx = match data.iter().position(is_valid) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+
+    let mut result = vec![None; length];
+    let mut window = SortedWindow::default();
+
+    // Missing values after begidx can't be ordered: no median while in the window
+    let mut missing = 0;
+
+    for i in begidx..length {
+        match data[i].filter(|v| !v.is_nan()) {
+            Some(value) => window.insert(value),
+            None => missing += 1,
+        }
+
+        if i >= begidx + period {
+            match data[i - period].filter(|v| !v.is_nan()) {
+                Some(value) => window.remove(value),
+                None => missing -= 1,
+            }
+        }
+
+        if i + 1 >= begidx + period && missing == 0 {
+            result[i] = window.median();
+        }
+    }
+
+    Ok(result)
+}
+
+/// Rolling percent rank
+///
+/// Percentage (0 to 100) of the previous `period` values that are strictly
+/// less than the current value. TA-Lib has no percent rank, so it runs in Rust
+/// over a sorted copy of the window.
+#[rustler::nif]
+pub fn stats_percent_rank(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::SortedWindow;
+
+    if period < 1 {
+        return Err("PERCENT_RANK: period must be >= 1".to_string());
+    }
+
+    let length = data.len();
+    let period = period as usize;
+
+    // Skip leading NaN values
+    let is_valid = |value: &Option<f64>| value.is_some_and(|v| !v.is_nan());
+    let begidx = match data.iter().position(is_valid) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+
+    let mut result = vec![None; length];
+
+    // Previous 'period' values, current bar excluded
+    let mut window = SortedWindow::default();
+
+    // Missing values after begidx can't be ranked against: no rank while in the window
+    let mut missing = 0;
+
+    for i in begidx..length {
+        let value = data[i].filter(|v| !v.is_nan());
+
+        if i >= begidx + period && missing == 0 {
+            result[i] = value.map(|v| window.count_below(v) as f64 * 100.0 / period as f64);
+        }
+
+        match value {
+            Some(value) => window.insert(value),
+            None => missing += 1,
+        }
+
+        if i >= begidx + period {
+            match data[i - period].filter(|v| !v.is_nan()) {
+                Some(value) => window.remove(value),
+                None => missing -= 1,
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+/// Rolling z-score
+///
+/// `(value - mean) / stddev` over the last `period` values, current bar included,
+/// with the population standard deviation of STDDEV. Runs in Rust on the same
+/// rolling variance window as the STDDEV and VAR states.
+#[rustler::nif]
+pub fn stats_zscore(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::stats_state::{new_var_window, var_window_next};
+
+    if period < 2 {
+        return Err("ZSCORE: period must be >= 2".to_string());
+    }
+
+    let length = data.len();
+
+    // Skip leading NaN values
+    let is_valid = |value: &Option<f64>| value.is_some_and(|v| !v.is_nan());
+    let begidx = match data.iter().position(is_valid) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+
+    let mut result = vec![None; begidx];
+    let mut window = new_var_window(period);
+
+    for value in &data[begidx..] {
+        // Missing values after begidx poison the running sums, as in TA-Lib
+        let value = value.unwrap_or(f64::NAN);
+        let (stats, new_window) = var_window_next(&window, value, true);
+        window = new_window;
+
+        // Same threshold as STDDEV: a (nearly) zero variance has no z-score
+        let zscore = stats
+            .filter(|(_, var)| *var >= 1e-8)
+            .map(|(mean, var)| (value - mean) / var.sqrt())
+            .filter(|v| !v.is_nan());
+
+        result.push(zscore);
+    }
+
+    Ok(result)
+}
+
+/// Rolling standard error of the mean
+///
+/// `stddev / sqrt(period)` over the last `period` values, current bar included,
+/// with the population standard deviation of STDDEV. Runs in Rust on the same
+/// rolling variance window as the STDDEV and VAR states.
+#[rustler::nif]
+pub fn stats_sem(data: Vec<Option<f64>>, period: i32) -> Result<Vec<Option<f64>>, String> {
+    use crate::stats_state::{new_var_window, var_window_next};
+
+    if period < 2 {
+        return Err("SEM: period must be >= 2".to_string());
+    }
+
+    let length = data.len();
+
+    // Skip leading NaN values
+    let is_valid = |value: &Option<f64>| value.is_some_and(|v| !v.is_nan());
+    let begidx = match data.iter().position(is_valid) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+
+    let mut result = vec![None; begidx];
+    let mut window = new_var_window(period);
+    let sqrt_period = (period as f64).sqrt();
+
+    for value in &data[begidx..] {
+        // Missing values after begidx poison the running sums, as in TA-Lib
+        let value = value.unwrap_or(f64::NAN);
+        let (stats, new_window) = var_window_next(&window, value, true);
+        window = new_window;
+
+        // Same as STDDEV: a (nearly) zero variance gives 0
+        let sem = stats
+            .map(|(_, var)| {
+                if var < 1e-8 {
+                    0.0
+                } else {
+                    var.sqrt() / sqrt_period
+                }
+            })
+            .filter(|v| !v.is_nan());
+
+        result.push(sem);
+    }
+
+    Ok(result)
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_stddev(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+    _nbdev: f64,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("STDDEV: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_var(
+    _data: Vec<Option<f64>>,
+    _period: i32,
+    _nbdev: f64,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("VAR: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_linearreg(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("LINEARREG: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_tsf(_data: Vec<Option<f64>>, _period: i32) -> Result<Vec<Option<f64>>, String> {
+    Err("TSF: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_correl(
+    _x: Vec<Option<f64>>,
+    _y: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("CORREL: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_beta(
+    _x: Vec<Option<f64>>,
+    _y: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("BETA: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
@@ -0,0 +1,9 @@
+// FFI declarations for TA-Lib statistic functions
+//
+// Re-exports the bindings generated from the TA-Lib headers (see ffi.rs).
+// Only compiled when ta-lib is available (has_talib cfg flag).
+
+pub use crate::ffi::{
+    TA_BETA_Lookback, TA_CORREL_Lookback, TA_LINEARREG_Lookback, TA_STDDEV_Lookback,
+    TA_TSF_Lookback, TA_VAR_Lookback, TA_BETA, TA_CORREL, TA_LINEARREG, TA_STDDEV, TA_TSF, TA_VAR,
+};
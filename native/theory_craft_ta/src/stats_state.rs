@@ -0,0 +1,932 @@
+use crate::helpers::SortedWindow;
+#[cfg(has_talib)]
+use crate::overlap_state::validate_recompute_interval;
+use crate::overlap_state::DEFAULT_RECOMPUTE_INTERVAL;
+use rustler::ResourceArc;
+#[cfg(not(has_talib))]
+use rustler::Term;
+use std::collections::VecDeque;
+
+/// Rolling variance window shared by STDDEV, VAR and the z-score
+#[derive(Clone)]
+pub(crate) struct VARWindow {
+    period: i32,
+    lookback_count: i32,
+    sum: f64,                // Sum of the window, current bar excluded
+    sum_sq: f64,             // Sum of squares of the window, current bar excluded
+    buffer: VecDeque<f64>,   // Last 'period' values, current bar included
+    recompute_interval: i32, // Bars between two rebuilds of the sums from the buffer
+}
+
+/// State for STDDEV calculation
+pub struct STDDEVState {
+    nbdev: f64,
+    window: VARWindow,
+}
+
+/// State for VAR calculation
+pub struct VARState {
+    nbdev: f64,
+    window: VARWindow,
+}
+
+/// Rolling least-squares window shared by LINEARREG and TSF
+///
+/// The bars of a full window are at x = 0 (oldest) to x = period - 1 (current).
+#[derive(Clone)]
+struct RegressionWindow {
+    period: i32,
+    lookback_count: i32,
+    sum_x: f64,            // Sum of x over a full window (constant)
+    sum_x2: f64,           // Sum of x² over a full window (constant)
+    sum_y: f64,            // Sum of the window, current bar excluded
+    sum_xy: f64,           // Sum of x × y of the window, current bar excluded
+    buffer: VecDeque<f64>, // Last 'period' values, current bar included
+}
+
+/// State for LINEARREG calculation
+pub struct LINEARREGState {
+    window: RegressionWindow,
+}
+
+/// State for TSF calculation
+pub struct TSFState {
+    window: RegressionWindow,
+}
+
+/// Running sums of (x, y) pairs
+#[derive(Clone, Copy, Default)]
+struct PairSums {
+    x: f64,
+    y: f64,
+    xx: f64,
+    yy: f64,
+    xy: f64,
+}
+
+/// Rolling covariance window shared by CORREL and BETA
+#[derive(Clone)]
+struct CovarianceWindow {
+    period: i32,
+    lookback_count: i32,
+    sums: PairSums,               // Sums of the window, current bar excluded
+    buffer: VecDeque<(f64, f64)>, // Last 'period' pairs, current bar included
+}
+
+/// State for CORREL calculation
+pub struct CORRELState {
+    window: CovarianceWindow,
+}
+
+/// State for BETA calculation
+pub struct BETAState {
+    lookback_count: i32,
+    current: Option<(f64, f64)>, // Prices of current bar (can change in UPDATE mode)
+    prev: Option<(f64, f64)>,    // Prices of previous bar (persisted in APPEND mode)
+    window: CovarianceWindow,    // Fed with the returns, from the second bar
+}
+
+/// State for rolling median calculation
+pub struct MedianState {
+    period: i32,
+    lookback_count: i32,
+    buffer: VecDeque<f64>, // Last 'period' values in arrival order, current bar included
+    sorted: SortedWindow,  // Same values in ascending order
+}
+
+pub(crate) fn new_var_window(period: i32) -> VARWindow {
+    VARWindow {
+        period,
+        lookback_count: 0,
+        sum: 0.0,
+        sum_sq: 0.0,
+        buffer: VecDeque::with_capacity(period as usize),
+        recompute_interval: DEFAULT_RECOMPUTE_INTERVAL,
+    }
+}
+
+/// Advances a rolling variance window by one value
+///
+/// Returns the mean and the variance of the window once it holds `period` values.
+/// The running sums exclude the current bar, so UPDATE mode just adds the
+/// replacing value to them instead of re-scanning the buffer. They are updated
+/// in the same order as TA-Lib's running totals (add the value leaving the
+/// current bar, then subtract the trailing one) so results match the batch.
+/// Every `recompute_interval` bars they are rebuilt from the buffer instead,
+/// which bounds the rounding drift of a long stream.
+pub(crate) fn var_window_next(
+    window: &VARWindow,
+    value: f64,
+    is_new_bar: bool,
+) -> (Option<(f64, f64)>, VARWindow) {
+    let mut new_window = window.clone();
+
+    if is_new_bar {
+        if window.lookback_count == 0 {
+            // A value pushed by an UPDATE before the first bar is not a bar
+            new_window.buffer.clear();
+        } else if let Some(&current) = window.buffer.back() {
+            // The current bar is closed: move it into the running sums
+            new_window.sum += current;
+            new_window.sum_sq += current * current;
+
+            // Drop the trailing value once the window is full
+            if window.buffer.len() == window.period as usize {
+                let trailing = new_window.buffer.pop_front().unwrap_or_default();
+                new_window.sum -= trailing;
+                new_window.sum_sq -= trailing * trailing;
+            }
+
+            // Drift correction, once every 'recompute_interval' bars
+            if window.lookback_count % window.recompute_interval == 0 {
+                new_window.sum = new_window.buffer.iter().sum();
+                new_window.sum_sq = new_window.buffer.iter().map(|v| v * v).sum();
+            }
+        }
+
+        new_window.buffer.push_back(value);
+        new_window.lookback_count += 1;
+    } else if let Some(last) = new_window.buffer.back_mut() {
+        // UPDATE mode: replace the current bar, the sums don't include it
+        *last = value;
+    } else {
+        new_window.buffer.push_back(value);
+    }
+
+    // Warmup phase: need 'period' values
+    if new_window.lookback_count < window.period {
+        return (None, new_window);
+    }
+
+    let period = window.period as f64;
+    let mean = (new_window.sum + value) / period;
+    let mean_sq = (new_window.sum_sq + value * value) / period;
+
+    (Some((mean, mean_sq - mean * mean)), new_window)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_stddev_state_init(
+    period: i32,
+    nbdev: f64,
+) -> Result<ResourceArc<STDDEVState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for STDDEV".to_string());
+    }
+
+    let state = STDDEVState {
+        nbdev,
+        window: new_var_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif(name = "stats_stddev_state_init")]
+pub fn stats_stddev_state_init_with_recompute_interval(
+    period: i32,
+    nbdev: f64,
+    recompute_interval: i32,
+) -> Result<ResourceArc<STDDEVState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for STDDEV".to_string());
+    }
+
+    validate_recompute_interval(recompute_interval, "STDDEV")?;
+
+    let state = STDDEVState {
+        nbdev,
+        window: VARWindow {
+            recompute_interval,
+            ..new_var_window(period)
+        },
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_stddev_state_next(
+    state_arc: ResourceArc<STDDEVState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<STDDEVState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (stats, new_window) = var_window_next(&state.window, value, is_new_bar);
+
+    // Same as TA-Lib: a (nearly) zero or negative variance gives 0
+    let stddev = stats.map(|(_, var)| {
+        if var < 1e-8 {
+            0.0
+        } else {
+            var.sqrt() * state.nbdev
+        }
+    });
+
+    let new_state = STDDEVState {
+        nbdev: state.nbdev,
+        window: new_window,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((stddev, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_var_state_init(period: i32, nbdev: f64) -> Result<ResourceArc<VARState>, String> {
+    if period < 1 {
+        return Err("Invalid period: must be >= 1 for VAR".to_string());
+    }
+
+    let state = VARState {
+        nbdev,
+        window: new_var_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif(name = "stats_var_state_init")]
+pub fn stats_var_state_init_with_recompute_interval(
+    period: i32,
+    nbdev: f64,
+    recompute_interval: i32,
+) -> Result<ResourceArc<VARState>, String> {
+    if period < 1 {
+        return Err("Invalid period: must be >= 1 for VAR".to_string());
+    }
+
+    validate_recompute_interval(recompute_interval, "VAR")?;
+
+    let state = VARState {
+        nbdev,
+        window: VARWindow {
+            recompute_interval,
+            ..new_var_window(period)
+        },
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_var_state_next(
+    state_arc: ResourceArc<VARState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<VARState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    // Like TA-Lib's TA_VAR, nbdev is accepted but doesn't scale the variance
+    let (stats, new_window) = var_window_next(&state.window, value, is_new_bar);
+    let variance = stats.map(|(_, var)| var);
+
+    let new_state = VARState {
+        nbdev: state.nbdev,
+        window: new_window,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((variance, new_resource))
+}
+
+#[cfg(has_talib)]
+fn new_regression_window(period: i32) -> RegressionWindow {
+    let n = period as f64;
+
+    RegressionWindow {
+        period,
+        lookback_count: 0,
+        sum_x: n * (n - 1.0) / 2.0,
+        sum_x2: n * (n - 1.0) * (2.0 * n - 1.0) / 6.0,
+        sum_y: 0.0,
+        sum_xy: 0.0,
+        buffer: VecDeque::with_capacity(period as usize),
+    }
+}
+
+/// Advances a rolling least-squares window by one value
+///
+/// Returns the slope and the intercept (value at x = 0, the oldest bar) of the
+/// line fitted to the window once it holds `period` values. The running sums
+/// exclude the current bar, so UPDATE mode just adds the replacing value to them.
+#[cfg(has_talib)]
+fn regression_window_next(
+    window: &RegressionWindow,
+    value: f64,
+    is_new_bar: bool,
+) -> (Option<(f64, f64)>, RegressionWindow) {
+    let mut new_window = window.clone();
+    let last_x = (window.period - 1) as f64;
+
+    if is_new_bar {
+        if window.lookback_count == 0 {
+            // A value pushed by an UPDATE before the first bar is not a bar
+            new_window.buffer.clear();
+        } else if let Some(&current) = window.buffer.back() {
+            // Drop the trailing value (at x = 0) once the window is full
+            if window.buffer.len() == window.period as usize {
+                new_window.sum_y -= new_window.buffer.pop_front().unwrap_or_default();
+            }
+
+            // Every closed bar moves one step back, then the current bar is closed
+            new_window.sum_xy -= new_window.sum_y;
+            new_window.sum_y += current;
+            new_window.sum_xy += (last_x - 1.0) * current;
+
+            // Drift correction, once per window turnover
+            if window.lookback_count % window.period == 0 {
+                new_window.sum_y = new_window.buffer.iter().sum();
+                new_window.sum_xy = new_window
+                    .buffer
+                    .iter()
+                    .enumerate()
+                    .map(|(x, y)| x as f64 * y)
+                    .sum();
+            }
+        }
+
+        new_window.buffer.push_back(value);
+        new_window.lookback_count += 1;
+    } else if let Some(last) = new_window.buffer.back_mut() {
+        // UPDATE mode: replace the current bar, the sums don't include it
+        *last = value;
+    } else {
+        new_window.buffer.push_back(value);
+    }
+
+    // Warmup phase: need 'period' values
+    if new_window.lookback_count < window.period {
+        return (None, new_window);
+    }
+
+    let n = window.period as f64;
+    let sum_y = new_window.sum_y + value;
+    let sum_xy = new_window.sum_xy + last_x * value;
+    let divisor = n * window.sum_x2 - window.sum_x * window.sum_x;
+
+    let slope = (n * sum_xy - window.sum_x * sum_y) / divisor;
+    let intercept = (sum_y - slope * window.sum_x) / n;
+
+    (Some((slope, intercept)), new_window)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_linearreg_state_init(period: i32) -> Result<ResourceArc<LINEARREGState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for LINEARREG".to_string());
+    }
+
+    let state = LINEARREGState {
+        window: new_regression_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_linearreg_state_next(
+    state_arc: ResourceArc<LINEARREGState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<LINEARREGState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (fit, new_window) = regression_window_next(&state.window, value, is_new_bar);
+
+    // Value of the fitted line at the current bar
+    let last_x = (state.window.period - 1) as f64;
+    let linearreg = fit.map(|(slope, intercept)| intercept + slope * last_x);
+
+    let new_state = LINEARREGState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((linearreg, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_tsf_state_init(period: i32) -> Result<ResourceArc<TSFState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for TSF".to_string());
+    }
+
+    let state = TSFState {
+        window: new_regression_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_tsf_state_next(
+    state_arc: ResourceArc<TSFState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TSFState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let (fit, new_window) = regression_window_next(&state.window, value, is_new_bar);
+
+    // Value of the fitted line one bar after the current one
+    let next_x = state.window.period as f64;
+    let tsf = fit.map(|(slope, intercept)| intercept + slope * next_x);
+
+    let new_state = TSFState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((tsf, new_resource))
+}
+
+#[cfg(has_talib)]
+fn new_covariance_window(period: i32) -> CovarianceWindow {
+    CovarianceWindow {
+        period,
+        lookback_count: 0,
+        sums: PairSums::default(),
+        buffer: VecDeque::with_capacity(period as usize),
+    }
+}
+
+#[cfg(has_talib)]
+impl PairSums {
+    fn add(self, (x, y): (f64, f64)) -> Self {
+        PairSums {
+            x: self.x + x,
+            y: self.y + y,
+            xx: self.xx + x * x,
+            yy: self.yy + y * y,
+            xy: self.xy + x * y,
+        }
+    }
+
+    fn sub(self, (x, y): (f64, f64)) -> Self {
+        PairSums {
+            x: self.x - x,
+            y: self.y - y,
+            xx: self.xx - x * x,
+            yy: self.yy - y * y,
+            xy: self.xy - x * y,
+        }
+    }
+}
+
+/// Advances a rolling covariance window by one (x, y) pair
+///
+/// Returns the centered sums of the window `(Sxx, Syy, Sxy)`, i.e. `period` times
+/// the variances of x and y and their covariance, once it holds `period` pairs.
+/// The running sums exclude the current bar, so UPDATE mode just adds the
+/// replacing pair to them.
+#[cfg(has_talib)]
+fn covariance_window_next(
+    window: &CovarianceWindow,
+    pair: (f64, f64),
+    is_new_bar: bool,
+) -> (Option<(f64, f64, f64)>, CovarianceWindow) {
+    let mut new_window = window.clone();
+
+    if is_new_bar {
+        if window.lookback_count == 0 {
+            // A pair pushed by an UPDATE before the first bar is not a bar
+            new_window.buffer.clear();
+        } else if let Some(&current) = window.buffer.back() {
+            // The current bar is closed: move it into the running sums
+            new_window.sums = new_window.sums.add(current);
+
+            // Drop the trailing pair once the window is full
+            if window.buffer.len() == window.period as usize {
+                let trailing = new_window.buffer.pop_front().unwrap_or_default();
+                new_window.sums = new_window.sums.sub(trailing);
+            }
+
+            // Drift correction, once per window turnover
+            if window.lookback_count % window.period == 0 {
+                new_window.sums = new_window
+                    .buffer
+                    .iter()
+                    .fold(PairSums::default(), |sums, &pair| sums.add(pair));
+            }
+        }
+
+        new_window.buffer.push_back(pair);
+        new_window.lookback_count += 1;
+    } else if let Some(last) = new_window.buffer.back_mut() {
+        // UPDATE mode: replace the current bar, the sums don't include it
+        *last = pair;
+    } else {
+        new_window.buffer.push_back(pair);
+    }
+
+    // Warmup phase: need 'period' pairs
+    if new_window.lookback_count < window.period {
+        return (None, new_window);
+    }
+
+    let sums = new_window.sums.add(pair);
+
+    let n = window.period as f64;
+    let centered_xx = sums.xx - sums.x * sums.x / n;
+    let centered_yy = sums.yy - sums.y * sums.y / n;
+    let centered_xy = sums.xy - sums.x * sums.y / n;
+
+    (Some((centered_xx, centered_yy, centered_xy)), new_window)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_correl_state_init(period: i32) -> Result<ResourceArc<CORRELState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for CORREL".to_string());
+    }
+
+    let state = CORRELState {
+        window: new_covariance_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_correl_state_next(
+    state_arc: ResourceArc<CORRELState>,
+    x: Option<f64>,
+    y: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<CORRELState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (x, y) = match (x, y) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let (sums, new_window) = covariance_window_next(&state.window, (x, y), is_new_bar);
+
+    // Same threshold as TA-Lib, but a (nearly) constant series has no correlation
+    let correl = sums
+        .filter(|(xx, yy, _)| xx * yy >= 1e-8)
+        .map(|(xx, yy, xy)| xy / (xx * yy).sqrt());
+
+    let new_state = CORRELState { window: new_window };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((correl, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_beta_state_init(period: i32) -> Result<ResourceArc<BETAState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for BETA".to_string());
+    }
+
+    let state = BETAState {
+        lookback_count: 0,
+        current: None,
+        prev: None,
+        window: new_covariance_window(period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+/// Bar-to-bar return, 0 when the previous price is (nearly) zero as in TA-Lib
+#[cfg(has_talib)]
+fn beta_return(price: f64, prev_price: f64) -> f64 {
+    if prev_price.abs() < 1e-8 {
+        0.0
+    } else {
+        (price - prev_price) / prev_price
+    }
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn stats_beta_state_next(
+    state_arc: ResourceArc<BETAState>,
+    x: Option<f64>,
+    y: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<BETAState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (x, y) = match (x, y) {
+        (Some(x), Some(y)) => (x, y),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let (prev, lookback_count) = if is_new_bar {
+        // APPEND mode: the current bar becomes the previous one
+        let prev = if state.lookback_count == 0 {
+            None
+        } else {
+            state.current
+        };
+        (prev, state.lookback_count + 1)
+    } else {
+        // UPDATE mode: keep the previous bar
+        (state.prev, state.lookback_count)
+    };
+
+    // The first bar has no return: the window starts with the second one
+    let (sums, window) = match prev {
+        Some((prev_x, prev_y)) => {
+            let returns = (beta_return(x, prev_x), beta_return(y, prev_y));
+            covariance_window_next(&state.window, returns, is_new_bar)
+        }
+        None => (None, state.window.clone()),
+    };
+
+    // Same threshold as TA-Lib, but a (nearly) constant x has no beta
+    let period = state.window.period as f64;
+    let beta = sums
+        .filter(|(xx, _, _)| (period * xx).abs() >= 1e-8)
+        .map(|(xx, _, xy)| xy / xx);
+
+    let new_state = BETAState {
+        lookback_count,
+        current: Some((x, y)),
+        prev,
+        window,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((beta, new_resource))
+}
+
+// The rolling median does not use TA-Lib, so its state is always available
+#[rustler::nif]
+pub fn stats_median_state_init(period: i32) -> Result<ResourceArc<MedianState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for MEDIAN".to_string());
+    }
+
+    let state = MedianState {
+        period,
+        lookback_count: 0,
+        buffer: VecDeque::with_capacity(period as usize),
+        sorted: SortedWindow::default(),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[rustler::nif]
+pub fn stats_median_state_next(
+    state_arc: ResourceArc<MedianState>,
+    value: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MedianState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let value = match value {
+        Some(value) => value,
+        None => return Ok((None, state_arc)),
+    };
+
+    let mut buffer = state.buffer.clone();
+    let mut sorted = state.sorted.clone();
+    let mut lookback_count = state.lookback_count;
+
+    if is_new_bar {
+        if lookback_count == 0 {
+            // A value pushed by an UPDATE before the first bar is not a bar
+            buffer.clear();
+            sorted = SortedWindow::default();
+        } else if buffer.len() >= state.period as usize {
+            if let Some(oldest) = buffer.pop_front() {
+                sorted.remove(oldest);
+            }
+        }
+
+        buffer.push_back(value);
+        sorted.insert(value);
+        lookback_count += 1;
+    } else if let Some(last) = buffer.back_mut() {
+        // UPDATE mode: swap the current value in the sorted window
+        sorted.remove(*last);
+        sorted.insert(value);
+        *last = value;
+    } else {
+        buffer.push_back(value);
+        sorted.insert(value);
+    }
+
+    // Warmup phase: need 'period' values
+    let median = if lookback_count >= state.period {
+        sorted.median()
+    } else {
+        None
+    };
+
+    let new_state = MedianState {
+        period: state.period,
+        lookback_count,
+        buffer,
+        sorted,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((median, new_resource))
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_stddev_state_init(
+    _period: i32,
+    _nbdev: f64,
+) -> Result<ResourceArc<STDDEVState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "stats_stddev_state_init")]
+pub fn stats_stddev_state_init_with_recompute_interval(
+    _period: i32,
+    _nbdev: f64,
+    _recompute_interval: i32,
+) -> Result<ResourceArc<STDDEVState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_stddev_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<STDDEVState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_var_state_init(_period: i32, _nbdev: f64) -> Result<ResourceArc<VARState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif(name = "stats_var_state_init")]
+pub fn stats_var_state_init_with_recompute_interval(
+    _period: i32,
+    _nbdev: f64,
+    _recompute_interval: i32,
+) -> Result<ResourceArc<VARState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_var_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<VARState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_linearreg_state_init(_period: i32) -> Result<ResourceArc<LINEARREGState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_linearreg_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<LINEARREGState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_tsf_state_init(_period: i32) -> Result<ResourceArc<TSFState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_tsf_state_next(
+    _state: Term,
+    _value: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TSFState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_correl_state_init(_period: i32) -> Result<ResourceArc<CORRELState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_correl_state_next(
+    _state: Term,
+    _x: Option<f64>,
+    _y: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<CORRELState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_beta_state_init(_period: i32) -> Result<ResourceArc<BETAState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn stats_beta_state_next(
+    _state: Term,
+    _x: Option<f64>,
+    _y: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<BETAState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
@@ -0,0 +1,262 @@
+// Implementation when ta-lib is available
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_atr(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    calculate_atr(&high, &low, &close, period)
+}
+
+#[cfg(has_talib)]
+pub(crate) fn calculate_atr(
+    high: &[Option<f64>],
+    low: &[Option<f64>],
+    close: &[Option<f64>],
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::volatility_ffi::{TA_ATR_Lookback, TA_ATR};
+
+    if period < 2 {
+        return Err("ATR: period must be >= 2".to_string());
+    }
+
+    validate_inputs(
+        "ATR",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(high);
+    let clean_low = options_to_nan(low);
+    let clean_close = options_to_nan(close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_ATR_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_ATR(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "ATR");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "ATR")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_trange(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::volatility_ffi::{TA_TRANGE_Lookback, TA_TRANGE};
+
+    validate_inputs(
+        "TRANGE",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_TRANGE_Lookback() };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_TRANGE(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "TRANGE");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "TRANGE")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_natr(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::volatility_ffi::{TA_NATR_Lookback, TA_NATR};
+
+    if period < 2 {
+        return Err("NATR: period must be >= 2".to_string());
+    }
+
+    validate_inputs(
+        "NATR",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_NATR_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_NATR(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "NATR");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "NATR")?;
+
+    Ok(result)
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_atr(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("ATR: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_trange(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("TRANGE: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_natr(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("NATR: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
@@ -0,0 +1,8 @@
+// FFI declarations for TA-Lib volatility functions
+//
+// Re-exports the bindings generated from the TA-Lib headers (see ffi.rs).
+// Only compiled when ta-lib is available (has_talib cfg flag).
+
+pub use crate::ffi::{
+    TA_ATR_Lookback, TA_NATR_Lookback, TA_TRANGE_Lookback, TA_ATR, TA_NATR, TA_TRANGE,
+};
@@ -0,0 +1,347 @@
+use rustler::ResourceArc;
+#[cfg(not(has_talib))]
+use rustler::Term;
+
+/// State for ATR calculation
+pub struct ATRState {
+    period: i32,
+    lookback_count: i32,
+    current_atr: Option<f64>, // ATR of current bar (can change in UPDATE mode)
+    prev_atr: Option<f64>,    // ATR of previous bar (persisted in APPEND mode)
+    current_close: Option<f64>, // Close of current bar (can change in UPDATE mode)
+    prev_close: Option<f64>,  // Close of previous bar (persisted in APPEND mode)
+    buffer: Vec<f64>,         // True ranges collected during warmup
+}
+
+/// State for NATR calculation: the ATR as a percentage of the close
+pub struct NATRState {
+    atr: ATRState,
+}
+
+/// State for TRANGE calculation
+pub struct TRANGEState {
+    current_close: Option<f64>, // Close of current bar (can change in UPDATE mode)
+    prev_close: Option<f64>,    // Close of previous bar (persisted in APPEND mode)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_atr_state_init(period: i32) -> Result<ResourceArc<ATRState>, String> {
+    let state = new_atr_state(period)?;
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+pub(crate) fn new_atr_state(period: i32) -> Result<ATRState, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for ATR".to_string());
+    }
+
+    Ok(ATRState {
+        period,
+        lookback_count: 0,
+        current_atr: None,
+        prev_atr: None,
+        current_close: None,
+        prev_close: None,
+        buffer: Vec::new(),
+    })
+}
+
+/// True range of a bar given the close of the previous one
+#[cfg(has_talib)]
+#[inline]
+pub(crate) fn true_range(high: f64, low: f64, prev_close: f64) -> f64 {
+    let range = high - low;
+    let high_gap = (high - prev_close).abs();
+    let low_gap = (low - prev_close).abs();
+    range.max(high_gap).max(low_gap)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_atr_state_next(
+    state_arc: ResourceArc<ATRState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ATRState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => (high, low, close),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let (atr, new_state) = step_atr(state, high, low, close, is_new_bar);
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((atr, new_resource))
+}
+
+/// Advances an ATR state by one bar
+#[cfg(has_talib)]
+pub(crate) fn step_atr(
+    state: &ATRState,
+    high: f64,
+    low: f64,
+    close: f64,
+    is_new_bar: bool,
+) -> (Option<f64>, ATRState) {
+    let new_lookback = if is_new_bar {
+        state.lookback_count + 1
+    } else {
+        state.lookback_count
+    };
+
+    // The true range always uses the close of the previous bar: in UPDATE mode
+    // it is the persisted one, never the close of the bar being updated
+    let new_prev_close = if is_new_bar {
+        state.current_close
+    } else {
+        state.prev_close
+    };
+
+    // The first bar has no previous close, hence no true range
+    let tr = new_prev_close.map(|prev_close| true_range(high, low, prev_close));
+
+    // Keep the true ranges until both ATRs are populated (needed to reseed in UPDATE mode)
+    let new_buffer = if new_lookback <= state.period || state.prev_atr.is_none() {
+        let mut buf = state.buffer.clone();
+        if let Some(tr) = tr {
+            if is_new_bar || buf.is_empty() {
+                buf.push(tr);
+            } else {
+                let last_idx = buf.len() - 1;
+                buf[last_idx] = tr;
+            }
+        }
+        buf
+    } else {
+        // After warmup AND both ATRs calculated - clear buffer to save memory
+        Vec::new()
+    };
+
+    // Warmup phase: need 'period' true ranges (period + 1 bars) before the first ATR
+    let tr = match tr {
+        Some(tr) if new_lookback > state.period => tr,
+        _ => {
+            let new_state = ATRState {
+                period: state.period,
+                lookback_count: new_lookback,
+                current_atr: state.current_atr,
+                prev_atr: state.prev_atr,
+                current_close: Some(close),
+                prev_close: new_prev_close,
+                buffer: new_buffer,
+            };
+            return (None, new_state);
+        }
+    };
+
+    // Base ATR: the one of the bar before the one being computed
+    let base_atr = if is_new_bar {
+        state.current_atr
+    } else {
+        state.prev_atr
+    };
+
+    let new_atr = match base_atr {
+        // Wilder smoothing: ATR = (prev_atr * (period - 1) + TR) / period
+        Some(prev) => {
+            let period = state.period as f64;
+            (prev * (period - 1.0) + tr) / period
+        }
+        // First ATR: SMA of the last 'period' true ranges
+        None => {
+            let start = new_buffer.len().saturating_sub(state.period as usize);
+            let sum: f64 = new_buffer[start..].iter().sum();
+            sum / state.period as f64
+        }
+    };
+
+    let new_prev_atr = if is_new_bar {
+        // In APPEND: current_atr becomes prev_atr for next iteration
+        state.current_atr
+    } else {
+        // In UPDATE: prev_atr stays the same
+        state.prev_atr
+    };
+
+    let new_state = ATRState {
+        period: state.period,
+        lookback_count: new_lookback,
+        current_atr: Some(new_atr),
+        prev_atr: new_prev_atr,
+        current_close: Some(close),
+        prev_close: new_prev_close,
+        buffer: new_buffer,
+    };
+
+    (Some(new_atr), new_state)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_natr_state_init(period: i32) -> Result<ResourceArc<NATRState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for NATR".to_string());
+    }
+
+    let state = NATRState {
+        atr: new_atr_state(period)?,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_natr_state_next(
+    state_arc: ResourceArc<NATRState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<NATRState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input (or a NaN close): return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) if !close.is_nan() => (high, low, close),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let (atr, new_atr) = step_atr(&state.atr, high, low, close, is_new_bar);
+
+    // TA-Lib gives 0 for a (nearly) zero close, where the NATR is undefined
+    let natr = atr
+        .filter(|_| close.abs() >= 1e-8)
+        .map(|atr| (atr / close) * 100.0);
+
+    let new_state = NATRState { atr: new_atr };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((natr, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_trange_state_init() -> Result<ResourceArc<TRANGEState>, String> {
+    let state = TRANGEState {
+        current_close: None,
+        prev_close: None,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volatility_trange_state_next(
+    state_arc: ResourceArc<TRANGEState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TRANGEState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close) = match (high, low, close) {
+        (Some(high), Some(low), Some(close)) => (high, low, close),
+        _ => return Ok((None, state_arc)),
+    };
+
+    // In UPDATE mode the previous close is fixed, only the current bar changes
+    let new_prev_close = if is_new_bar {
+        state.current_close
+    } else {
+        state.prev_close
+    };
+
+    // The first bar has no previous close, hence no true range
+    let tr = new_prev_close.map(|prev_close| true_range(high, low, prev_close));
+
+    let new_state = TRANGEState {
+        current_close: Some(close),
+        prev_close: new_prev_close,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((tr, new_resource))
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_atr_state_init(_period: i32) -> Result<ResourceArc<ATRState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_atr_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ATRState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_natr_state_init(_period: i32) -> Result<ResourceArc<NATRState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_natr_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<NATRState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_trange_state_init() -> Result<ResourceArc<TRANGEState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volatility_trange_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<TRANGEState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
@@ -0,0 +1,338 @@
+// Implementation when ta-lib is available
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_obv(
+    close: Vec<Option<f64>>,
+    volume: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::volume_ffi::{TA_OBV_Lookback, TA_OBV};
+
+    validate_inputs("OBV", &[("close", close.len()), ("volume", volume.len())])?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_close = options_to_nan(&close);
+    let clean_volume = options_to_nan(&volume);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_close, &clean_volume]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_OBV_Lookback() };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_OBV(
+            0,
+            endidx,
+            clean_close[begidx..].as_ptr(),
+            clean_volume[begidx..].as_ptr(),
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "OBV");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "OBV")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_ad(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    volume: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::volume_ffi::{TA_AD_Lookback, TA_AD};
+
+    validate_inputs(
+        "AD",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+            ("volume", volume.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let clean_volume = options_to_nan(&volume);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close, &clean_volume]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_AD_Lookback() };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_AD(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            clean_volume[begidx..].as_ptr(),
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "AD");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "AD")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_adosc(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    volume: Vec<Option<f64>>,
+    fast_period: i32,
+    slow_period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::volume_ffi::{TA_ADOSC_Lookback, TA_ADOSC};
+
+    if fast_period < 2 || slow_period < 2 {
+        return Err("ADOSC: fast_period and slow_period must be >= 2".to_string());
+    }
+
+    validate_inputs(
+        "ADOSC",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+            ("volume", volume.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let clean_volume = options_to_nan(&volume);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close, &clean_volume]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_ADOSC_Lookback(fast_period, slow_period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_ADOSC(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            clean_volume[begidx..].as_ptr(),
+            fast_period,
+            slow_period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "ADOSC");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "ADOSC")?;
+
+    Ok(result)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_mfi(
+    high: Vec<Option<f64>>,
+    low: Vec<Option<f64>>,
+    close: Vec<Option<f64>>,
+    volume: Vec<Option<f64>>,
+    period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    use crate::helpers::{build_result, check_begidx_multi, options_to_nan, validate_inputs};
+    use crate::volume_ffi::{TA_MFI_Lookback, TA_MFI};
+
+    if period < 2 {
+        return Err("MFI: period must be >= 2".to_string());
+    }
+
+    validate_inputs(
+        "MFI",
+        &[
+            ("high", high.len()),
+            ("low", low.len()),
+            ("close", close.len()),
+            ("volume", volume.len()),
+        ],
+    )?;
+
+    if close.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let clean_high = options_to_nan(&high);
+    let clean_low = options_to_nan(&low);
+    let clean_close = options_to_nan(&close);
+    let clean_volume = options_to_nan(&volume);
+    let length = clean_close.len();
+
+    // Skip leading bars where any of the inputs is NaN
+    let begidx = match check_begidx_multi(&[&clean_high, &clean_low, &clean_close, &clean_volume]) {
+        Some(idx) => idx,
+        None => return Ok(vec![None; length]),
+    };
+    let endidx = (length - begidx - 1) as i32;
+
+    let lookback = unsafe { TA_MFI_Lookback(period) };
+    let total_lookback = begidx as i32 + lookback;
+
+    if total_lookback >= length as i32 {
+        return Ok(vec![None; length]);
+    }
+
+    let mut out_beg_idx: i32 = 0;
+    let mut out_nb_element: i32 = 0;
+    let valid_data_len = length - begidx;
+    let mut out_real: Vec<f64> = vec![0.0; valid_data_len];
+
+    let ret_code = unsafe {
+        TA_MFI(
+            0,
+            endidx,
+            clean_high[begidx..].as_ptr(),
+            clean_low[begidx..].as_ptr(),
+            clean_close[begidx..].as_ptr(),
+            clean_volume[begidx..].as_ptr(),
+            period,
+            &mut out_beg_idx as *mut i32,
+            &mut out_nb_element as *mut i32,
+            out_real.as_mut_ptr(),
+        )
+    };
+
+    check_ret_code!(ret_code, "MFI");
+
+    // TA-Lib reports where its output starts, relative to the sliced input
+    let first_idx = begidx as i32 + out_beg_idx;
+    let result = build_result(first_idx, out_nb_element, &out_real, "MFI")?;
+
+    Ok(result)
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_obv(
+    _close: Vec<Option<f64>>,
+    _volume: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("OBV: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_ad(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _volume: Vec<Option<f64>>,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("AD: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_adosc(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _volume: Vec<Option<f64>>,
+    _fast_period: i32,
+    _slow_period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("ADOSC: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_mfi(
+    _high: Vec<Option<f64>>,
+    _low: Vec<Option<f64>>,
+    _close: Vec<Option<f64>>,
+    _volume: Vec<Option<f64>>,
+    _period: i32,
+) -> Result<Vec<Option<f64>>, String> {
+    Err("MFI: TA-Lib not available. Please use the Elixir backend.".to_string())
+}
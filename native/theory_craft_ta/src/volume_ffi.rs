@@ -0,0 +1,9 @@
+// FFI declarations for TA-Lib volume functions
+//
+// Re-exports the bindings generated from the TA-Lib headers (see ffi.rs).
+// Only compiled when ta-lib is available (has_talib cfg flag).
+
+pub use crate::ffi::{
+    TA_ADOSC_Lookback, TA_AD_Lookback, TA_MFI_Lookback, TA_OBV_Lookback, TA_AD, TA_ADOSC, TA_MFI,
+    TA_OBV,
+};
@@ -0,0 +1,493 @@
+use crate::overlap_state::EMAState;
+#[cfg(has_talib)]
+use crate::overlap_state::{new_ema_state, step_ema, SeedMode, DEFAULT_RECOMPUTE_INTERVAL};
+use rustler::ResourceArc;
+#[cfg(not(has_talib))]
+use rustler::Term;
+use std::collections::VecDeque;
+
+/// State for OBV calculation
+pub struct OBVState {
+    lookback_count: i32,
+    current_close: Option<f64>, // Close of current bar (can change in UPDATE mode)
+    current_obv: Option<f64>,   // OBV of current bar (can change in UPDATE mode)
+    prev_close: Option<f64>,    // Close of previous bar (persisted in APPEND mode)
+    prev_obv: Option<f64>,      // OBV of previous bar (persisted in APPEND mode)
+}
+
+/// Running Accumulation/Distribution line shared by AD and ADOSC
+#[derive(Clone)]
+struct ADLine {
+    lookback_count: i32,
+    current_ad: Option<f64>, // A/D of current bar (can change in UPDATE mode)
+    prev_ad: Option<f64>,    // A/D of previous bar (persisted in APPEND mode)
+}
+
+/// State for AD calculation
+pub struct ADState {
+    line: ADLine,
+}
+
+/// State for ADOSC calculation
+pub struct ADOSCState {
+    slowest_period: i32,
+    line: ADLine,
+    fast_ema_state: EMAState, // EMAs of the A/D line, seeded with its first value
+    slow_ema_state: EMAState,
+}
+
+/// Positive and negative money flow of a bar (at most one of them is non-zero)
+type MoneyFlow = (f64, f64);
+
+/// State for MFI calculation
+pub struct MFIState {
+    period: i32,
+    lookback_count: i32,
+    prev_typical: Option<f64>,       // Typical price of the last closed bar
+    current_typical: f64,            // Typical price of current bar (can change in UPDATE mode)
+    current_flow: Option<MoneyFlow>, // Flow of current bar, None without a previous typical price
+    flows: VecDeque<MoneyFlow>,      // Flows of the closed bars in the window
+    sums: MoneyFlow,                 // Sums of the window, current bar excluded
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_obv_state_init() -> Result<ResourceArc<OBVState>, String> {
+    let state = OBVState {
+        lookback_count: 0,
+        current_close: None,
+        current_obv: None,
+        prev_close: None,
+        prev_obv: None,
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_obv_state_next(
+    state_arc: ResourceArc<OBVState>,
+    close: Option<f64>,
+    volume: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<OBVState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (close, volume) = match (close, volume) {
+        (Some(close), Some(volume)) => (close, volume),
+        _ => return Ok((None, state_arc)),
+    };
+
+    // In APPEND mode the current bar becomes the previous one, in UPDATE mode the
+    // total up to the previous bar is fixed. A value set by an UPDATE before the
+    // first bar is not a bar and is dropped.
+    let (new_lookback, new_prev_close, new_prev_obv) = if !is_new_bar {
+        (state.lookback_count, state.prev_close, state.prev_obv)
+    } else if state.lookback_count == 0 {
+        (1, None, None)
+    } else {
+        (
+            state.lookback_count + 1,
+            state.current_close,
+            state.current_obv,
+        )
+    };
+
+    // Same as TA-Lib: the first bar starts the total with its own volume
+    let obv = match (new_prev_close, new_prev_obv) {
+        (Some(prev_close), Some(prev_obv)) if close > prev_close => prev_obv + volume,
+        (Some(prev_close), Some(prev_obv)) if close < prev_close => prev_obv - volume,
+        (Some(_), Some(prev_obv)) => prev_obv,
+        _ => volume,
+    };
+
+    let new_state = OBVState {
+        lookback_count: new_lookback,
+        current_close: Some(close),
+        current_obv: Some(obv),
+        prev_close: new_prev_close,
+        prev_obv: new_prev_obv,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((Some(obv), new_resource))
+}
+
+#[cfg(has_talib)]
+fn new_ad_line() -> ADLine {
+    ADLine {
+        lookback_count: 0,
+        current_ad: None,
+        prev_ad: None,
+    }
+}
+
+/// Advances an A/D line by one bar
+///
+/// In APPEND mode the current total becomes the committed one, in UPDATE mode
+/// only the contribution of the forming bar is recomputed on top of the total
+/// committed before it. A value set by an UPDATE before the first bar is not a
+/// bar and is dropped.
+#[cfg(has_talib)]
+fn ad_line_next(
+    line: &ADLine,
+    high: f64,
+    low: f64,
+    close: f64,
+    volume: f64,
+    is_new_bar: bool,
+) -> (f64, ADLine) {
+    let (new_lookback, new_prev_ad) = if !is_new_bar {
+        (line.lookback_count, line.prev_ad)
+    } else if line.lookback_count == 0 {
+        (1, None)
+    } else {
+        (line.lookback_count + 1, line.current_ad)
+    };
+
+    // Same as TA-Lib: a bar without range doesn't move the line
+    let range = high - low;
+    let money_flow = if range > 0.0 {
+        ((close - low) - (high - close)) / range * volume
+    } else {
+        0.0
+    };
+    let ad = new_prev_ad.unwrap_or(0.0) + money_flow;
+
+    let new_line = ADLine {
+        lookback_count: new_lookback,
+        current_ad: Some(ad),
+        prev_ad: new_prev_ad,
+    };
+
+    (ad, new_line)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_ad_state_init() -> Result<ResourceArc<ADState>, String> {
+    let state = ADState {
+        line: new_ad_line(),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_ad_state_next(
+    state_arc: ResourceArc<ADState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    volume: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ADState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close, volume) = match (high, low, close, volume) {
+        (Some(high), Some(low), Some(close), Some(volume)) => (high, low, close, volume),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let (ad, new_line) = ad_line_next(&state.line, high, low, close, volume, is_new_bar);
+
+    let new_state = ADState { line: new_line };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((Some(ad), new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_adosc_state_init(
+    fast_period: i32,
+    slow_period: i32,
+) -> Result<ResourceArc<ADOSCState>, String> {
+    if fast_period < 2 || slow_period < 2 {
+        return Err("Invalid period: fast and slow periods must be >= 2 for ADOSC".to_string());
+    }
+
+    // TA-Lib seeds both EMAs with the first A/D value rather than with an SMA
+    let new_ema = |period| EMAState {
+        seed_mode: SeedMode::First,
+        ..new_ema_state(period)
+    };
+
+    let state = ADOSCState {
+        slowest_period: fast_period.max(slow_period),
+        line: new_ad_line(),
+        fast_ema_state: new_ema(fast_period),
+        slow_ema_state: new_ema(slow_period),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_adosc_state_next(
+    state_arc: ResourceArc<ADOSCState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    volume: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ADOSCState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close, volume) = match (high, low, close, volume) {
+        (Some(high), Some(low), Some(close), Some(volume)) => (high, low, close, volume),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let (ad, new_line) = ad_line_next(&state.line, high, low, close, volume, is_new_bar);
+    let (fast_ema, new_fast_ema_state) = step_ema(&state.fast_ema_state, ad, is_new_bar);
+    let (slow_ema, new_slow_ema_state) = step_ema(&state.slow_ema_state, ad, is_new_bar);
+
+    // TA-Lib's ADOSC lookback is the warmup of the slowest EMA, even though
+    // both EMAs are defined from the first bar
+    let adosc = match (fast_ema, slow_ema) {
+        (Some(fast), Some(slow)) if new_line.lookback_count >= state.slowest_period => {
+            Some(fast - slow)
+        }
+        _ => None,
+    };
+
+    let new_state = ADOSCState {
+        slowest_period: state.slowest_period,
+        line: new_line,
+        fast_ema_state: new_fast_ema_state,
+        slow_ema_state: new_slow_ema_state,
+    };
+
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((adosc, new_resource))
+}
+
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_mfi_state_init(period: i32) -> Result<ResourceArc<MFIState>, String> {
+    if period < 2 {
+        return Err("Invalid period: must be >= 2 for MFI".to_string());
+    }
+
+    let state = MFIState {
+        period,
+        lookback_count: 0,
+        prev_typical: None,
+        current_typical: 0.0,
+        current_flow: None,
+        flows: VecDeque::with_capacity(period as usize),
+        sums: (0.0, 0.0),
+    };
+
+    let resource = ResourceArc::new(state);
+    Ok(resource)
+}
+
+/// Advances the Money Flow Index by one bar
+///
+/// The typical price of a bar, weighted by its volume, is a positive flow when
+/// it rose since the previous bar and a negative one when it fell. The window
+/// sums exclude the current bar, whose flow is recomputed against the same
+/// previous typical price in UPDATE mode.
+#[cfg(has_talib)]
+#[rustler::nif]
+pub fn volume_mfi_state_next(
+    state_arc: ResourceArc<MFIState>,
+    high: Option<f64>,
+    low: Option<f64>,
+    close: Option<f64>,
+    volume: Option<f64>,
+    is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MFIState>), String> {
+    let state = &*state_arc;
+
+    // Handle nil input: return nil without modifying state
+    let (high, low, close, volume) = match (high, low, close, volume) {
+        (Some(high), Some(low), Some(close), Some(volume)) => (high, low, close, volume),
+        _ => return Ok((None, state_arc)),
+    };
+
+    let mut lookback_count = state.lookback_count;
+    let mut prev_typical = state.prev_typical;
+    let mut flows = state.flows.clone();
+    let mut sums = state.sums;
+
+    // A bar given by an UPDATE before the first bar is not a bar
+    if is_new_bar && lookback_count > 0 {
+        // The current bar is closed: move its flow into the window sums
+        if let Some(flow) = state.current_flow {
+            flows.push_back(flow);
+            sums.0 += flow.0;
+            sums.1 += flow.1;
+
+            // The window holds 'period - 1' closed bars besides the current one
+            if flows.len() >= state.period as usize {
+                let (positive, negative) = flows.pop_front().unwrap_or_default();
+                sums.0 -= positive;
+                sums.1 -= negative;
+            }
+
+            // Drift correction, once every DEFAULT_RECOMPUTE_INTERVAL bars
+            if lookback_count % DEFAULT_RECOMPUTE_INTERVAL == 0 {
+                sums = flows
+                    .iter()
+                    .fold((0.0, 0.0), |acc, f| (acc.0 + f.0, acc.1 + f.1));
+            }
+        }
+
+        prev_typical = Some(state.current_typical);
+    }
+
+    if is_new_bar {
+        lookback_count += 1;
+    }
+
+    let typical = (high + low + close) / 3.0;
+    let current_flow = prev_typical.map(|prev_typical| {
+        let money_flow = typical * volume;
+
+        if typical < prev_typical {
+            (0.0, money_flow)
+        } else if typical > prev_typical {
+            (money_flow, 0.0)
+        } else {
+            (0.0, 0.0)
+        }
+    });
+
+    // Warmup phase: need 'period' flows, i.e. 'period + 1' bars. As in TA-Lib,
+    // MFI = 100 × positive / (positive + negative), which is defined without any
+    // negative flow, and a window with (almost) no flow at all gives 0
+    let mfi = match current_flow {
+        Some(flow) if lookback_count > state.period => {
+            let positive = sums.0 + flow.0;
+            let total = positive + sums.1 + flow.1;
+
+            if total < 1.0 {
+                Some(0.0)
+            } else {
+                Some(100.0 * (positive / total))
+            }
+        }
+        _ => None,
+    };
+
+    let new_state = MFIState {
+        period: state.period,
+        lookback_count,
+        prev_typical,
+        current_typical: typical,
+        current_flow,
+        flows,
+        sums,
+    };
+    let new_resource = ResourceArc::new(new_state);
+
+    Ok((mfi, new_resource))
+}
+
+// Stub implementations when ta-lib is not available
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_obv_state_init() -> Result<ResourceArc<OBVState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_obv_state_next(
+    _state: Term,
+    _close: Option<f64>,
+    _volume: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<OBVState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_ad_state_init() -> Result<ResourceArc<ADState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_ad_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _volume: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ADState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_adosc_state_init(
+    _fast_period: i32,
+    _slow_period: i32,
+) -> Result<ResourceArc<ADOSCState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_adosc_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _volume: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<ADOSCState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_mfi_state_init(_period: i32) -> Result<ResourceArc<MFIState>, String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
+
+#[cfg(not(has_talib))]
+#[rustler::nif]
+pub fn volume_mfi_state_next(
+    _state: Term,
+    _high: Option<f64>,
+    _low: Option<f64>,
+    _close: Option<f64>,
+    _volume: Option<f64>,
+    _is_new_bar: bool,
+) -> Result<(Option<f64>, ResourceArc<MFIState>), String> {
+    Err(
+        "TA-Lib not available. Please build ta-lib using tools/build_talib.cmd or use the Elixir backend."
+    .to_string())
+}
@@ -0,0 +1,44 @@
+defmodule TheoryCraftTA.Assertions do
+  @moduledoc """
+  Assertions shared by the indicator tests.
+  """
+
+  import ExUnit.Assertions
+
+  @doc """
+  Asserts that two series have the same length and the same values, within `delta`.
+
+  A `nil` in `expected` (warmup or missing value) must be `nil` in `result` too.
+  """
+  @spec assert_values_in_delta([float() | nil], [float() | nil], float()) :: :ok
+  def assert_values_in_delta(result, expected, delta \\ 1.0e-9) do
+    assert length(result) == length(expected)
+
+    for {value, expected_value} <- Enum.zip(result, expected) do
+      case expected_value do
+        nil -> assert value == nil
+        _ -> assert_in_delta(value, expected_value, delta)
+      end
+    end
+
+    :ok
+  end
+
+  @doc """
+  Asserts that a state value matches its batch counterpart: both `nil`, or equal within
+  `delta`.
+  """
+  @spec assert_same_value(float() | nil, float() | nil, float()) :: :ok
+  def assert_same_value(value, expected, delta \\ 1.0e-9)
+
+  def assert_same_value(nil, nil, _delta), do: :ok
+
+  def assert_same_value(value, expected, delta) when is_float(value) and is_float(expected) do
+    assert_in_delta(value, expected, delta)
+    :ok
+  end
+
+  def assert_same_value(_value, _expected, _delta) do
+    flunk("Mismatch between state and batch")
+  end
+end
@@ -0,0 +1,84 @@
+defmodule TheoryCraftTA.BatchTest do
+  use ExUnit.Case, async: true
+
+  alias TheoryCraftTA.Native
+
+  @ohlc %{
+    open: [11.8, 11.6, 12.3, 11.9, 12.8, 13.2, 12.6, 13.6],
+    high: [12.0, 12.5, 12.2, 13.0, 13.4, 13.1, 13.8, 14.2],
+    low: [11.0, 11.6, 11.4, 12.1, 12.6, 12.4, 12.9, 13.3],
+    close: [11.5, 12.3, 11.9, 12.8, 13.2, 12.6, 13.6, 13.9]
+  }
+
+  describe "compute_batch/2" do
+    test "computes each spec like its own batch function, in order" do
+      specs = [
+        {:sma, %{period: 3, source: :close}},
+        {:ema, %{period: 4, source: :high}},
+        {:rsi, %{period: 3, source: :close}},
+        {:atr, %{period: 3}},
+        {:wma, %{period: 2, source: :open}}
+      ]
+
+      assert Native.compute_batch(@ohlc, specs) == [
+               Native.overlap_sma(@ohlc.close, 3),
+               Native.overlap_ema(@ohlc.high, 4),
+               Native.momentum_rsi(@ohlc.close, 3),
+               Native.volatility_atr(@ohlc.high, @ohlc.low, @ohlc.close, 3),
+               Native.overlap_wma(@ohlc.open, 2)
+             ]
+    end
+
+    test "returns an error for an invalid spec without failing the others" do
+      specs = [{:sma, %{period: 1, source: :close}}, {:sma, %{period: 2, source: :low}}]
+
+      assert [{:error, reason}, {:ok, sma}] = Native.compute_batch(@ohlc, specs)
+      assert reason =~ "period must be >= 2"
+      assert {:ok, sma} == Native.overlap_sma(@ohlc.low, 2)
+    end
+
+    test "returns an error for mismatched series" do
+      ohlc = %{@ohlc | low: Enum.drop(@ohlc.low, 1)}
+
+      assert [{:error, reason}] = Native.compute_batch(ohlc, [{:atr, %{period: 3}}])
+      assert reason == "ATR: high/low/close length mismatch (8/7/8)"
+    end
+
+    test "returns empty for no specs" do
+      assert [] = Native.compute_batch(@ohlc, [])
+    end
+
+    test "raises for an unknown indicator" do
+      assert_raise ArgumentError, fn ->
+        Native.compute_batch(@ohlc, [{:unknown, %{period: 3}}])
+      end
+    end
+  end
+
+  describe "max_lookback/1" do
+    test "returns the largest lookback of the specs" do
+      specs = [{:sma, %{period: 200, source: :close}}, {:ema, %{period: 12, source: :close}}]
+      assert {:ok, 199} = Native.max_lookback(specs)
+    end
+
+    test "is the index of the first value of every spec" do
+      specs = [{:sma, %{period: 3, source: :close}}, {:atr, %{period: 3}}]
+      assert {:ok, 3} = Native.max_lookback(specs)
+
+      for {:ok, values} <- Native.compute_batch(@ohlc, specs) do
+        assert values |> Enum.drop(3) |> Enum.all?(&is_float/1)
+      end
+    end
+
+    test "returns 0 for no specs" do
+      assert {:ok, 0} = Native.max_lookback([])
+    end
+
+    test "returns an error for an invalid spec" do
+      specs = [{:sma, %{period: 3, source: :close}}, {:rsi, %{period: 1, source: :close}}]
+
+      assert {:error, reason} = Native.max_lookback(specs)
+      assert reason == "RSI: period must be >= 2"
+    end
+  end
+end
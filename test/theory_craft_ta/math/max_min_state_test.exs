@@ -0,0 +1,223 @@
+defmodule TheoryCraftTA.Math.MAXMINStateTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Math.{MAX, MIN}
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Math.MAX
+  doctest TheoryCraftTA.Math.MIN
+
+  @data [3.0, 1.0, 4.0, 1.5, 5.0, 9.0, 2.0, 6.0]
+
+  ## Batch calculation tests
+
+  describe "math_max/2 and math_min/2" do
+    test "return the rolling max and min once the window is full" do
+      assert {:ok, [nil, nil, 4.0, 4.0, 5.0, 9.0, 9.0, 9.0]} = Native.math_max(@data, 3)
+      assert {:ok, [nil, nil, 1.0, 1.0, 1.5, 1.5, 2.0, 2.0]} = Native.math_min(@data, 3)
+    end
+
+    test "return error for period < 2" do
+      assert {:error, reason} = Native.math_max(@data, 1)
+      assert reason =~ "period must be >= 2"
+
+      assert {:error, reason} = Native.math_min(@data, 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "return empty for empty input" do
+      assert {:ok, []} = Native.math_max([], 3)
+      assert {:ok, []} = Native.math_min([], 3)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "math_max_state_init/1 and math_min_state_init/1" do
+    test "return error for period < 2" do
+      assert {:error, reason} = Native.math_max_state_init(1)
+      assert reason =~ "Invalid period"
+
+      assert {:error, reason} = Native.math_min_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests
+
+  describe "math_max_state_next/3 and math_min_state_next/3" do
+    test "return the rolling max and min once the window is full" do
+      data = [3.0, 1.0, 4.0, 1.5, 5.0, 9.0, 2.0, 6.0]
+
+      assert stream(:max, data, 3) == [nil, nil, 4.0, 4.0, 5.0, 9.0, 9.0, 9.0]
+      assert stream(:min, data, 3) == [nil, nil, 1.0, 1.0, 1.5, 1.5, 2.0, 2.0]
+    end
+
+    test "keep a tied extremum until its last occurrence leaves the window" do
+      data = [5.0, 1.0, 5.0, 1.0, 2.0, 3.0]
+
+      assert stream(:max, data, 3) == [nil, nil, 5.0, 5.0, 5.0, 3.0]
+      assert stream(:min, data, 3) == [nil, nil, 1.0, 1.0, 1.0, 1.0]
+    end
+
+    test "UPDATE replaces the value of the current bar" do
+      {:ok, state} = Native.math_max_state_init(2)
+      {:ok, {nil, state}} = Native.math_max_state_next(state, 5.0, true)
+      {:ok, {5.0, state}} = Native.math_max_state_next(state, 3.0, true)
+
+      # The current bar becomes the max, then drops back below the previous one
+      assert {:ok, {7.0, state}} = Native.math_max_state_next(state, 7.0, false)
+      assert {:ok, {5.0, state}} = Native.math_max_state_next(state, 4.0, false)
+
+      # The updated value is the one kept once the bar is closed
+      assert {:ok, {4.0, _state}} = Native.math_max_state_next(state, 1.0, true)
+    end
+
+    test "handle nil values without modifying the state" do
+      {:ok, state} = Native.math_min_state_init(2)
+      {:ok, {nil, state}} = Native.math_min_state_next(state, 5.0, true)
+
+      assert {:ok, {nil, ^state}} = Native.math_min_state_next(state, nil, true)
+      assert {:ok, {3.0, _state}} = Native.math_min_state_next(state, 3.0, true)
+    end
+  end
+
+  ## Public API tests
+
+  describe "max/2 and min/2" do
+    test "match the native calculation" do
+      assert MAX.max(@data, 3) == Native.math_max(@data, 3)
+      assert MIN.min(@data, 3) == Native.math_min(@data, 3)
+    end
+
+    test "maintain DataSeries type in output" do
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = MAX.max(ds, 3)
+      assert DataSeries.values(result) == [9.0, 9.0, 9.0, 5.0, 4.0, 4.0, nil, nil]
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = MAX.init(period: 1, data: "eurusd_m1", name: "max")
+      assert reason =~ "Invalid period"
+
+      assert {:error, reason} = MIN.init(period: 1, data: "eurusd_m1", name: "min")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, max_batch} = MAX.max(@data, 3)
+      {:ok, min_batch} = MIN.min(@data, 3)
+
+      for {module, batch_result} <- [{MAX, max_batch}, {MIN, min_batch}] do
+        {:ok, state} = module.init(period: 3, data: "eurusd_m1", name: "extremum")
+
+        {stream_result, _state} =
+          Enum.map_reduce(@data, state, fn value, st ->
+            {:ok, result, new_st} = module.next(close_event(value, true), st)
+            {result.value, new_st}
+          end)
+
+        assert stream_result == batch_result
+      end
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = MAX.init(period: 3, data: "eurusd_m1", name: "max")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = MAX.next(close_event(value, true), st)
+          new_st
+        end)
+
+      assert {:ok, %{value: 100.0}, state} = MAX.next(close_event(100.0, false), state)
+      assert {:ok, %{value: 9.0}, _state} = MAX.next(close_event(1.0, false), state)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches a naive window" do
+    property "APPEND mode matches the naive rolling max and min" do
+      check all(
+              data <- list_of(values(), min_length: 1, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        assert stream(:max, data, period) == naive(&Enum.max/1, data, period)
+        assert stream(:min, data, period) == naive(&Enum.min/1, data, period)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(values(), min_length: 20, max_length: 100),
+              period <- integer(2..20),
+              updates <- list_of(values(), min_length: 1, max_length: 5)
+            ) do
+        for {kind, aggregate} <- [max: &Enum.max/1, min: &Enum.min/1] do
+          {init, next} = functions(kind)
+          {:ok, state} = init.(period)
+
+          state =
+            Enum.reduce(data, state, fn value, st ->
+              {:ok, {_result, new_st}} = next.(st, value, true)
+              new_st
+            end)
+
+          Enum.reduce(updates, {state, data}, fn value, {st, current_data} ->
+            {:ok, {result, new_st}} = next.(st, value, false)
+
+            updated_data = List.replace_at(current_data, -1, value)
+            assert result == List.last(naive(aggregate, updated_data, period))
+
+            {new_st, updated_data}
+          end)
+        end
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp stream(kind, data, period) do
+    {init, next} = functions(kind)
+    {:ok, state} = init.(period)
+
+    {results, _state} =
+      Enum.map_reduce(data, state, fn value, st ->
+        {:ok, {result, new_st}} = next.(st, value, true)
+        {result, new_st}
+      end)
+
+    results
+  end
+
+  defp functions(:max), do: {&Native.math_max_state_init/1, &Native.math_max_state_next/3}
+  defp functions(:min), do: {&Native.math_min_state_init/1, &Native.math_min_state_next/3}
+
+  defp naive(aggregate, data, period) do
+    data
+    |> Enum.with_index()
+    |> Enum.map(fn
+      {_value, index} when index < period - 1 -> nil
+      {_value, index} -> data |> Enum.slice(index - period + 1, period) |> aggregate.()
+    end)
+  end
+
+  # Few distinct values so that ties are generated too
+  defp values, do: one_of([integer(1..5), float(min: -1000.0, max: 1000.0)]) |> map(&(&1 / 1))
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
@@ -0,0 +1,59 @@
+defmodule TheoryCraftTA.Math.MINMAXINDEXTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "math_minmaxindex/2" do
+    test "returns the indices of the rolling min and max" do
+      data = [3.0, 1.0, 4.0, 1.5, 5.0, 9.0, 2.0, 6.0]
+
+      assert {:ok, {min_idx, max_idx}} = Native.math_minmaxindex(data, 3)
+      assert min_idx == [nil, nil, 1, 1, 3, 3, 6, 6]
+      assert max_idx == [nil, nil, 2, 2, 4, 5, 5, 5]
+    end
+
+    test "indices refer to the original input when it starts with nil" do
+      assert {:ok, {[nil, nil, nil, 2], [nil, nil, nil, 3]}} =
+               Native.math_minmaxindex([nil, 3.0, 1.0, 4.0], 3)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.math_minmaxindex([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], []}} = Native.math_minmaxindex([], 3)
+    end
+
+    test "handles insufficient data" do
+      assert {:ok, {[nil, nil], [nil, nil]}} = Native.math_minmaxindex([1.0, 2.0], 3)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: indices point at the window extrema" do
+    property "the values at the indices are the min and max of each window" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 1, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, {min_idx, max_idx}} = Native.math_minmaxindex(data, period)
+
+        for {{min_i, max_i}, i} <- Enum.with_index(Enum.zip(min_idx, max_idx)),
+            min_i != nil do
+          window = Enum.slice(data, i - period + 1, period)
+
+          assert min_i in (i - period + 1)..i
+          assert max_i in (i - period + 1)..i
+          assert Enum.at(data, min_i) == Enum.min(window)
+          assert Enum.at(data, max_i) == Enum.max(window)
+        end
+      end
+    end
+  end
+end
@@ -0,0 +1,233 @@
+defmodule TheoryCraftTA.Math.SUMTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Math.SUM
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Math.SUM
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0]
+
+  ## Batch calculation tests
+
+  describe "math_sum/2" do
+    test "returns the rolling sum" do
+      assert {:ok, [nil, nil, 6.0, 9.0, 12.0]} = Native.math_sum([1.0, 2.0, 3.0, 4.0, 5.0], 3)
+    end
+
+    test "handles nil at the beginning" do
+      assert {:ok, [nil, nil, nil, 9.0]} = Native.math_sum([nil, 2.0, 3.0, 4.0], 3)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.math_sum([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.math_sum([], 3)
+    end
+  end
+
+  ## Streaming API tests
+
+  describe "math_sum_state_next/3" do
+    test "returns nil during warmup, then the rolling sum" do
+      {:ok, state} = Native.math_sum_state_init(3)
+
+      {results, _state} =
+        Enum.map_reduce([1.0, 2.0, 3.0, 4.0, 5.0], state, fn value, st ->
+          {:ok, {result, new_st}} = Native.math_sum_state_next(st, value, true)
+          {result, new_st}
+        end)
+
+      assert results == [nil, nil, 6.0, 9.0, 12.0]
+    end
+
+    test "UPDATE replaces the value of the current bar" do
+      {:ok, state} = Native.math_sum_state_init(2)
+      {:ok, {nil, state}} = Native.math_sum_state_next(state, 1.0, true)
+      {:ok, {3.0, state}} = Native.math_sum_state_next(state, 2.0, true)
+
+      assert {:ok, {11.0, state}} = Native.math_sum_state_next(state, 10.0, false)
+      assert {:ok, {6.0, state}} = Native.math_sum_state_next(state, 5.0, false)
+
+      # The updated value is the one kept once the bar is closed
+      assert {:ok, {12.0, _state}} = Native.math_sum_state_next(state, 7.0, true)
+    end
+
+    test "handles nil values without modifying the state" do
+      {:ok, state} = Native.math_sum_state_init(2)
+      {:ok, {nil, state}} = Native.math_sum_state_next(state, 1.0, true)
+
+      assert {:ok, {nil, ^state}} = Native.math_sum_state_next(state, nil, true)
+    end
+
+    test "doesn't drift over a long series" do
+      {:ok, state} = Native.math_sum_state_init(3)
+
+      # Adding and removing large values loses the small ones in a running sum
+      large = Stream.cycle([1.0e16, 1.0, -1.0e16, 0.1]) |> Enum.take(10_000)
+      small = [0.5, 0.25, 0.125, 0.5, 0.25, 0.125]
+
+      {results, _state} =
+        Enum.map_reduce(large ++ small, state, fn value, st ->
+          {:ok, {result, new_st}} = Native.math_sum_state_next(st, value, true)
+          {result, new_st}
+        end)
+
+      # Once the large values have left the window, the sum is exact again
+      assert List.last(results) == 0.875
+    end
+
+    test "returns error for recompute_interval < 1" do
+      assert {:error, reason} = Native.math_sum_state_init(3, 0)
+      assert reason == "Invalid recompute_interval: must be >= 1 for SUM"
+    end
+
+    test "a huge recompute interval keeps the drift bounded on price-like data" do
+      {:ok, state} = Native.math_sum_state_init(20, 2_147_483_647)
+
+      data = for i <- 1..100_000, do: 100.0 + :math.sin(i) * 10.0 + i / 1000
+      expected = data |> Enum.chunk_every(20, 1, :discard) |> Enum.map(&Enum.sum/1)
+
+      {results, _state} =
+        Enum.map_reduce(data, state, fn value, st ->
+          {:ok, {result, new_st}} = Native.math_sum_state_next(st, value, true)
+          {result, new_st}
+        end)
+
+      # No rebuild at all: the drift documented on DEFAULT_RECOMPUTE_INTERVAL
+      for {result, expected_value} <- Enum.zip(Enum.drop(results, 19), expected) do
+        assert_in_delta(result, expected_value, 1.0e-6)
+      end
+    end
+  end
+
+  ## Public API tests
+
+  describe "sum/2" do
+    test "matches the native calculation" do
+      assert SUM.sum(@data, 3) == Native.math_sum(@data, 3)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.math_sum(@data, 3)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = SUM.sum(ds, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = SUM.init(period: 1, data: "eurusd_m1", name: "sum")
+      assert reason =~ "Invalid period"
+    end
+
+    test "passes the recompute_interval to the native state" do
+      opts = [period: 3, data: "eurusd_m1", name: "sum"]
+
+      assert {:ok, %SUM{}} = SUM.init([recompute_interval: 5] ++ opts)
+      assert {:error, reason} = SUM.init([recompute_interval: 0] ++ opts)
+      assert reason =~ "Invalid recompute_interval"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = SUM.sum(@data, 3)
+      {:ok, state} = SUM.init(period: 3, data: "eurusd_m1", name: "sum")
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = SUM.next(close_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = SUM.init(period: 3, data: "eurusd_m1", name: "sum")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = SUM.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = SUM.next(close_event(100.0, false), state)
+      {:ok, batch_result} = SUM.sum(List.replace_at(@data, -1, 100.0), 3)
+
+      assert_same_value(result.value, List.last(batch_result))
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch SUM" do
+      check all(
+              data <- list_of(float(min: -1000.0, max: 1000.0), min_length: 1, max_length: 300),
+              period <- integer(2..30)
+            ) do
+        {:ok, batch_result} = Native.math_sum(data, period)
+        {:ok, state} = Native.math_sum_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(state, fn {value, expected}, st ->
+          {:ok, {result, new_st}} = Native.math_sum_state_next(st, value, true)
+
+          case expected do
+            nil -> assert result == nil
+            _ -> assert_in_delta(result, expected, 1.0e-6)
+          end
+
+          new_st
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: -1000.0, max: 1000.0), min_length: 30, max_length: 200),
+              period <- integer(2..30),
+              updates <- list_of(float(min: -1000.0, max: 1000.0), min_length: 1, max_length: 5)
+            ) do
+        {:ok, state} = Native.math_sum_state_init(period)
+
+        state =
+          Enum.reduce(data, state, fn value, st ->
+            {:ok, {_result, new_st}} = Native.math_sum_state_next(st, value, true)
+            new_st
+          end)
+
+        Enum.reduce(updates, {state, data}, fn value, {st, current_data} ->
+          {:ok, {result, new_st}} = Native.math_sum_state_next(st, value, false)
+
+          updated_data = List.replace_at(current_data, -1, value)
+          {:ok, batch_result} = Native.math_sum(updated_data, period)
+          assert_in_delta(result, List.last(batch_result), 1.0e-6)
+
+          {new_st, updated_data}
+        end)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
@@ -0,0 +1,176 @@
+defmodule TheoryCraftTA.Momentum.APOTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.APO
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Momentum.APO
+
+  # Every TA_MAType supported by the streaming MA state (MAMA is not)
+  @ma_types [0, 1, 2, 3, 4, 5, 6, 8]
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0]
+  @opts [fast_period: 3, slow_period: 5, ma_type: 1, data: "eurusd_m1", name: "apo"]
+
+  ## Batch calculation tests
+
+  describe "momentum_apo/4" do
+    test "calculates correctly with SMA" do
+      data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+
+      # SMA(2) - SMA(3) on a linear series
+      assert {:ok, result} = Native.momentum_apo(data, 2, 3, 0)
+      assert Enum.take(result, 2) == [nil, nil]
+      Enum.each(Enum.drop(result, 2), &assert_in_delta(&1, 0.5, 1.0e-12))
+    end
+
+    test "swaps the periods when the slow one is shorter" do
+      data = Enum.map(1..50, &(&1 * 1.0))
+      assert Native.momentum_apo(data, 26, 12, 1) == Native.momentum_apo(data, 12, 26, 1)
+    end
+
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.momentum_apo([1.0, 2.0, 3.0], 1, 3, 0)
+      assert reason =~ "must be >= 2"
+
+      assert {:error, reason} = Native.momentum_apo([1.0, 2.0, 3.0], 2, 3, 9)
+      assert reason =~ "ma_type must be between 0 and 8"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.momentum_apo([], 12, 26, 1)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "momentum_apo_state_init/3" do
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.momentum_apo_state_init(1, 26, 1)
+      assert reason =~ "Invalid period"
+
+      assert {:error, reason} = Native.momentum_apo_state_init(12, 26, 7)
+      assert reason =~ "MAMA"
+    end
+  end
+
+  ## Public API tests
+
+  describe "apo/4" do
+    test "matches the native calculation" do
+      assert APO.apo(@data, 3, 5, 1) == Native.momentum_apo(@data, 3, 5, 1)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.momentum_apo(@data, 3, 5, 1)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = APO.apo(ds, 3, 5, 1)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "uses the TA-Lib defaults" do
+      assert {:ok, %APO{fast_period: 12, slow_period: 26, ma_type: 0}} =
+               APO.init(data: "eurusd_m1", name: "apo")
+    end
+
+    test "returns error for an invalid ma_type" do
+      assert {:error, reason} = APO.init(ma_type: 7, data: "eurusd_m1", name: "apo")
+      assert reason =~ "MAMA"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = APO.apo(@data, 3, 5, 1)
+      {:ok, state} = APO.init(@opts)
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = APO.next(close_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result, 1.0e-6)
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = APO.init(@opts)
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = APO.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = APO.next(close_event(100.0, false), state)
+      {:ok, batch_result} = APO.apo(List.replace_at(@data, -1, 100.0), 3, 5, 1)
+
+      assert_same_value(result.value, List.last(batch_result), 1.0e-6)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch APO for every ma_type" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              fast_period <- integer(2..15),
+              slow_period <- integer(2..30),
+              ma_type <- member_of(@ma_types)
+            ) do
+        {:ok, batch_result} = Native.momentum_apo(data, fast_period, slow_period, ma_type)
+        {:ok, initial_state} = Native.momentum_apo_state_init(fast_period, slow_period, ma_type)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          {:ok, {result, new_state}} = Native.momentum_apo_state_next(state, value, true)
+          assert_same_value(result, expected, 1.0e-6)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              fast_period <- integer(2..15),
+              slow_period <- integer(2..30),
+              ma_type <- member_of(@ma_types),
+              update_value <- float(min: 1.0, max: 1000.0)
+            ) do
+        {:ok, state} = Native.momentum_apo_state_init(fast_period, slow_period, ma_type)
+
+        state =
+          Enum.reduce(data, state, fn value, st ->
+            {:ok, {_result, new_st}} = Native.momentum_apo_state_next(st, value, true)
+            new_st
+          end)
+
+        {:ok, {result, _state}} = Native.momentum_apo_state_next(state, update_value, false)
+
+        updated_data = List.replace_at(data, -1, update_value)
+        {:ok, batch_result} = Native.momentum_apo(updated_data, fast_period, slow_period, ma_type)
+
+        assert_same_value(result, List.last(batch_result), 1.0e-6)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
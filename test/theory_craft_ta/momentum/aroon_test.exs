@@ -0,0 +1,220 @@
+defmodule TheoryCraftTA.Momentum.AROONTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.AROON
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Momentum.AROON
+
+  @high [10.0, 12.0, 11.0, 13.0, 12.0]
+  @low [8.0, 9.0, 7.0, 10.0, 9.0]
+
+  ## Batch calculation tests
+
+  describe "momentum_aroon/3" do
+    test "calculates correctly with period=2" do
+      # The window spans 'period + 1' bars
+      assert {:ok, {down, up}} = Native.momentum_aroon(@high, @low, 2)
+      assert down == [nil, nil, 100.0, 50.0, 0.0]
+      assert up == [nil, nil, 50.0, 100.0, 50.0]
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_aroon(@high, @low, 1)
+      assert reason == "AROON: period must be >= 2"
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = Native.momentum_aroon(@high, Enum.drop(@low, 1), 2)
+      assert reason == "AROON: high/low length mismatch (5/4)"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], []}} = Native.momentum_aroon([], [], 14)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "momentum_aroon_state_init/1" do
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_aroon_state_init(1)
+      assert reason == "Invalid period: must be >= 2 for AROON"
+    end
+  end
+
+  describe "momentum_aroon_state_next/4" do
+    test "replaying the series matches batch" do
+      {:ok, state} = Native.momentum_aroon_state_init(2)
+
+      assert {[nil, nil, 100.0, 50.0, 0.0], [nil, nil, 50.0, 100.0, 50.0]} =
+               stream(state, Enum.zip(@high, @low))
+    end
+
+    test "UPDATE moves the extrema with the current bar" do
+      {:ok, state} = Native.momentum_aroon_state_init(2)
+
+      state =
+        Enum.reduce(Enum.zip(Enum.take(@high, 4), Enum.take(@low, 4)), state, fn bar, st ->
+          {:ok, {_down, _up, new_st}} = step(st, bar, true)
+          new_st
+        end)
+
+      # The current bar is now the lowest low, the highest high is the oldest bar
+      assert {:ok, {100.0, 0.0, state}} = step(state, {11.5, 6.0}, false)
+
+      # Back to the values of the batch
+      assert {:ok, {50.0, 100.0, _state}} = step(state, {13.0, 10.0}, false)
+    end
+
+    test "the newest bar wins on ties" do
+      {:ok, state} = Native.momentum_aroon_state_init(2)
+      {:ok, {nil, nil, state}} = step(state, {10.0, 5.0}, true)
+      {:ok, {nil, nil, state}} = step(state, {10.0, 5.0}, true)
+
+      assert {:ok, {50.0, 50.0, _state}} = step(state, {9.0, 6.0}, true)
+    end
+
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.momentum_aroon_state_init(2)
+      {:ok, {nil, nil, state}} = step(state, {10.0, 8.0}, true)
+
+      assert {:ok, {nil, nil, ^state}} = step(state, {nil, 8.0}, true)
+    end
+  end
+
+  ## Public API tests
+
+  describe "aroon/3" do
+    test "matches the native calculation" do
+      assert AROON.aroon(@high, @low, 2) == Native.momentum_aroon(@high, @low, 2)
+    end
+
+    test "maintains DataSeries type in both lines" do
+      [high, low] = for values <- [@high, @low], do: data_series(values)
+
+      assert {:ok, {%DataSeries{} = down, %DataSeries{} = up}} = AROON.aroon(high, low, 2)
+      assert DataSeries.values(down) == [0.0, 50.0, 100.0, nil, nil]
+      assert DataSeries.values(up) == [50.0, 100.0, 50.0, nil, nil]
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = AROON.init(period: 1, data: "eurusd_m1", name: "aroon")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "emits the down and up lines once the window is full" do
+      {:ok, state} = AROON.init(period: 2, data: "eurusd_m1", name: "aroon")
+
+      {stream_result, _state} =
+        @high
+        |> Enum.zip(@low)
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = AROON.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert stream_result == [
+               nil,
+               nil,
+               %{down: 100.0, up: 50.0},
+               %{down: 50.0, up: 100.0},
+               %{down: 0.0, up: 50.0}
+             ]
+    end
+
+    test "UPDATE replaces the high and low of the current bar" do
+      {:ok, state} = AROON.init(period: 2, data: "eurusd_m1", name: "aroon")
+
+      state =
+        @high
+        |> Enum.zip(@low)
+        |> Enum.reduce(state, fn bar, st ->
+          {:ok, _result, new_st} = AROON.next(bar_event(bar, true), st)
+          new_st
+        end)
+
+      # The current bar now makes both the highest high and the lowest low
+      assert {:ok, %{value: %{down: 100.0, up: 100.0}}, _state} =
+               AROON.next(bar_event({14.0, 6.0}, false), state)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch AROON" do
+      check all(
+              bars <- list_of(bar(), min_length: 1, max_length: 200),
+              period <- integer(2..30)
+            ) do
+        {high, low} = Enum.unzip(bars)
+        {:ok, state} = Native.momentum_aroon_state_init(period)
+
+        assert {:ok, stream(state, bars)} == Native.momentum_aroon(high, low, period)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE before each APPEND ends on the batch result" do
+      check all(
+              bars <- list_of({bar(), bar()}, min_length: 1, max_length: 100),
+              period <- integer(2..20)
+            ) do
+        {high, low} = bars |> Enum.map(fn {_forming, final} -> final end) |> Enum.unzip()
+        {:ok, state} = Native.momentum_aroon_state_init(period)
+
+        {values, _state} =
+          Enum.map_reduce(bars, state, fn {forming, final}, st ->
+            {:ok, {_down, _up, st}} = step(st, forming, true)
+            {:ok, {down, up, st}} = step(st, final, false)
+            {{down, up}, st}
+          end)
+
+        assert {:ok, Enum.unzip(values)} == Native.momentum_aroon(high, low, period)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp bar() do
+    # Few distinct prices, so that ties are common
+    gen all(
+          low <- integer(1..20),
+          range <- integer(0..5)
+        ) do
+      {low + range + 0.0, low + 0.0}
+    end
+  end
+
+  defp step(state, {high, low}, new_bar?) do
+    Native.momentum_aroon_state_next(state, high, low, new_bar?)
+  end
+
+  defp stream(state, bars) do
+    {values, _state} =
+      Enum.map_reduce(bars, state, fn bar, st ->
+        {:ok, {down, up, new_st}} = step(st, bar, true)
+        {{down, up}, new_st}
+      end)
+
+    Enum.unzip(values)
+  end
+
+  defp bar_event({high, low}, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{high: high, low: low, new_bar?: new_bar?}}}
+  end
+
+  defp data_series(values) do
+    Enum.reduce(values, DataSeries.new(), &DataSeries.add(&2, &1))
+  end
+end
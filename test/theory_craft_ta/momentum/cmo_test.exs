@@ -0,0 +1,185 @@
+defmodule TheoryCraftTA.Momentum.CMOTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.CMO
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Momentum.CMO
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0]
+
+  ## Batch calculation tests
+
+  describe "momentum_cmo/2" do
+    test "calculates correctly with period=3" do
+      data = [1.0, 2.0, 4.0, 3.0, 3.0, 1.0]
+
+      # Deltas: +1, +2, -1, 0, -2
+      # [+1, +2, -1]: 100 * (3 - 1) / 4, [+2, -1, 0]: 100 * (2 - 1) / 3
+      # [-1, 0, -2]: 100 * (0 - 3) / 3
+      assert {:ok, [nil, nil, nil, 50.0, cmo, -100.0]} = Native.momentum_cmo(data, 3)
+      assert_in_delta cmo, 33.3333333333, 1.0e-9
+    end
+
+    test "is zero on a constant series" do
+      assert {:ok, [nil, nil, 0.0, 0.0]} = Native.momentum_cmo(List.duplicate(5.0, 4), 2)
+    end
+
+    test "skips nil values like the streaming state" do
+      assert {:ok, [nil, nil, nil, 100.0, nil, 0.0]} =
+               Native.momentum_cmo([nil, 1.0, 2.0, 3.0, nil, 2.0], 2)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_cmo([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.momentum_cmo([], 3)
+    end
+
+    test "handles insufficient data" do
+      assert {:ok, [nil, nil, nil]} = Native.momentum_cmo([1.0, 2.0, 3.0], 3)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "momentum_cmo_state_init/1" do
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_cmo_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "momentum_cmo_state_next/3" do
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.momentum_cmo_state_init(2)
+      {:ok, {nil, state}} = Native.momentum_cmo_state_next(state, 1.0, true)
+
+      assert {:ok, {nil, ^state}} = Native.momentum_cmo_state_next(state, nil, true)
+    end
+
+    test "UPDATE mode adjusts the last delta" do
+      {:ok, state} = Native.momentum_cmo_state_init(2)
+      {:ok, {nil, state}} = Native.momentum_cmo_state_next(state, 1.0, true)
+      {:ok, {nil, state}} = Native.momentum_cmo_state_next(state, 2.0, true)
+      {:ok, {100.0, state}} = Native.momentum_cmo_state_next(state, 3.0, true)
+
+      # Deltas [+1, -1] balance out, [+1, +2] only go up
+      assert {:ok, {0.0, state}} = Native.momentum_cmo_state_next(state, 1.0, false)
+      assert {:ok, {100.0, _state}} = Native.momentum_cmo_state_next(state, 4.0, false)
+    end
+  end
+
+  ## Public API tests
+
+  describe "cmo/2" do
+    test "matches the native calculation" do
+      assert CMO.cmo(@data, 3) == Native.momentum_cmo(@data, 3)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.momentum_cmo(@data, 3)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = CMO.cmo(ds, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = CMO.init(period: 1, data: "eurusd_m1", name: "cmo")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = CMO.cmo(@data, 3)
+      {:ok, state} = CMO.init(period: 3, data: "eurusd_m1", name: "cmo")
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = CMO.next(close_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = CMO.init(period: 3, data: "eurusd_m1", name: "cmo")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = CMO.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = CMO.next(close_event(100.0, false), state)
+      {:ok, batch_result} = CMO.cmo(List.replace_at(@data, -1, 100.0), 3)
+
+      assert_same_value(result.value, List.last(batch_result))
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch CMO" do
+      check all(
+              data <- list_of(price(), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, batch_result} = Native.momentum_cmo(data, period)
+        {:ok, initial_state} = Native.momentum_cmo_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          {:ok, {result, new_state}} = Native.momentum_cmo_state_next(state, value, true)
+          assert_same_value(result, expected)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE before each APPEND ends on the batch result" do
+      check all(
+              data <- list_of({price(), price()}, min_length: 10, max_length: 100),
+              period <- integer(2..10)
+            ) do
+        final_data = Enum.map(data, fn {_forming, final} -> final end)
+        {:ok, batch_result} = Native.momentum_cmo(final_data, period)
+        {:ok, initial_state} = Native.momentum_cmo_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {{forming, final}, expected}, state ->
+          {:ok, {_result, state}} = Native.momentum_cmo_state_next(state, forming, true)
+          {:ok, {result, new_state}} = Native.momentum_cmo_state_next(state, final, false)
+          assert_same_value(result, expected)
+          new_state
+        end)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp price(), do: float(min: 1.0, max: 1000.0)
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
@@ -0,0 +1,223 @@
+defmodule TheoryCraftTA.Momentum.DXTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.DX
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Momentum.DX
+
+  @high [12.0, 12.5, 12.2, 13.0, 13.4, 13.1, 13.8, 14.2]
+  @low [11.0, 11.6, 11.4, 12.1, 12.6, 12.4, 12.9, 13.3]
+  @close [11.5, 12.3, 11.9, 12.8, 13.2, 12.6, 13.6, 13.9]
+
+  ## Batch calculation tests
+
+  describe "momentum_dx/4" do
+    test "emits the DX after period deltas" do
+      assert {:ok, result} = Native.momentum_dx(@high, @low, @close, 3)
+      assert Enum.take(result, 3) == List.duplicate(nil, 3)
+
+      # Bar 3: +DI = 47.887323943662, -DI = 5.633802816901
+      assert_in_delta Enum.at(result, 3), 78.947368421053, 1.0e-9
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_dx(@high, @low, @close, 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = Native.momentum_dx(@high, @low, Enum.drop(@close, 1), 3)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.momentum_dx([], [], [], 3)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "momentum_dx_state_init/1" do
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_dx_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "momentum_dx_state_next/5" do
+    test "replaying the series matches batch" do
+      {:ok, batch_result} = Native.momentum_dx(@high, @low, @close, 3)
+      {:ok, state} = Native.momentum_dx_state_init(3)
+
+      {stream_result, _state} =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, {dx, new_st}} = step(st, bar, true)
+          {dx, new_st}
+        end)
+
+      Enum.zip_with(stream_result, batch_result, &assert_same_value(&1, &2, 1.0e-6))
+    end
+
+    test "an undefined DX repeats the previous one, starting from 0" do
+      {:ok, state} = Native.momentum_dx_state_init(2)
+
+      {results, _state} =
+        Enum.map_reduce(List.duplicate({12.0, 12.0, 12.0}, 4), state, fn bar, st ->
+          {:ok, {dx, new_st}} = step(st, bar, true)
+          {dx, new_st}
+        end)
+
+      assert results == [nil, nil, 0.0, 0.0]
+    end
+
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.momentum_dx_state_init(2)
+      {:ok, {nil, state}} = step(state, {12.0, 11.0, 11.5}, true)
+
+      assert {:ok, {nil, ^state}} = Native.momentum_dx_state_next(state, 12.0, nil, 11.5, true)
+    end
+  end
+
+  ## Public API tests
+
+  describe "dx/4" do
+    test "matches the native calculation" do
+      assert DX.dx(@high, @low, @close, 3) == Native.momentum_dx(@high, @low, @close, 3)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.momentum_dx(@high, @low, @close, 3)
+      [high, low, close] = for values <- [@high, @low, @close], do: data_series(values)
+
+      assert {:ok, %DataSeries{} = result} = DX.dx(high, low, close, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = DX.init(period: 1, data: "eurusd_m1", name: "dx")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = DX.dx(@high, @low, @close, 3)
+      {:ok, state} = DX.init(period: 3, data: "eurusd_m1", name: "dx")
+
+      {stream_result, _state} =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = DX.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE replaces the high, low and close of the current bar" do
+      {:ok, state} = DX.init(period: 3, data: "eurusd_m1", name: "dx")
+
+      state =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.reduce(state, fn bar, st ->
+          {:ok, _result, new_st} = DX.next(bar_event(bar, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = DX.next(bar_event({15.0, 13.8, 14.9}, false), state)
+
+      high = List.replace_at(@high, -1, 15.0)
+      low = List.replace_at(@low, -1, 13.8)
+      close = List.replace_at(@close, -1, 14.9)
+      {:ok, batch_result} = DX.dx(high, low, close, 3)
+
+      assert_same_value(result.value, List.last(batch_result))
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch DX" do
+      check all(
+              bars <- list_of(bar(), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, initial_state} = Native.momentum_dx_state_init(period)
+
+        bars
+        |> Enum.zip(batch_dx(bars, period))
+        |> Enum.reduce(initial_state, fn {bar, expected}, state ->
+          {:ok, {result, new_state}} = step(state, bar, true)
+          assert_same_value(result, expected, 1.0e-6)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE before each APPEND ends on the batch result" do
+      check all(
+              bars <- list_of({bar(), bar()}, min_length: 10, max_length: 100),
+              period <- integer(2..10)
+            ) do
+        final_bars = Enum.map(bars, fn {_forming, final} -> final end)
+        {:ok, initial_state} = Native.momentum_dx_state_init(period)
+
+        bars
+        |> Enum.zip(batch_dx(final_bars, period))
+        |> Enum.reduce(initial_state, fn {{forming, final}, expected}, state ->
+          {:ok, {_, state}} = step(state, forming, true)
+          {:ok, {result, new_state}} = step(state, final, false)
+          assert_same_value(result, expected, 1.0e-6)
+          new_state
+        end)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp bar() do
+    gen all(
+          low <- float(min: 1.0, max: 1000.0),
+          range <- one_of([constant(0.0), float(min: 0.0, max: 10.0)]),
+          position <- float(min: 0.0, max: 1.0)
+        ) do
+      {low + range, low, low + range * position}
+    end
+  end
+
+  defp step(state, {high, low, close}, new_bar?) do
+    Native.momentum_dx_state_next(state, high, low, close, new_bar?)
+  end
+
+  defp batch_dx(bars, period) do
+    [high, low, close] = for i <- 0..2, do: Enum.map(bars, &elem(&1, i))
+    {:ok, result} = Native.momentum_dx(high, low, close, period)
+    result
+  end
+
+  defp bar_event({high, low, close}, new_bar?) do
+    %MarketEvent{
+      data: %{"eurusd_m1" => %Bar{high: high, low: low, close: close, new_bar?: new_bar?}}
+    }
+  end
+
+  defp data_series(values) do
+    Enum.reduce(values, DataSeries.new(), &DataSeries.add(&2, &1))
+  end
+end
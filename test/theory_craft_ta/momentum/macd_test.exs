@@ -0,0 +1,376 @@
+defmodule TheoryCraftTA.Momentum.MACDTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.Momentum.MACD
+
+  doctest TheoryCraftTA.Momentum.MACD
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0, 14.0, 13.0]
+
+  ## Batch calculation tests
+
+  describe "macd/4 with list input" do
+    test "calculates correctly with periods 3, 6 and 3" do
+      assert {:ok, {macd, signal, hist}} = MACD.macd(@data, 3, 6, 3)
+
+      # Python result (macd): [nan nan nan nan nan nan nan 0.83333333 0.66666667 ...]
+      assert_values_in_delta(macd, [
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        0.8333333333333321,
+        0.6666666666666643,
+        0.8333333333333321,
+        0.5595238095238084,
+        0.1675170068027203
+      ])
+
+      # Python result (signal): [nan nan nan nan nan nan nan 0.77777778 0.72222222 ...]
+      assert_values_in_delta(signal, [
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        0.7777777777777762,
+        0.7222222222222203,
+        0.7777777777777762,
+        0.6686507936507924,
+        0.41808390022675634
+      ])
+
+      # Python result (hist): [nan nan nan nan nan nan nan 0.05555556 -0.05555556 ...]
+      assert_values_in_delta(hist, [
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        0.05555555555555591,
+        -0.055555555555556024,
+        0.05555555555555591,
+        -0.10912698412698396,
+        -0.25056689342403604
+      ])
+    end
+
+    test "swaps the periods when the slow one is shorter" do
+      assert MACD.macd(@data, 6, 3, 3) == MACD.macd(@data, 3, 6, 3)
+    end
+
+    test "raises for fast_period=1" do
+      assert {:error, reason} = MACD.macd(@data, 1, 6, 3)
+      assert reason =~ "fast_period and slow_period must be >= 2"
+    end
+
+    test "raises for signal_period=0" do
+      assert {:error, reason} = MACD.macd(@data, 3, 6, 0)
+      assert reason =~ "signal_period must be >= 1"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], [], []}} = MACD.macd([], 3, 6, 3)
+    end
+
+    test "handles insufficient data" do
+      data = Enum.take(@data, 7)
+      assert {:ok, {macd, signal, hist}} = MACD.macd(data, 3, 6, 3)
+      assert macd == List.duplicate(nil, 7)
+      assert signal == List.duplicate(nil, 7)
+      assert hist == List.duplicate(nil, 7)
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      data = [nil, nil | Enum.drop(@data, 2)]
+      # Python result (macd): [nan ... nan 0.87585034 0.5898931 0.18920936]
+      assert {:ok, {macd, _signal, _hist}} = MACD.macd(data, 3, 6, 3)
+
+      assert_values_in_delta(macd, [
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        0.8758503401360542,
+        0.5898931000971803,
+        0.18920935721227217
+      ])
+    end
+  end
+
+  describe "macd/4 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      data = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, {macd, signal, hist}} = MACD.macd(data, 3, 6, 3)
+      assert %DataSeries{} = macd
+      assert %DataSeries{} = signal
+      assert %DataSeries{} = hist
+      assert [_, _, _, _, _, nil, nil, nil, nil, nil, nil, nil] = DataSeries.values(macd)
+    end
+  end
+
+  describe "macd/4 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      data =
+        @data
+        |> Enum.with_index()
+        |> Enum.reduce(TimeSeries.new(), fn {value, i}, ts ->
+          TimeSeries.add(ts, DateTime.add(~U[2024-01-01 00:00:00Z], i, :minute), value)
+        end)
+
+      assert {:ok, {macd, signal, hist}} = MACD.macd(data, 3, 6, 3)
+      assert %TimeSeries{} = macd
+      assert %TimeSeries{} = signal
+      assert %TimeSeries{} = hist
+      assert [_, _, _, _, _, nil, nil, nil, nil, nil, nil, nil] = TimeSeries.values(macd)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, state} = MACD.init(data: "eurusd_m1", name: "macd")
+      assert %MACD{fast_period: 12, slow_period: 26, signal_period: 9} = state
+    end
+
+    test "returns error for a period < 2" do
+      assert {:error, msg} = MACD.init(fast_period: 1, data: "eurusd_m1", name: "macd")
+      assert msg =~ "Invalid period"
+    end
+
+    test "returns error for a signal period < 1" do
+      assert {:error, msg} = MACD.init(signal_period: 0, data: "eurusd_m1", name: "macd")
+      assert msg =~ "Invalid signal period"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "matches batch MACD once the signal is available" do
+      {:ok, {macd, signal, hist}} = MACD.macd(@data, 3, 6, 3)
+      {:ok, state} = init_state(3, 6, 3)
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = MACD.next(bar_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      for {lines, expected} <- Enum.zip(stream_result, Enum.zip([macd, signal, hist])) do
+        assert_lines_in_delta(lines, expected)
+      end
+    end
+
+    test "emits the MACD line before the signal is warmed up" do
+      {:ok, state} = init_state(3, 6, 3)
+
+      {stream_result, _state} =
+        Enum.map_reduce(Enum.take(@data, 7), state, fn value, st ->
+          {:ok, result, new_st} = MACD.next(bar_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert [nil, nil, nil, nil, nil, lines6, lines7] = stream_result
+
+      # The fast EMA is seeded on the 6th bar with the SMA of the last 3 values
+      assert %{signal: nil, hist: nil} = lines6
+      assert_in_delta(lines6.macd, 0.8333333333333321, 1.0e-9)
+      assert %{signal: nil, hist: nil} = lines7
+      assert_in_delta(lines7.macd, 0.6666666666666643, 1.0e-9)
+    end
+
+    test "processes bars correctly in UPDATE mode" do
+      {:ok, state} = init_state(3, 6, 3)
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = MACD.next(bar_event(value, true), st)
+          new_st
+        end)
+
+      # Replace the last bar (13.0 -> 16.0): same as batch over the updated data
+      {:ok, result, _state} = MACD.next(bar_event(16.0, false), state)
+
+      assert_lines_in_delta(
+        result.value,
+        {0.8103741496598627, 0.7395124716553275, 0.07086167800453513}
+      )
+    end
+
+    test "handles nil values from upstream indicators" do
+      {:ok, state} =
+        MACD.init(fast_period: 2, slow_period: 3, signal_period: 1, data: "indicator", name: "m")
+
+      {results, _state} =
+        Enum.map_reduce([nil, 100.0, 110.0, 120.0], state, fn value, st ->
+          event = %MarketEvent{
+            data: %{
+              "indicator" => %IndicatorValue{value: value, data_name: "eurusd_m1"},
+              "eurusd_m1" => %Bar{close: 1.23, new_bar?: true}
+            }
+          }
+
+          {:ok, result, new_st} = MACD.next(event, st)
+          {result.value, new_st}
+        end)
+
+      # EMA(2) = 115.0 and EMA(3) = 110.0, a 1-period signal follows the MACD line
+      assert [nil, nil, nil, lines] = results
+      assert_lines_in_delta(lines, {5.0, 5.0, 0.0})
+    end
+
+    test "matches recorded values for a mixed APPEND/UPDATE sequence" do
+      # Pins the output of the nested EMAs, including the UPDATE of a bar in warmup
+      {:ok, state} = init_state(2, 3, 2)
+
+      events = [
+        {100.0, true},
+        {104.0, true},
+        {101.0, false},
+        {108.0, true},
+        {107.0, true},
+        {111.0, false},
+        {115.0, true},
+        {112.0, true},
+        {118.0, true},
+        {116.0, false},
+        {121.0, true},
+        {119.0, true}
+      ]
+
+      {results, _state} =
+        Enum.map_reduce(events, state, fn {value, new_bar?}, st ->
+          {:ok, result, new_st} = MACD.next(bar_event(value, new_bar?), st)
+          {result.value, new_st}
+        end)
+
+      assert [nil, nil, nil, %{macd: macd4, signal: nil, hist: nil} | lines] = results
+      assert_in_delta(macd4, 1.5, 1.0e-9)
+
+      expected = [
+        {1.1666666666666714, 1.3333333333333357, -0.1666666666666643},
+        {1.8333333333333286, 1.6666666666666643, 0.1666666666666643},
+        {1.9444444444444429, 1.85185185185185, 0.09259259259259278},
+        {0.8148148148148096, 1.1604938271604897, -0.3456790123456801},
+        {1.3549382716049365, 1.290123456790121, 0.06481481481481555},
+        {1.021604938271608, 1.0679012345679018, -0.04629629629629384},
+        {1.5488683127572074, 1.3885459533607722, 0.16032235939643513},
+        {0.7871227709190691, 0.9875971650663036, -0.20047439414723445}
+      ]
+
+      for {line, expected_line} <- Enum.zip(lines, expected) do
+        assert_lines_in_delta(line, expected_line)
+      end
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch MACD" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 30, max_length: 300),
+              fast_period <- integer(2..15),
+              slow_period <- integer(2..30),
+              signal_period <- integer(1..10)
+            ) do
+        {:ok, {macd, signal, hist}} = MACD.macd(data, fast_period, slow_period, signal_period)
+        {:ok, initial_state} = init_state(fast_period, slow_period, signal_period)
+
+        data
+        |> Enum.zip(Enum.zip([macd, signal, hist]))
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          {:ok, result, new_state} = MACD.next(bar_event(value, true), state)
+          assert_lines_in_delta(result.value, expected, 1.0e-6)
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 30, max_length: 200),
+              fast_period <- integer(2..10),
+              slow_period <- integer(2..20),
+              signal_period <- integer(1..8),
+              update_values <-
+                list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 5)
+            ) do
+        {:ok, state} = init_state(fast_period, slow_period, signal_period)
+
+        final_state =
+          Enum.reduce(data, state, fn value, st ->
+            {:ok, _result, new_state} = MACD.next(bar_event(value, true), st)
+            new_state
+          end)
+
+        Enum.reduce(update_values, {final_state, data}, fn update_value, {state, current_data} ->
+          {:ok, result, new_state} = MACD.next(bar_event(update_value, false), state)
+
+          updated_data = List.replace_at(current_data, -1, update_value)
+
+          {:ok, {macd, signal, hist}} =
+            MACD.macd(updated_data, fast_period, slow_period, signal_period)
+
+          expected = {List.last(macd), List.last(signal), List.last(hist)}
+          assert_lines_in_delta(result.value, expected, 1.0e-6)
+
+          {new_state, updated_data}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp init_state(fast_period, slow_period, signal_period) do
+    MACD.init(
+      fast_period: fast_period,
+      slow_period: slow_period,
+      signal_period: signal_period,
+      data: "test",
+      name: "macd"
+    )
+  end
+
+  defp bar_event(value, new_bar?) do
+    %MarketEvent{data: %{"test" => %Bar{close: value, new_bar?: new_bar?}}}
+  end
+
+  # The stream emits the MACD line before the signal, where the batch has no value yet
+  defp assert_lines_in_delta(lines, expected, delta \\ 1.0e-9)
+
+  defp assert_lines_in_delta(nil, {nil, nil, nil}, _delta), do: :ok
+
+  defp assert_lines_in_delta(%{signal: nil, hist: nil}, {nil, nil, nil}, _delta), do: :ok
+
+  defp assert_lines_in_delta(lines, {macd, signal, hist}, delta) do
+    assert %{macd: m, signal: s, hist: h} = lines
+    assert_in_delta(m, macd, delta)
+    assert_in_delta(s, signal, delta)
+    assert_in_delta(h, hist, delta)
+  end
+end
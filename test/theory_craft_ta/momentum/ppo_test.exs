@@ -0,0 +1,193 @@
+defmodule TheoryCraftTA.Momentum.PPOTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.PPO
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Momentum.PPO
+
+  # Every TA_MAType supported by the streaming MA state (MAMA is not)
+  @ma_types [0, 1, 2, 3, 4, 5, 6, 8]
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0]
+  @opts [fast_period: 3, slow_period: 5, ma_type: 1, data: "eurusd_m1", name: "ppo"]
+
+  ## Batch calculation tests
+
+  describe "momentum_ppo/4" do
+    test "calculates correctly with SMA" do
+      data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+
+      # SMA(2) - SMA(3) on a linear series
+      assert {:ok, result} = Native.momentum_ppo(data, 2, 3, 0)
+      assert Enum.take(result, 2) == [nil, nil]
+
+      # 100 * 0.5 / SMA(3)
+      [25.0, 100 / 6, 12.5, 10.0]
+      |> Enum.zip(Enum.drop(result, 2))
+      |> Enum.each(fn {expected, value} -> assert_in_delta(value, expected, 1.0e-9) end)
+    end
+
+    test "gives 0 when the slow MA is zero" do
+      assert {:ok, [nil, nil, +0.0, +0.0]} = Native.momentum_ppo(List.duplicate(0.0, 4), 2, 3, 0)
+    end
+
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.momentum_ppo([1.0, 2.0, 3.0], 1, 3, 0)
+      assert reason =~ "must be >= 2"
+
+      assert {:error, reason} = Native.momentum_ppo([1.0, 2.0, 3.0], 2, 3, 9)
+      assert reason =~ "ma_type must be between 0 and 8"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.momentum_ppo([], 12, 26, 1)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "momentum_ppo_state_init/3" do
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.momentum_ppo_state_init(1, 26, 1)
+      assert reason =~ "Invalid period"
+
+      assert {:error, reason} = Native.momentum_ppo_state_init(12, 26, 7)
+      assert reason =~ "MAMA"
+    end
+  end
+
+  describe "momentum_ppo_state_next/3" do
+    test "gives 0 when the slow MA is zero" do
+      {:ok, state} = Native.momentum_ppo_state_init(2, 3, 0)
+
+      {results, _state} =
+        Enum.map_reduce(List.duplicate(0.0, 4), state, fn value, st ->
+          {:ok, {result, new_st}} = Native.momentum_ppo_state_next(st, value, true)
+          {result, new_st}
+        end)
+
+      assert results == [nil, nil, 0.0, 0.0]
+    end
+  end
+
+  ## Public API tests
+
+  describe "ppo/4" do
+    test "matches the native calculation" do
+      assert PPO.ppo(@data, 3, 5, 1) == Native.momentum_ppo(@data, 3, 5, 1)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.momentum_ppo(@data, 3, 5, 1)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = PPO.ppo(ds, 3, 5, 1)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "uses the TA-Lib defaults" do
+      assert {:ok, %PPO{fast_period: 12, slow_period: 26, ma_type: 0}} =
+               PPO.init(data: "eurusd_m1", name: "ppo")
+    end
+
+    test "returns error for an invalid ma_type" do
+      assert {:error, reason} = PPO.init(ma_type: 7, data: "eurusd_m1", name: "ppo")
+      assert reason =~ "MAMA"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = PPO.ppo(@data, 3, 5, 1)
+      {:ok, state} = PPO.init(@opts)
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = PPO.next(close_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result, 1.0e-6)
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = PPO.init(@opts)
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = PPO.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = PPO.next(close_event(100.0, false), state)
+      {:ok, batch_result} = PPO.ppo(List.replace_at(@data, -1, 100.0), 3, 5, 1)
+
+      assert_same_value(result.value, List.last(batch_result), 1.0e-6)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch PPO for every ma_type" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              fast_period <- integer(2..15),
+              slow_period <- integer(2..30),
+              ma_type <- member_of(@ma_types)
+            ) do
+        {:ok, batch_result} = Native.momentum_ppo(data, fast_period, slow_period, ma_type)
+        {:ok, initial_state} = Native.momentum_ppo_state_init(fast_period, slow_period, ma_type)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          {:ok, {result, new_state}} = Native.momentum_ppo_state_next(state, value, true)
+          assert_same_value(result, expected, 1.0e-6)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              fast_period <- integer(2..15),
+              slow_period <- integer(2..30),
+              ma_type <- member_of(@ma_types),
+              update_value <- float(min: 1.0, max: 1000.0)
+            ) do
+        {:ok, state} = Native.momentum_ppo_state_init(fast_period, slow_period, ma_type)
+
+        state =
+          Enum.reduce(data, state, fn value, st ->
+            {:ok, {_result, new_st}} = Native.momentum_ppo_state_next(st, value, true)
+            new_st
+          end)
+
+        {:ok, {result, _state}} = Native.momentum_ppo_state_next(state, update_value, false)
+
+        updated_data = List.replace_at(data, -1, update_value)
+        {:ok, batch_result} = Native.momentum_ppo(updated_data, fast_period, slow_period, ma_type)
+
+        assert_same_value(result, List.last(batch_result), 1.0e-6)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
@@ -0,0 +1,257 @@
+defmodule TheoryCraftTA.Momentum.ROCTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.Momentum.ROC
+
+  doctest TheoryCraftTA.Momentum.ROC
+
+  ## Batch calculation tests
+
+  describe "roc/2 with list input" do
+    test "calculates correctly with period=3" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      # Python result: [nan nan nan 20. 4.54545455 23.80952381 4.16666667 21.73913043]
+      assert {:ok, result} = ROC.roc(data, 3)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        19.999999999999996,
+        4.545454545454541,
+        23.809523809523814,
+        4.166666666666674,
+        21.739130434782616
+      ])
+    end
+
+    test "handles period=1 (minimum valid)" do
+      data = [1.0, 2.0, 4.0, 7.0]
+      assert {:ok, result} = ROC.roc(data, 1)
+      assert result == [nil, 100.0, 100.0, 75.0]
+    end
+
+    test "returns 0 when the trailing value is zero" do
+      data = [0.0, 1.0, 2.0]
+      assert {:ok, result} = ROC.roc(data, 1)
+      assert result == [nil, 0.0, 100.0]
+    end
+
+    test "raises for period=0" do
+      data = [1.0, 2.0, 3.0]
+      assert {:error, reason} = ROC.roc(data, 0)
+      assert reason =~ "period must be >= 1"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = ROC.roc([], 3)
+    end
+
+    test "handles insufficient data" do
+      data = [1.0, 2.0, 3.0]
+      assert {:ok, result} = ROC.roc(data, 3)
+      assert result == [nil, nil, nil]
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      data = [nil, nil, 1.0, 2.0, 4.0, 7.0]
+      assert {:ok, result} = ROC.roc(data, 2)
+      assert result == [nil, nil, nil, nil, 300.0, 250.0]
+    end
+  end
+
+  describe "roc/2 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      data =
+        DataSeries.new()
+        |> DataSeries.add(1.0)
+        |> DataSeries.add(2.0)
+        |> DataSeries.add(3.0)
+        |> DataSeries.add(4.0)
+        |> DataSeries.add(5.0)
+
+      assert {:ok, result} = ROC.roc(data, 3)
+      assert %DataSeries{} = result
+      assert [_, _, nil, nil, nil] = DataSeries.values(result)
+    end
+  end
+
+  describe "roc/2 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      ts =
+        TimeSeries.new()
+        |> TimeSeries.add(~U[2024-01-01 00:00:00Z], 1.0)
+        |> TimeSeries.add(~U[2024-01-01 00:01:00Z], 2.0)
+        |> TimeSeries.add(~U[2024-01-01 00:02:00Z], 3.0)
+        |> TimeSeries.add(~U[2024-01-01 00:03:00Z], 4.0)
+        |> TimeSeries.add(~U[2024-01-01 00:04:00Z], 5.0)
+
+      assert {:ok, result} = ROC.roc(ts, 3)
+      assert %TimeSeries{} = result
+      assert [_, _, nil, nil, nil] = TimeSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} =
+               ROC.init(period: 10, data: "eurusd_m1", name: "roc10", source: :close)
+    end
+
+    test "returns error for period < 1" do
+      assert {:error, msg} =
+               ROC.init(period: 0, data: "eurusd_m1", name: "roc0", source: :close)
+
+      assert msg =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "emits the first value exactly at the lookback, like batch" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      {:ok, batch_result} = ROC.roc(data, 3)
+      {:ok, state} = ROC.init(period: 3, data: "eurusd_m1", name: "roc3", source: :close)
+
+      {stream_result, _state} =
+        Enum.map_reduce(data, state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_st} = ROC.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert stream_result == batch_result
+    end
+
+    test "processes bars correctly in UPDATE mode" do
+      {:ok, state} = ROC.init(period: 2, data: "eurusd_m1", name: "roc2", source: :close)
+
+      state =
+        Enum.reduce([1.0, 2.0, 4.0], state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, _result, new_st} = ROC.next(event, st)
+          new_st
+        end)
+
+      # Replace the last bar (4.0 -> 8.0): the window doesn't shift
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 8.0, new_bar?: false}}}
+      {:ok, result, state} = ROC.next(event, state)
+      assert result.value == 700.0
+
+      # The next bar compares against the second bar (2.0)
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 5.0, new_bar?: true}}}
+      {:ok, result, _state} = ROC.next(event, state)
+      assert result.value == 150.0
+    end
+
+    test "handles nil values from upstream indicators" do
+      {:ok, state} = ROC.init(period: 1, data: "indicator", name: "roc1", source: :close)
+
+      {results, _state} =
+        Enum.map_reduce([nil, 100.0, 110.0], state, fn value, st ->
+          event = %MarketEvent{
+            data: %{
+              "indicator" => %IndicatorValue{value: value, data_name: "eurusd_m1"},
+              "eurusd_m1" => %Bar{close: 1.23, new_bar?: true}
+            }
+          }
+
+          {:ok, result, new_st} = ROC.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert [nil, nil, value] = results
+      assert_in_delta(value, 10.0, 1.0e-9)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch ROC" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 21, max_length: 500),
+              period <- integer(1..50)
+            ) do
+        {:ok, batch_result} = ROC.roc(data, period)
+
+        {:ok, initial_state} =
+          ROC.init(period: period, data: "test", name: "roc", source: :close)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected_value}, state ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: value, new_bar?: true}}
+          }
+
+          {:ok, result, new_state} = ROC.next(event, state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 15, max_length: 200),
+              period <- integer(1..30),
+              update_values <-
+                list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 5)
+            ) do
+        {:ok, state} = ROC.init(period: period, data: "test", name: "roc", source: :close)
+
+        final_state =
+          Enum.reduce(data, state, fn value, st ->
+            event = %MarketEvent{
+              data: %{"test" => %Bar{close: value, new_bar?: true}}
+            }
+
+            {:ok, _result, new_state} = ROC.next(event, st)
+            new_state
+          end)
+
+        Enum.reduce(update_values, {final_state, data}, fn update_value, {state, current_data} ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: update_value, new_bar?: false}}
+          }
+
+          {:ok, result, new_state} = ROC.next(event, state)
+
+          updated_data = List.replace_at(current_data, -1, update_value)
+          {:ok, batch_result} = ROC.roc(updated_data, period)
+          batch_roc = List.last(batch_result)
+
+          case {result.value, batch_roc} do
+            {nil, nil} ->
+              :ok
+
+            {s_val, b_val} when is_float(s_val) and is_float(b_val) ->
+              assert_in_delta(s_val, b_val, 1.0e-9)
+
+            _ ->
+              flunk("Mismatch between state UPDATE and batch")
+          end
+
+          {new_state, updated_data}
+        end)
+      end
+    end
+  end
+end
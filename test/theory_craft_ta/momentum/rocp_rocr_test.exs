@@ -0,0 +1,217 @@
+defmodule TheoryCraftTA.Momentum.ROCPROCRTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.{ROCP, ROCR, ROCR100}
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Momentum.ROCP
+  doctest TheoryCraftTA.Momentum.ROCR
+  doctest TheoryCraftTA.Momentum.ROCR100
+
+  @data [1.0, 2.0, 4.0, 5.0]
+
+  # {name, batch function, state init, state next}
+  @variants [
+    {"ROCP", &Native.momentum_rocp/2, &Native.momentum_rocp_state_init/1,
+     &Native.momentum_rocp_state_next/3},
+    {"ROCR", &Native.momentum_rocr/2, &Native.momentum_rocr_state_init/1,
+     &Native.momentum_rocr_state_next/3},
+    {"ROCR100", &Native.momentum_rocr100/2, &Native.momentum_rocr100_state_init/1,
+     &Native.momentum_rocr100_state_next/3}
+  ]
+
+  # {module, batch function, native batch function}
+  @wrappers [
+    {ROCP, &ROCP.rocp/2, &Native.momentum_rocp/2},
+    {ROCR, &ROCR.rocr/2, &Native.momentum_rocr/2},
+    {ROCR100, &ROCR100.rocr100/2, &Native.momentum_rocr100/2}
+  ]
+
+  ## Batch calculation tests
+
+  describe "momentum_rocp/2, momentum_rocr/2 and momentum_rocr100/2" do
+    test "calculate correctly with period=1" do
+      assert {:ok, [nil, 1.0, 1.0, 0.25]} = Native.momentum_rocp(@data, 1)
+      assert {:ok, [nil, 2.0, 2.0, 1.25]} = Native.momentum_rocr(@data, 1)
+      assert {:ok, [nil, 200.0, 200.0, 125.0]} = Native.momentum_rocr100(@data, 1)
+    end
+
+    test "handle NaN at beginning (warmup scenario)" do
+      assert {:ok, [nil, nil, nil, 1.5]} = Native.momentum_rocp([nil | Enum.drop(@data, 1)], 2)
+    end
+
+    test "return error for period < 1" do
+      for {name, batch, _init, _next} <- @variants do
+        assert {:error, reason} = batch.(@data, 0)
+        assert reason == "#{name}: period must be >= 1"
+      end
+    end
+
+    test "return empty for empty input" do
+      for {_name, batch, _init, _next} <- @variants do
+        assert {:ok, []} = batch.([], 2)
+      end
+    end
+  end
+
+  ## Streaming tests
+
+  describe "state_init/1" do
+    test "returns error for period < 1" do
+      for {name, _batch, init, _next} <- @variants do
+        assert {:error, reason} = init.(0)
+        assert reason == "Invalid period: must be >= 1 for #{name}"
+      end
+    end
+  end
+
+  describe "state_next/3" do
+    test "replaying the series matches batch" do
+      for {_name, batch, init, next} <- @variants do
+        {:ok, expected} = batch.(@data, 2)
+        assert stream(init, next, @data, 2) == expected
+      end
+    end
+
+    test "returns nil for a zero trailing value, where TA-Lib returns 0" do
+      data = [0.0, 1.0, 2.0]
+
+      for {_name, batch, init, next} <- @variants do
+        assert {:ok, [nil, 0.0, _]} = batch.(data, 1)
+        assert [nil, nil, value] = stream(init, next, data, 1)
+        assert is_float(value)
+      end
+    end
+
+    test "returns nil without modifying the state on nil input" do
+      for {_name, _batch, init, next} <- @variants do
+        {:ok, state} = init.(1)
+        {:ok, {nil, state}} = next.(state, 1.0, true)
+
+        assert {:ok, {nil, ^state}} = next.(state, nil, true)
+      end
+    end
+  end
+
+  ## Public API tests
+
+  describe "rocp/2, rocr/2 and rocr100/2" do
+    test "match the native calculation" do
+      for {_module, batch, native} <- @wrappers do
+        assert batch.(@data, 2) == native.(@data, 2)
+      end
+    end
+
+    test "maintain DataSeries type in output" do
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = ROCR.rocr(ds, 1)
+      assert DataSeries.values(result) == [1.25, 2.0, 2.0, nil]
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      for {module, _batch, _native} <- @wrappers do
+        assert {:error, reason} = module.init(period: 0, data: "eurusd_m1", name: "roc")
+        assert reason =~ "Invalid period"
+      end
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      for {module, batch, _native} <- @wrappers do
+        {:ok, batch_result} = batch.(@data, 2)
+        {:ok, state} = module.init(period: 2, data: "eurusd_m1", name: "roc")
+
+        {stream_result, _state} =
+          Enum.map_reduce(@data, state, fn value, st ->
+            {:ok, result, new_st} = module.next(close_event(value, true), st)
+            {result.value, new_st}
+          end)
+
+        assert_values_in_delta(stream_result, batch_result)
+      end
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = ROCP.init(period: 2, data: "eurusd_m1", name: "rocp")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = ROCP.next(close_event(value, true), st)
+          new_st
+        end)
+
+      # The last bar now closes at 6.0, 2 bars after 2.0
+      assert {:ok, %{value: 2.0}, _state} = ROCP.next(close_event(6.0, false), state)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches each batch function" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 200),
+              period <- integer(1..20)
+            ) do
+        for {_name, batch, init, next} <- @variants do
+          {:ok, expected} = batch.(data, period)
+          assert_values_in_delta(stream(init, next, data, period), expected)
+        end
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 25, max_length: 100),
+              period <- integer(1..20),
+              updates <- list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 5)
+            ) do
+        for {_name, batch, init, next} <- @variants do
+          {:ok, state} = init.(period)
+
+          state =
+            Enum.reduce(data, state, fn value, state ->
+              {:ok, {_value, state}} = next.(state, value, true)
+              state
+            end)
+
+          Enum.reduce(updates, state, fn update, state ->
+            {:ok, {result, state}} = next.(state, update, false)
+            {:ok, expected} = batch.(List.replace_at(data, -1, update), period)
+            assert_in_delta(result, List.last(expected), 1.0e-9)
+            state
+          end)
+        end
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp stream(init, next, data, period) do
+    {:ok, state} = init.(period)
+
+    {values, _state} =
+      Enum.map_reduce(data, state, fn value, state ->
+        {:ok, {result, state}} = next.(state, value, true)
+        {result, state}
+      end)
+
+    values
+  end
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
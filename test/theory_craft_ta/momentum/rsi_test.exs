@@ -0,0 +1,310 @@
+defmodule TheoryCraftTA.Momentum.RSITest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Momentum.RSI
+
+  doctest TheoryCraftTA.Momentum.RSI
+
+  ## Batch calculation tests
+
+  describe "rsi/2 with list input" do
+    test "calculates correctly with period=3" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      # Python result: [nan nan nan 83.33333333 66.66666667 82.45614035 66.66666667 82.0952381]
+      assert {:ok, result} = RSI.rsi(data, 3)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        83.33333333333334,
+        66.66666666666667,
+        82.45614035087719,
+        66.66666666666667,
+        82.09523809523809
+      ])
+    end
+
+    test "returns 100 for a steady uptrend and 0 for a steady downtrend" do
+      assert {:ok, up} = RSI.rsi([1.0, 2.0, 3.0, 4.0, 5.0], 3)
+      assert up == [nil, nil, nil, 100.0, 100.0]
+
+      assert {:ok, down} = RSI.rsi([5.0, 4.0, 3.0, 2.0, 1.0], 3)
+      assert down == [nil, nil, nil, 0.0, 0.0]
+    end
+
+    test "returns 0 for a flat series" do
+      assert {:ok, result} = RSI.rsi([1.0, 1.0, 1.0, 1.0, 1.0], 3)
+      assert result == [nil, nil, nil, 0.0, 0.0]
+    end
+
+    test "raises for period=1" do
+      data = [1.0, 2.0, 3.0]
+      assert {:error, reason} = RSI.rsi(data, 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = RSI.rsi([], 3)
+    end
+
+    test "handles insufficient data (period >= data length)" do
+      data = [1.0, 2.0, 3.0]
+      assert {:ok, result} = RSI.rsi(data, 3)
+      assert result == [nil, nil, nil]
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0]
+      # Python result: [nan nan nan nan nan 100. 100.]
+      assert {:ok, result} = RSI.rsi(data, 3)
+      assert result == [nil, nil, nil, nil, nil, 100.0, 100.0]
+    end
+
+    test "handles NaN in middle (invalid data scenario)" do
+      data = [1.0, 2.0, 3.0, nil, 5.0, 6.0, 7.0, 8.0]
+      # Python result: [nan nan nan nan nan nan nan nan]
+      assert {:ok, result} = RSI.rsi(data, 3)
+      assert result == [nil, nil, nil, nil, nil, nil, nil, nil]
+    end
+  end
+
+  describe "rsi/2 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      data =
+        DataSeries.new()
+        |> DataSeries.add(1.0)
+        |> DataSeries.add(2.0)
+        |> DataSeries.add(3.0)
+        |> DataSeries.add(4.0)
+        |> DataSeries.add(5.0)
+
+      assert {:ok, result} = RSI.rsi(data, 3)
+      assert %DataSeries{} = result
+      assert [100.0, 100.0, nil, nil, nil] = DataSeries.values(result)
+    end
+  end
+
+  describe "rsi/2 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      ts =
+        TimeSeries.new()
+        |> TimeSeries.add(~U[2024-01-01 00:00:00Z], 1.0)
+        |> TimeSeries.add(~U[2024-01-01 00:01:00Z], 2.0)
+        |> TimeSeries.add(~U[2024-01-01 00:02:00Z], 3.0)
+        |> TimeSeries.add(~U[2024-01-01 00:03:00Z], 4.0)
+        |> TimeSeries.add(~U[2024-01-01 00:04:00Z], 5.0)
+
+      assert {:ok, result} = RSI.rsi(ts, 3)
+      assert %TimeSeries{} = result
+      assert [100.0, 100.0, nil, nil, nil] = TimeSeries.values(result)
+    end
+  end
+
+  describe "momentum_rsi_f32/2" do
+    property "matches the f64 result rounded to f32" do
+      # Quarters are exact in f32, so only the output narrowing can differ
+      check all(
+              data <- list_of(map(integer(4..4000), &(&1 / 4)), min_length: 10, max_length: 200),
+              period <- integer(2..10)
+            ) do
+        {:ok, f64_result} = Native.momentum_rsi(data, period)
+        {:ok, f32_result} = Native.momentum_rsi_f32(data, period)
+
+        assert length(f32_result) == length(f64_result)
+
+        Enum.zip_with(f32_result, f64_result, fn
+          nil, nil -> :ok
+          val, exp -> assert_in_delta(val, exp, abs(exp) * 1.0e-6)
+        end)
+      end
+    end
+
+    test "treats nil as missing like the f64 path" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+      {:ok, f64_result} = Native.momentum_rsi(data, 3)
+      assert {:ok, f32_result} = Native.momentum_rsi_f32(data, 3)
+      assert Enum.map(f32_result, &is_nil/1) == Enum.map(f64_result, &is_nil/1)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} =
+               RSI.init(period: 14, data: "eurusd_m1", name: "rsi14", source: :close)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, msg} = RSI.init(period: 1, data: "eurusd_m1", name: "rsi1", source: :close)
+      assert msg =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "emits the first value after period deltas, like batch" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      {:ok, batch_result} = RSI.rsi(data, 3)
+      {:ok, state} = RSI.init(period: 3, data: "eurusd_m1", name: "rsi3", source: :close)
+
+      {stream_result, _state} =
+        Enum.map_reduce(data, state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_st} = RSI.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert Enum.find_index(stream_result, & &1) == 3
+
+      for {stream_value, batch_value} <- Enum.zip(stream_result, batch_result) do
+        case batch_value do
+          nil -> assert stream_value == nil
+          _ -> assert_in_delta(stream_value, batch_value, 1.0e-9)
+        end
+      end
+    end
+
+    test "processes bars correctly in UPDATE mode" do
+      {:ok, state} = RSI.init(period: 3, data: "eurusd_m1", name: "rsi3", source: :close)
+
+      state =
+        Enum.reduce([10.0, 11.0, 10.5, 12.0, 11.5], state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, _result, new_st} = RSI.next(event, st)
+          new_st
+        end)
+
+      # Replace the last bar twice (11.5 -> 13.0 -> 9.0): uses the persisted averages
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 13.0, new_bar?: false}}}
+      {:ok, result1, state} = RSI.next(event, state)
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 9.0, new_bar?: false}}}
+      {:ok, result2, _state} = RSI.next(event, state)
+
+      {:ok, batch1} = RSI.rsi([10.0, 11.0, 10.5, 12.0, 13.0], 3)
+      {:ok, batch2} = RSI.rsi([10.0, 11.0, 10.5, 12.0, 9.0], 3)
+      assert_in_delta(result1.value, List.last(batch1), 1.0e-9)
+      assert_in_delta(result2.value, List.last(batch2), 1.0e-9)
+    end
+
+    test "handles nil values from upstream indicators" do
+      {:ok, state} = RSI.init(period: 2, data: "indicator", name: "rsi2", source: :close)
+
+      # First value is nil (upstream not ready)
+      event1 = %MarketEvent{
+        data: %{
+          "indicator" => %IndicatorValue{value: nil, data_name: "eurusd_m1"},
+          "eurusd_m1" => %Bar{close: 1.23, new_bar?: true}
+        }
+      }
+
+      {:ok, result1, state} = RSI.next(event1, state)
+      assert result1.value == nil
+
+      # Next values are valid, the first RSI needs 2 deltas (3 values)
+      {results, _state} =
+        Enum.map_reduce([1.0, 2.0, 3.0], state, fn value, st ->
+          event = %MarketEvent{
+            data: %{
+              "indicator" => %IndicatorValue{value: value, data_name: "eurusd_m1"},
+              "eurusd_m1" => %Bar{close: 1.24, new_bar?: true}
+            }
+          }
+
+          {:ok, result, new_st} = RSI.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert results == [nil, nil, 100.0]
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch RSI" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 21, max_length: 500),
+              period <- integer(2..50)
+            ) do
+        {:ok, batch_result} = RSI.rsi(data, period)
+
+        {:ok, initial_state} = RSI.init(period: period, data: "test", name: "rsi", source: :close)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected_value}, state ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: value, new_bar?: true}}
+          }
+
+          {:ok, result, new_state} = RSI.next(event, state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 15, max_length: 200),
+              period <- integer(2..30),
+              update_values <-
+                list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 5)
+            ) do
+        {:ok, state} = RSI.init(period: period, data: "test", name: "rsi", source: :close)
+
+        final_state =
+          Enum.reduce(data, state, fn value, st ->
+            event = %MarketEvent{
+              data: %{"test" => %Bar{close: value, new_bar?: true}}
+            }
+
+            {:ok, _result, new_state} = RSI.next(event, st)
+            new_state
+          end)
+
+        Enum.reduce(update_values, {final_state, data}, fn update_value, {state, current_data} ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: update_value, new_bar?: false}}
+          }
+
+          {:ok, result, new_state} = RSI.next(event, state)
+
+          updated_data = List.replace_at(current_data, -1, update_value)
+          {:ok, batch_result} = RSI.rsi(updated_data, period)
+          batch_rsi = List.last(batch_result)
+
+          case {result.value, batch_rsi} do
+            {nil, nil} ->
+              :ok
+
+            {s_val, b_val} when is_float(s_val) and is_float(b_val) ->
+              assert_in_delta(s_val, b_val, 1.0e-9)
+
+            _ ->
+              flunk("Mismatch between state UPDATE and batch")
+          end
+
+          {new_state, updated_data}
+        end)
+      end
+    end
+  end
+end
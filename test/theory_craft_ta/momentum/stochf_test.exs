@@ -0,0 +1,94 @@
+defmodule TheoryCraftTA.Momentum.STOCHFTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "momentum_stochf/6" do
+    test "calculates correctly with fastk_period=3, fastd_period=2" do
+      high = [3.0, 4.0, 5.0, 6.0, 5.0, 4.0]
+      low = [1.0, 2.0, 3.0, 4.0, 3.0, 2.0]
+      close = [2.0, 3.0, 4.0, 5.0, 4.0, 3.0]
+
+      # %K: 100 * (close - lowest low) / (highest high - lowest low) over 3 bars
+      # => 75.0 (from index 2), 75.0, 33.33, 25.0; %D: SMA(2) of %K
+      assert {:ok, {fastk, fastd}} = Native.momentum_stochf(high, low, close, 3, 2, 0)
+
+      expected_fastk = [nil, nil, nil, 75.0, 100.0 / 3, 25.0]
+      expected_fastd = [nil, nil, nil, 75.0, (75.0 + 100.0 / 3) / 2, (100.0 / 3 + 25.0) / 2]
+
+      for {value, expected} <- Enum.zip(fastk ++ fastd, expected_fastk ++ expected_fastd) do
+        case expected do
+          nil -> assert value == nil
+          _ -> assert_in_delta value, expected, 1.0e-9
+        end
+      end
+    end
+
+    test "skips leading bars where any input is nil" do
+      high = [nil, 3.0, 4.0, 5.0]
+      low = [1.0, 1.0, 2.0, 3.0]
+      close = [2.0, 2.0, 3.0, 4.0]
+
+      assert {:ok, {[nil, nil, nil, 75.0], [nil, nil, nil, 75.0]}} =
+               Native.momentum_stochf(high, low, close, 3, 1, 0)
+    end
+
+    test "returns error for invalid periods" do
+      assert {:error, reason} = Native.momentum_stochf([1.0], [1.0], [1.0], 0, 3, 0)
+      assert reason =~ "must be >= 1"
+
+      assert {:error, reason} = Native.momentum_stochf([1.0], [1.0], [1.0], 5, 0, 0)
+      assert reason =~ "must be >= 1"
+    end
+
+    test "returns error for an invalid MA type" do
+      assert {:error, reason} = Native.momentum_stochf([1.0], [1.0], [1.0], 5, 3, 9)
+      assert reason =~ "fastd_ma_type must be between 0 and 8"
+    end
+
+    test "returns error for inputs of different lengths" do
+      assert {:error, reason} = Native.momentum_stochf([1.0, 2.0], [1.0], [1.0, 2.0], 5, 3, 0)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], []}} = Native.momentum_stochf([], [], [], 5, 3, 0)
+    end
+
+    test "handles insufficient data" do
+      assert {:ok, {[nil, nil], [nil, nil]}} =
+               Native.momentum_stochf([2.0, 3.0], [1.0, 2.0], [1.5, 2.5], 2, 2, 0)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: fast stochastic bounds" do
+    property "%K stays within 0..100 and equals %D when fastd_period=1" do
+      check all(
+              bars <-
+                list_of(
+                  tuple({float(min: 1.0, max: 1000.0), float(min: 0.0, max: 50.0)}),
+                  min_length: 1,
+                  max_length: 200
+                ),
+              fastk_period <- integer(1..20)
+            ) do
+        low = Enum.map(bars, fn {low, _range} -> low end)
+        high = Enum.map(bars, fn {low, range} -> low + range end)
+        close = Enum.map(bars, fn {low, range} -> low + range / 2 end)
+
+        {:ok, {fastk, fastd}} = Native.momentum_stochf(high, low, close, fastk_period, 1, 0)
+
+        assert fastk == fastd
+
+        for value <- fastk, value != nil do
+          assert value >= 0.0 and value <= 100.0
+        end
+      end
+    end
+  end
+end
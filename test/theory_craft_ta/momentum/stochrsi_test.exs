@@ -0,0 +1,66 @@
+defmodule TheoryCraftTA.Momentum.STOCHRSITest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "momentum_stochrsi/5" do
+    test "first value comes after the RSI, %K and %D lookbacks" do
+      data = Enum.map(1..40, &(:math.sin(&1 / 3) * 10.0 + 50.0))
+
+      assert {:ok, {fastk, fastd}} = Native.momentum_stochrsi(data, 14, 5, 3, 0)
+
+      # RSI(14): 14, %K(5): 4, %D(3): 2
+      assert Enum.find_index(fastk, &(&1 != nil)) == 20
+      assert Enum.find_index(fastd, &(&1 != nil)) == 20
+      assert length(fastk) == 40
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_stochrsi([1.0, 2.0, 3.0], 1, 5, 3, 0)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns error for invalid fastk or fastd periods" do
+      assert {:error, reason} = Native.momentum_stochrsi([1.0, 2.0, 3.0], 14, 0, 3, 0)
+      assert reason =~ "must be >= 1"
+
+      assert {:error, reason} = Native.momentum_stochrsi([1.0, 2.0, 3.0], 14, 5, 0, 0)
+      assert reason =~ "must be >= 1"
+    end
+
+    test "returns error for an invalid MA type" do
+      assert {:error, reason} = Native.momentum_stochrsi([1.0, 2.0, 3.0], 14, 5, 3, -1)
+      assert reason =~ "fastd_ma_type must be between 0 and 8"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], []}} = Native.momentum_stochrsi([], 14, 5, 3, 0)
+    end
+
+    test "handles insufficient data" do
+      assert {:ok, {[nil, nil, nil], [nil, nil, nil]}} =
+               Native.momentum_stochrsi([1.0, 2.0, 3.0], 2, 2, 2, 0)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: STOCHRSI is the fast stochastic of RSI" do
+    property "matches momentum_stochf applied to momentum_rsi" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 1, max_length: 200),
+              period <- integer(2..20),
+              fastk_period <- integer(1..10),
+              fastd_period <- integer(1..5)
+            ) do
+        {:ok, rsi} = Native.momentum_rsi(data, period)
+        expected = Native.momentum_stochf(rsi, rsi, rsi, fastk_period, fastd_period, 0)
+
+        assert Native.momentum_stochrsi(data, period, fastk_period, fastd_period, 0) == expected
+      end
+    end
+  end
+end
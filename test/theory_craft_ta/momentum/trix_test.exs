@@ -0,0 +1,182 @@
+defmodule TheoryCraftTA.Momentum.TRIXTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.TRIX
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Momentum.TRIX
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0]
+
+  ## Batch calculation tests
+
+  describe "momentum_trix/2" do
+    test "starts after the three EMA warmups and one extra bar" do
+      data = Enum.map(1..20, &(&1 * 1.0))
+
+      # lookback = 3 * (period - 1) + 1
+      assert {:ok, result} = Native.momentum_trix(data, 3)
+      assert Enum.take(result, 7) == List.duplicate(nil, 7)
+      assert Enum.all?(Enum.drop(result, 7), &is_float/1)
+    end
+
+    test "is zero on a constant series" do
+      assert {:ok, result} = Native.momentum_trix(List.duplicate(5.0, 10), 2)
+      assert Enum.drop(result, 4) == List.duplicate(0.0, 6)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_trix([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.momentum_trix([], 3)
+    end
+
+    test "handles insufficient data" do
+      assert {:ok, [nil, nil, nil]} = Native.momentum_trix([1.0, 2.0, 3.0], 2)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "momentum_trix_state_init/1" do
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.momentum_trix_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Public API tests
+
+  describe "trix/2" do
+    test "matches the native calculation" do
+      assert TRIX.trix(@data, 2) == Native.momentum_trix(@data, 2)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.momentum_trix(@data, 2)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = TRIX.trix(ds, 2)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = TRIX.init(period: 1, data: "eurusd_m1", name: "trix")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = TRIX.trix(@data, 2)
+      {:ok, state} = TRIX.init(period: 2, data: "eurusd_m1", name: "trix")
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = TRIX.next(close_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = TRIX.init(period: 2, data: "eurusd_m1", name: "trix")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = TRIX.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = TRIX.next(close_event(100.0, false), state)
+      {:ok, batch_result} = TRIX.trix(List.replace_at(@data, -1, 100.0), 2)
+
+      assert_same_value(result.value, List.last(batch_result))
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch TRIX" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, batch_result} = Native.momentum_trix(data, period)
+        {:ok, initial_state} = Native.momentum_trix_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          {:ok, {result, new_state}} = Native.momentum_trix_state_next(state, value, true)
+          assert_same_value(result, expected)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20),
+              update_value <- float(min: 1.0, max: 1000.0)
+            ) do
+        {:ok, state} = Native.momentum_trix_state_init(period)
+
+        state =
+          Enum.reduce(data, state, fn value, st ->
+            {:ok, {_result, new_st}} = Native.momentum_trix_state_next(st, value, true)
+            new_st
+          end)
+
+        {:ok, {result, _state}} = Native.momentum_trix_state_next(state, update_value, false)
+        {:ok, batch_result} =
+          Native.momentum_trix(List.replace_at(data, -1, update_value), period)
+
+        assert_same_value(result, List.last(batch_result))
+      end
+    end
+
+    property "UPDATE before each APPEND ends on the batch result" do
+      check all(
+              data <- list_of({price(), price()}, min_length: 10, max_length: 100),
+              period <- integer(2..10)
+            ) do
+        final_data = Enum.map(data, fn {_forming, final} -> final end)
+        {:ok, batch_result} = Native.momentum_trix(final_data, period)
+        {:ok, initial_state} = Native.momentum_trix_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {{forming, final}, expected}, state ->
+          {:ok, {_result, state}} = Native.momentum_trix_state_next(state, forming, true)
+          {:ok, {result, new_state}} = Native.momentum_trix_state_next(state, final, false)
+          assert_same_value(result, expected)
+          new_state
+        end)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp price(), do: float(min: 1.0, max: 1000.0)
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
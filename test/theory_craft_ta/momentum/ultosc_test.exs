@@ -0,0 +1,242 @@
+defmodule TheoryCraftTA.Momentum.ULTOSCTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.ULTOSC
+  alias TheoryCraftTA.Native
+
+  doctest TheoryCraftTA.Momentum.ULTOSC
+
+  @high [10.0, 11.0, 12.0, 11.5, 13.0]
+  @low [9.0, 10.0, 10.5, 10.0, 11.0]
+  @close [9.5, 10.5, 11.5, 10.5, 12.5]
+
+  @opts [period1: 1, period2: 2, period3: 3, data: "eurusd_m1", name: "ultosc"]
+
+  ## Batch calculation tests
+
+  describe "momentum_ultosc/6" do
+    test "calculates correctly with periods 1, 2 and 3" do
+      # Bar 3: 4 × 0.5/1.5 + 2 × 1.5/3.0 + 2.5/4.5, over the weights (7), in percent
+      assert {:ok, [nil, nil, nil, value1, value2]} =
+               Native.momentum_ultosc(@high, @low, @close, 1, 2, 3)
+
+      assert_in_delta value1, 41.2698413, 1.0e-6
+      assert_in_delta value2, 72.6623377, 1.0e-6
+    end
+
+    test "sorts the periods, like TA-Lib" do
+      assert Native.momentum_ultosc(@high, @low, @close, 3, 1, 2) ==
+               Native.momentum_ultosc(@high, @low, @close, 1, 2, 3)
+    end
+
+    test "returns error for a period < 1" do
+      assert {:error, reason} = Native.momentum_ultosc(@high, @low, @close, 0, 2, 3)
+      assert reason == "ULTOSC: periods must be >= 1"
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = Native.momentum_ultosc(@high, @low, Enum.drop(@close, 1), 1, 2, 3)
+      assert reason == "ULTOSC: high/low/close length mismatch (5/5/4)"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.momentum_ultosc([], [], [], 7, 14, 28)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "momentum_ultosc_state_init/3" do
+    test "returns error for a period < 1" do
+      assert {:error, reason} = Native.momentum_ultosc_state_init(7, 0, 28)
+      assert reason == "Invalid period: must be >= 1 for ULTOSC"
+    end
+  end
+
+  describe "momentum_ultosc_state_next/5" do
+    test "replaying the series matches batch" do
+      {:ok, expected} = Native.momentum_ultosc(@high, @low, @close, 3, 1, 2)
+      {:ok, state} = Native.momentum_ultosc_state_init(3, 1, 2)
+
+      assert_values_in_delta(stream(state, Enum.zip([@high, @low, @close])), expected, 1.0e-6)
+    end
+
+    test "UPDATE keeps the close of the previous bar" do
+      {:ok, state} = Native.momentum_ultosc_state_init(1, 2, 3)
+      bars = Enum.zip([@high, @low, @close])
+
+      state =
+        Enum.reduce(Enum.take(bars, 4), state, fn bar, st ->
+          {:ok, {_value, new_st}} = step(st, bar, true)
+          new_st
+        end)
+
+      {:ok, {_value, state}} = step(state, {11.0, 9.0, 9.5}, false)
+
+      # The 4th bar of the batch, given as an update of the forming bar
+      assert {:ok, {value, _state}} = step(state, {11.5, 10.0, 10.5}, false)
+
+      assert_in_delta value, 41.2698413, 1.0e-6
+    end
+
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.momentum_ultosc_state_init(1, 2, 3)
+      {:ok, {nil, state}} = step(state, {10.0, 9.0, 9.5}, true)
+
+      assert {:ok, {nil, ^state}} = step(state, {11.0, nil, 10.5}, true)
+    end
+  end
+
+  ## Public API tests
+
+  describe "ultosc/6" do
+    test "matches the native calculation" do
+      assert ULTOSC.ultosc(@high, @low, @close, 1, 2, 3) ==
+               Native.momentum_ultosc(@high, @low, @close, 1, 2, 3)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.momentum_ultosc(@high, @low, @close, 1, 2, 3)
+      [high, low, close] = for values <- [@high, @low, @close], do: data_series(values)
+
+      assert {:ok, %DataSeries{} = result} = ULTOSC.ultosc(high, low, close, 1, 2, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "uses the usual 7, 14 and 28 periods by default" do
+      assert {:ok, %ULTOSC{period1: 7, period2: 14, period3: 28}} =
+               ULTOSC.init(data: "eurusd_m1", name: "ultosc")
+    end
+
+    test "returns error for an invalid period" do
+      assert {:error, reason} = ULTOSC.init(period2: 0, data: "eurusd_m1", name: "ultosc")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = ULTOSC.ultosc(@high, @low, @close, 1, 2, 3)
+      {:ok, state} = ULTOSC.init(@opts)
+
+      {stream_result, _state} =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = ULTOSC.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result, 1.0e-6)
+    end
+
+    test "UPDATE replaces the high, low and close of the current bar" do
+      {:ok, state} = ULTOSC.init(@opts)
+
+      state =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.reduce(state, fn bar, st ->
+          {:ok, _result, new_st} = ULTOSC.next(bar_event(bar, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = ULTOSC.next(bar_event({12.0, 10.0, 10.5}, false), state)
+
+      high = List.replace_at(@high, -1, 12.0)
+      low = List.replace_at(@low, -1, 10.0)
+      close = List.replace_at(@close, -1, 10.5)
+      {:ok, batch_result} = ULTOSC.ultosc(high, low, close, 1, 2, 3)
+
+      assert_same_value(result.value, List.last(batch_result), 1.0e-6)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch ULTOSC" do
+      check all(
+              bars <- list_of(bar(), min_length: 1, max_length: 200),
+              periods <- list_of(integer(1..30), length: 3)
+            ) do
+        [period1, period2, period3] = periods
+        [high, low, close] = for i <- 0..2, do: Enum.map(bars, &elem(&1, i))
+
+        {:ok, expected} = Native.momentum_ultosc(high, low, close, period1, period2, period3)
+        {:ok, state} = Native.momentum_ultosc_state_init(period1, period2, period3)
+
+        assert_values_in_delta(stream(state, bars), expected, 1.0e-6)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE before each APPEND ends on the batch result" do
+      check all(
+              bars <- list_of({bar(), bar()}, min_length: 1, max_length: 100),
+              periods <- list_of(integer(1..20), length: 3)
+            ) do
+        [period1, period2, period3] = periods
+        final_bars = Enum.map(bars, fn {_forming, final} -> final end)
+        [high, low, close] = for i <- 0..2, do: Enum.map(final_bars, &elem(&1, i))
+
+        {:ok, expected} = Native.momentum_ultosc(high, low, close, period1, period2, period3)
+        {:ok, state} = Native.momentum_ultosc_state_init(period1, period2, period3)
+
+        {values, _state} =
+          Enum.map_reduce(bars, state, fn {forming, final}, st ->
+            {:ok, {_value, st}} = step(st, forming, true)
+            {:ok, {value, st}} = step(st, final, false)
+            {value, st}
+          end)
+
+        assert_values_in_delta(values, expected, 1.0e-6)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp bar() do
+    # The close is placed in the range, which can be empty (high == low)
+    gen all(
+          low <- float(min: 1.0, max: 1000.0),
+          range <- one_of([constant(0.0), float(min: 0.0, max: 50.0)]),
+          position <- float(min: 0.0, max: 1.0)
+        ) do
+      {low + range, low, low + range * position}
+    end
+  end
+
+  defp step(state, {high, low, close}, new_bar?) do
+    Native.momentum_ultosc_state_next(state, high, low, close, new_bar?)
+  end
+
+  defp stream(state, bars) do
+    {values, _state} =
+      Enum.map_reduce(bars, state, fn bar, st ->
+        {:ok, {value, new_st}} = step(st, bar, true)
+        {value, new_st}
+      end)
+
+    values
+  end
+
+  defp bar_event({high, low, close}, new_bar?) do
+    %MarketEvent{
+      data: %{"eurusd_m1" => %Bar{high: high, low: low, close: close, new_bar?: new_bar?}}
+    }
+  end
+
+  defp data_series(values) do
+    Enum.reduce(values, DataSeries.new(), &DataSeries.add(&2, &1))
+  end
+end
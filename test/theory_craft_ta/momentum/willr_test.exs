@@ -0,0 +1,311 @@
+defmodule TheoryCraftTA.Momentum.WILLRTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.WILLR
+
+  doctest TheoryCraftTA.Momentum.WILLR
+
+  @high [12.0, 12.5, 12.2, 13.0, 13.4, 13.1, 13.8, 14.2]
+  @low [11.0, 11.6, 11.4, 12.1, 12.6, 12.4, 12.9, 13.3]
+  @close [11.5, 12.3, 11.9, 12.8, 13.2, 12.6, 13.6, 13.9]
+
+  ## Batch calculation tests
+
+  describe "willr/4 with list input" do
+    test "calculates correctly with period=3" do
+      # Python result: [nan nan -40. -12.5 -10. -61.53846154 -14.28571429 -16.66666667]
+      assert {:ok, result} = WILLR.willr(@high, @low, @close, 3)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        -39.99999999999998,
+        -12.499999999999957,
+        -10.000000000000053,
+        -61.53846153846156,
+        -14.285714285714358,
+        -16.666666666666618
+      ])
+    end
+
+    test "calculates correctly with period=5" do
+      # Python result: [nan nan nan nan -8.33333333 -40. -8.33333333 -14.28571429]
+      assert {:ok, result} = WILLR.willr(@high, @low, @close, 5)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        nil,
+        -8.333333333333377,
+        -40.000000000000036,
+        -8.333333333333377,
+        -14.285714285714237
+      ])
+    end
+
+    test "raises for period=1" do
+      assert {:error, reason} = WILLR.willr(@high, @low, @close, 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = WILLR.willr(@high, @low, Enum.drop(@close, 1), 3)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = WILLR.willr([], [], [], 3)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, result} =
+               WILLR.willr(Enum.take(@high, 2), Enum.take(@low, 2), Enum.take(@close, 2), 3)
+
+      assert result == [nil, nil]
+    end
+
+    test "returns 0 for a flat market" do
+      flat = List.duplicate(10.0, 5)
+      assert {:ok, [nil, nil, +0.0, +0.0, +0.0]} = WILLR.willr(flat, flat, flat, 3)
+    end
+
+    test "reaches -100 at the lowest low and 0 at the highest high" do
+      high = [12.0, 12.5, 12.2, 12.4]
+      low = [11.0, 11.6, 11.4, 11.5]
+      close = [11.5, 12.3, 11.0, 12.5]
+
+      assert {:ok, [nil, nil, lowest, highest]} = WILLR.willr(high, low, close, 3)
+      assert_in_delta(lowest, -100.0, 1.0e-9)
+      assert_in_delta(highest, 0.0, 1.0e-9)
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      high = [nil, nil | Enum.drop(@high, 2)]
+      low = [nil, nil | Enum.drop(@low, 2)]
+      close = [nil, nil | Enum.drop(@close, 2)]
+      # Python result: [nan nan nan nan -10. -61.53846154 -14.28571429 -16.66666667]
+      assert {:ok, result} = WILLR.willr(high, low, close, 3)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        nil,
+        -10.000000000000053,
+        -61.53846153846156,
+        -14.285714285714358,
+        -16.666666666666618
+      ])
+    end
+  end
+
+  describe "willr/4 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      high = Enum.reduce(@high, DataSeries.new(), &DataSeries.add(&2, &1))
+      low = Enum.reduce(@low, DataSeries.new(), &DataSeries.add(&2, &1))
+      close = Enum.reduce(@close, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, result} = WILLR.willr(high, low, close, 3)
+      assert %DataSeries{} = result
+      assert [_, _, _, _, _, _, nil, nil] = DataSeries.values(result)
+    end
+  end
+
+  describe "willr/4 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      times = Enum.map(0..7, &DateTime.add(~U[2024-01-01 00:00:00Z], &1, :minute))
+
+      [high, low, close] =
+        for values <- [@high, @low, @close] do
+          times
+          |> Enum.zip(values)
+          |> Enum.reduce(TimeSeries.new(), fn {time, value}, ts ->
+            TimeSeries.add(ts, time, value)
+          end)
+        end
+
+      assert {:ok, result} = WILLR.willr(high, low, close, 3)
+      assert %TimeSeries{} = result
+      assert [_, _, _, _, _, _, nil, nil] = TimeSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} = WILLR.init(period: 14, data: "eurusd_m1", name: "willr14")
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, msg} = WILLR.init(period: 1, data: "eurusd_m1", name: "willr1")
+      assert msg =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "matches batch once warmed up" do
+      {:ok, batch_result} = WILLR.willr(@high, @low, @close, 3)
+      {:ok, state} = WILLR.init(period: 3, data: "eurusd_m1", name: "willr3")
+
+      {stream_result, _state} =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = WILLR.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE replaces the high, low and close of the current bar" do
+      {:ok, state} = WILLR.init(period: 3, data: "eurusd_m1", name: "willr3")
+
+      state =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.take(4)
+        |> Enum.reduce(state, fn bar, st ->
+          {:ok, _result, new_st} = WILLR.next(bar_event(bar, true), st)
+          new_st
+        end)
+
+      # The 4th bar is still open: its high goes 13.0 -> 13.5, so the window is 11.4..13.5
+      {:ok, result1, state} = WILLR.next(bar_event({13.5, 12.1, 13.4}, false), state)
+      assert_in_delta(result1.value, -100 * (13.5 - 13.4) / (13.5 - 11.4), 1.0e-9)
+
+      # The high of the previous tick is forgotten: the window is now 11.2..12.5
+      {:ok, result2, _state} = WILLR.next(bar_event({12.3, 11.2, 11.3}, false), state)
+      assert_in_delta(result2.value, -100 * (12.5 - 11.3) / (12.5 - 11.2), 1.0e-9)
+    end
+
+    test "handles nil values without modifying the state" do
+      {:ok, state} = WILLR.init(period: 2, data: "eurusd_m1", name: "willr2")
+
+      event = %MarketEvent{
+        data: %{"eurusd_m1" => %Bar{high: nil, low: nil, close: nil, new_bar?: true}}
+      }
+
+      {:ok, result, state} = WILLR.next(event, state)
+      assert result.value == nil
+
+      {results, _state} =
+        [{12.0, 11.0, 11.5}, {12.5, 11.6, 12.3}]
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = WILLR.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      # The window is 11.0..12.5 and the close is 12.3
+      assert [nil, value] = results
+      assert_in_delta(value, -100 * (12.5 - 12.3) / (12.5 - 11.0), 1.0e-9)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch WILLR" do
+      check all(
+              bars <- bars_generator(21, 300),
+              period <- integer(2..50)
+            ) do
+        {high, low, close} = unzip_bars(bars)
+        {:ok, batch_result} = WILLR.willr(high, low, close, period)
+
+        {:ok, initial_state} = WILLR.init(period: period, data: "test", name: "willr")
+
+        bars
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {bar, expected_value}, state ->
+          {:ok, result, new_state} = WILLR.next(bar_event("test", bar, true), state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last bar" do
+      check all(
+              bars <- bars_generator(15, 200),
+              period <- integer(2..30),
+              update_bars <- bars_generator(2, 5)
+            ) do
+        {:ok, state} = WILLR.init(period: period, data: "test", name: "willr")
+
+        final_state =
+          Enum.reduce(bars, state, fn bar, st ->
+            {:ok, _result, new_state} = WILLR.next(bar_event("test", bar, true), st)
+            new_state
+          end)
+
+        Enum.reduce(update_bars, {final_state, bars}, fn update_bar, {state, current_bars} ->
+          {:ok, result, new_state} = WILLR.next(bar_event("test", update_bar, false), state)
+
+          updated_bars = List.replace_at(current_bars, -1, update_bar)
+          {high, low, close} = unzip_bars(updated_bars)
+          {:ok, batch_result} = WILLR.willr(high, low, close, period)
+          batch_willr = List.last(batch_result)
+
+          case {result.value, batch_willr} do
+            {nil, nil} ->
+              :ok
+
+            {s_val, b_val} when is_float(s_val) and is_float(b_val) ->
+              assert_in_delta(s_val, b_val, 1.0e-9)
+
+            _ ->
+              flunk("Mismatch between state UPDATE and batch")
+          end
+
+          {new_state, updated_bars}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp bar_event(data_name \\ "eurusd_m1", {high, low, close}, new_bar?) do
+    %MarketEvent{
+      data: %{data_name => %Bar{high: high, low: low, close: close, new_bar?: new_bar?}}
+    }
+  end
+
+  defp bars_generator(min_length, max_length) do
+    price = float(min: 1.0, max: 1000.0)
+    spread = float(min: 0.0, max: 10.0)
+
+    gen all(
+          bars <-
+            list_of(
+              tuple({price, spread, spread}),
+              min_length: min_length,
+              max_length: max_length
+            )
+        ) do
+      Enum.map(bars, fn {close, up, down} -> {close + up, close - down, close} end)
+    end
+  end
+
+  defp unzip_bars(bars) do
+    {Enum.map(bars, &elem(&1, 0)), Enum.map(bars, &elem(&1, 1)), Enum.map(bars, &elem(&1, 2))}
+  end
+end
@@ -0,0 +1,290 @@
+defmodule TheoryCraftTA.Overlap.BBANDSTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.Overlap.BBANDS
+
+  doctest TheoryCraftTA.Overlap.BBANDS
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+
+  ## Batch calculation tests
+
+  describe "bbands/5 with list input" do
+    test "calculates correctly with period=3 and SMA middle" do
+      assert {:ok, {upper, middle, lower}} = BBANDS.bbands(@data, 3, 2.0, 2.0, 0)
+
+      # Python result (middle): [nan nan 10.5 11.16666667 11.33333333 12.16666667 ...]
+      assert_values_in_delta(middle, [
+        nil,
+        nil,
+        10.5,
+        11.166666666666666,
+        11.333333333333334,
+        12.166666666666666,
+        12.333333333333334,
+        13.166666666666666
+      ])
+
+      # Python result (upper): [nan nan 11.31649658 12.4138858 12.58055246 13.4138858 ...]
+      assert_values_in_delta(upper, [
+        nil,
+        nil,
+        11.316496580927737,
+        12.41388579559133,
+        12.580552462257977,
+        13.413885795591309,
+        13.580552462257977,
+        14.413885795591353
+      ])
+
+      # Python result (lower): [nan nan 9.68350342 9.91944754 10.0861142 10.91944754 ...]
+      assert_values_in_delta(lower, [
+        nil,
+        nil,
+        9.683503419072263,
+        9.919447537742002,
+        10.086114204408691,
+        10.919447537742023,
+        11.086114204408691,
+        11.919447537741979
+      ])
+    end
+
+    test "uses separate deviations for the upper and lower bands" do
+      assert {:ok, {upper, middle, lower}} = BBANDS.bbands(@data, 3, 1.0, 3.0, 0)
+
+      for {u, m, l} <- Enum.zip([upper, middle, lower]), not is_nil(m) do
+        assert_in_delta(l, m - 3 * (u - m), 1.0e-9)
+      end
+    end
+
+    test "raises for period=1" do
+      assert {:error, reason} = BBANDS.bbands(@data, 1, 2.0, 2.0, 0)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "raises for an unknown ma_type" do
+      assert {:error, reason} = BBANDS.bbands(@data, 3, 2.0, 2.0, 9)
+      assert reason =~ "ma_type must be between 0 and 8"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], [], []}} = BBANDS.bbands([], 3, 2.0, 2.0, 0)
+    end
+
+    test "handles insufficient data" do
+      assert {:ok, {upper, middle, lower}} = BBANDS.bbands([1.0, 2.0], 3, 2.0, 2.0, 0)
+      assert upper == [nil, nil]
+      assert middle == [nil, nil]
+      assert lower == [nil, nil]
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0]
+      assert {:ok, {_upper, middle, _lower}} = BBANDS.bbands(data, 3, 2.0, 2.0, 0)
+      assert_values_in_delta(middle, [nil, nil, nil, nil, 2.0, 3.0])
+    end
+  end
+
+  describe "bbands/5 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      data = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, {upper, middle, lower}} = BBANDS.bbands(data, 3, 2.0, 2.0, 0)
+      assert %DataSeries{} = upper
+      assert %DataSeries{} = middle
+      assert %DataSeries{} = lower
+      assert [_, _, _, _, _, 10.5, nil, nil] = DataSeries.values(middle)
+    end
+  end
+
+  describe "bbands/5 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      data =
+        @data
+        |> Enum.with_index()
+        |> Enum.reduce(TimeSeries.new(), fn {value, i}, ts ->
+          TimeSeries.add(ts, DateTime.add(~U[2024-01-01 00:00:00Z], i, :minute), value)
+        end)
+
+      assert {:ok, {upper, middle, lower}} = BBANDS.bbands(data, 3, 2.0, 2.0, 0)
+      assert %TimeSeries{} = upper
+      assert %TimeSeries{} = middle
+      assert %TimeSeries{} = lower
+      assert [_, _, _, _, _, 10.5, nil, nil] = TimeSeries.values(middle)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, state} = BBANDS.init(period: 20, data: "eurusd_m1", name: "bb20")
+      assert %BBANDS{nb_dev_up: 2.0, nb_dev_dn: 2.0, ma_type: 0} = state
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, msg} = BBANDS.init(period: 1, data: "eurusd_m1", name: "bb1")
+      assert msg =~ "Invalid period"
+    end
+
+    test "returns error for a non-SMA middle band" do
+      assert {:error, msg} = BBANDS.init(period: 20, ma_type: 1, data: "eurusd_m1", name: "bb")
+      assert msg =~ "only SMA (0) is supported"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "matches batch BBANDS with SMA middle" do
+      {:ok, {upper, middle, lower}} = BBANDS.bbands(@data, 3, 2.0, 1.5, 0)
+
+      {:ok, state} =
+        BBANDS.init(period: 3, nb_dev_up: 2.0, nb_dev_dn: 1.5, data: "eurusd_m1", name: "bb3")
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_st} = BBANDS.next(event, st)
+          {result.value, new_st}
+        end)
+
+      for {bands, expected} <- Enum.zip(stream_result, Enum.zip([upper, middle, lower])) do
+        assert_bands_in_delta(bands, expected)
+      end
+    end
+
+    test "processes bars correctly in UPDATE mode" do
+      {:ok, state} = BBANDS.init(period: 3, data: "eurusd_m1", name: "bb3")
+
+      state =
+        Enum.reduce([1.0, 2.0, 3.0, 4.0], state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, _result, new_st} = BBANDS.next(event, st)
+          new_st
+        end)
+
+      # Replace the last bar (4.0 -> 8.0): same as batch over [1.0, 2.0, 3.0, 8.0]
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 8.0, new_bar?: false}}}
+      {:ok, result, _state} = BBANDS.next(event, state)
+
+      {:ok, {upper, middle, lower}} = BBANDS.bbands([1.0, 2.0, 3.0, 8.0], 3, 2.0, 2.0, 0)
+      expected = {List.last(upper), List.last(middle), List.last(lower)}
+      assert_bands_in_delta(result.value, expected)
+    end
+
+    test "handles nil values from upstream indicators" do
+      {:ok, state} = BBANDS.init(period: 2, data: "indicator", name: "bb2")
+
+      {results, _state} =
+        Enum.map_reduce([nil, 100.0, 110.0], state, fn value, st ->
+          event = %MarketEvent{
+            data: %{
+              "indicator" => %IndicatorValue{value: value, data_name: "eurusd_m1"},
+              "eurusd_m1" => %Bar{close: 1.23, new_bar?: true}
+            }
+          }
+
+          {:ok, result, new_st} = BBANDS.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert [nil, nil, bands] = results
+      assert_bands_in_delta(bands, {115.0, 105.0, 95.0})
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch BBANDS" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 21, max_length: 500),
+              period <- integer(2..50),
+              nb_dev_up <- float(min: 0.5, max: 3.0),
+              nb_dev_dn <- float(min: 0.5, max: 3.0)
+            ) do
+        {:ok, {upper, middle, lower}} = BBANDS.bbands(data, period, nb_dev_up, nb_dev_dn, 0)
+
+        {:ok, initial_state} =
+          BBANDS.init(
+            period: period,
+            nb_dev_up: nb_dev_up,
+            nb_dev_dn: nb_dev_dn,
+            data: "test",
+            name: "bb"
+          )
+
+        data
+        |> Enum.zip(Enum.zip([upper, middle, lower]))
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: value, new_bar?: true}}
+          }
+
+          {:ok, result, new_state} = BBANDS.next(event, state)
+          assert_bands_in_delta(result.value, expected, 1.0e-6)
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 15, max_length: 200),
+              period <- integer(2..30),
+              update_values <-
+                list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 5)
+            ) do
+        {:ok, state} = BBANDS.init(period: period, data: "test", name: "bb")
+
+        final_state =
+          Enum.reduce(data, state, fn value, st ->
+            event = %MarketEvent{
+              data: %{"test" => %Bar{close: value, new_bar?: true}}
+            }
+
+            {:ok, _result, new_state} = BBANDS.next(event, st)
+            new_state
+          end)
+
+        Enum.reduce(update_values, {final_state, data}, fn update_value, {state, current_data} ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: update_value, new_bar?: false}}
+          }
+
+          {:ok, result, new_state} = BBANDS.next(event, state)
+
+          updated_data = List.replace_at(current_data, -1, update_value)
+          {:ok, {upper, middle, lower}} = BBANDS.bbands(updated_data, period, 2.0, 2.0, 0)
+          expected = {List.last(upper), List.last(middle), List.last(lower)}
+          assert_bands_in_delta(result.value, expected, 1.0e-6)
+
+          {new_state, updated_data}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp assert_bands_in_delta(bands, expected, delta \\ 1.0e-9)
+
+  defp assert_bands_in_delta(nil, {nil, nil, nil}, _delta), do: :ok
+
+  defp assert_bands_in_delta(bands, {upper, middle, lower}, delta) do
+    assert %{upper: u, middle: m, lower: l} = bands
+    assert_in_delta(u, upper, delta)
+    assert_in_delta(m, middle, delta)
+    assert_in_delta(l, lower, delta)
+  end
+end
@@ -0,0 +1,69 @@
+defmodule TheoryCraftTA.Overlap.ChandelierTest do
+  use ExUnit.Case, async: true
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "overlap_chandelier/5" do
+    test "calculates correctly against hand-computed values" do
+      high = [11.0, 12.0, 13.0, 14.0, 12.0]
+      low = [9.0, 10.0, 11.0, 12.0, 10.0]
+      close = [10.0, 11.0, 12.0, 13.0, 11.0]
+
+      assert {:ok, {long_exit, short_exit}} = Native.overlap_chandelier(high, low, close, 2, 1.0)
+
+      # ATR(2): TR = 2, 2, 2, 3 so ATR = 2 at bar 2, 2 at bar 3, (2 + 3) / 2 = 2.5 at bar 4
+      # Highest high (2): 13, 14, 14 - lowest low (2): 10, 11, 10
+      assert_values_in_delta(long_exit, [nil, nil, 11.0, 12.0, 11.5])
+      assert_values_in_delta(short_exit, [nil, nil, 12.0, 13.0, 12.5])
+    end
+
+    test "the long exit trails an uptrend" do
+      high = Enum.map(1..20, &(&1 + 1.0))
+      low = Enum.map(1..20, &(&1 - 1.0))
+      close = Enum.map(1..20, &(&1 * 1.0))
+
+      assert {:ok, {long_exit, short_exit}} = Native.overlap_chandelier(high, low, close, 5, 3.0)
+
+      # Aligned on the ATR warmup, one bar longer than the highest high
+      assert Enum.find_index(long_exit, & &1) == 5
+      assert Enum.find_index(short_exit, & &1) == 5
+
+      # Constant ranges: the ATR is 2, so the exit stays 6 below the last high
+      long_exit
+      |> Enum.zip(high)
+      |> Enum.drop(5)
+      |> Enum.each(fn {exit, high} -> assert_in_delta exit, high - 6.0, 1.0e-9 end)
+    end
+
+    test "the long exit holds on a pullback while the highest high is in the window" do
+      high = [11.0, 12.0, 13.0, 14.0, 13.0, 12.0, 11.0]
+      low = [9.0, 10.0, 11.0, 12.0, 11.0, 10.0, 9.0]
+      close = [10.0, 11.0, 12.0, 13.0, 12.0, 11.0, 10.0]
+
+      assert {:ok, {long_exit, _short_exit}} =
+               Native.overlap_chandelier(high, low, close, 3, 1.0)
+
+      # The range is 2 on every bar, so the ATR stays at 2
+      assert_values_in_delta(long_exit, [nil, nil, nil, 12.0, 12.0, 12.0, 11.0])
+    end
+
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.overlap_chandelier([1.0], [1.0], [1.0], 1, 3.0)
+      assert reason =~ "period must be >= 2"
+
+      assert {:error, reason} = Native.overlap_chandelier([1.0], [1.0], [1.0], 22, 0.0)
+      assert reason =~ "multiplier must be > 0"
+
+      assert {:error, reason} = Native.overlap_chandelier([1.0], [], [1.0], 22, 3.0)
+      assert reason == "CHANDELIER: high/low/close length mismatch (1/0/1)"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], []}} = Native.overlap_chandelier([], [], [], 22, 3.0)
+    end
+  end
+end
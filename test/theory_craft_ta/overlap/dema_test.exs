@@ -28,13 +28,13 @@ defmodule TheoryCraftTA.Overlap.DEMATest do
     test "raises for period=1" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = DEMA.dema(data, 1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for period=0" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = DEMA.dema(data, 0)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "returns empty for empty input" do
@@ -253,6 +253,101 @@ defmodule TheoryCraftTA.Overlap.DEMATest do
       assert result_final.value != nil
       assert is_float(result_final.value)
     end
+
+    test "matches recorded values for a mixed APPEND/UPDATE sequence" do
+      # Pins the output of the nested EMAs, including the UPDATE of a bar in warmup
+      {:ok, state} = DEMA.init(period: 2, data: "eurusd_m1", name: "dema2", source: :close)
+
+      events = [
+        {100.0, true},
+        {104.0, true},
+        {101.0, false},
+        {108.0, true},
+        {107.0, true},
+        {111.0, false},
+        {115.0, true},
+        {112.0, true},
+        {118.0, true},
+        {116.0, false},
+        {121.0, true},
+        {119.0, true}
+      ]
+
+      {results, _state} =
+        Enum.map_reduce(events, state, fn {value, new_bar?}, st ->
+          event = %MarketEvent{
+            data: %{"eurusd_m1" => %Bar{close: value, new_bar?: new_bar?}}
+          }
+
+          {:ok, result, new_st} = DEMA.next(event, st)
+          {result.value, new_st}
+        end)
+
+      expected = [
+        nil,
+        nil,
+        nil,
+        108.0,
+        107.66666666666667,
+        111.22222222222223,
+        115.03703703703704,
+        112.77777777777777,
+        117.51440329218106,
+        115.73662551440329,
+        120.62688614540467,
+        119.55829903978052
+      ]
+
+      for {value, expected_value} <- Enum.zip(results, expected) do
+        case expected_value do
+          nil -> assert value == nil
+          _ -> assert_in_delta(value, expected_value, 1.0e-9)
+        end
+      end
+    end
+
+    test "first value is emitted at the batch lookback index" do
+      data = Enum.map(1..40, &(&1 * 1.0))
+
+      for period <- [2, 3, 5, 7] do
+        {:ok, state} = DEMA.init(period: period, data: "test", name: "dema", source: :close)
+
+        {results, _state} =
+          Enum.map_reduce(data, state, fn value, st ->
+            event = %MarketEvent{data: %{"test" => %Bar{close: value, new_bar?: true}}}
+            {:ok, result, new_state} = DEMA.next(event, st)
+            {result.value, new_state}
+          end)
+
+        {:ok, batch} = DEMA.dema(data, period)
+        lookback = 2 * (period - 1)
+
+        assert Enum.find_index(results, &(&1 != nil)) == lookback
+        assert Enum.find_index(batch, &(&1 != nil)) == lookback
+      end
+    end
+  end
+
+  describe "next/2 with :seed_mode" do
+    test ":first seeds the inner EMAs with the first value and emits from the first bar" do
+      {:ok, state} = DEMA.init(period: 3, data: "eurusd_m1", name: "dema3", seed_mode: :first)
+
+      {values, _state} =
+        Enum.map_reduce([100.0, 110.0, 120.0], state, fn close, state ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: true}}}
+          {:ok, result, state} = DEMA.next(event, state)
+          {result.value, state}
+        end)
+
+      assert values == [100.0, 107.5, 117.5]
+    end
+
+    test "init returns error for an unknown seed mode" do
+      assert {:error, reason} =
+               DEMA.init(period: 3, data: "eurusd_m1", name: "dema3", seed_mode: :zero)
+
+      assert reason =~ "Invalid seed_mode"
+    end
   end
 
   ## Property-based tests
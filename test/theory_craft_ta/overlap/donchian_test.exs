@@ -0,0 +1,54 @@
+defmodule TheoryCraftTA.Overlap.DonchianTest do
+  use ExUnit.Case, async: true
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "overlap_donchian/3" do
+    test "follows the newest high and the oldest low on a rising series" do
+      high = Enum.map(1..6, &(&1 + 1.0))
+      low = Enum.map(1..6, &(&1 - 1.0))
+
+      assert {:ok, {upper, middle, lower}} = Native.overlap_donchian(high, low, 3)
+
+      assert upper == [nil, nil, 4.0, 5.0, 6.0, 7.0]
+      assert lower == [nil, nil, 0.0, 1.0, 2.0, 3.0]
+      assert middle == [nil, nil, 2.0, 3.0, 4.0, 5.0]
+    end
+
+    test "follows the oldest high and the newest low on a falling series" do
+      high = Enum.map(6..1//-1, &(&1 + 1.0))
+      low = Enum.map(6..1//-1, &(&1 - 1.0))
+
+      assert {:ok, {upper, middle, lower}} = Native.overlap_donchian(high, low, 3)
+
+      assert upper == [nil, nil, 7.0, 6.0, 5.0, 4.0]
+      assert lower == [nil, nil, 3.0, 2.0, 1.0, 0.0]
+      assert middle == [nil, nil, 5.0, 4.0, 3.0, 2.0]
+    end
+
+    test "skips bars where either input is missing" do
+      high = [nil, 5.0, 2.0, 3.0, 4.0, 5.0]
+      low = [0.0, nil, 1.0, 2.0, 3.0, 4.0]
+
+      assert {:ok, {upper, middle, lower}} = Native.overlap_donchian(high, low, 2)
+
+      assert upper == [nil, nil, nil, 3.0, 4.0, 5.0]
+      assert lower == [nil, nil, nil, 1.0, 2.0, 3.0]
+      assert middle == [nil, nil, nil, 2.0, 3.0, 4.0]
+    end
+
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.overlap_donchian([1.0], [1.0], 1)
+      assert reason =~ "period must be >= 2"
+
+      assert {:error, reason} = Native.overlap_donchian([1.0], [], 20)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], [], []}} = Native.overlap_donchian([], [], 20)
+    end
+  end
+end
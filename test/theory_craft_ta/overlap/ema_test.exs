@@ -4,6 +4,7 @@ defmodule TheoryCraftTA.Overlap.EMATest do
 
   alias TheoryCraft.{DataSeries, TimeSeries}
   alias TheoryCraft.MarketSource.{Bar, IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.Native
   alias TheoryCraftTA.Overlap.EMA
 
   doctest TheoryCraftTA.Overlap.EMA
@@ -28,19 +29,19 @@ defmodule TheoryCraftTA.Overlap.EMATest do
     test "raises for period=1" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = EMA.ema(data, 1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for period=0" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = EMA.ema(data, 0)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for negative period" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = EMA.ema(data, -1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "returns empty for empty input" do
@@ -73,6 +74,22 @@ defmodule TheoryCraftTA.Overlap.EMATest do
       assert {:ok, result} = EMA.ema(data, 3)
       assert result == [nil, nil, 2.0, nil, nil, nil, nil, nil]
     end
+
+    test "handles all-NaN input" do
+      data = [nil, nil, nil, nil, nil]
+      # Python result: [nan nan nan nan nan]
+      assert {:ok, result} = EMA.ema(data, 2)
+      assert result == [nil, nil, nil, nil, nil]
+    end
+
+    test "aligns the first value with leading NaN and lookback" do
+      for leading <- 0..4, period <- 2..5 do
+        data = List.duplicate(nil, leading) ++ Enum.map(1..10, &(&1 * 1.0))
+        assert {:ok, result} = EMA.ema(data, period)
+        assert length(result) == length(data)
+        assert Enum.find_index(result, & &1) == leading + period - 1
+      end
+    end
   end
 
   describe "ema/2 with DataSeries input" do
@@ -113,6 +130,100 @@ defmodule TheoryCraftTA.Overlap.EMATest do
     end
   end
 
+  describe "overlap_ema_f32/2" do
+    property "matches the f64 result rounded to f32" do
+      # Quarters are exact in f32, so only the output narrowing can differ
+      check all(
+              data <- list_of(map(integer(4..4000), &(&1 / 4)), min_length: 10, max_length: 200),
+              period <- integer(2..10)
+            ) do
+        {:ok, f64_result} = Native.overlap_ema(data, period)
+        {:ok, f32_result} = Native.overlap_ema_f32(data, period)
+
+        assert length(f32_result) == length(f64_result)
+
+        Enum.zip_with(f32_result, f64_result, fn
+          nil, nil -> :ok
+          val, exp -> assert_in_delta(val, exp, abs(exp) * 1.0e-6)
+        end)
+      end
+    end
+
+    test "treats nil as missing like the f64 path" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+      {:ok, f64_result} = Native.overlap_ema(data, 3)
+      assert {:ok, f32_result} = Native.overlap_ema_f32(data, 3)
+      assert Enum.map(f32_result, &is_nil/1) == Enum.map(f64_result, &is_nil/1)
+    end
+  end
+
+  describe "overlap_ema_trimmed/2" do
+    property "padding the trimmed result reproduces the full result" do
+      check all(
+              leading <- integer(0..5),
+              values <- list_of(float(min: 1.0, max: 1000.0), max_length: 100),
+              period <- integer(2..10)
+            ) do
+        data = List.duplicate(nil, leading) ++ values
+
+        {:ok, full_result} = Native.overlap_ema(data, period)
+        {:ok, {first_index, trimmed}} = Native.overlap_ema_trimmed(data, period)
+
+        assert List.duplicate(nil, first_index) ++ trimmed == full_result
+      end
+    end
+
+    test "reports the total lookback as the first index" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0]
+      assert {:ok, {4, trimmed}} = Native.overlap_ema_trimmed(data, 3)
+      assert length(trimmed) == 3
+    end
+
+    test "returns no values when there is not enough data" do
+      assert {:ok, {2, []}} = Native.overlap_ema_trimmed([1.0, 2.0], 3)
+      assert {:ok, {0, []}} = Native.overlap_ema_trimmed([], 3)
+    end
+  end
+
+  describe "overlap_ema_multi/2" do
+    property "each output matches an individual overlap_ema call" do
+      check all(
+              leading <- integer(0..5),
+              values <- list_of(float(min: 1.0, max: 1000.0), max_length: 300),
+              periods <- list_of(integer(2..50), max_length: 10)
+            ) do
+        data = List.duplicate(nil, leading) ++ values
+        expected = Enum.map(periods, &Native.overlap_ema(data, &1))
+
+        assert Native.overlap_ema_multi(data, periods) == expected
+      end
+    end
+
+    test "computes an EMA ribbon in one call" do
+      data = Enum.map(1..300, &(&1 * 1.0))
+      periods = [5, 10, 20, 50, 100, 200]
+
+      results = Native.overlap_ema_multi(data, periods)
+      assert length(results) == length(periods)
+
+      for {result, period} <- Enum.zip(results, periods) do
+        assert {:ok, _values} = result
+        assert result == Native.overlap_ema(data, period)
+      end
+    end
+
+    test "returns an error only for the invalid periods" do
+      assert [{:ok, [nil, 1.5, 2.5]}, {:error, reason}] =
+               Native.overlap_ema_multi([1.0, 2.0, 3.0], [2, 1])
+
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert [{:ok, []}, {:ok, []}] = Native.overlap_ema_multi([], [2, 3])
+    end
+  end
+
   ## State initialization tests
 
   describe "init/1" do
@@ -188,6 +299,41 @@ defmodule TheoryCraftTA.Overlap.EMATest do
       assert result3.value == 110.0
     end
 
+    test "UPDATE before the first bar does not skew the SMA seed" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", source: :close)
+
+      # Intrabar correction received before any complete bar
+      event0 = %MarketEvent{
+        data: %{"eurusd_m1" => %Bar{close: 100.0, new_bar?: false}}
+      }
+
+      {:ok, result0, state0} = EMA.next(event0, state)
+      assert result0.value == nil
+
+      event1 = %MarketEvent{
+        data: %{"eurusd_m1" => %Bar{close: 100.0, new_bar?: true}}
+      }
+
+      {:ok, result1, state1} = EMA.next(event1, state0)
+      assert result1.value == nil
+
+      event2 = %MarketEvent{
+        data: %{"eurusd_m1" => %Bar{close: 110.0, new_bar?: true}}
+      }
+
+      {:ok, result2, state2} = EMA.next(event2, state1)
+      # Seed is the SMA of the last 2 values [100.0, 110.0]
+      assert result2.value == 105.0
+
+      # UPDATE on the seed bar recomputes the SMA seed over [100.0, 120.0]
+      event3 = %MarketEvent{
+        data: %{"eurusd_m1" => %Bar{close: 120.0, new_bar?: false}}
+      }
+
+      {:ok, result3, _state3} = EMA.next(event3, state2)
+      assert result3.value == 110.0
+    end
+
     test "handles nil values from upstream indicators" do
       {:ok, state} =
         EMA.init(
@@ -232,6 +378,220 @@ defmodule TheoryCraftTA.Overlap.EMATest do
     end
   end
 
+  describe "value/1 and ready?/1" do
+    test "return the latest value and the warmup status" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", source: :close)
+      assert EMA.value(state) == nil
+      refute EMA.ready?(state)
+
+      {:ok, _result1, state1} = EMA.next(bar_event(100.0, true), state)
+      assert EMA.value(state1) == nil
+      refute EMA.ready?(state1)
+
+      {:ok, result2, state2} = EMA.next(bar_event(110.0, true), state1)
+      assert EMA.value(state2) == result2.value
+      assert EMA.ready?(state2)
+
+      {:ok, result3, state3} = EMA.next(bar_event(120.0, false), state2)
+      assert EMA.value(state3) == result3.value
+    end
+  end
+
+  describe "peek/2" do
+    test "returns the same value as an identical UPDATE" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", source: :close)
+      {:ok, _result1, state1} = EMA.next(bar_event(100.0, true), state)
+      {:ok, _result2, state2} = EMA.next(bar_event(110.0, true), state1)
+      {:ok, _result3, state3} = EMA.next(bar_event(120.0, true), state2)
+
+      {:ok, peeked} = EMA.peek(state3, 130.0)
+      {:ok, result, _state} = EMA.next(bar_event(130.0, false), state3)
+      assert peeked == result.value
+    end
+
+    test "does not advance the state" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", source: :close)
+      {:ok, _result1, state1} = EMA.next(bar_event(100.0, true), state)
+
+      # Still in warmup: the peeked value doesn't count as a bar
+      assert EMA.peek(state1, 110.0) == {:ok, nil}
+      assert EMA.peek(state1, nil) == {:ok, nil}
+
+      {:ok, result2, _state2} = EMA.next(bar_event(110.0, true), state1)
+      assert result2.value == 105.0
+    end
+
+    test "returns an error for a missing value with nan_policy: :reject" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", nan_policy: :reject)
+      {:ok, _result1, state1} = EMA.next(bar_event(100.0, true), state)
+      {:ok, _result2, state2} = EMA.next(bar_event(110.0, true), state1)
+
+      assert {:error, reason} = EMA.peek(state2, nil)
+      assert reason =~ "missing input rejected"
+    end
+  end
+
+  describe "undo/1" do
+    test "next(append) -> undo returns to the prior state" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", source: :close)
+      {:ok, _result1, state1} = EMA.next(bar_event(100.0, true), state)
+      {:ok, _result2, state2} = EMA.next(bar_event(110.0, true), state1)
+
+      {:ok, result3, state3} = EMA.next(bar_event(120.0, true), state2)
+      assert result3.value == 115.0
+
+      assert {:ok, restored} = EMA.undo(state3)
+
+      # The restored state behaves like the one the undone bar was appended to
+      for event <- [bar_event(130.0, true), bar_event(112.0, false)] do
+        {:ok, expected, _state} = EMA.next(event, state2)
+        {:ok, result, _state} = EMA.next(event, restored)
+        assert result.value == expected.value
+      end
+    end
+
+    test "reverts the UPDATEs received since the last APPEND" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", source: :close)
+      {:ok, _result1, state1} = EMA.next(bar_event(100.0, true), state)
+      {:ok, _result2, state2} = EMA.next(bar_event(110.0, true), state1)
+      {:ok, _result3, state3} = EMA.next(bar_event(120.0, true), state2)
+      {:ok, _result4, state4} = EMA.next(bar_event(125.0, false), state3)
+
+      assert {:ok, restored} = EMA.undo(state4)
+
+      {:ok, expected, _state} = EMA.next(bar_event(130.0, true), state2)
+      {:ok, result, _state} = EMA.next(bar_event(130.0, true), restored)
+      assert result.value == expected.value
+    end
+
+    test "restores the warmup buffer" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", source: :close)
+      {:ok, _result1, state1} = EMA.next(bar_event(90.0, true), state)
+      {:ok, restored} = EMA.undo(state1)
+
+      {:ok, result1, state1} = EMA.next(bar_event(100.0, true), restored)
+      assert result1.value == nil
+
+      {:ok, result2, _state2} = EMA.next(bar_event(110.0, true), state1)
+      assert result2.value == 105.0
+    end
+
+    test "returns error when there is nothing to undo" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", source: :close)
+      assert {:error, msg} = EMA.undo(state)
+      assert msg =~ "Nothing to undo"
+
+      # Only the last APPEND is kept
+      {:ok, _result1, state1} = EMA.next(bar_event(100.0, true), state)
+      {:ok, restored} = EMA.undo(state1)
+      assert {:error, _msg} = EMA.undo(restored)
+    end
+  end
+
+  describe "next/2 with :nan_policy" do
+    test "returns nil for a missing value by default, without modifying the state" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2")
+      {:ok, _result, state} = EMA.next(bar_event(100.0, true), state)
+      {:ok, _result, state} = EMA.next(bar_event(110.0, true), state)
+
+      assert {:ok, %IndicatorValue{value: nil}, ^state} = EMA.next(bar_event(nil, true), state)
+    end
+
+    test ":carry_forward returns the current EMA and recovers on the next valid bar" do
+      {:ok, state} =
+        EMA.init(period: 2, data: "eurusd_m1", name: "ema2", nan_policy: :carry_forward)
+
+      {:ok, _result, state} = EMA.next(bar_event(100.0, true), state)
+      {:ok, _result, state} = EMA.next(bar_event(110.0, true), state)
+
+      {:ok, result, state} = EMA.next(bar_event(nil, true), state)
+      assert result.value == 105.0
+
+      # The missing bar is ignored: same as the EMA of [100.0, 110.0, 120.0]
+      {:ok, result, _state} = EMA.next(bar_event(120.0, true), state)
+      assert result.value == 115.0
+    end
+
+    test ":reject returns an error and leaves the state usable" do
+      {:ok, state} = EMA.init(period: 2, data: "eurusd_m1", name: "ema2", nan_policy: :reject)
+      {:ok, _result, state} = EMA.next(bar_event(100.0, true), state)
+
+      assert {:error, reason} = EMA.next(bar_event(nil, true), state)
+      assert reason =~ "missing input rejected"
+
+      {:ok, result, _state} = EMA.next(bar_event(110.0, true), state)
+      assert result.value == 105.0
+    end
+
+    test "init returns error for an unknown policy" do
+      assert {:error, reason} =
+               EMA.init(period: 2, data: "eurusd_m1", name: "ema2", nan_policy: :zero)
+
+      assert reason =~ "Invalid nan_policy"
+    end
+  end
+
+  describe "next/2 with :seed_mode" do
+    test ":sma waits for period bars and seeds with their average by default" do
+      {:ok, state} = EMA.init(period: 3, data: "eurusd_m1", name: "ema3")
+
+      {values, _state} = stream_closes([100.0, 110.0, 120.0, 130.0], state)
+
+      assert values == [nil, nil, 110.0, 120.0]
+    end
+
+    test ":first seeds with the first value and emits from the first bar" do
+      {:ok, state} = EMA.init(period: 3, data: "eurusd_m1", name: "ema3", seed_mode: :first)
+      refute EMA.ready?(state)
+
+      {values, state} = stream_closes([100.0, 110.0, 120.0, 130.0], state)
+
+      # k = 2 / (3 + 1) = 0.5
+      assert values == [100.0, 105.0, 112.5, 121.25]
+      assert EMA.ready?(state)
+    end
+
+    test ":first re-seeds when the first bar is updated" do
+      {:ok, state} = EMA.init(period: 3, data: "eurusd_m1", name: "ema3", seed_mode: :first)
+      {:ok, _result, state} = EMA.next(bar_event(100.0, true), state)
+
+      {:ok, result, state} = EMA.next(bar_event(104.0, false), state)
+      assert result.value == 104.0
+
+      {:ok, result, _state} = EMA.next(bar_event(110.0, true), state)
+      assert result.value == 107.0
+    end
+
+    test "init returns error for an unknown seed mode" do
+      assert {:error, reason} =
+               EMA.init(period: 3, data: "eurusd_m1", name: "ema3", seed_mode: :zero)
+
+      assert reason =~ "Invalid seed_mode"
+    end
+  end
+
+  describe "Native.overlap_ema_state_init/2" do
+    test "accepts a seed mode" do
+      {:ok, state} = Native.overlap_ema_state_init(3, :first)
+
+      {:ok, {100.0, state}} = Native.overlap_ema_state_next(state, 100.0, true)
+      assert {:ok, {105.0, _state}} = Native.overlap_ema_state_next(state, 110.0, true)
+    end
+
+    test "accepts a nan policy" do
+      {:ok, state} = Native.overlap_ema_state_init(2, :carry_forward)
+
+      {:ok, {nil, state}} = Native.overlap_ema_state_next(state, 100.0, true)
+      {:ok, {105.0, state}} = Native.overlap_ema_state_next(state, 110.0, true)
+      assert {:ok, {105.0, _state}} = Native.overlap_ema_state_next(state, nil, true)
+    end
+
+    test "returns error for an unknown option" do
+      assert {:error, reason} = Native.overlap_ema_state_init(3, :zero)
+      assert reason =~ "Invalid option"
+    end
+  end
+
   ## Property-based tests
 
   describe "property: state-based APPEND matches batch calculation" do
@@ -267,6 +627,33 @@ defmodule TheoryCraftTA.Overlap.EMATest do
         end)
       end
     end
+
+    property "leading nil values don't shift the SMA seed" do
+      check all(
+              leading <- integer(1..10),
+              values <- list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 60),
+              period <- integer(2..20)
+            ) do
+        # Batch seeds on the first valid window, at begidx + period - 1
+        data = List.duplicate(nil, leading) ++ values
+        {:ok, batch_result} = Native.overlap_ema(data, period)
+        {:ok, initial_state} = Native.overlap_ema_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected_value}, state ->
+          {:ok, {ema_value, new_state}} = Native.overlap_ema_state_next(state, value, true)
+
+          case {ema_value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
   end
 
   describe "property: UPDATE mode behaves correctly" do
@@ -321,4 +708,17 @@ defmodule TheoryCraftTA.Overlap.EMATest do
       end
     end
   end
+
+  ## Private functions
+
+  defp stream_closes(closes, state) do
+    Enum.map_reduce(closes, state, fn close, state ->
+      {:ok, result, state} = EMA.next(bar_event(close, true), state)
+      {result.value, state}
+    end)
+  end
+
+  defp bar_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
 end
@@ -0,0 +1,80 @@
+defmodule TheoryCraftTA.Overlap.FRAMATest do
+  use ExUnit.Case, async: true
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Overlap.FRAMA
+
+  doctest TheoryCraftTA.Overlap.FRAMA
+
+  ## Batch calculation tests
+
+  describe "overlap_frama/2" do
+    test "follows a trend and barely moves in a choppy window" do
+      # Trend: N1 = N2 = 0.5, N3 = 0.75, D < 1 so alpha is clamped to 1
+      assert {:ok, [nil, nil, nil, 4.0]} = Native.overlap_frama([1.0, 2.0, 3.0, 4.0], 4)
+
+      # Chop: N1 = N2 = 1, N3 = 0.5, D = 2 so alpha = exp(-4.6), from the previous value
+      assert {:ok, [nil, nil, nil, frama]} = Native.overlap_frama([1.0, 3.0, 1.0, 3.0], 4)
+      alpha = :math.exp(-4.6)
+      assert_in_delta frama, alpha * 3.0 + (1 - alpha) * 1.0, 1.0e-9
+    end
+
+    test "reacts to a step faster than an EMA of the same period" do
+      data = List.duplicate(10.0, 20) ++ List.duplicate(20.0, 20)
+
+      {:ok, frama} = Native.overlap_frama(data, 10)
+      {:ok, ema} = Native.overlap_ema(data, 10)
+
+      # Flat, then the step is in the recent half only: D = 1 and alpha = 1
+      assert Enum.slice(frama, 9..19) == List.duplicate(10.0, 11)
+      assert Enum.at(frama, 20) == 20.0
+
+      for index <- 20..29 do
+        assert abs(Enum.at(frama, index) - 20.0) < abs(Enum.at(ema, index) - 20.0)
+      end
+    end
+
+    test "skips nil values" do
+      # The halves of a 2-bar window have no range: alpha is 1
+      assert {:ok, [nil, nil, 2.0, nil, 4.0]} =
+               Native.overlap_frama([nil, 1.0, 2.0, nil, 4.0], 2)
+    end
+
+    test "returns error for an odd period or a period < 2" do
+      assert {:error, reason} = Native.overlap_frama([1.0, 2.0, 3.0], 3)
+      assert reason == "FRAMA: period must be even and >= 2"
+
+      assert {:error, _reason} = Native.overlap_frama([1.0, 2.0, 3.0], 0)
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.overlap_frama([], 4)
+    end
+
+    test "handles insufficient data" do
+      assert {:ok, [nil, nil, nil]} = Native.overlap_frama([1.0, 2.0, 3.0], 4)
+    end
+  end
+
+  ## Public API tests
+
+  describe "frama/2" do
+    test "matches the native calculation" do
+      data = [1.0, 3.0, 2.0, 5.0, 4.0, 6.0, 8.0, 7.0]
+      assert FRAMA.frama(data, 4) == Native.overlap_frama(data, 4)
+    end
+
+    test "maintains DataSeries type in output" do
+      ds = Enum.reduce([1.0, 2.0, 3.0, 4.0], DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = FRAMA.frama(ds, 4)
+      assert DataSeries.values(result) == [4.0, nil, nil, nil]
+    end
+
+    test "returns error for an odd period" do
+      assert {:error, reason} = FRAMA.frama([1.0, 2.0, 3.0], 3)
+      assert reason =~ "period must be even"
+    end
+  end
+end
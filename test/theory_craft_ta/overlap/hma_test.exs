@@ -0,0 +1,169 @@
+defmodule TheoryCraftTA.Overlap.HMATest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Overlap.HMA
+
+  doctest TheoryCraftTA.Overlap.HMA
+
+  @data [
+    44.34,
+    44.09,
+    44.15,
+    43.61,
+    44.33,
+    44.83,
+    45.10,
+    45.42,
+    45.84,
+    46.08,
+    45.89,
+    46.03,
+    45.61,
+    46.28,
+    46.28
+  ]
+
+  ## Batch calculation tests
+
+  describe "overlap_hma/2" do
+    test "matches reference values with period=9" do
+      # half = 4, sqrt = 3: lookback = (9 - 1) + (3 - 1)
+      assert {:ok, result} = Native.overlap_hma(@data, 9)
+      assert Enum.take(result, 10) == List.duplicate(nil, 10)
+
+      expected = [46.290741, 46.339519, 46.15663, 46.103556, 46.160926]
+
+      result
+      |> Enum.drop(10)
+      |> Enum.zip(expected)
+      |> Enum.each(fn {value, exp} -> assert_in_delta(value, exp, 1.0e-6) end)
+    end
+
+    test "matches reference values with period=4" do
+      # half = 2, sqrt = 2: lookback = (4 - 1) + (2 - 1)
+      assert {:ok, result} = Native.overlap_hma(@data, 4)
+      assert Enum.take(result, 4) == List.duplicate(nil, 4)
+
+      expected = [
+        43.963667,
+        44.681111,
+        45.188889,
+        45.454444,
+        45.799889,
+        46.126333,
+        46.085111,
+        45.997333,
+        45.769889,
+        45.969222,
+        46.333778
+      ]
+
+      result
+      |> Enum.drop(4)
+      |> Enum.zip(expected)
+      |> Enum.each(fn {value, exp} -> assert_in_delta(value, exp, 1.0e-6) end)
+    end
+
+    test "uses a 1-period WMA as the identity with period=2" do
+      # half = 1, sqrt = 1: HMA = 2 * data - WMA(data, 2)
+      assert {:ok, result} = Native.overlap_hma([1.0, 2.0, 4.0], 2)
+      assert_in_delta_list(result, [nil, 2.0 * 2.0 - 5.0 / 3.0, 2.0 * 4.0 - 10.0 / 3.0])
+    end
+
+    test "skips leading nil values" do
+      assert {:ok, result} = Native.overlap_hma([nil, nil | @data], 4)
+      assert Enum.take(result, 6) == List.duplicate(nil, 6)
+      assert_in_delta Enum.at(result, 6), 43.963667, 1.0e-6
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_hma(@data, 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.overlap_hma([], 9)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, [nil, nil, nil]} = Native.overlap_hma([1.0, 2.0, 3.0], 9)
+    end
+  end
+
+  describe "hma/2" do
+    test "matches the native calculation" do
+      assert HMA.hma(@data, 9) == Native.overlap_hma(@data, 9)
+    end
+
+    test "maintains DataSeries type in output" do
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = HMA.hma(ds, 4)
+      assert result |> DataSeries.values() |> Enum.take(-4) == List.duplicate(nil, 4)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = HMA.hma(@data, 1)
+      assert reason =~ "period must be >= 2"
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: matches a reference HMA implementation" do
+    property "HMA = WMA(2 * WMA(n / 2) - WMA(n), sqrt(n))" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, result} = Native.overlap_hma(data, period)
+        assert_in_delta_list(result, reference_hma(data, period))
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp reference_hma(data, period) do
+    half = wma(data, div(period, 2))
+    full = wma(data, period)
+
+    raw =
+      Enum.zip_with(half, full, fn
+        h, f when is_float(h) and is_float(f) -> 2.0 * h - f
+        _, _ -> nil
+      end)
+
+    {leading, values} = Enum.split_while(raw, &is_nil/1)
+    leading ++ wma(values, trunc(:math.sqrt(period)))
+  end
+
+  defp wma(data, period) do
+    weights = Enum.sum(1..period)
+
+    windows =
+      data
+      |> Enum.chunk_every(period, 1, :discard)
+      |> Enum.map(fn window ->
+        window |> Enum.with_index(1) |> Enum.map(fn {v, w} -> v * w end) |> Enum.sum()
+      end)
+      |> Enum.map(&(&1 / weights))
+
+    List.duplicate(nil, min(period - 1, length(data))) ++ windows
+  end
+
+  defp assert_in_delta_list(actual, expected) do
+    assert length(actual) == length(expected)
+
+    actual
+    |> Enum.zip(expected)
+    |> Enum.each(fn
+      {nil, nil} -> :ok
+      {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-6)
+      _ -> flunk("Mismatch between HMA and the reference implementation")
+    end)
+  end
+end
@@ -0,0 +1,296 @@
+defmodule TheoryCraftTA.Overlap.KAMATest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Overlap.KAMA
+
+  doctest TheoryCraftTA.Overlap.KAMA
+
+  ## Batch calculation tests
+
+  describe "kama/2 with list input" do
+    test "calculates correctly with period=3" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      # Python result: [nan nan nan 10.82566385 10.8487296 11.37504341 11.41352267 12.04631098]
+      assert {:ok, result} = KAMA.kama(data, 3)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        10.825663853239295,
+        10.848729595597682,
+        11.375043409416078,
+        11.413522673811771,
+        12.046310975605538
+      ])
+    end
+
+    test "handles a steady trend (efficiency ratio = 1)" do
+      data = [1.0, 2.0, 3.0, 4.0, 5.0]
+      # Python result: [nan nan nan 3.44444444 4.13580247]
+      assert {:ok, result} = KAMA.kama(data, 3)
+      assert_values_in_delta(result, [nil, nil, nil, 3.444444444444444, 4.135802469135802])
+    end
+
+    test "raises for period=1" do
+      data = [1.0, 2.0, 3.0]
+      assert {:error, reason} = KAMA.kama(data, 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = KAMA.kama([], 3)
+    end
+
+    test "handles insufficient data (period >= data length)" do
+      data = [1.0, 2.0, 3.0]
+      assert {:ok, result} = KAMA.kama(data, 3)
+      assert result == [nil, nil, nil]
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0]
+      # Python result: [nan nan nan nan nan 3.44444444 4.13580247]
+      assert {:ok, result} = KAMA.kama(data, 3)
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        3.444444444444444,
+        4.135802469135802
+      ])
+    end
+  end
+
+  describe "kama/2 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      data =
+        DataSeries.new()
+        |> DataSeries.add(1.0)
+        |> DataSeries.add(2.0)
+        |> DataSeries.add(3.0)
+        |> DataSeries.add(4.0)
+        |> DataSeries.add(5.0)
+
+      assert {:ok, result} = KAMA.kama(data, 3)
+      assert %DataSeries{} = result
+      assert [_, _, nil, nil, nil] = DataSeries.values(result)
+    end
+  end
+
+  describe "kama/2 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      ts =
+        TimeSeries.new()
+        |> TimeSeries.add(~U[2024-01-01 00:00:00Z], 1.0)
+        |> TimeSeries.add(~U[2024-01-01 00:01:00Z], 2.0)
+        |> TimeSeries.add(~U[2024-01-01 00:02:00Z], 3.0)
+        |> TimeSeries.add(~U[2024-01-01 00:03:00Z], 4.0)
+        |> TimeSeries.add(~U[2024-01-01 00:04:00Z], 5.0)
+
+      assert {:ok, result} = KAMA.kama(ts, 3)
+      assert %TimeSeries{} = result
+      assert [_, _, nil, nil, nil] = TimeSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} =
+               KAMA.init(period: 10, data: "eurusd_m1", name: "kama10", source: :close)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, msg} =
+               KAMA.init(period: 1, data: "eurusd_m1", name: "kama1", source: :close)
+
+      assert msg =~ "Invalid period"
+    end
+
+    test "returns error when fast period >= slow period" do
+      assert {:error, msg} =
+               KAMA.init(
+                 period: 10,
+                 fast_period: 30,
+                 slow_period: 2,
+                 data: "eurusd_m1",
+                 name: "kama10"
+               )
+
+      assert msg =~ "fast period must be < slow period"
+    end
+
+    test "default fast/slow periods reproduce the native defaults (2 and 30)" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      {:ok, default_state} = Native.overlap_kama_state_init(3)
+      {:ok, explicit_state} = Native.overlap_kama_state_init(3, 2, 30)
+
+      Enum.reduce(data, {default_state, explicit_state}, fn value, {st1, st2} ->
+        {:ok, {value1, new_st1}} = Native.overlap_kama_state_next(st1, value, true)
+        {:ok, {value2, new_st2}} = Native.overlap_kama_state_next(st2, value, true)
+        assert value1 == value2
+        {new_st1, new_st2}
+      end)
+    end
+
+    test "custom fast/slow periods change the smoothing" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      # Reference: TA-Lib KAMA loop with constants 2/(3+1) and 2/(20+1)
+      expected = [nil, nil, nil, nil, 11.963898144291731, 12.055673039390607]
+
+      {:ok, state} =
+        KAMA.init(period: 4, fast_period: 3, slow_period: 20, data: "eurusd_m1", name: "kama4")
+
+      {results, _state} =
+        data
+        |> Enum.take(6)
+        |> Enum.map_reduce(state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_st} = KAMA.next(event, st)
+          {result.value, new_st}
+        end)
+
+      for {value, expected_value} <- Enum.zip(results, expected) do
+        case expected_value do
+          nil -> assert value == nil
+          _ -> assert_in_delta(value, expected_value, 1.0e-9)
+        end
+      end
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "emits the first value exactly at the lookback, like batch" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      {:ok, batch_result} = KAMA.kama(data, 3)
+      {:ok, state} = KAMA.init(period: 3, data: "eurusd_m1", name: "kama3", source: :close)
+
+      {stream_result, _state} =
+        Enum.map_reduce(data, state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_st} = KAMA.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert Enum.find_index(stream_result, & &1) == 3
+      assert Enum.find_index(batch_result, & &1) == 3
+
+      for {stream_value, batch_value} <- Enum.zip(stream_result, batch_result),
+          not is_nil(batch_value) do
+        assert_in_delta(stream_value, batch_value, 1.0e-9)
+      end
+    end
+
+    test "processes bars correctly in UPDATE mode" do
+      {:ok, state} = KAMA.init(period: 3, data: "eurusd_m1", name: "kama3", source: :close)
+
+      state =
+        Enum.reduce([1.0, 2.0, 3.0, 4.0], state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, _result, new_st} = KAMA.next(event, st)
+          new_st
+        end)
+
+      # Replace the last bar (4.0 -> 5.0): same as batch over [1.0, 2.0, 3.0, 5.0]
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 5.0, new_bar?: false}}}
+      {:ok, result, _state} = KAMA.next(event, state)
+
+      {:ok, batch_result} = KAMA.kama([1.0, 2.0, 3.0, 5.0], 3)
+      assert_in_delta(result.value, List.last(batch_result), 1.0e-9)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch KAMA" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 21, max_length: 500),
+              period <- integer(2..50)
+            ) do
+        {:ok, batch_result} = KAMA.kama(data, period)
+
+        {:ok, initial_state} =
+          KAMA.init(period: period, data: "test", name: "kama", source: :close)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected_value}, state ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: value, new_bar?: true}}
+          }
+
+          {:ok, result, new_state} = KAMA.next(event, state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 15, max_length: 200),
+              period <- integer(2..30),
+              update_values <-
+                list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 5)
+            ) do
+        {:ok, state} = KAMA.init(period: period, data: "test", name: "kama", source: :close)
+
+        final_state =
+          Enum.reduce(data, state, fn value, st ->
+            event = %MarketEvent{
+              data: %{"test" => %Bar{close: value, new_bar?: true}}
+            }
+
+            {:ok, _result, new_state} = KAMA.next(event, st)
+            new_state
+          end)
+
+        Enum.reduce(update_values, {final_state, data}, fn update_value, {state, current_data} ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: update_value, new_bar?: false}}
+          }
+
+          {:ok, result, new_state} = KAMA.next(event, state)
+
+          updated_data = List.replace_at(current_data, -1, update_value)
+          {:ok, batch_result} = KAMA.kama(updated_data, period)
+          batch_kama = List.last(batch_result)
+
+          case {result.value, batch_kama} do
+            {nil, nil} ->
+              :ok
+
+            {s_val, b_val} when is_float(s_val) and is_float(b_val) ->
+              assert_in_delta(s_val, b_val, 1.0e-9)
+
+            _ ->
+              flunk("Mismatch between state UPDATE and batch")
+          end
+
+          {new_state, updated_data}
+        end)
+      end
+    end
+  end
+end
@@ -0,0 +1,73 @@
+defmodule TheoryCraftTA.Overlap.KeltnerTest do
+  use ExUnit.Case, async: true
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "overlap_keltner/6" do
+    test "calculates correctly against hand-computed values" do
+      high = [11.0, 12.0, 13.0, 14.0, 14.0]
+      low = [9.0, 10.0, 11.0, 12.0, 10.0]
+      close = [10.0, 11.0, 12.0, 13.0, 11.0]
+
+      assert {:ok, {upper, middle, lower}} =
+               Native.overlap_keltner(high, low, close, 2, 2, 2.0)
+
+      # EMA(2): seeded at bar 1 with (10 + 11) / 2, then k = 2/3
+      #   10.5 -> 11.5 -> 12.5 -> 11.5
+      # ATR(2): TR = 2, 2, 2, 4 so ATR = 2 at bar 2, 2 at bar 3, (2 + 4) / 2 = 3 at bar 4
+      # Both are aligned on the later warmup (ATR, bar 2)
+      assert_values_in_delta(middle, [nil, nil, 11.5, 12.5, 11.5])
+      assert_values_in_delta(upper, [nil, nil, 15.5, 16.5, 17.5])
+      assert_values_in_delta(lower, [nil, nil, 7.5, 8.5, 5.5])
+    end
+
+    test "aligns on the later warmup when the EMA is the slowest" do
+      high = Enum.map(1..10, &(&1 + 1.0))
+      low = Enum.map(1..10, &(&1 - 1.0))
+      close = Enum.map(1..10, &(&1 * 1.0))
+
+      assert {:ok, {upper, middle, lower}} =
+               Native.overlap_keltner(high, low, close, 5, 2, 1.0)
+
+      for band <- [upper, middle, lower] do
+        assert Enum.find_index(band, & &1) == 4
+      end
+    end
+
+    test "shares the first valid bar across high, low and close" do
+      high = [nil, 11.0, 12.0, 13.0, 14.0]
+      low = [8.0, 9.0, 10.0, 11.0, 12.0]
+      close = [1.0, 10.0, 11.0, 12.0, 13.0]
+
+      assert {:ok, {upper, middle, lower}} =
+               Native.overlap_keltner(high, low, close, 2, 2, 2.0)
+
+      # The close of the incomplete first bar is not part of the EMA
+      assert_values_in_delta(middle, [nil, nil, nil, 11.5, 12.5])
+      assert_values_in_delta(upper, [nil, nil, nil, 15.5, 16.5])
+      assert_values_in_delta(lower, [nil, nil, nil, 7.5, 8.5])
+    end
+
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.overlap_keltner([1.0], [1.0], [1.0], 1, 10, 2.0)
+      assert reason =~ "ema_period must be >= 2"
+
+      assert {:error, reason} = Native.overlap_keltner([1.0], [1.0], [1.0], 20, 1, 2.0)
+      assert reason =~ "atr_period must be >= 2"
+
+      assert {:error, reason} = Native.overlap_keltner([1.0], [1.0], [1.0], 20, 10, 0.0)
+      assert reason =~ "multiplier must be > 0"
+
+      assert {:error, reason} = Native.overlap_keltner([1.0], [], [1.0], 20, 10, 2.0)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], [], []}} = Native.overlap_keltner([], [], [], 20, 10, 2.0)
+    end
+  end
+end
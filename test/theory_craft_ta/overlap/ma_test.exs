@@ -0,0 +1,157 @@
+defmodule TheoryCraftTA.Overlap.MATest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Overlap.{DEMA, EMA, KAMA, MA, SMA, T3, TEMA, TRIMA, WMA}
+
+  doctest TheoryCraftTA.Overlap.MA
+
+  # ma_type => dedicated indicator with the options TA-Lib's MA uses
+  @dedicated [
+    {0, SMA, []},
+    {1, EMA, []},
+    {2, WMA, []},
+    {3, DEMA, []},
+    {4, TEMA, []},
+    {5, TRIMA, []},
+    {6, KAMA, []},
+    {8, T3, [vfactor: 0.7]}
+  ]
+
+  ## Batch calculation tests
+
+  describe "ma/3 with list input" do
+    test "calculates an SMA with ma_type=0" do
+      data = [1.0, 2.0, 3.0, 4.0, 5.0]
+      assert {:ok, [nil, nil, 2.0, 3.0, 4.0]} = MA.ma(data, 3, 0)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = MA.ma([1.0, 2.0, 3.0], 1, 0)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns error for an unknown ma_type" do
+      assert {:error, reason} = MA.ma([1.0, 2.0, 3.0], 2, 9)
+      assert reason =~ "ma_type must be between 0 and 8"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = MA.ma([], 3, 0)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} =
+               MA.init(period: 10, ma_type: 1, data: "eurusd_m1", name: "ma10", source: :close)
+    end
+
+    test "defaults to an SMA" do
+      assert {:ok, %MA{ma_type: 0}} = MA.init(period: 10, data: "eurusd_m1", name: "ma10")
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, msg} = MA.init(period: 1, data: "eurusd_m1", name: "ma1")
+      assert msg =~ "Invalid period"
+    end
+
+    test "returns error for MAMA" do
+      assert {:error, msg} = MA.init(period: 10, ma_type: 7, data: "eurusd_m1", name: "ma10")
+      assert msg =~ "MAMA (7) is not supported"
+    end
+
+    test "returns error for an unknown ma_type" do
+      assert {:error, msg} = MA.init(period: 10, ma_type: 9, data: "eurusd_m1", name: "ma10")
+      assert msg =~ "Invalid ma_type"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 matches the dedicated indicators" do
+    for {ma_type, module, extra_opts} <- @dedicated do
+      @ma_type ma_type
+      @module module
+      @extra_opts extra_opts
+
+      test "ma_type=#{ma_type} matches #{inspect(module)} in APPEND and UPDATE modes" do
+        events = [
+          {100.0, true},
+          {104.0, true},
+          {101.0, false},
+          {108.0, true},
+          {107.0, true},
+          {111.0, false},
+          {115.0, true},
+          {112.0, true},
+          {118.0, true},
+          {116.0, false},
+          {121.0, true},
+          {119.0, true}
+        ]
+
+        {:ok, ma_state} = MA.init(period: 3, ma_type: @ma_type, data: "test", name: "ma")
+        {:ok, state} = @module.init([period: 3, data: "test", name: "ma"] ++ @extra_opts)
+
+        Enum.reduce(events, {ma_state, state}, fn {value, new_bar?}, {ma_st, st} ->
+          event = %MarketEvent{data: %{"test" => %Bar{close: value, new_bar?: new_bar?}}}
+          {:ok, ma_result, new_ma_st} = MA.next(event, ma_st)
+          {:ok, result, new_st} = @module.next(event, st)
+          assert ma_result.value == result.value
+          {new_ma_st, new_st}
+        end)
+      end
+    end
+
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = MA.init(period: 2, ma_type: 1, data: "test", name: "ma")
+
+      state =
+        Enum.reduce([1.0, 2.0], state, fn value, st ->
+          event = %MarketEvent{data: %{"test" => %Bar{close: value, new_bar?: true}}}
+          {:ok, _result, new_st} = MA.next(event, st)
+          new_st
+        end)
+
+      event = %MarketEvent{data: %{"test" => %Bar{close: nil, new_bar?: true}}}
+      assert {:ok, %{value: nil}, ^state} = MA.next(event, state)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch MA" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..10),
+              ma_type <- member_of([0, 1, 2, 3, 4, 5, 6, 8])
+            ) do
+        {:ok, batch_result} = MA.ma(data, period, ma_type)
+        {:ok, initial_state} = MA.init(period: period, ma_type: ma_type, data: "test", name: "ma")
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected_value}, state ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: value, new_bar?: true}}
+          }
+
+          {:ok, result, new_state} = MA.next(event, state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-6)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+end
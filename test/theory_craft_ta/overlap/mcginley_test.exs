@@ -0,0 +1,180 @@
+defmodule TheoryCraftTA.Overlap.McGinleyTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Overlap.McGinley
+
+  doctest TheoryCraftTA.Overlap.McGinley
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0]
+
+  ## Batch calculation tests
+
+  describe "overlap_mcginley/2" do
+    test "calculates correctly with period=2" do
+      # Seeded with the first price, then md + (price - md) / (2 * (price / md)^4)
+      # 10 + 10 / (2 * 16) = 10.3125
+      assert {:ok, [10.0, 10.3125, md]} = Native.overlap_mcginley([10.0, 20.0, 15.0], 2)
+      assert_in_delta md, 10.3125 + 4.6875 / (2 * :math.pow(15.0 / 10.3125, 4)), 1.0e-9
+    end
+
+    test "is constant on a constant series" do
+      assert {:ok, [5.0, 5.0, 5.0, 5.0]} = Native.overlap_mcginley(List.duplicate(5.0, 4), 3)
+    end
+
+    test "restarts from the price after a zero" do
+      assert {:ok, [10.0, 0.0, 5.0]} = Native.overlap_mcginley([10.0, 0.0, 5.0], 2)
+    end
+
+    test "skips nil values like the streaming state" do
+      assert {:ok, [nil, 10.0, nil, 10.3125]} =
+               Native.overlap_mcginley([nil, 10.0, nil, 20.0], 2)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_mcginley([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.overlap_mcginley([], 3)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "overlap_mcginley_state_init/1" do
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_mcginley_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "overlap_mcginley_state_next/3" do
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.overlap_mcginley_state_init(2)
+      {:ok, {10.0, state}} = Native.overlap_mcginley_state_next(state, 10.0, true)
+
+      assert {:ok, {nil, ^state}} = Native.overlap_mcginley_state_next(state, nil, true)
+    end
+
+    test "UPDATE mode recomputes from the previous bar" do
+      {:ok, state} = Native.overlap_mcginley_state_init(2)
+      {:ok, {10.0, state}} = Native.overlap_mcginley_state_next(state, 10.0, true)
+      {:ok, {10.3125, state}} = Native.overlap_mcginley_state_next(state, 20.0, true)
+
+      assert {:ok, {10.0, state}} = Native.overlap_mcginley_state_next(state, 10.0, false)
+      assert {:ok, {10.3125, _state}} = Native.overlap_mcginley_state_next(state, 20.0, false)
+    end
+  end
+
+  ## Public API tests
+
+  describe "mcginley/2" do
+    test "matches the native calculation" do
+      assert McGinley.mcginley(@data, 3) == Native.overlap_mcginley(@data, 3)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.overlap_mcginley(@data, 3)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = McGinley.mcginley(ds, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = McGinley.init(period: 1, data: "eurusd_m1", name: "mcginley")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = McGinley.mcginley(@data, 3)
+      {:ok, state} = McGinley.init(period: 3, data: "eurusd_m1", name: "mcginley")
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = McGinley.next(close_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = McGinley.init(period: 3, data: "eurusd_m1", name: "mcginley")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = McGinley.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = McGinley.next(close_event(100.0, false), state)
+      {:ok, batch_result} = McGinley.mcginley(List.replace_at(@data, -1, 100.0), 3)
+
+      assert_same_value(result.value, List.last(batch_result))
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch McGinley Dynamic" do
+      check all(
+              data <- list_of(price(), min_length: 1, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, batch_result} = Native.overlap_mcginley(data, period)
+        {:ok, initial_state} = Native.overlap_mcginley_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          {:ok, {result, new_state}} = Native.overlap_mcginley_state_next(state, value, true)
+          assert_in_delta(result, expected, 1.0e-9)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE before each APPEND ends on the batch result" do
+      check all(
+              data <- list_of({price(), price()}, min_length: 1, max_length: 100),
+              period <- integer(2..10)
+            ) do
+        final_data = Enum.map(data, fn {_forming, final} -> final end)
+        {:ok, batch_result} = Native.overlap_mcginley(final_data, period)
+        {:ok, initial_state} = Native.overlap_mcginley_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {{forming, final}, expected}, state ->
+          {:ok, {_result, state}} = Native.overlap_mcginley_state_next(state, forming, true)
+          {:ok, {result, new_state}} = Native.overlap_mcginley_state_next(state, final, false)
+          assert_in_delta(result, expected, 1.0e-9)
+          new_state
+        end)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp price(), do: float(min: 1.0, max: 1000.0)
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
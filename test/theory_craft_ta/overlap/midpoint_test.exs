@@ -28,13 +28,13 @@ defmodule TheoryCraftTA.Overlap.MIDPOINTTest do
     test "raises for period=1" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = MIDPOINT.midpoint(data, 1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for period=0" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = MIDPOINT.midpoint(data, 0)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "returns empty for empty input" do
@@ -225,6 +225,24 @@ defmodule TheoryCraftTA.Overlap.MIDPOINTTest do
     end
   end
 
+  describe "next/2 with :nan_policy" do
+    test ":carry_forward returns the current MIDPOINT and recovers on the next valid bar" do
+      {:ok, state} =
+        MIDPOINT.init(period: 2, data: "eurusd_m1", name: "midpoint2", nan_policy: :carry_forward)
+
+      {:ok, _result, state} = MIDPOINT.next(close_event(100.0), state)
+      {:ok, result, state} = MIDPOINT.next(close_event(110.0), state)
+      assert result.value == 105.0
+
+      {:ok, %IndicatorValue{value: carried}, state} = MIDPOINT.next(close_event(nil), state)
+      assert carried == result.value
+
+      # The missing bar is ignored: same as the MIDPOINT of [100.0, 110.0, 120.0]
+      {:ok, result, _state} = MIDPOINT.next(close_event(120.0), state)
+      assert result.value == 115.0
+    end
+  end
+
   ## Property-based tests
 
   describe "property: state-based APPEND matches batch calculation" do
@@ -315,4 +333,10 @@ defmodule TheoryCraftTA.Overlap.MIDPOINTTest do
       end
     end
   end
+
+  ## Private functions
+
+  defp close_event(close) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: true}}}
+  end
 end
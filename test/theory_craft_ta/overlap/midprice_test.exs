@@ -0,0 +1,271 @@
+defmodule TheoryCraftTA.Overlap.MIDPRICETest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Overlap.MIDPRICE
+
+  doctest TheoryCraftTA.Overlap.MIDPRICE
+
+  @high [12.0, 12.5, 12.2, 13.0, 13.4, 13.1, 13.8, 14.2]
+  @low [11.0, 11.6, 11.4, 12.1, 12.6, 12.4, 12.9, 13.3]
+
+  ## Batch calculation tests
+
+  describe "midprice/3 with list input" do
+    test "calculates correctly with period=3" do
+      # Python result: [nan nan 11.75 12.2 12.4 12.75 13.1 13.3]
+      assert {:ok, result} = MIDPRICE.midprice(@high, @low, 3)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        11.75,
+        12.2,
+        12.4,
+        12.75,
+        13.100000000000001,
+        13.3
+      ])
+    end
+
+    test "calculates correctly with period=5" do
+      # Python result: [nan nan nan nan 12.2 12.4 12.6 13.15]
+      assert {:ok, result} = MIDPRICE.midprice(@high, @low, 5)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        nil,
+        12.2,
+        12.4,
+        12.600000000000001,
+        13.149999999999999
+      ])
+    end
+
+    test "raises for period=1" do
+      assert {:error, reason} = MIDPRICE.midprice(@high, @low, 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = MIDPRICE.midprice(@high, Enum.drop(@low, 1), 3)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = MIDPRICE.midprice([], [], 3)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, result} = MIDPRICE.midprice(Enum.take(@high, 2), Enum.take(@low, 2), 3)
+      assert result == [nil, nil]
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      high = [nil, nil | Enum.drop(@high, 2)]
+      low = [nil, nil | Enum.drop(@low, 2)]
+      # Python result: [nan nan nan nan 12.4 12.75 13.1 13.3]
+      assert {:ok, result} = MIDPRICE.midprice(high, low, 3)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        nil,
+        12.4,
+        12.75,
+        13.100000000000001,
+        13.3
+      ])
+    end
+  end
+
+  describe "midprice/3 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      high = Enum.reduce(@high, DataSeries.new(), &DataSeries.add(&2, &1))
+      low = Enum.reduce(@low, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, result} = MIDPRICE.midprice(high, low, 3)
+      assert %DataSeries{} = result
+      assert [_, _, _, _, _, 11.75, nil, nil] = DataSeries.values(result)
+    end
+  end
+
+  describe "midprice/3 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      times = Enum.map(0..7, &DateTime.add(~U[2024-01-01 00:00:00Z], &1, :minute))
+
+      [high, low] =
+        for values <- [@high, @low] do
+          times
+          |> Enum.zip(values)
+          |> Enum.reduce(TimeSeries.new(), fn {time, value}, ts ->
+            TimeSeries.add(ts, time, value)
+          end)
+        end
+
+      assert {:ok, result} = MIDPRICE.midprice(high, low, 3)
+      assert %TimeSeries{} = result
+      assert [_, _, _, _, _, 11.75, nil, nil] = TimeSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} = MIDPRICE.init(period: 14, data: "eurusd_m1", name: "midprice14")
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, msg} = MIDPRICE.init(period: 1, data: "eurusd_m1", name: "midprice1")
+      assert msg =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "matches batch once warmed up" do
+      {:ok, batch_result} = MIDPRICE.midprice(@high, @low, 3)
+      {:ok, state} = MIDPRICE.init(period: 3, data: "eurusd_m1", name: "midprice3")
+
+      {stream_result, _state} =
+        [@high, @low]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = MIDPRICE.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE can lower the highest high of the window" do
+      {:ok, state} = MIDPRICE.init(period: 3, data: "eurusd_m1", name: "midprice3")
+
+      state =
+        Enum.reduce([{12.0, 11.0}, {12.5, 11.6}, {15.0, 11.4}], state, fn bar, st ->
+          {:ok, _result, new_st} = MIDPRICE.next(bar_event(bar, true), st)
+          new_st
+        end)
+
+      # The 3rd bar is still open: its high drops back below the 2nd bar's high
+      {:ok, result, _state} = MIDPRICE.next(bar_event({12.2, 11.4}, false), state)
+      assert_in_delta(result.value, (12.5 + 11.0) / 2, 1.0e-9)
+    end
+
+    test "handles nil values without modifying the state" do
+      {:ok, state} = MIDPRICE.init(period: 2, data: "eurusd_m1", name: "midprice2")
+
+      event = %MarketEvent{
+        data: %{"eurusd_m1" => %Bar{high: nil, low: nil, new_bar?: true}}
+      }
+
+      {:ok, result, state} = MIDPRICE.next(event, state)
+      assert result.value == nil
+
+      {results, _state} =
+        Enum.map_reduce([{12.0, 11.0}, {12.5, 11.6}], state, fn bar, st ->
+          {:ok, result, new_st} = MIDPRICE.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert [nil, value] = results
+      assert_in_delta(value, (12.5 + 11.0) / 2, 1.0e-9)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch MIDPRICE" do
+      check all(
+              bars <- bars_generator(21, 300),
+              period <- integer(2..50)
+            ) do
+        {high, low} = Enum.unzip(bars)
+        {:ok, batch_result} = MIDPRICE.midprice(high, low, period)
+
+        {:ok, initial_state} = MIDPRICE.init(period: period, data: "test", name: "midprice")
+
+        bars
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {bar, expected_value}, state ->
+          {:ok, result, new_state} = MIDPRICE.next(bar_event("test", bar, true), state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: mixed APPEND/UPDATE matches a naive window" do
+    property "every tick matches max/min over the last period bars" do
+      check all(
+              ticks <-
+                list_of(tuple({bar_generator(), boolean()}), min_length: 1, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, initial_state} = MIDPRICE.init(period: period, data: "test", name: "midprice")
+
+        Enum.reduce(ticks, {initial_state, []}, fn {bar, new_bar?}, {state, bars} ->
+          {:ok, result, new_state} = MIDPRICE.next(bar_event("test", bar, new_bar?), state)
+
+          # Bars are stored newest first, an UPDATE before the first bar is not a bar
+          bars =
+            case {new_bar?, bars} do
+              {true, _} -> [bar | bars]
+              {false, []} -> []
+              {false, [_ | rest]} -> [bar | rest]
+            end
+
+          assert result.value == naive_midprice(bars, period)
+
+          {new_state, bars}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp bar_event(data_name \\ "eurusd_m1", {high, low}, new_bar?) do
+    %MarketEvent{
+      data: %{data_name => %Bar{high: high, low: low, new_bar?: new_bar?}}
+    }
+  end
+
+  defp bar_generator() do
+    gen all(
+          price <- float(min: 1.0, max: 1000.0),
+          up <- float(min: 0.0, max: 10.0),
+          down <- float(min: 0.0, max: 10.0)
+        ) do
+      {price + up, price - down}
+    end
+  end
+
+  defp bars_generator(min_length, max_length) do
+    list_of(bar_generator(), min_length: min_length, max_length: max_length)
+  end
+
+  defp naive_midprice(bars, period) when length(bars) < period, do: nil
+
+  defp naive_midprice(bars, period) do
+    {highs, lows} = bars |> Enum.take(period) |> Enum.unzip()
+    (Enum.max(highs) + Enum.min(lows)) / 2
+  end
+end
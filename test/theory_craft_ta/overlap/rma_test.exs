@@ -0,0 +1,171 @@
+defmodule TheoryCraftTA.Overlap.RMATest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Momentum.RSI
+  alias TheoryCraftTA.Overlap.RMA
+
+  doctest TheoryCraftTA.Overlap.RMA
+
+  ## Batch calculation tests
+
+  describe "rma/2 with list input" do
+    test "calculates correctly with period=3" do
+      data = [1.0, 2.0, 3.0, 4.0, 5.0]
+      # Seed: (1 + 2 + 3) / 3 = 2.0, then RMA += (P - RMA) / 3
+      assert {:ok, result} = RMA.rma(data, 3)
+      assert Enum.take(result, 2) == [nil, nil]
+      assert_in_delta Enum.at(result, 2), 2.0, 1.0e-12
+      assert_in_delta Enum.at(result, 3), 2.6666666667, 0.0001
+      assert_in_delta Enum.at(result, 4), 3.4444444444, 0.0001
+    end
+
+    test "skips leading nil values" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0]
+      assert {:ok, [nil, nil, nil, nil, 2.0, _]} = RMA.rma(data, 3)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = RMA.rma([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = RMA.rma([], 3)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, [nil, nil]} = RMA.rma([1.0, 2.0], 3)
+    end
+  end
+
+  describe "rma/2 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      data =
+        DataSeries.new()
+        |> DataSeries.add(1.0)
+        |> DataSeries.add(2.0)
+        |> DataSeries.add(3.0)
+
+      assert {:ok, %DataSeries{} = result} = RMA.rma(data, 2)
+      assert DataSeries.values(result) == [2.25, 1.5, nil]
+    end
+  end
+
+  describe "rma/2 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      ts =
+        TimeSeries.new()
+        |> TimeSeries.add(~U[2024-01-01 00:00:00Z], 1.0)
+        |> TimeSeries.add(~U[2024-01-01 00:01:00Z], 2.0)
+        |> TimeSeries.add(~U[2024-01-01 00:02:00Z], 3.0)
+
+      assert {:ok, %TimeSeries{} = result} = RMA.rma(ts, 2)
+      assert TimeSeries.values(result) == [2.25, 1.5, nil]
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} = RMA.init(period: 14, data: "eurusd_m1", name: "rma14")
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, msg} = RMA.init(period: 1, data: "eurusd_m1", name: "rma1")
+      assert msg =~ "Invalid period"
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch RMA" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, batch_result} = RMA.rma(data, period)
+        {:ok, initial_state} = RMA.init(period: period, data: "test", name: "rma")
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected_value}, state ->
+          event = %MarketEvent{data: %{"test" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_state} = RMA.next(event, state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20),
+              update_value <- float(min: 1.0, max: 1000.0)
+            ) do
+        {:ok, state} = RMA.init(period: period, data: "test", name: "rma")
+
+        state =
+          Enum.reduce(data, state, fn value, st ->
+            event = %MarketEvent{data: %{"test" => %Bar{close: value, new_bar?: true}}}
+            {:ok, _result, new_st} = RMA.next(event, st)
+            new_st
+          end)
+
+        event = %MarketEvent{data: %{"test" => %Bar{close: update_value, new_bar?: false}}}
+        {:ok, result, _state} = RMA.next(event, state)
+
+        {:ok, batch_result} = RMA.rma(List.replace_at(data, -1, update_value), period)
+
+        case {result.value, List.last(batch_result)} do
+          {nil, nil} -> :ok
+          {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+          _ -> flunk("Mismatch between state UPDATE and batch")
+        end
+      end
+    end
+  end
+
+  describe "property: RMA of gains and losses reproduces RSI" do
+    property "RSI = 100 * RMA(gains) / (RMA(gains) + RMA(losses))" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        changes = data |> Enum.chunk_every(2, 1, :discard) |> Enum.map(fn [a, b] -> b - a end)
+        gains = [nil | Enum.map(changes, &max(&1, 0.0))]
+        losses = [nil | Enum.map(changes, &max(-&1, 0.0))]
+
+        {:ok, avg_gains} = RMA.rma(gains, period)
+        {:ok, avg_losses} = RMA.rma(losses, period)
+        {:ok, rsi} = RSI.rsi(data, period)
+
+        [avg_gains, avg_losses, rsi]
+        |> Enum.zip()
+        |> Enum.each(fn
+          {nil, nil, nil} ->
+            :ok
+
+          {avg_gain, avg_loss, expected} when avg_gain + avg_loss > 0 ->
+            assert_in_delta(100.0 * avg_gain / (avg_gain + avg_loss), expected, 1.0e-6)
+
+          {_avg_gain, _avg_loss, expected} ->
+            assert expected == 0.0
+        end)
+      end
+    end
+  end
+end
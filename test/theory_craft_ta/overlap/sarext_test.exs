@@ -0,0 +1,74 @@
+defmodule TheoryCraftTA.Overlap.SAREXTTest do
+  use ExUnit.Case, async: true
+
+  alias TheoryCraftTA.Native
+
+  @default_accel [0.02, 0.02, 0.2, 0.02, 0.02, 0.2]
+
+  defp sarext(high, low, start_value \\ 0.0, offset_on_reverse \\ 0.0, accel \\ @default_accel) do
+    apply(Native, :overlap_sarext, [high, low, start_value, offset_on_reverse | accel])
+  end
+
+  ## Batch calculation tests
+
+  describe "overlap_sarext/10" do
+    test "follows a steady uptrend below the lows" do
+      low = Enum.map(0..9, &(10.0 + &1))
+      high = Enum.map(low, &(&1 + 1.0))
+
+      assert {:ok, [nil, sar1, sar2, sar3 | rest] = result} = sarext(high, low)
+
+      # Long from the second bar: SAR starts on the first low, then moves by
+      # AF * (EP - SAR) with the AF growing by 0.02 on each new high
+      assert_in_delta sar1, 10.0, 1.0e-9
+      assert_in_delta sar2, 10.04, 1.0e-9
+      assert_in_delta sar3, 10.1584, 1.0e-9
+
+      for {sar, low} <- Enum.zip(tl(result), tl(low)) do
+        assert sar > 0.0 and sar < low
+      end
+
+      assert length(rest) == 6
+    end
+
+    test "encodes a downtrend as negative values above the highs" do
+      high = Enum.map(0..9, &(30.0 - &1))
+      low = Enum.map(high, &(&1 - 1.0))
+
+      assert {:ok, [nil | values]} = sarext(high, low)
+
+      for {sar, high} <- Enum.zip(values, tl(high)) do
+        assert sar < 0.0 and abs(sar) > high
+      end
+    end
+
+    test "skips leading bars where any input is nil" do
+      assert {:ok, [nil, nil, 10.0]} = sarext([nil, 11.0, 12.0], [9.0, 10.0, 11.0])
+    end
+
+    test "returns error for a negative offset_on_reverse" do
+      assert {:error, reason} = sarext([2.0], [1.0], 0.0, -0.1)
+      assert reason =~ "offset_on_reverse must be >= 0"
+    end
+
+    test "returns error for a negative acceleration factor" do
+      assert {:error, reason} =
+               sarext([2.0], [1.0], 0.0, 0.0, [0.02, 0.02, 0.2, 0.02, -0.02, 0.2])
+
+      assert reason =~ "acceleration factors must be >= 0"
+    end
+
+    test "returns error for inputs of different lengths" do
+      assert {:error, reason} = sarext([2.0, 3.0], [1.0])
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = sarext([], [])
+    end
+
+    test "handles insufficient data" do
+      assert {:ok, [nil]} = sarext([2.0], [1.0])
+    end
+  end
+end
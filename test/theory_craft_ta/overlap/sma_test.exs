@@ -4,10 +4,18 @@ defmodule TheoryCraftTA.Overlap.SMATest do
 
   alias TheoryCraft.{DataSeries, TimeSeries}
   alias TheoryCraft.MarketSource.{Bar, IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.Native
   alias TheoryCraftTA.Overlap.SMA
 
   doctest TheoryCraftTA.Overlap.SMA
 
+  @ohlc %{
+    open: [10.0, 11.0, 12.0, 11.0],
+    high: [12.0, 13.0, 14.0, 13.0],
+    low: [8.0, 10.0, 11.0, 9.0],
+    close: [11.0, 12.0, 11.0, 12.0]
+  }
+
   ## Batch calculation tests
 
   describe "sma/2 with list input" do
@@ -28,19 +36,26 @@ defmodule TheoryCraftTA.Overlap.SMATest do
     test "raises for period=1" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = SMA.sma(data, 1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for period=0" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = SMA.sma(data, 0)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for negative period" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = SMA.sma(data, -1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "rejects invalid periods before calling TA-Lib" do
+      data = [1.0, 2.0, 3.0]
+      assert {:error, "SMA: period must be >= 2"} = SMA.sma(data, 0)
+      assert {:error, "SMA: period must be >= 2"} = SMA.sma(data, -5)
+      assert {:error, "SMA: period must be >= 2"} = SMA.sma([], 0)
     end
 
     test "returns empty for empty input" do
@@ -73,6 +88,22 @@ defmodule TheoryCraftTA.Overlap.SMATest do
       assert {:ok, result} = SMA.sma(data, 3)
       assert result == [nil, nil, 2.0, nil, nil, nil, nil, nil]
     end
+
+    test "handles all-NaN input" do
+      data = [nil, nil, nil, nil, nil]
+      # Python result: [nan nan nan nan nan]
+      assert {:ok, result} = SMA.sma(data, 2)
+      assert result == [nil, nil, nil, nil, nil]
+    end
+
+    test "aligns the first value with leading NaN and lookback" do
+      for leading <- 0..4, period <- 2..5 do
+        data = List.duplicate(nil, leading) ++ Enum.map(1..10, &(&1 * 1.0))
+        assert {:ok, result} = SMA.sma(data, period)
+        assert length(result) == length(data)
+        assert Enum.find_index(result, & &1) == leading + period - 1
+      end
+    end
   end
 
   describe "sma/2 with DataSeries input" do
@@ -113,6 +144,285 @@ defmodule TheoryCraftTA.Overlap.SMATest do
     end
   end
 
+  describe "overlap_sma_f32/2" do
+    property "matches the f64 result rounded to f32" do
+      # Quarters are exact in f32, so only the output narrowing can differ
+      check all(
+              data <- list_of(map(integer(4..4000), &(&1 / 4)), min_length: 10, max_length: 200),
+              period <- integer(2..10)
+            ) do
+        {:ok, f64_result} = Native.overlap_sma(data, period)
+        {:ok, f32_result} = Native.overlap_sma_f32(data, period)
+
+        assert length(f32_result) == length(f64_result)
+
+        Enum.zip_with(f32_result, f64_result, fn
+          nil, nil -> :ok
+          val, exp -> assert_in_delta(val, exp, abs(exp) * 1.0e-6)
+        end)
+      end
+    end
+
+    test "treats nil as missing like the f64 path" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+      {:ok, f64_result} = Native.overlap_sma(data, 3)
+      assert {:ok, f32_result} = Native.overlap_sma_f32(data, 3)
+      assert Enum.map(f32_result, &is_nil/1) == Enum.map(f64_result, &is_nil/1)
+    end
+  end
+
+  describe "overlap_sma_trimmed/2" do
+    property "padding the trimmed result reproduces the full result" do
+      check all(
+              leading <- integer(0..5),
+              values <- list_of(float(min: 1.0, max: 1000.0), max_length: 100),
+              period <- integer(2..10)
+            ) do
+        data = List.duplicate(nil, leading) ++ values
+
+        {:ok, full_result} = Native.overlap_sma(data, period)
+        {:ok, {first_index, trimmed}} = Native.overlap_sma_trimmed(data, period)
+
+        assert List.duplicate(nil, first_index) ++ trimmed == full_result
+      end
+    end
+
+    test "reports the total lookback as the first index" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0]
+      assert {:ok, {4, trimmed}} = Native.overlap_sma_trimmed(data, 3)
+      assert length(trimmed) == 3
+    end
+
+    test "returns no values when there is not enough data" do
+      assert {:ok, {2, []}} = Native.overlap_sma_trimmed([1.0, 2.0], 3)
+      assert {:ok, {0, []}} = Native.overlap_sma_trimmed([], 3)
+    end
+  end
+
+  describe "overlap_sma_iodata/2" do
+    property "decoding the binary reproduces the trimmed result" do
+      check all(
+              leading <- integer(0..5),
+              values <- list_of(float(min: 1.0, max: 1000.0), max_length: 100),
+              period <- integer(2..10)
+            ) do
+        data = List.duplicate(nil, leading) ++ values
+
+        {:ok, {first_index, trimmed}} = Native.overlap_sma_trimmed(data, period)
+        {:ok, {^first_index, binary}} = Native.overlap_sma_iodata(data, period)
+
+        assert byte_size(binary) == 8 * length(trimmed)
+        assert decode_f64(binary) == trimmed
+      end
+    end
+
+    test "returns an empty binary when there is not enough data" do
+      assert {:ok, {2, ""}} = Native.overlap_sma_iodata([1.0, 2.0], 3)
+    end
+  end
+
+  describe "overlap_sma_with_sentinel/3" do
+    test "treats the sentinel as missing on input and output" do
+      data = [-1.0, 1.0, 2.0, 3.0, 4.0, 5.0]
+
+      assert {:ok, [-1.0, -1.0, -1.0, 2.0, 3.0, 4.0]} =
+               Native.overlap_sma_with_sentinel(data, 3, -1.0)
+    end
+
+    test "matches overlap_sma with nil in place of the sentinel" do
+      data = [-1.0, -1.0, 10.0, 11.5, 12.0, -1.0, 13.0, 12.5, 14.0, 15.5]
+      {:ok, expected} = Native.overlap_sma(Enum.map(data, &if(&1 == -1.0, do: nil, else: &1)), 2)
+
+      assert {:ok, result} = Native.overlap_sma_with_sentinel(data, 2, -1.0)
+      assert result == Enum.map(expected, &(&1 || -1.0))
+    end
+
+    test "returns all sentinels when there is not enough data" do
+      assert {:ok, [0.0, 0.0]} = Native.overlap_sma_with_sentinel([1.0, 2.0], 3, 0.0)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_sma_with_sentinel([1.0, 2.0], 1, -1.0)
+      assert reason =~ "period must be >= 2"
+    end
+  end
+
+  describe "overlap_sma_multi/2" do
+    test "computes each series independently on mixed lengths" do
+      series = [
+        [1.0, 2.0, 3.0, 4.0, 5.0],
+        [],
+        [1.0, 2.0],
+        [nil, nil, 10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0]
+      ]
+
+      assert [
+               {:ok, [nil, nil, 2.0, 3.0, 4.0]},
+               {:ok, []},
+               {:ok, [nil, nil]},
+               {:ok, [nil, nil, nil, nil, 20.0, 30.0, 40.0, 50.0, 60.0]}
+             ] = Native.overlap_sma_multi(series, 3)
+    end
+
+    test "returns an error for each series on invalid period" do
+      assert [{:error, reason}, {:error, _}] = Native.overlap_sma_multi([[1.0], [2.0]], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for no series" do
+      assert [] = Native.overlap_sma_multi([], 3)
+    end
+
+    property "matches overlap_sma on each series, in order" do
+      check all(
+              series <-
+                list_of(list_of(float(min: 1.0, max: 1000.0), max_length: 100), max_length: 50),
+              period <- integer(2..10)
+            ) do
+        expected = Enum.map(series, &Native.overlap_sma(&1, period))
+        assert Native.overlap_sma_multi(series, period) == expected
+      end
+    end
+  end
+
+  describe "overlap_sma_windows/4" do
+    test "computes the SMA of each window, restarting the warmup" do
+      data = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]
+
+      assert {:ok, [first, second]} = Native.overlap_sma_windows(data, 3, 5, 2)
+      assert first == [nil, nil, 2.0, 3.0, 4.0]
+      assert second == [nil, nil, 4.0, 5.0, 6.0]
+    end
+
+    test "drops a trailing window shorter than the window length" do
+      assert {:ok, windows} = Native.overlap_sma_windows([1.0, 2.0, 3.0, 4.0], 2, 3, 1)
+      assert windows == [[nil, 1.5, 2.5], [nil, 2.5, 3.5]]
+
+      assert {:ok, []} = Native.overlap_sma_windows([1.0, 2.0], 2, 3, 1)
+    end
+
+    test "matches overlap_sma on each slice" do
+      data = Enum.map(1..30, &(:math.sin(&1) * 10.0 + 50.0))
+      {:ok, windows} = Native.overlap_sma_windows(data, 4, 10, 7)
+
+      expected =
+        for start <- [0, 7, 14] do
+          {:ok, sma} = Native.overlap_sma(Enum.slice(data, start, 10), 4)
+          sma
+        end
+
+      assert windows == expected
+    end
+
+    test "returns an error for invalid parameters" do
+      assert {:error, reason} = Native.overlap_sma_windows([1.0, 2.0, 3.0], 3, 2, 1)
+      assert reason =~ "window must be >= period"
+
+      assert {:error, reason} = Native.overlap_sma_windows([1.0, 2.0, 3.0], 2, 3, 0)
+      assert reason =~ "step must be >= 1"
+
+      assert {:error, reason} = Native.overlap_sma_windows([1.0, 2.0, 3.0], 1, 3, 1)
+      assert reason =~ "period must be >= 2"
+    end
+  end
+
+  describe "overlap_sma_partial/2" do
+    test "averages the leading values during the warmup, then matches overlap_sma" do
+      data = [2.0, 4.0, 6.0, 8.0, 10.0, 12.0]
+      {:ok, sma} = Native.overlap_sma(data, 4)
+
+      assert {:ok, partial} = Native.overlap_sma_partial(data, 4)
+      assert partial == [2.0, 3.0, 4.0, 5.0, 7.0, 9.0]
+
+      # Both agree from the first full window on
+      assert Enum.take(sma, 3) == [nil, nil, nil]
+      assert Enum.drop(partial, 3) == Enum.drop(sma, 3)
+    end
+
+    test "keeps leading nil and skips missing values in the warmup" do
+      # Past the warmup, the missing value is propagated as with overlap_sma
+      assert {:ok, [nil, 1.0, 1.0, nil, nil, nil]} =
+               Native.overlap_sma_partial([nil, 1.0, nil, 3.0, 5.0, 7.0], 3)
+    end
+
+    test "averages the whole series when it is shorter than the period" do
+      assert {:ok, [1.0, 1.5]} = Native.overlap_sma_partial([1.0, 2.0], 5)
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.overlap_sma_partial([], 3)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_sma_partial([1.0, 2.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+  end
+
+  describe "overlap_sma_annotated/3" do
+    test "flags the last value as forming when its bar is not closed" do
+      assert {:ok, result} = Native.overlap_sma_annotated([1.0, 2.0, 3.0, 4.0], 2, false)
+      assert result == [{nil, true}, {1.5, true}, {2.5, true}, {3.5, false}]
+    end
+
+    test "flags every value as final when the last bar is closed" do
+      data = [1.0, 2.0, 3.0, 4.0]
+      {:ok, sma} = Native.overlap_sma(data, 2)
+
+      assert {:ok, result} = Native.overlap_sma_annotated(data, 2, true)
+      assert result == Enum.map(sma, &{&1, true})
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.overlap_sma_annotated([], 2, false)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_sma_annotated([1.0, 2.0], 1, false)
+      assert reason =~ "period must be >= 2"
+    end
+  end
+
+  describe "overlap_sma_source/3" do
+    test "computes SMA on the close with :close" do
+      assert Native.overlap_sma_source(@ohlc, 2, :close) == Native.overlap_sma(@ohlc.close, 2)
+    end
+
+    test "computes SMA on the median price with :hl2" do
+      assert {:ok, [nil, 10.75, 12.0, 11.75]} = Native.overlap_sma_source(@ohlc, 2, :hl2)
+    end
+
+    test "computes SMA on the typical price with :hlc3" do
+      {:ok, typprice} = Native.price_typprice(@ohlc.high, @ohlc.low, @ohlc.close)
+
+      assert Native.overlap_sma_source(@ohlc, 2, :hlc3) == Native.overlap_sma(typprice, 2)
+    end
+
+    test "computes SMA on the average price with :ohlc4" do
+      # Average prices: 10.25, 11.5, 12.0, 11.25
+      assert {:ok, [nil, 10.875, 11.75, 11.625]} = Native.overlap_sma_source(@ohlc, 2, :ohlc4)
+    end
+
+    test "handles a bar with a missing value like a nil input" do
+      ohlc = %{@ohlc | high: [nil | Enum.drop(@ohlc.high, 1)]}
+
+      assert {:ok, [nil, nil, 12.0, 11.75]} = Native.overlap_sma_source(ohlc, 2, :hl2)
+    end
+
+    test "only requires the columns of the source to have the same length" do
+      ohlc = %{@ohlc | open: []}
+
+      assert {:ok, _} = Native.overlap_sma_source(ohlc, 2, :hlc3)
+      assert {:error, reason} = Native.overlap_sma_source(ohlc, 2, :ohlc4)
+      assert reason == "SMA: open/high/low/close length mismatch (0/4/4/4)"
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_sma_source(@ohlc, 1, :hl2)
+      assert reason =~ "period must be >= 2"
+    end
+  end
+
   ## State initialization tests
 
   describe "init/1" do
@@ -232,6 +542,217 @@ defmodule TheoryCraftTA.Overlap.SMATest do
     end
   end
 
+  describe "value/1 and ready?/1" do
+    test "return the latest value and the warmup status" do
+      {:ok, state} = SMA.init(period: 2, data: "eurusd_m1", name: "sma2", source: :close)
+      assert SMA.value(state) == nil
+      refute SMA.ready?(state)
+
+      {:ok, _result1, state1} = SMA.next(bar_event(100.0, true), state)
+      assert SMA.value(state1) == nil
+      refute SMA.ready?(state1)
+
+      {:ok, result2, state2} = SMA.next(bar_event(110.0, true), state1)
+      assert SMA.value(state2) == result2.value
+      assert SMA.ready?(state2)
+
+      {:ok, result3, state3} = SMA.next(bar_event(120.0, false), state2)
+      assert SMA.value(state3) == result3.value
+    end
+  end
+
+  describe "peek/2" do
+    test "returns the same value as an identical UPDATE" do
+      {:ok, state} = SMA.init(period: 2, data: "eurusd_m1", name: "sma2", source: :close)
+      {:ok, _result1, state1} = SMA.next(bar_event(100.0, true), state)
+      {:ok, _result2, state2} = SMA.next(bar_event(110.0, true), state1)
+      {:ok, _result3, state3} = SMA.next(bar_event(120.0, true), state2)
+
+      {:ok, peeked} = SMA.peek(state3, 130.0)
+      {:ok, result, _state} = SMA.next(bar_event(130.0, false), state3)
+      assert peeked == result.value
+    end
+
+    test "does not advance the state" do
+      {:ok, state} = SMA.init(period: 2, data: "eurusd_m1", name: "sma2", source: :close)
+      {:ok, _result1, state1} = SMA.next(bar_event(100.0, true), state)
+
+      # Still in warmup: the peeked value doesn't count as a bar
+      assert SMA.peek(state1, 110.0) == {:ok, nil}
+      assert SMA.peek(state1, nil) == {:ok, nil}
+
+      {:ok, result2, _state2} = SMA.next(bar_event(110.0, true), state1)
+      assert result2.value == 105.0
+    end
+
+    test "returns an error for a missing value with nan_policy: :reject" do
+      {:ok, state} = SMA.init(period: 2, data: "eurusd_m1", name: "sma2", nan_policy: :reject)
+      {:ok, _result1, state1} = SMA.next(bar_event(100.0, true), state)
+      {:ok, _result2, state2} = SMA.next(bar_event(110.0, true), state1)
+
+      assert {:error, reason} = SMA.peek(state2, nil)
+      assert reason =~ "missing input rejected"
+    end
+  end
+
+  describe "undo/1" do
+    test "next(append) -> undo returns to the prior state" do
+      {:ok, state} = SMA.init(period: 2, data: "eurusd_m1", name: "sma2", source: :close)
+      {:ok, _result1, state1} = SMA.next(bar_event(100.0, true), state)
+      {:ok, _result2, state2} = SMA.next(bar_event(110.0, true), state1)
+
+      {:ok, result3, state3} = SMA.next(bar_event(120.0, true), state2)
+      assert result3.value == 115.0
+
+      assert {:ok, restored} = SMA.undo(state3)
+
+      # The restored state behaves like the one the undone bar was appended to
+      for event <- [bar_event(130.0, true), bar_event(112.0, false)] do
+        {:ok, expected, _state} = SMA.next(event, state2)
+        {:ok, result, _state} = SMA.next(event, restored)
+        assert result.value == expected.value
+      end
+    end
+
+    test "reverts the UPDATEs received since the last APPEND" do
+      {:ok, state} = SMA.init(period: 2, data: "eurusd_m1", name: "sma2", source: :close)
+      {:ok, _result1, state1} = SMA.next(bar_event(100.0, true), state)
+      {:ok, _result2, state2} = SMA.next(bar_event(110.0, true), state1)
+      {:ok, _result3, state3} = SMA.next(bar_event(120.0, true), state2)
+      {:ok, _result4, state4} = SMA.next(bar_event(125.0, false), state3)
+
+      assert {:ok, restored} = SMA.undo(state4)
+
+      {:ok, expected, _state} = SMA.next(bar_event(130.0, true), state2)
+      {:ok, result, _state} = SMA.next(bar_event(130.0, true), restored)
+      assert result.value == expected.value
+    end
+
+    test "restores the warmup buffer" do
+      {:ok, state} = SMA.init(period: 2, data: "eurusd_m1", name: "sma2", source: :close)
+      {:ok, _result1, state1} = SMA.next(bar_event(90.0, true), state)
+      {:ok, restored} = SMA.undo(state1)
+
+      {:ok, result1, state1} = SMA.next(bar_event(100.0, true), restored)
+      assert result1.value == nil
+
+      {:ok, result2, _state2} = SMA.next(bar_event(110.0, true), state1)
+      assert result2.value == 105.0
+    end
+
+    test "returns error when there is nothing to undo" do
+      {:ok, state} = SMA.init(period: 2, data: "eurusd_m1", name: "sma2", source: :close)
+      assert {:error, msg} = SMA.undo(state)
+      assert msg =~ "Nothing to undo"
+
+      # Only the last APPEND is kept
+      {:ok, _result1, state1} = SMA.next(bar_event(100.0, true), state)
+      {:ok, restored} = SMA.undo(state1)
+      assert {:error, _msg} = SMA.undo(restored)
+    end
+  end
+
+  describe "overlap_sma_state_init_many/1" do
+    test "builds states behaving like individually created ones" do
+      periods = [2, 3, 5]
+      assert {:ok, states} = Native.overlap_sma_state_init_many(periods)
+      assert length(states) == 3
+
+      values = [100.0, 110.0, 120.0, 115.0, 130.0, 125.0]
+
+      for {state, period} <- Enum.zip(states, periods) do
+        {:ok, single_state} = Native.overlap_sma_state_init(period)
+
+        Enum.reduce(values, {state, single_state}, fn value, {state, single} ->
+          {:ok, {result, state}} = Native.overlap_sma_state_next(state, value, true)
+          {:ok, {expected, single}} = Native.overlap_sma_state_next(single, value, true)
+          assert result == expected
+
+          {state, single}
+        end)
+      end
+    end
+
+    test "returns error with the index of an invalid period" do
+      assert {:error, reason} = Native.overlap_sma_state_init_many([2, 14, 1, 0])
+      assert reason == "Invalid period at index 2: must be >= 2 for SMA"
+    end
+
+    test "returns empty for no periods" do
+      assert {:ok, []} = Native.overlap_sma_state_init_many([])
+    end
+  end
+
+  describe "overlap_sma_state_next_ts/3" do
+    test "a greater timestamp appends a new bar" do
+      {:ok, state} = Native.overlap_sma_state_init(2)
+      {:ok, {nil, state}} = Native.overlap_sma_state_next_ts(state, 100.0, 1_000)
+      {:ok, {105.0, state}} = Native.overlap_sma_state_next_ts(state, 110.0, 2_000)
+
+      assert {:ok, {115.0, _state}} = Native.overlap_sma_state_next_ts(state, 120.0, 3_000)
+    end
+
+    test "the same timestamp updates the current bar" do
+      {:ok, state} = Native.overlap_sma_state_init(2)
+      {:ok, {nil, state}} = Native.overlap_sma_state_next_ts(state, 100.0, 1_000)
+      {:ok, {105.0, state}} = Native.overlap_sma_state_next_ts(state, 110.0, 2_000)
+      {:ok, {110.0, state}} = Native.overlap_sma_state_next_ts(state, 120.0, 2_000)
+
+      # The next bar follows the updated one
+      assert {:ok, {125.0, _state}} = Native.overlap_sma_state_next_ts(state, 130.0, 3_000)
+    end
+
+    test "matches state_next with the equivalent is_new_bar flags" do
+      ticks = [{100.0, 1}, {104.0, 1}, {110.0, 2}, {108.0, 2}, {120.0, 5}, {90.0, 6}]
+      {:ok, state} = Native.overlap_sma_state_init(3)
+
+      Enum.reduce(ticks, {state, state, nil}, fn {value, ts}, {ts_state, flag_state, last_ts} ->
+        {:ok, {expected, flag_state}} =
+          Native.overlap_sma_state_next(flag_state, value, ts != last_ts)
+
+        {:ok, {result, ts_state}} = Native.overlap_sma_state_next_ts(ts_state, value, ts)
+        assert result == expected
+
+        {ts_state, flag_state, ts}
+      end)
+    end
+
+    test "returns error for an out-of-order timestamp" do
+      {:ok, state} = Native.overlap_sma_state_init(2)
+      {:ok, {nil, state}} = Native.overlap_sma_state_next_ts(state, 100.0, 2_000)
+
+      assert {:error, reason} = Native.overlap_sma_state_next_ts(state, 110.0, 1_000)
+      assert reason == "SMA: timestamp 1000 is older than the last bar (2000)"
+    end
+
+    test "a nil value leaves the last timestamp unchanged" do
+      {:ok, state} = Native.overlap_sma_state_init(2)
+      {:ok, {nil, state}} = Native.overlap_sma_state_next_ts(state, 100.0, 1_000)
+      {:ok, {nil, state}} = Native.overlap_sma_state_next_ts(state, nil, 2_000)
+
+      # The bar at 2000 was never seen, so it is a new bar
+      assert {:ok, {105.0, _state}} = Native.overlap_sma_state_next_ts(state, 110.0, 2_000)
+    end
+  end
+
+  describe "next/2 with :nan_policy" do
+    test ":carry_forward returns the current SMA and recovers on the next valid bar" do
+      {:ok, state} =
+        SMA.init(period: 2, data: "eurusd_m1", name: "sma2", nan_policy: :carry_forward)
+
+      {:ok, _result, state} = SMA.next(bar_event(100.0, true), state)
+      {:ok, result, state} = SMA.next(bar_event(110.0, true), state)
+      assert result.value == 105.0
+
+      {:ok, %IndicatorValue{value: carried}, state} = SMA.next(bar_event(nil, true), state)
+      assert carried == result.value
+
+      # The missing bar is ignored: same as the SMA of [100.0, 110.0, 120.0]
+      {:ok, result, _state} = SMA.next(bar_event(120.0, true), state)
+      assert result.value == 115.0
+    end
+  end
+
   ## Property-based tests
 
   describe "property: state-based APPEND matches batch calculation" do
@@ -321,4 +842,20 @@ defmodule TheoryCraftTA.Overlap.SMATest do
       end
     end
   end
+
+  ## Private functions
+
+  defp bar_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+
+  # NaN doesn't match the float pattern: gaps decode as nil
+  defp decode_f64(binary) do
+    for <<chunk::binary-size(8) <- binary>> do
+      case chunk do
+        <<value::float-native-64>> -> value
+        _nan -> nil
+      end
+    end
+  end
 end
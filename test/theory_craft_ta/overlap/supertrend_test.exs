@@ -0,0 +1,159 @@
+defmodule TheoryCraftTA.Overlap.SuperTrendTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "overlap_supertrend/5" do
+    test "stays in an uptrend on a clean uptrend" do
+      high = Enum.map(1..20, &(&1 + 1.0))
+      low = Enum.map(1..20, &(&1 - 1.0))
+      close = Enum.map(1..20, &(&1 + 0.5))
+
+      assert {:ok, {line, direction}} = Native.overlap_supertrend(high, low, close, 3, 2.0)
+
+      # ATR(3) is undefined for the first 3 bars
+      assert Enum.take(line, 3) == [nil, nil, nil]
+      assert Enum.take(direction, 3) == [nil, nil, nil]
+      assert Enum.drop(direction, 3) == List.duplicate(1, 17)
+
+      # The line is the lower band, below the lows and never falling
+      line = Enum.drop(line, 3)
+      assert line == Enum.sort(line)
+      assert Enum.all?(Enum.zip(line, Enum.drop(low, 3)), fn {st, l} -> st < l end)
+    end
+
+    test "flips direction on a whipsaw" do
+      high = [11.0, 12.0, 13.0, 14.0, 15.0, 10.0, 9.0, 16.0, 17.0]
+      low = [9.0, 10.0, 11.0, 12.0, 13.0, 7.0, 6.0, 13.0, 15.0]
+      close = [10.5, 11.5, 12.5, 13.5, 14.5, 7.5, 6.5, 15.5, 16.5]
+
+      assert {:ok, {_line, direction}} = Native.overlap_supertrend(high, low, close, 2, 1.0)
+
+      assert [nil, nil, 1, 1, 1, -1, -1, 1, 1] = direction
+    end
+
+    test "skips leading nil values" do
+      high = [nil, nil | Enum.map(1..10, &(&1 + 1.0))]
+      low = [nil, nil | Enum.map(1..10, &(&1 - 1.0))]
+      close = [nil, nil | Enum.map(1..10, &(&1 * 1.0))]
+
+      assert {:ok, {line, direction}} = Native.overlap_supertrend(high, low, close, 3, 3.0)
+      assert length(line) == 12
+      assert Enum.find_index(line, & &1) == 5
+      assert Enum.find_index(direction, & &1) == 5
+    end
+
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.overlap_supertrend([1.0], [1.0], [1.0], 1, 3.0)
+      assert reason =~ "period must be >= 2"
+
+      assert {:error, reason} = Native.overlap_supertrend([1.0], [1.0], [1.0], 3, 0.0)
+      assert reason =~ "multiplier must be > 0"
+
+      assert {:error, reason} = Native.overlap_supertrend([1.0], [1.0], [], 3, 3.0)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, {[], []}} = Native.overlap_supertrend([], [], [], 3, 3.0)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "overlap_supertrend_state_init/2" do
+    test "returns error for invalid parameters" do
+      assert {:error, msg} = Native.overlap_supertrend_state_init(1, 3.0)
+      assert msg =~ "Invalid period"
+
+      assert {:error, msg} = Native.overlap_supertrend_state_init(10, -1.0)
+      assert msg =~ "Invalid multiplier"
+    end
+  end
+
+  describe "overlap_supertrend_state_next/5" do
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.overlap_supertrend_state_init(2, 1.0)
+
+      assert {:ok, {nil, nil, ^state}} =
+               Native.overlap_supertrend_state_next(state, 11.0, nil, 10.0, true)
+    end
+
+    test "UPDATE mode can flip the trend of the forming bar and flip it back" do
+      high = [11.0, 12.0, 13.0, 14.0]
+      low = [9.0, 10.0, 11.0, 12.0]
+      close = [10.5, 11.5, 12.5, 13.5]
+
+      {:ok, state} = Native.overlap_supertrend_state_init(2, 1.0)
+
+      state =
+        Enum.reduce(Enum.zip([high, low, close]), state, fn {h, l, c}, st ->
+          {:ok, {_line, _direction, new_st}} =
+            Native.overlap_supertrend_state_next(st, h, l, c, true)
+
+          new_st
+        end)
+
+      assert {:ok, {_, -1, state}} =
+               Native.overlap_supertrend_state_next(state, 14.0, 9.0, 9.5, true)
+
+      assert {:ok, {_, 1, _state}} =
+               Native.overlap_supertrend_state_next(state, 15.0, 13.0, 14.5, false)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based calculation matches batch calculation" do
+    property "APPEND mode, with an UPDATE of each forming bar, matches batch SuperTrend" do
+      check all(
+              bars <- list_of({bar(), bar()}, min_length: 10, max_length: 100),
+              period <- integer(2..10),
+              multiplier <- member_of([1.0, 2.0, 3.0])
+            ) do
+        final_bars = Enum.map(bars, fn {_forming, final} -> final end)
+        [high, low, close] = for i <- 0..2, do: Enum.map(final_bars, &elem(&1, i))
+        {:ok, {line, direction}} = Native.overlap_supertrend(high, low, close, period, multiplier)
+        {:ok, initial_state} = Native.overlap_supertrend_state_init(period, multiplier)
+
+        bars
+        |> Enum.zip(Enum.zip(line, direction))
+        |> Enum.reduce(initial_state, fn {{{fh, fl, fc}, {h, l, c}}, expected}, state ->
+          {:ok, {_, _, state}} = Native.overlap_supertrend_state_next(state, fh, fl, fc, true)
+
+          {:ok, {st_line, st_dir, new_state}} =
+            Native.overlap_supertrend_state_next(state, h, l, c, false)
+
+          case {{st_line, st_dir}, expected} do
+            {{nil, nil}, {nil, nil}} ->
+              :ok
+
+            {{val, dir}, {exp, exp_dir}} when is_float(val) and is_float(exp) ->
+              assert_in_delta(val, exp, 1.0e-6)
+              assert dir == exp_dir
+
+            _ ->
+              flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp bar() do
+    gen all(
+          mid <- float(min: 10.0, max: 100.0),
+          range <- float(min: 0.1, max: 5.0),
+          close_pos <- float(min: 0.0, max: 1.0)
+        ) do
+      {mid + range, mid - range, mid - range + 2 * range * close_pos}
+    end
+  end
+end
@@ -32,19 +32,26 @@ defmodule TheoryCraftTA.Overlap.T3Test do
     test "raises for period < 2" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = T3.t3(data, 1, 0.7)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for period=0" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = T3.t3(data, 0, 0.7)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "returns empty for empty input" do
       assert {:ok, []} = T3.t3([], 3, 0.7)
     end
 
+    test "returns error for vfactor outside [0.0, 1.0]" do
+      for vfactor <- [-0.1, 1.5] do
+        assert {:error, reason} = T3.t3([1.0, 2.0, 3.0], 2, vfactor)
+        assert reason =~ "vfactor must be between 0.0 and 1.0"
+      end
+    end
+
     test "handles insufficient data" do
       data = [1.0, 2.0]
       assert {:ok, result} = T3.t3(data, 3, 0.7)
@@ -136,6 +143,15 @@ defmodule TheoryCraftTA.Overlap.T3Test do
 
       assert msg =~ "Invalid period"
     end
+
+    test "returns error for vfactor outside [0.0, 1.0]" do
+      for vfactor <- [-0.1, 1.5] do
+        assert {:error, msg} =
+                 T3.init(period: 3, vfactor: vfactor, data: "eurusd_m1", name: "t3")
+
+        assert msg =~ "Invalid vfactor"
+      end
+    end
   end
 
   ## Streaming API tests (next/2 with MarketEvent)
@@ -257,6 +273,133 @@ defmodule TheoryCraftTA.Overlap.T3Test do
         new_state
       end)
     end
+
+    test "matches recorded values for a mixed APPEND/UPDATE sequence" do
+      # Pins the output of the nested EMAs, including the UPDATE of a bar in warmup
+      {:ok, state} =
+        T3.init(period: 2, vfactor: 0.7, data: "eurusd_m1", name: "t3", source: :close)
+
+      events = [
+        {100.0, true},
+        {104.0, true},
+        {101.0, false},
+        {108.0, true},
+        {107.0, true},
+        {111.0, false},
+        {115.0, true},
+        {112.0, true},
+        {118.0, true},
+        {116.0, false},
+        {121.0, true},
+        {119.0, true}
+      ]
+
+      {results, _state} =
+        Enum.map_reduce(events, state, fn {value, new_bar?}, st ->
+          event = %MarketEvent{
+            data: %{"eurusd_m1" => %Bar{close: value, new_bar?: new_bar?}}
+          }
+
+          {:ok, result, new_st} = T3.next(event, st)
+          {result.value, new_st}
+        end)
+
+      expected = [
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        nil,
+        116.11033531473862,
+        114.9835534217346,
+        118.65068343240353,
+        119.53606580975111
+      ]
+
+      for {value, expected_value} <- Enum.zip(results, expected) do
+        case expected_value do
+          nil -> assert value == nil
+          _ -> assert_in_delta(value, expected_value, 1.0e-9)
+        end
+      end
+    end
+
+    test "a state in warmup is left untouched by the states derived from it" do
+      {:ok, state} = T3.init(period: 3, vfactor: 0.7, data: "eurusd_m1", name: "t3")
+
+      run = fn state, values ->
+        Enum.map_reduce(values, state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_st} = T3.next(event, st)
+          {result.value, new_st}
+        end)
+      end
+
+      # Only the first nested EMAs are fed after 3 bars, the later ones are idle
+      {warmup, warm_state} = run.(state, [100.0, 102.0, 101.0])
+      assert warmup == [nil, nil, nil]
+
+      continuation = Enum.map(1..20, &(100.0 + 5.0 * :math.cos(&1)))
+      {first, _state} = run.(warm_state, continuation)
+      {_other, _state} = run.(warm_state, Enum.map(continuation, &(&1 * 2.0)))
+      {again, _state} = run.(warm_state, continuation)
+
+      assert again == first
+      assert Enum.any?(first, &is_float/1)
+    end
+  end
+
+  describe "next/2 with the vfactor bounds" do
+    test "matches batch for vfactor 0.0, 0.5, 0.7 and 1.0" do
+      data = Enum.map(1..40, &(100.0 + 10.0 * :math.sin(&1 / 3)))
+
+      for vfactor <- [0.0, 0.5, 0.7, 1.0] do
+        {:ok, batch_result} = T3.t3(data, 3, vfactor)
+        {:ok, state} = T3.init(period: 3, vfactor: vfactor, data: "eurusd_m1", name: "t3")
+
+        {stream_result, _state} =
+          Enum.map_reduce(data, state, fn value, st ->
+            event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+            {:ok, result, new_st} = T3.next(event, st)
+            {result.value, new_st}
+          end)
+
+        stream_result
+        |> Enum.zip(batch_result)
+        |> Enum.each(fn
+          {nil, nil} -> :ok
+          {value, expected} -> assert_in_delta(value, expected, 1.0e-9)
+        end)
+      end
+    end
+  end
+
+  describe "next/2 with :seed_mode" do
+    test ":first seeds the inner EMAs with the first value and emits from the first bar" do
+      {:ok, state} =
+        T3.init(period: 3, vfactor: 0.7, data: "eurusd_m1", name: "t3", seed_mode: :first)
+
+      {values, _state} =
+        Enum.map_reduce([100.0, 110.0, 120.0], state, fn close, state ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: true}}}
+          {:ok, result, state} = T3.next(event, state)
+          {result.value, state}
+        end)
+
+      # All six EMAs start at the first value and the coefficients sum to 1
+      assert_in_delta hd(values), 100.0, 1.0e-9
+      assert Enum.all?(values, &is_float/1)
+    end
+
+    test "init returns error for an unknown seed mode" do
+      assert {:error, reason} =
+               T3.init(period: 3, vfactor: 0.7, data: "eurusd_m1", name: "t3", seed_mode: :zero)
+
+      assert reason =~ "Invalid seed_mode"
+    end
   end
 
   ## Property-based tests
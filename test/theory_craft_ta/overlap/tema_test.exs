@@ -21,13 +21,13 @@ defmodule TheoryCraftTA.Overlap.TEMATest do
     test "raises for period=1" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = TEMA.tema(data, 1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for period=0" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = TEMA.tema(data, 0)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "returns empty for empty input" do
@@ -268,6 +268,101 @@ defmodule TheoryCraftTA.Overlap.TEMATest do
       assert result_final.value != nil
       assert is_float(result_final.value)
     end
+
+    test "matches recorded values for a mixed APPEND/UPDATE sequence" do
+      # Pins the output of the nested EMAs, including the UPDATE of a bar in warmup
+      {:ok, state} = TEMA.init(period: 2, data: "eurusd_m1", name: "tema2", source: :close)
+
+      events = [
+        {100.0, true},
+        {104.0, true},
+        {101.0, false},
+        {108.0, true},
+        {107.0, true},
+        {111.0, false},
+        {115.0, true},
+        {112.0, true},
+        {118.0, true},
+        {116.0, false},
+        {121.0, true},
+        {119.0, true}
+      ]
+
+      {results, _state} =
+        Enum.map_reduce(events, state, fn {value, new_bar?}, st ->
+          event = %MarketEvent{
+            data: %{"eurusd_m1" => %Bar{close: value, new_bar?: new_bar?}}
+          }
+
+          {:ok, result, new_st} = TEMA.next(event, st)
+          {result.value, new_st}
+        end)
+
+      expected = [
+        nil,
+        nil,
+        nil,
+        nil,
+        107.66666666666666,
+        111.22222222222219,
+        115.01234567901236,
+        112.25102880658436,
+        117.6625514403292,
+        115.7366255144033,
+        120.8756287151349,
+        119.26901386983694
+      ]
+
+      for {value, expected_value} <- Enum.zip(results, expected) do
+        case expected_value do
+          nil -> assert value == nil
+          _ -> assert_in_delta(value, expected_value, 1.0e-9)
+        end
+      end
+    end
+
+    test "first value is emitted at the batch lookback index" do
+      data = Enum.map(1..40, &(&1 * 1.0))
+
+      for period <- [2, 3, 5, 7] do
+        {:ok, state} = TEMA.init(period: period, data: "test", name: "tema", source: :close)
+
+        {results, _state} =
+          Enum.map_reduce(data, state, fn value, st ->
+            event = %MarketEvent{data: %{"test" => %Bar{close: value, new_bar?: true}}}
+            {:ok, result, new_state} = TEMA.next(event, st)
+            {result.value, new_state}
+          end)
+
+        {:ok, batch} = TEMA.tema(data, period)
+        lookback = 3 * (period - 1)
+
+        assert Enum.find_index(results, &(&1 != nil)) == lookback
+        assert Enum.find_index(batch, &(&1 != nil)) == lookback
+      end
+    end
+  end
+
+  describe "next/2 with :seed_mode" do
+    test ":first seeds the inner EMAs with the first value and emits from the first bar" do
+      {:ok, state} = TEMA.init(period: 3, data: "eurusd_m1", name: "tema3", seed_mode: :first)
+
+      {values, _state} =
+        Enum.map_reduce([100.0, 110.0, 120.0], state, fn close, state ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: true}}}
+          {:ok, result, state} = TEMA.next(event, state)
+          {result.value, state}
+        end)
+
+      assert values == [100.0, 108.75, 119.375]
+    end
+
+    test "init returns error for an unknown seed mode" do
+      assert {:error, reason} =
+               TEMA.init(period: 3, data: "eurusd_m1", name: "tema3", seed_mode: :zero)
+
+      assert reason =~ "Invalid seed_mode"
+    end
   end
 
   ## Property-based tests
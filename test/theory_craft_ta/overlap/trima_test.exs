@@ -28,13 +28,13 @@ defmodule TheoryCraftTA.Overlap.TRIMATest do
     test "raises for period=1" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = TRIMA.trima(data, 1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for period=0" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = TRIMA.trima(data, 0)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "returns empty for empty input" do
@@ -209,6 +209,31 @@ defmodule TheoryCraftTA.Overlap.TRIMATest do
       {:ok, result3, _state3} = TRIMA.next(event3, state2)
       assert result3.value == 105.0
     end
+
+    test "matches batch TRIMA for even periods" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0, 14.5, 16.0, 15.5, 17.0]
+
+      for period <- [4, 6, 8, 10] do
+        {:ok, batch_result} = TRIMA.trima(data, period)
+
+        {:ok, state} =
+          TRIMA.init(period: period, data: "eurusd_m1", name: "trima", source: :close)
+
+        {stream_result, _state} =
+          Enum.map_reduce(data, state, fn value, st ->
+            event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+            {:ok, result, new_st} = TRIMA.next(event, st)
+            {result.value, new_st}
+          end)
+
+        for {stream_value, batch_value} <- Enum.zip(stream_result, batch_result) do
+          case batch_value do
+            nil -> assert stream_value == nil
+            _ -> assert_in_delta(stream_value, batch_value, 1.0e-9)
+          end
+        end
+      end
+    end
   end
 
   ## Property-based tests
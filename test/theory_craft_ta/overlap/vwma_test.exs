@@ -0,0 +1,90 @@
+defmodule TheoryCraftTA.Overlap.VWMATest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Overlap.VWMA
+
+  doctest TheoryCraftTA.Overlap.VWMA
+
+  ## Batch calculation tests
+
+  describe "overlap_vwma/3" do
+    test "calculates correctly with period=2" do
+      close = [1.0, 2.0, 4.0, 3.0]
+      volume = [1.0, 3.0, 1.0, 0.0]
+
+      # (1*1 + 2*3) / 4, (2*3 + 4*1) / 4, (4*1 + 3*0) / 1
+      assert {:ok, [nil, 1.75, 2.5, 4.0]} = Native.overlap_vwma(close, volume, 2)
+    end
+
+    test "returns nil for windows without volume" do
+      close = [1.0, 2.0, 3.0, 4.0, 5.0]
+      volume = [1.0, 0.0, 0.0, 0.0, 2.0]
+
+      assert {:ok, [nil, 1.0, nil, nil, 5.0]} = Native.overlap_vwma(close, volume, 2)
+    end
+
+    test "skips bars where either input is missing" do
+      close = [nil, 1.0, 2.0, 4.0]
+      volume = [1.0, nil, 1.0, 3.0]
+
+      assert {:ok, [nil, nil, nil, 3.5]} = Native.overlap_vwma(close, volume, 2)
+    end
+
+    test "returns error for invalid parameters" do
+      assert {:error, reason} = Native.overlap_vwma([1.0, 2.0], [1.0, 1.0], 1)
+      assert reason =~ "period must be >= 2"
+
+      assert {:error, reason} = Native.overlap_vwma([1.0, 2.0], [1.0], 2)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.overlap_vwma([], [], 3)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, [nil, nil]} = Native.overlap_vwma([1.0, 2.0], [1.0, 1.0], 3)
+    end
+  end
+
+  describe "vwma/3" do
+    test "maintains DataSeries type in output" do
+      close = Enum.reduce([1.0, 2.0, 4.0, 3.0], DataSeries.new(), &DataSeries.add(&2, &1))
+      volume = Enum.reduce([1.0, 3.0, 1.0, 0.0], DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = VWMA.vwma(close, volume, 2)
+      assert DataSeries.values(result) == [4.0, 2.5, 1.75, nil]
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = VWMA.vwma([1.0, 2.0], [1.0], 2)
+      assert reason =~ "length mismatch"
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: constant volume reduces to SMA" do
+    property "VWMA with a constant volume matches SMA" do
+      check all(
+              close <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              volume <- float(min: 1.0, max: 1.0e6),
+              period <- integer(2..20)
+            ) do
+        {:ok, vwma} = Native.overlap_vwma(close, List.duplicate(volume, length(close)), period)
+        {:ok, sma} = Native.overlap_sma(close, period)
+
+        vwma
+        |> Enum.zip(sma)
+        |> Enum.each(fn
+          {nil, nil} -> :ok
+          {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-6)
+          _ -> flunk("Mismatch between VWMA and SMA")
+        end)
+      end
+    end
+  end
+end
@@ -36,19 +36,19 @@ defmodule TheoryCraftTA.Overlap.WMATest do
     test "raises for period=1" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = WMA.wma(data, 1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for period=0" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = WMA.wma(data, 0)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "raises for negative period" do
       data = [1.0, 2.0, 3.0]
       assert {:error, reason} = WMA.wma(data, -1)
-      assert reason =~ "Invalid parameters"
+      assert reason =~ "period must be >= 2"
     end
 
     test "returns empty for empty input" do
@@ -222,6 +222,27 @@ defmodule TheoryCraftTA.Overlap.WMATest do
       assert_in_delta result3.value, 113.3333333, 0.0001
     end
 
+    test "UPDATE right after warmup completes uses the full window" do
+      {:ok, state} = WMA.init(period: 3, data: "eurusd_m1", name: "wma3", source: :close)
+
+      state =
+        Enum.reduce([1.0, 2.0, 3.0], state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, _result, new_st} = WMA.next(event, st)
+          new_st
+        end)
+
+      # Replace the bar that completed the warmup (3.0 -> 6.0)
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 6.0, new_bar?: false}}}
+      {:ok, result, _state} = WMA.next(event, state)
+
+      # WMA(3) = (1*1 + 2*2 + 6*3) / (1+2+3) = 23 / 6 ≈ 3.8333
+      assert_in_delta result.value, 3.8333333, 0.0001
+
+      {:ok, batch_result} = WMA.wma([1.0, 2.0, 6.0], 3)
+      assert_in_delta result.value, List.last(batch_result), 0.0001
+    end
+
     test "handles nil values from upstream indicators" do
       {:ok, state} =
         WMA.init(
@@ -267,6 +288,24 @@ defmodule TheoryCraftTA.Overlap.WMATest do
     end
   end
 
+  describe "next/2 with :nan_policy" do
+    test ":carry_forward returns the current WMA and recovers on the next valid bar" do
+      {:ok, state} =
+        WMA.init(period: 2, data: "eurusd_m1", name: "wma2", nan_policy: :carry_forward)
+
+      {:ok, _result, state} = WMA.next(close_event(100.0), state)
+      {:ok, result, state} = WMA.next(close_event(110.0), state)
+      assert_in_delta result.value, 106.6666666667, 1.0e-9
+
+      {:ok, %IndicatorValue{value: carried}, state} = WMA.next(close_event(nil), state)
+      assert carried == result.value
+
+      # The missing bar is ignored: same as the WMA of [100.0, 110.0, 120.0]
+      {:ok, result, _state} = WMA.next(close_event(120.0), state)
+      assert_in_delta result.value, 116.6666666667, 1.0e-9
+    end
+  end
+
   ## Property-based tests
 
   describe "property: state-based APPEND matches batch calculation" do
@@ -356,4 +395,10 @@ defmodule TheoryCraftTA.Overlap.WMATest do
       end
     end
   end
+
+  ## Private functions
+
+  defp close_event(close) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: true}}}
+  end
 end
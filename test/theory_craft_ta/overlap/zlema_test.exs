@@ -0,0 +1,175 @@
+defmodule TheoryCraftTA.Overlap.ZLEMATest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Overlap.ZLEMA
+
+  doctest TheoryCraftTA.Overlap.ZLEMA
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0]
+
+  ## Batch calculation tests
+
+  describe "overlap_zlema/2" do
+    test "calculates correctly with period=3" do
+      # lag = 1: EMA(3) of [nil, 3, 4, 5, 6], seeded with (3 + 4 + 5) / 3
+      assert {:ok, [nil, nil, nil, 4.0, 5.0]} =
+               Native.overlap_zlema([1.0, 2.0, 3.0, 4.0, 5.0], 3)
+    end
+
+    test "skips leading nil values" do
+      assert {:ok, [nil, nil, nil, nil, nil, 4.0, 5.0]} =
+               Native.overlap_zlema([nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0], 3)
+    end
+
+    test "tracks price faster than EMA on a step input" do
+      data = List.duplicate(1.0, 10) ++ List.duplicate(2.0, 10)
+
+      {:ok, zlema} = Native.overlap_zlema(data, 5)
+      {:ok, ema} = Native.overlap_ema(data, 5)
+
+      # Both are settled on the first level before the step
+      assert_in_delta Enum.at(zlema, 9), 1.0, 1.0e-12
+      assert_in_delta Enum.at(ema, 9), 1.0, 1.0e-12
+
+      zlema
+      |> Enum.zip(ema)
+      |> Enum.drop(10)
+      |> Enum.each(fn {zlema_value, ema_value} ->
+        assert abs(zlema_value - 2.0) < abs(ema_value - 2.0)
+      end)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_zlema([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.overlap_zlema([], 3)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, [nil, nil, nil]} = Native.overlap_zlema([1.0, 2.0, 3.0], 5)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "overlap_zlema_state_init/1" do
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.overlap_zlema_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Public API tests
+
+  describe "zlema/2" do
+    test "matches the native calculation" do
+      assert ZLEMA.zlema(@data, 3) == Native.overlap_zlema(@data, 3)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.overlap_zlema(@data, 3)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = ZLEMA.zlema(ds, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = ZLEMA.init(period: 1, data: "eurusd_m1", name: "zlema")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = ZLEMA.zlema(@data, 3)
+      {:ok, state} = ZLEMA.init(period: 3, data: "eurusd_m1", name: "zlema")
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = ZLEMA.next(close_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = ZLEMA.init(period: 3, data: "eurusd_m1", name: "zlema")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = ZLEMA.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = ZLEMA.next(close_event(100.0, false), state)
+      {:ok, batch_result} = ZLEMA.zlema(List.replace_at(@data, -1, 100.0), 3)
+
+      assert_same_value(result.value, List.last(batch_result))
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch ZLEMA" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, batch_result} = Native.overlap_zlema(data, period)
+        {:ok, initial_state} = Native.overlap_zlema_state_init(period)
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          {:ok, {result, new_state}} = Native.overlap_zlema_state_next(state, value, true)
+          assert_same_value(result, expected)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20),
+              update_value <- float(min: 1.0, max: 1000.0)
+            ) do
+        {:ok, state} = Native.overlap_zlema_state_init(period)
+
+        state =
+          Enum.reduce(data, state, fn value, st ->
+            {:ok, {_result, new_st}} = Native.overlap_zlema_state_next(st, value, true)
+            new_st
+          end)
+
+        {:ok, {result, _state}} = Native.overlap_zlema_state_next(state, update_value, false)
+        {:ok, batch_result} =
+          Native.overlap_zlema(List.replace_at(data, -1, update_value), period)
+
+        assert_same_value(result, List.last(batch_result))
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
@@ -0,0 +1,113 @@
+defmodule TheoryCraftTA.PipelineTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Initialization tests
+
+  describe "pipeline_init/1" do
+    test "returns error for an empty pipeline" do
+      assert {:error, reason} = Native.pipeline_init([])
+      assert reason =~ "at least one stage"
+    end
+
+    test "returns error for an unsupported indicator" do
+      assert {:error, reason} = Native.pipeline_init([{:sma, 3}, {:unknown, 3}])
+      assert reason =~ "unsupported indicator"
+    end
+
+    test "returns error for an invalid stage period" do
+      assert {:error, reason} = Native.pipeline_init([{:rsi, 1}])
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Streaming tests
+
+  describe "pipeline_next/3" do
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.pipeline_init([{:sma, 2}])
+      {:ok, {nil, state}} = Native.pipeline_next(state, 1.0, true)
+
+      assert {:ok, {nil, ^state}} = Native.pipeline_next(state, nil, true)
+    end
+
+    test "propagates nil until every stage is warmed up" do
+      {:ok, state} = Native.pipeline_init([{:sma, 2}, {:sma, 2}])
+
+      {results, _state} =
+        Enum.map_reduce([1.0, 3.0, 5.0, 7.0], state, fn value, st ->
+          {:ok, {result, new_st}} = Native.pipeline_next(st, value, true)
+          {result, new_st}
+        end)
+
+      # SMA(2): [nil, 2, 4, 6], then SMA(2) of it: [nil, nil, 3, 5]
+      assert results == [nil, nil, 3.0, 5.0]
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: a single stage matches its own stream" do
+    property "[{:sma, 3}] matches the SMA stream" do
+      check all(data <- list_of(float(min: 1.0, max: 1000.0), min_length: 1, max_length: 200)) do
+        {:ok, pipeline} = Native.pipeline_init([{:sma, 3}])
+        {:ok, sma} = Native.overlap_sma_state_init(3)
+
+        Enum.reduce(data, {pipeline, sma}, fn value, {pipeline, sma} ->
+          {:ok, {result, pipeline}} = Native.pipeline_next(pipeline, value, true)
+          {:ok, {expected, sma}} = Native.overlap_sma_state_next(sma, value, true)
+          assert result == expected
+          {pipeline, sma}
+        end)
+      end
+    end
+  end
+
+  describe "property: chained stages match the chained batch calculation" do
+    property "[{:rsi, 14}, {:sma, 9}] matches SMA(9) of RSI(14)" do
+      check all(data <- list_of(float(min: 1.0, max: 1000.0), min_length: 30, max_length: 200)) do
+        {:ok, rsi} = Native.momentum_rsi(data, 14)
+        {:ok, expected} = Native.overlap_sma(rsi, 9)
+        {:ok, initial_state} = Native.pipeline_init([{:rsi, 14}, {:sma, 9}])
+
+        data
+        |> Enum.zip(expected)
+        |> Enum.reduce(initial_state, fn {value, expected_value}, state ->
+          {:ok, {result, new_state}} = Native.pipeline_next(state, value, true)
+
+          case {result, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch between pipeline and batch")
+          end
+
+          new_state
+        end)
+      end
+    end
+
+    property "UPDATE recalculates every stage with the replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 30, max_length: 200),
+              update_value <- float(min: 1.0, max: 1000.0)
+            ) do
+        {:ok, state} = Native.pipeline_init([{:rsi, 14}, {:ema, 5}])
+
+        state =
+          Enum.reduce(data, state, fn value, st ->
+            {:ok, {_result, new_st}} = Native.pipeline_next(st, value, true)
+            new_st
+          end)
+
+        {:ok, {result, _state}} = Native.pipeline_next(state, update_value, false)
+
+        {:ok, rsi} = Native.momentum_rsi(List.replace_at(data, -1, update_value), 14)
+        {:ok, expected} = Native.overlap_ema(rsi, 5)
+
+        assert_in_delta result, List.last(expected), 1.0e-9
+      end
+    end
+  end
+end
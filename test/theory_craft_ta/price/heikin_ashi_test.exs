@@ -0,0 +1,113 @@
+defmodule TheoryCraftTA.Price.HeikinAshiTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "price_heikin_ashi/4" do
+    test "calculates correctly on a short OHLC sequence" do
+      open = [nil, 10.0, 11.0, 12.0, 11.0]
+      high = [nil, 12.0, 13.0, 12.5, 11.0]
+      low = [nil, 9.0, 10.0, 10.5, 8.0]
+      close = [nil, 11.0, 12.0, 11.0, 8.5]
+
+      assert {:ok, {ha_open, ha_high, ha_low, ha_close}} =
+               Native.price_heikin_ashi(open, high, low, close)
+
+      assert ha_open == [nil, 10.0, 10.25, 10.875, 11.1875]
+      assert ha_high == [nil, 12.0, 13.0, 12.5, 11.1875]
+      assert ha_low == [nil, 9.0, 10.0, 10.5, 8.0]
+      assert ha_close == [nil, 10.5, 11.5, 11.5, 9.625]
+    end
+
+    test "returns error for mismatched lengths" do
+      assert {:error, reason} = Native.price_heikin_ashi([1.0], [1.0], [1.0], [])
+      assert reason == "HEIKIN_ASHI: open/high/low/close length mismatch (1/1/1/0)"
+    end
+  end
+
+  ## Streaming tests
+
+  describe "price_heikin_ashi_state_next/6" do
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.price_heikin_ashi_state_init()
+      {:ok, {_, _, _, _, state}} = step(state, {1.0, 2.0, 0.5, 1.5}, true)
+
+      assert {:ok, {nil, nil, nil, nil, ^state}} =
+               Native.price_heikin_ashi_state_next(state, 1.0, nil, 0.5, 1.5, true)
+    end
+
+    test "UPDATE mode recomputes the current bar from the previous one" do
+      {:ok, state} = Native.price_heikin_ashi_state_init()
+      {:ok, {_, _, _, _, state}} = step(state, {10.0, 12.0, 9.0, 11.0}, true)
+      {:ok, {_, _, _, _, state}} = step(state, {11.0, 11.5, 10.5, 11.0}, true)
+
+      # Same as the second bar of the batch test: the forming bar is discarded
+      assert {:ok, {10.25, 13.0, 10.0, 11.5, _state}} =
+               step(state, {11.0, 13.0, 10.0, 12.0}, false)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "streaming matches batch Heikin-Ashi" do
+      check all(bars <- list_of(bar(), min_length: 1, max_length: 100)) do
+        {:ok, initial_state} = Native.price_heikin_ashi_state_init()
+
+        bars
+        |> Enum.zip(batch_heikin_ashi(bars))
+        |> Enum.reduce(initial_state, fn {bar, expected}, state ->
+          {:ok, {ha_open, ha_high, ha_low, ha_close, new_state}} = step(state, bar, true)
+          assert_bar_in_delta({ha_open, ha_high, ha_low, ha_close}, expected)
+          new_state
+        end)
+      end
+    end
+
+    property "UPDATE before each APPEND ends on the batch result" do
+      check all(bars <- list_of({bar(), bar()}, min_length: 1, max_length: 50)) do
+        final_bars = Enum.map(bars, fn {_forming, final} -> final end)
+        {:ok, initial_state} = Native.price_heikin_ashi_state_init()
+
+        bars
+        |> Enum.zip(batch_heikin_ashi(final_bars))
+        |> Enum.reduce(initial_state, fn {{forming, final}, expected}, state ->
+          {:ok, {_, _, _, _, state}} = step(state, forming, true)
+          {:ok, {ha_open, ha_high, ha_low, ha_close, new_state}} = step(state, final, false)
+          assert_bar_in_delta({ha_open, ha_high, ha_low, ha_close}, expected)
+          new_state
+        end)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp bar() do
+    gen all(
+          open <- float(min: 1.0, max: 1000.0),
+          close <- float(min: 1.0, max: 1000.0),
+          wick_up <- float(min: 0.0, max: 10.0),
+          wick_down <- float(min: 0.0, max: 10.0)
+        ) do
+      {open, max(open, close) + wick_up, min(open, close) - wick_down, close}
+    end
+  end
+
+  defp step(state, {open, high, low, close}, new_bar?) do
+    Native.price_heikin_ashi_state_next(state, open, high, low, close, new_bar?)
+  end
+
+  defp batch_heikin_ashi(bars) do
+    [open, high, low, close] = for i <- 0..3, do: Enum.map(bars, &elem(&1, i))
+    {:ok, {ha_open, ha_high, ha_low, ha_close}} = Native.price_heikin_ashi(open, high, low, close)
+    Enum.zip([ha_open, ha_high, ha_low, ha_close])
+  end
+
+  defp assert_bar_in_delta(actual, expected) do
+    for i <- 0..3, do: assert_in_delta(elem(actual, i), elem(expected, i), 1.0e-9)
+  end
+end
@@ -0,0 +1,45 @@
+defmodule TheoryCraftTA.Price.PivotsTest do
+  use ExUnit.Case, async: true
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "price_pivots/3" do
+    test "calculates the levels of each bar from the previous one" do
+      high = [12.0, 13.0, 14.0]
+      low = [9.0, 10.0, 11.0]
+      close = [12.0, 11.5, 13.0]
+
+      assert {:ok, {pivot, r1, r2, r3, s1, s2, s3}} = Native.price_pivots(high, low, close)
+
+      # P = (H + L + C) / 3, R1 = 2P - L, R2 = P + (H - L), R3 = H + 2(P - L)
+      # S1 = 2P - H, S2 = P - (H - L), S3 = L - 2(H - P)
+      assert pivot == [nil, 11.0, 11.5]
+      assert r1 == [nil, 13.0, 13.0]
+      assert r2 == [nil, 14.0, 14.5]
+      assert r3 == [nil, 16.0, 16.0]
+      assert s1 == [nil, 10.0, 10.0]
+      assert s2 == [nil, 8.0, 8.5]
+      assert s3 == [nil, 7.0, 7.0]
+    end
+
+    test "has no levels after an incomplete bar" do
+      high = [nil, 12.0, nil, 13.0, 14.0]
+      low = [nil, 9.0, 10.0, 10.0, 11.0]
+      close = [nil, 12.0, 11.0, 11.5, 13.0]
+
+      assert {:ok, {pivot, _r1, _r2, _r3, _s1, _s2, _s3}} = Native.price_pivots(high, low, close)
+      assert pivot == [nil, nil, 11.0, nil, 11.5]
+    end
+
+    test "returns error for mismatched lengths" do
+      assert {:error, reason} = Native.price_pivots([1.0], [1.0], [])
+      assert reason == "PIVOTS: high/low/close length mismatch (1/1/0)"
+    end
+
+    test "returns empty series for empty input" do
+      assert {:ok, {[], [], [], [], [], [], []}} = Native.price_pivots([], [], [])
+    end
+  end
+end
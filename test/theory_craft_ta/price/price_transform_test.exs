@@ -0,0 +1,97 @@
+defmodule TheoryCraftTA.Price.PriceTransformTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  @high [12.0, 13.0, 12.5]
+  @low [9.0, 10.0, 10.5]
+  @close [12.0, 11.0, 12.5]
+
+  ## Batch calculation tests
+
+  describe "price_typprice/3, price_medprice/2 and price_wclprice/3" do
+    test "calculate correctly" do
+      assert Native.price_typprice(@high, @low, @close) == {:ok, [11.0, 34.0 / 3, 35.5 / 3]}
+      assert {:ok, [10.5, 11.5, 11.5]} = Native.price_medprice(@high, @low)
+      assert {:ok, [11.25, 11.25, 12.0]} = Native.price_wclprice(@high, @low, @close)
+    end
+
+    test "return nil for a bar with a missing value" do
+      high = [nil | Enum.drop(@high, 1)]
+
+      assert {:ok, [nil, _, _]} = Native.price_typprice(high, @low, @close)
+      assert {:ok, [nil, _, _]} = Native.price_medprice(high, @low)
+      assert {:ok, [nil, _, _]} = Native.price_wclprice(high, @low, @close)
+    end
+
+    test "return error for mismatched lengths" do
+      assert {:error, reason} = Native.price_typprice(@high, @low, [])
+      assert reason == "TYPPRICE: high/low/close length mismatch (3/3/0)"
+
+      assert {:error, reason} = Native.price_medprice(@high, [])
+      assert reason == "MEDPRICE: high/low length mismatch (3/0)"
+    end
+  end
+
+  ## Streaming tests
+
+  describe "price_*_state_next" do
+    test "return the transform of the bar with no warmup" do
+      {:ok, typprice} = Native.price_typprice_state_init()
+      {:ok, medprice} = Native.price_medprice_state_init()
+      {:ok, wclprice} = Native.price_wclprice_state_init()
+
+      assert {:ok, {11.0, _}} = Native.price_typprice_state_next(typprice, 12.0, 9.0, 12.0, true)
+      assert {:ok, {10.5, _}} = Native.price_medprice_state_next(medprice, 12.0, 9.0, true)
+      assert {:ok, {11.25, _}} = Native.price_wclprice_state_next(wclprice, 12.0, 9.0, 12.0, true)
+    end
+
+    test "return nil on nil input" do
+      {:ok, state} = Native.price_typprice_state_init()
+
+      assert {:ok, {nil, ^state}} = Native.price_typprice_state_next(state, 12.0, nil, 12.0, true)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "each state matches its batch transform element-wise" do
+      check all(bars <- list_of(bar(), min_length: 1, max_length: 100)) do
+        [high, low, close] = for i <- 0..2, do: Enum.map(bars, &elem(&1, i))
+
+        {:ok, typprice} = Native.price_typprice(high, low, close)
+        {:ok, medprice} = Native.price_medprice(high, low)
+        {:ok, wclprice} = Native.price_wclprice(high, low, close)
+
+        {:ok, typ_state} = Native.price_typprice_state_init()
+        {:ok, med_state} = Native.price_medprice_state_init()
+        {:ok, wcl_state} = Native.price_wclprice_state_init()
+
+        for {{h, l, c}, i} <- Enum.with_index(bars) do
+          assert {:ok, {value, _}} = Native.price_typprice_state_next(typ_state, h, l, c, true)
+          assert value == Enum.at(typprice, i)
+
+          assert {:ok, {value, _}} = Native.price_medprice_state_next(med_state, h, l, true)
+          assert value == Enum.at(medprice, i)
+
+          assert {:ok, {value, _}} = Native.price_wclprice_state_next(wcl_state, h, l, c, true)
+          assert value == Enum.at(wclprice, i)
+        end
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp bar() do
+    gen all(
+          low <- float(min: 1.0, max: 1000.0),
+          range <- float(min: 0.0, max: 50.0),
+          position <- float(min: 0.0, max: 1.0)
+        ) do
+      {low + range, low, low + range * position}
+    end
+  end
+end
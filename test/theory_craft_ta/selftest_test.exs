@@ -0,0 +1,39 @@
+defmodule TheoryCraftTA.SelfTestTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  describe "selftest_ema/2" do
+    test "reports no divergence between the EMA state and batch" do
+      data = Enum.map(1..50, &(:math.sin(&1 / 5) * 10 + 100))
+
+      assert {:ok, %{max_abs_diff: diff, first_divergence_index: nil}} =
+               Native.selftest_ema(data, 10)
+
+      assert diff < 1.0e-9
+    end
+
+    test "returns error for an invalid period" do
+      assert {:error, reason} = Native.selftest_ema([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+  end
+
+  describe "selftest_ma/3" do
+    property "streaming matches batch for every supported ma_type" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 1, max_length: 200),
+              period <- integer(2..20),
+              ma_type <- member_of([0, 1, 2, 3, 4, 5, 6, 8])
+            ) do
+        assert {:ok, %{first_divergence_index: nil}} = Native.selftest_ma(data, period, ma_type)
+      end
+    end
+
+    test "returns error for MAMA" do
+      assert {:error, reason} = Native.selftest_ma([1.0, 2.0, 3.0], 2, 7)
+      assert reason =~ "MAMA"
+    end
+  end
+end
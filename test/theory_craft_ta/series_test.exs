@@ -0,0 +1,112 @@
+defmodule TheoryCraftTA.SeriesTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  describe "analyze_gaps/1" do
+    test "reports leading, interior and trailing gaps" do
+      data = [nil, nil, 1.0, nil, 2.0, 3.0, nil, nil, nil, 4.0, nil]
+
+      assert Native.analyze_gaps(data) == [
+               %{start: 0, len: 2},
+               %{start: 3, len: 1},
+               %{start: 6, len: 3},
+               %{start: 10, len: 1}
+             ]
+    end
+
+    test "returns no gap for a complete or empty series" do
+      assert Native.analyze_gaps([1.0, 2.0, 3.0]) == []
+      assert Native.analyze_gaps([]) == []
+    end
+
+    test "returns a single gap for an all-nil series" do
+      assert Native.analyze_gaps([nil, nil, nil]) == [%{start: 0, len: 3}]
+    end
+
+    property "gaps cover exactly the nil values" do
+      check all(data <- list_of(one_of([constant(nil), float()]), max_length: 100)) do
+        gaps = Native.analyze_gaps(data)
+
+        covered = Enum.flat_map(gaps, &Enum.to_list(&1.start..(&1.start + &1.len - 1)))
+        nils = for {nil, i} <- Enum.with_index(data), do: i
+
+        assert covered == nils
+
+        # Each run is maximal: two gaps are never adjacent
+        for [gap, next] <- Enum.chunk_every(gaps, 2, 1, :discard) do
+          assert gap.start + gap.len < next.start
+        end
+      end
+    end
+  end
+
+  describe "fill_forward/1" do
+    test "carries the last value over the gaps" do
+      assert Native.fill_forward([1.0, nil, nil, 4.0, nil]) == [1.0, 1.0, 1.0, 4.0, 4.0]
+    end
+
+    test "leaves the leading gap without a prior value" do
+      assert Native.fill_forward([nil, nil, 3.0, nil]) == [nil, nil, 3.0, 3.0]
+    end
+
+    test "returns an all-nil or empty series unchanged" do
+      assert Native.fill_forward([nil, nil]) == [nil, nil]
+      assert Native.fill_forward([]) == []
+    end
+  end
+
+  describe "fill_backward/1" do
+    test "carries the next value back over the gaps" do
+      assert Native.fill_backward([nil, 2.0, nil, nil, 5.0]) == [2.0, 2.0, 5.0, 5.0, 5.0]
+    end
+
+    test "leaves the trailing gap without a next value" do
+      assert Native.fill_backward([1.0, nil, 3.0, nil]) == [1.0, 3.0, 3.0, nil]
+    end
+
+    test "returns an all-nil or empty series unchanged" do
+      assert Native.fill_backward([nil, nil]) == [nil, nil]
+      assert Native.fill_backward([]) == []
+    end
+  end
+
+  describe "fill_linear/1" do
+    test "interpolates the gaps between two values" do
+      assert Native.fill_linear([1.0, nil, nil, 4.0, nil, 2.0]) == [1.0, 2.0, 3.0, 4.0, 3.0, 2.0]
+    end
+
+    test "leaves the leading and trailing gaps" do
+      assert Native.fill_linear([nil, 1.0, nil, 2.0, nil]) == [nil, 1.0, 1.5, 2.0, nil]
+    end
+
+    test "returns an all-nil or empty series unchanged" do
+      assert Native.fill_linear([nil, nil]) == [nil, nil]
+      assert Native.fill_linear([]) == []
+    end
+  end
+
+  describe "property: fills only replace the gaps" do
+    property "every fill keeps the values and has no interior gap left" do
+      check all(data <- list_of(one_of([constant(nil), float(min: -1000.0, max: 1000.0)]))) do
+        for fill <- [&Native.fill_forward/1, &Native.fill_backward/1, &Native.fill_linear/1] do
+          filled = fill.(data)
+
+          assert length(filled) == length(data)
+
+          for {value, filled_value} <- Enum.zip(data, filled), value != nil do
+            assert filled_value == value
+          end
+        end
+
+        # Forward and back fills leave exactly the leading and trailing gaps
+        leading = Enum.take_while(data, &is_nil/1)
+        trailing = data |> Enum.reverse() |> Enum.take_while(&is_nil/1)
+
+        assert Enum.count(Native.fill_forward(data), &is_nil/1) == length(leading)
+        assert Enum.count(Native.fill_backward(data), &is_nil/1) == length(trailing)
+      end
+    end
+  end
+end
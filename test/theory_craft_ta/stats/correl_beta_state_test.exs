@@ -0,0 +1,269 @@
+defmodule TheoryCraftTA.Stats.CORRELBETAStateTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Stats.{BETA, CORREL}
+
+  doctest TheoryCraftTA.Stats.BETA
+  doctest TheoryCraftTA.Stats.CORREL
+
+  @x [100.0, 110.0, 99.0, 108.9, 112.0, 105.0, 107.5, 111.0]
+  @y [50.0, 60.0, 48.0, 57.6, 58.0, 55.5, 54.0, 57.0]
+
+  ## Batch calculation tests
+
+  describe "stats_correl/3 and stats_beta/3" do
+    test "return the correlation of the values and the beta of the returns" do
+      # y moves twice as much as x, in percent
+      x = [100.0, 110.0, 99.0, 108.9]
+      y = [50.0, 60.0, 48.0, 57.6]
+
+      assert {:ok, [nil, correl1, correl2, correl3]} = Native.stats_correl(x, y, 2)
+      assert {:ok, [nil, nil, beta1, beta2]} = Native.stats_beta(x, y, 2)
+
+      for value <- [correl1, correl2, correl3], do: assert_in_delta(value, 1.0, 1.0e-9)
+      for value <- [beta1, beta2], do: assert_in_delta(value, 2.0, 1.0e-9)
+    end
+
+    test "return error for period < 2" do
+      assert {:error, reason} = Native.stats_correl([1.0, 2.0], [1.0, 2.0], 1)
+      assert reason =~ "period must be >= 2"
+
+      assert {:error, reason} = Native.stats_beta([1.0, 2.0], [1.0, 2.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "return error when inputs have different lengths" do
+      assert {:error, reason} = Native.stats_correl([1.0, 2.0, 3.0], [1.0, 2.0], 2)
+      assert reason == "CORREL: x/y length mismatch (3/2)"
+    end
+
+    test "return empty for empty input" do
+      assert {:ok, []} = Native.stats_correl([], [], 2)
+      assert {:ok, []} = Native.stats_beta([], [], 2)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "stats_correl_state_init/1 and stats_beta_state_init/1" do
+    test "return error for period < 2" do
+      assert {:error, reason} = Native.stats_correl_state_init(1)
+      assert reason =~ "Invalid period"
+
+      assert {:error, reason} = Native.stats_beta_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests
+
+  describe "stats_correl_state_next/4 and stats_beta_state_next/4" do
+    test "return the correlation and the beta once the window is full" do
+      pairs = [{100.0, 50.0}, {110.0, 60.0}, {99.0, 48.0}, {108.9, 57.6}]
+
+      assert [nil, correl1, _correl2, correl3] = stream(:correl, pairs, 2)
+      assert [nil, nil, beta1, beta2] = stream(:beta, pairs, 2)
+
+      for value <- [correl1, correl3], do: assert_in_delta(value, 1.0, 1.0e-9)
+      for value <- [beta1, beta2], do: assert_in_delta(value, 2.0, 1.0e-9)
+    end
+
+    test "return nil for a zero variance, where TA-Lib returns 0" do
+      pairs = [{5.0, 1.0}, {5.0, 2.0}, {5.0, 3.0}, {5.0, 4.0}]
+      {x, y} = Enum.unzip(pairs)
+
+      assert {:ok, [nil, 0.0, 0.0, 0.0]} = Native.stats_correl(x, y, 2)
+      assert stream(:correl, pairs, 2) == [nil, nil, nil, nil]
+
+      assert {:ok, [nil, nil, 0.0, 0.0]} = Native.stats_beta(x, y, 2)
+      assert stream(:beta, pairs, 2) == [nil, nil, nil, nil]
+    end
+
+    test "UPDATE replaces the prices of the current bar" do
+      {:ok, state} = Native.stats_beta_state_init(2)
+      {:ok, {nil, state}} = Native.stats_beta_state_next(state, 100.0, 50.0, true)
+      {:ok, {nil, state}} = Native.stats_beta_state_next(state, 110.0, 60.0, true)
+      {:ok, {beta, state}} = Native.stats_beta_state_next(state, 99.0, 48.0, true)
+      assert_in_delta beta, 2.0, 1.0e-9
+
+      # The 3rd bar still returns against the 2nd one: y now only moves as much as x
+      {:ok, {beta, state}} = Native.stats_beta_state_next(state, 99.0, 54.0, false)
+      assert_in_delta beta, 1.5, 1.0e-9
+
+      # The next bar returns against the updated prices
+      {:ok, {beta, _state}} = Native.stats_beta_state_next(state, 108.9, 59.4, true)
+      assert_in_delta beta, 1.0, 1.0e-9
+    end
+
+    test "handle nil values without modifying the state" do
+      {:ok, state} = Native.stats_correl_state_init(2)
+      {:ok, {nil, state}} = Native.stats_correl_state_next(state, 1.0, 2.0, true)
+
+      assert {:ok, {nil, ^state}} = Native.stats_correl_state_next(state, nil, 3.0, true)
+      assert {:ok, {nil, ^state}} = Native.stats_correl_state_next(state, 3.0, nil, true)
+    end
+  end
+
+  ## Public API tests
+
+  describe "correl/3 and beta/3" do
+    test "match the native calculation" do
+      assert CORREL.correl(@x, @y, 3) == Native.stats_correl(@x, @y, 3)
+      assert BETA.beta(@x, @y, 3) == Native.stats_beta(@x, @y, 3)
+    end
+
+    test "maintain DataSeries type in output" do
+      {:ok, expected} = Native.stats_correl(@x, @y, 3)
+      [x, y] = for values <- [@x, @y], do: data_series(values)
+
+      assert {:ok, %DataSeries{} = result} = CORREL.correl(x, y, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      opts = [period: 1, data: "eurusd_m1", other: "gbpusd_m1", name: "pair"]
+
+      assert {:error, reason} = CORREL.init(opts)
+      assert reason =~ "Invalid period"
+
+      assert {:error, reason} = BETA.init(opts)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode, reading y from the other stream" do
+      {:ok, correl_batch} = CORREL.correl(@x, @y, 3)
+      {:ok, beta_batch} = BETA.beta(@x, @y, 3)
+
+      for {module, batch_result} <- [{CORREL, correl_batch}, {BETA, beta_batch}] do
+        {:ok, state} = module.init(period: 3, data: "eurusd_m1", other: "gbpusd_m1", name: "pair")
+
+        {stream_result, _state} =
+          @x
+          |> Enum.zip(@y)
+          |> Enum.map_reduce(state, fn {x, y}, st ->
+            {:ok, result, new_st} = module.next(pair_event(x, y, true), st)
+            {result.value, new_st}
+          end)
+
+        assert_state_values_in_delta(stream_result, batch_result)
+      end
+    end
+
+    test "returns nil until both streams have a value" do
+      {:ok, state} = CORREL.init(period: 2, data: "eurusd_m1", other: "gbpusd_m1", name: "pair")
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 100.0, new_bar?: true}}}
+
+      assert {:ok, %{value: nil}, ^state} = CORREL.next(event, state)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch CORREL and BETA" do
+      check all(
+              pairs <- list_of(pair(), min_length: 1, max_length: 300),
+              period <- integer(2..30)
+            ) do
+        {x, y} = Enum.unzip(pairs)
+
+        for kind <- [:correl, :beta] do
+          {:ok, batch_result} = batch(kind, x, y, period)
+          assert_state_values_in_delta(stream(kind, pairs, period), batch_result)
+        end
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last pair" do
+      check all(
+              pairs <- list_of(pair(), min_length: 32, max_length: 200),
+              period <- integer(2..30),
+              updates <- list_of(pair(), min_length: 1, max_length: 5)
+            ) do
+        for kind <- [:correl, :beta] do
+          {init, next} = functions(kind)
+          {:ok, state} = init.(period)
+
+          state =
+            Enum.reduce(pairs, state, fn {x, y}, st ->
+              {:ok, {_result, new_st}} = next.(st, x, y, true)
+              new_st
+            end)
+
+          Enum.reduce(updates, {state, pairs}, fn {x, y} = pair, {st, current_pairs} ->
+            {:ok, {result, new_st}} = next.(st, x, y, false)
+
+            updated_pairs = List.replace_at(current_pairs, -1, pair)
+            {updated_x, updated_y} = Enum.unzip(updated_pairs)
+            {:ok, batch_result} = batch(kind, updated_x, updated_y, period)
+            assert_state_values_in_delta([result], [List.last(batch_result)])
+
+            {new_st, updated_pairs}
+          end)
+        end
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp stream(kind, pairs, period) do
+    {init, next} = functions(kind)
+    {:ok, state} = init.(period)
+
+    {results, _state} =
+      Enum.map_reduce(pairs, state, fn {x, y}, st ->
+        {:ok, {result, new_st}} = next.(st, x, y, true)
+        {result, new_st}
+      end)
+
+    results
+  end
+
+  defp batch(:correl, x, y, period), do: Native.stats_correl(x, y, period)
+  defp batch(:beta, x, y, period), do: Native.stats_beta(x, y, period)
+
+  defp functions(:correl),
+    do: {&Native.stats_correl_state_init/1, &Native.stats_correl_state_next/4}
+
+  defp functions(:beta), do: {&Native.stats_beta_state_init/1, &Native.stats_beta_state_next/4}
+
+  # Positive prices, as BETA runs on returns
+  defp pair, do: tuple({float(min: 1.0, max: 1000.0), float(min: 1.0, max: 1000.0)})
+
+  # The states return nil where TA-Lib returns 0 for a zero variance
+  defp assert_state_values_in_delta(result, expected) do
+    assert length(result) == length(expected)
+
+    for {value, expected_value} <- Enum.zip(result, expected) do
+      case {value, expected_value} do
+        {nil, nil} -> :ok
+        {nil, expected_value} -> assert expected_value == 0.0
+        {value, expected_value} -> assert_in_delta(value, expected_value, 1.0e-6)
+      end
+    end
+  end
+
+  defp data_series(values) do
+    Enum.reduce(values, DataSeries.new(), &DataSeries.add(&2, &1))
+  end
+
+  defp pair_event(x, y, new_bar?) do
+    %MarketEvent{
+      data: %{
+        "eurusd_m1" => %Bar{close: x, new_bar?: new_bar?},
+        "gbpusd_m1" => %Bar{close: y, new_bar?: new_bar?}
+      }
+    }
+  end
+end
@@ -0,0 +1,235 @@
+defmodule TheoryCraftTA.Stats.LINEARREGTSFStateTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Stats.{LINEARREG, TSF}
+
+  doctest TheoryCraftTA.Stats.LINEARREG
+  doctest TheoryCraftTA.Stats.TSF
+
+  @data [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0, 13.5, 15.0]
+
+  ## Batch calculation tests
+
+  describe "stats_linearreg/2 and stats_tsf/2" do
+    test "fit a straight line exactly" do
+      data = [1.0, 3.0, 5.0, 7.0, 9.0]
+
+      assert {:ok, [nil, nil, lr1, lr2, lr3]} = Native.stats_linearreg(data, 3)
+      assert {:ok, [nil, nil, tsf1, tsf2, tsf3]} = Native.stats_tsf(data, 3)
+
+      for {value, expected} <- [{lr1, 5.0}, {lr2, 7.0}, {lr3, 9.0}] do
+        assert_in_delta value, expected, 1.0e-9
+      end
+
+      for {value, expected} <- [{tsf1, 7.0}, {tsf2, 9.0}, {tsf3, 11.0}] do
+        assert_in_delta value, expected, 1.0e-9
+      end
+    end
+
+    test "return error for period < 2" do
+      assert {:error, reason} = Native.stats_linearreg([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+
+      assert {:error, reason} = Native.stats_tsf([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "return empty for empty input" do
+      assert {:ok, []} = Native.stats_linearreg([], 3)
+      assert {:ok, []} = Native.stats_tsf([], 3)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "stats_linearreg_state_init/1 and stats_tsf_state_init/1" do
+    test "return error for period < 2" do
+      assert {:error, reason} = Native.stats_linearreg_state_init(1)
+      assert reason =~ "Invalid period"
+
+      assert {:error, reason} = Native.stats_tsf_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests
+
+  describe "stats_linearreg_state_next/3 and stats_tsf_state_next/3" do
+    test "return the fitted value and the forecast once the window is full" do
+      # The last two windows lie on a line of slope -1
+      data = [4.0, 1.0, 0.0, -1.0, -2.0]
+
+      assert [nil, nil, lr1, lr2, lr3] = stream(:linearreg, data, 3)
+      assert [nil, nil, tsf1, _tsf2, tsf3] = stream(:tsf, data, 3)
+
+      # 4, 1, 0: slope -2, centered on the mean 5/3
+      assert_in_delta lr1, 5.0 / 3.0 - 2.0, 1.0e-9
+      assert_in_delta tsf1, 5.0 / 3.0 - 4.0, 1.0e-9
+      assert_in_delta lr2, -1.0, 1.0e-9
+      assert_in_delta lr3, -2.0, 1.0e-9
+      assert_in_delta tsf3, -3.0, 1.0e-9
+    end
+
+    test "UPDATE replaces the value of the current bar" do
+      {:ok, state} = Native.stats_tsf_state_init(2)
+      {:ok, {nil, state}} = Native.stats_tsf_state_next(state, 1.0, true)
+      {:ok, {tsf, state}} = Native.stats_tsf_state_next(state, 2.0, true)
+      assert_in_delta tsf, 3.0, 1.0e-9
+
+      {:ok, {tsf, state}} = Native.stats_tsf_state_next(state, 0.0, false)
+      assert_in_delta tsf, -1.0, 1.0e-9
+
+      # The updated value is the one kept once the bar is closed
+      {:ok, {tsf, _state}} = Native.stats_tsf_state_next(state, 2.0, true)
+      assert_in_delta tsf, 4.0, 1.0e-9
+    end
+
+    test "handle nil values without modifying the state" do
+      {:ok, state} = Native.stats_linearreg_state_init(2)
+      {:ok, {nil, state}} = Native.stats_linearreg_state_next(state, 5.0, true)
+
+      assert {:ok, {nil, ^state}} = Native.stats_linearreg_state_next(state, nil, true)
+    end
+  end
+
+  ## Public API tests
+
+  describe "linearreg/2 and tsf/2" do
+    test "match the native calculation" do
+      assert LINEARREG.linearreg(@data, 3) == Native.stats_linearreg(@data, 3)
+      assert TSF.tsf(@data, 3) == Native.stats_tsf(@data, 3)
+    end
+
+    test "maintain DataSeries type in output" do
+      {:ok, expected} = Native.stats_tsf(@data, 3)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = TSF.tsf(ds, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = LINEARREG.init(period: 1, data: "eurusd_m1", name: "linearreg")
+      assert reason =~ "Invalid period"
+
+      assert {:error, reason} = TSF.init(period: 1, data: "eurusd_m1", name: "tsf")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, linearreg_batch} = LINEARREG.linearreg(@data, 3)
+      {:ok, tsf_batch} = TSF.tsf(@data, 3)
+
+      for {module, batch_result} <- [{LINEARREG, linearreg_batch}, {TSF, tsf_batch}] do
+        {:ok, state} = module.init(period: 3, data: "eurusd_m1", name: "regression")
+
+        {stream_result, _state} =
+          Enum.map_reduce(@data, state, fn value, st ->
+            {:ok, result, new_st} = module.next(close_event(value, true), st)
+            {result.value, new_st}
+          end)
+
+        assert_values_in_delta(stream_result, batch_result)
+      end
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = TSF.init(period: 3, data: "eurusd_m1", name: "tsf")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = TSF.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = TSF.next(close_event(100.0, false), state)
+      {:ok, batch_result} = TSF.tsf(List.replace_at(@data, -1, 100.0), 3)
+
+      assert_same_value(result.value, List.last(batch_result))
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch LINEARREG and TSF" do
+      check all(
+              data <- list_of(float(min: -1000.0, max: 1000.0), min_length: 1, max_length: 300),
+              period <- integer(2..30)
+            ) do
+        for kind <- [:linearreg, :tsf] do
+          {:ok, batch_result} = batch(kind, data, period)
+          assert_values_in_delta(stream(kind, data, period), batch_result, 1.0e-6)
+        end
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: -1000.0, max: 1000.0), min_length: 30, max_length: 200),
+              period <- integer(2..30),
+              updates <- list_of(float(min: -1000.0, max: 1000.0), min_length: 1, max_length: 5)
+            ) do
+        for kind <- [:linearreg, :tsf] do
+          {init, next} = functions(kind)
+          {:ok, state} = init.(period)
+
+          state =
+            Enum.reduce(data, state, fn value, st ->
+              {:ok, {_result, new_st}} = next.(st, value, true)
+              new_st
+            end)
+
+          Enum.reduce(updates, {state, data}, fn value, {st, current_data} ->
+            {:ok, {result, new_st}} = next.(st, value, false)
+
+            updated_data = List.replace_at(current_data, -1, value)
+            {:ok, batch_result} = batch(kind, updated_data, period)
+            assert_in_delta(result, List.last(batch_result), 1.0e-6)
+
+            {new_st, updated_data}
+          end)
+        end
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp stream(kind, data, period) do
+    {init, next} = functions(kind)
+    {:ok, state} = init.(period)
+
+    {results, _state} =
+      Enum.map_reduce(data, state, fn value, st ->
+        {:ok, {result, new_st}} = next.(st, value, true)
+        {result, new_st}
+      end)
+
+    results
+  end
+
+  defp batch(:linearreg, data, period), do: Native.stats_linearreg(data, period)
+  defp batch(:tsf, data, period), do: Native.stats_tsf(data, period)
+
+  defp functions(:linearreg),
+    do: {&Native.stats_linearreg_state_init/1, &Native.stats_linearreg_state_next/3}
+
+  defp functions(:tsf), do: {&Native.stats_tsf_state_init/1, &Native.stats_tsf_state_next/3}
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
@@ -0,0 +1,185 @@
+defmodule TheoryCraftTA.Stats.MedianTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Stats.Median
+
+  doctest TheoryCraftTA.Stats.Median
+
+  @data [5.0, 1.0, 4.0, 2.0, 3.0, 7.0, 6.0, 6.5, 8.0, 2.5]
+
+  ## Batch calculation tests
+
+  describe "stats_median/2" do
+    test "calculates correctly with odd and even periods" do
+      data = [5.0, 1.0, 4.0, 2.0, 3.0]
+
+      assert {:ok, [nil, nil, 4.0, 2.0, 3.0]} = Native.stats_median(data, 3)
+      assert {:ok, [nil, nil, nil, 3.0, 2.5]} = Native.stats_median(data, 4)
+    end
+
+    test "skips leading nil values" do
+      assert {:ok, [nil, nil, nil, 2.0]} = Native.stats_median([nil, 3.0, 1.0, 2.0], 3)
+    end
+
+    test "returns nil while a gap is in the window" do
+      data = [1.0, 2.0, nil, 4.0, 5.0, 6.0]
+      assert {:ok, [nil, 1.5, nil, nil, 4.5, 5.5]} = Native.stats_median(data, 2)
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.stats_median([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.stats_median([], 3)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, [nil, nil]} = Native.stats_median([1.0, 2.0], 3)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "stats_median_state_init/1" do
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.stats_median_state_init(1)
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Public API tests
+
+  describe "median/2" do
+    test "matches the native calculation" do
+      assert Median.median(@data, 3) == Native.stats_median(@data, 3)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.stats_median(@data, 3)
+      ds = Enum.reduce(@data, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, %DataSeries{} = result} = Median.median(ds, 3)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = Median.init(period: 1, data: "eurusd_m1", name: "median")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = Median.median(@data, 3)
+      {:ok, state} = Median.init(period: 3, data: "eurusd_m1", name: "median")
+
+      {stream_result, _state} =
+        Enum.map_reduce(@data, state, fn value, st ->
+          {:ok, result, new_st} = Median.next(close_event(value, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE replaces the close of the current bar" do
+      {:ok, state} = Median.init(period: 3, data: "eurusd_m1", name: "median")
+
+      state =
+        Enum.reduce(@data, state, fn value, st ->
+          {:ok, _result, new_st} = Median.next(close_event(value, true), st)
+          new_st
+        end)
+
+      {:ok, result, _state} = Median.next(close_event(100.0, false), state)
+      {:ok, batch_result} = Median.median(List.replace_at(@data, -1, 100.0), 3)
+
+      assert_same_value(result.value, List.last(batch_result))
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: matches a naive median" do
+    property "batch median matches sorting each window" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, result} = Native.stats_median(data, period)
+        assert result == naive_median(data, period)
+      end
+    end
+
+    property "APPEND mode matches the naive median" do
+      check all(
+              data <- list_of(integer(1..20), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        data = Enum.map(data, &(&1 * 1.0))
+        {:ok, initial_state} = Native.stats_median_state_init(period)
+
+        data
+        |> Enum.zip(naive_median(data, period))
+        |> Enum.reduce(initial_state, fn {value, expected}, state ->
+          {:ok, {result, new_state}} = Native.stats_median_state_next(state, value, true)
+          assert result == expected
+          new_state
+        end)
+      end
+    end
+
+    property "UPDATE before each APPEND matches the naive median" do
+      check all(
+              data <- list_of({integer(1..20), integer(1..20)}, min_length: 10, max_length: 100),
+              period <- integer(2..20)
+            ) do
+        final_data = Enum.map(data, fn {_forming, final} -> final * 1.0 end)
+        {:ok, initial_state} = Native.stats_median_state_init(period)
+
+        data
+        |> Enum.zip(naive_median(final_data, period))
+        |> Enum.reduce(initial_state, fn {{forming, final}, expected}, state ->
+          {:ok, {_, state}} = Native.stats_median_state_next(state, forming * 1.0, true)
+          {:ok, {result, new_state}} = Native.stats_median_state_next(state, final * 1.0, false)
+          assert result == expected
+          new_state
+        end)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp naive_median(data, period) do
+    medians =
+      data
+      |> Enum.chunk_every(period, 1, :discard)
+      |> Enum.map(fn window ->
+        sorted = Enum.sort(window)
+        middle = div(period, 2)
+
+        if rem(period, 2) == 1 do
+          Enum.at(sorted, middle)
+        else
+          (Enum.at(sorted, middle - 1) + Enum.at(sorted, middle)) / 2.0
+        end
+      end)
+
+    List.duplicate(nil, min(period - 1, length(data))) ++ medians
+  end
+
+  defp close_event(close, new_bar?) do
+    %MarketEvent{data: %{"eurusd_m1" => %Bar{close: close, new_bar?: new_bar?}}}
+  end
+end
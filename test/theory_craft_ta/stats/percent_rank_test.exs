@@ -0,0 +1,81 @@
+defmodule TheoryCraftTA.Stats.PercentRankTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "stats_percent_rank/2" do
+    test "calculates correctly with period=4" do
+      data = [3.0, 1.0, 4.0, 1.0, 5.0, 2.0, 1.0]
+
+      # 5.0 is above all of [3, 1, 4, 1], 2.0 above two of [1, 4, 1, 5]
+      # and 1.0 above none of [4, 1, 5, 2]
+      assert {:ok, [nil, nil, nil, nil, 100.0, 50.0, +0.0]} =
+               Native.stats_percent_rank(data, 4)
+    end
+
+    test "ranks a monotonic increasing series at 100" do
+      data = Enum.map(1..20, &(&1 * 1.0))
+
+      assert {:ok, result} = Native.stats_percent_rank(data, 5)
+      assert Enum.take(result, 5) == List.duplicate(nil, 5)
+      assert Enum.drop(result, 5) == List.duplicate(100.0, 15)
+    end
+
+    test "ranks a monotonic decreasing series at 0" do
+      data = Enum.map(20..1//-1, &(&1 * 1.0))
+
+      assert {:ok, result} = Native.stats_percent_rank(data, 5)
+      assert Enum.drop(result, 5) == List.duplicate(0.0, 15)
+    end
+
+    test "skips leading nil values" do
+      assert {:ok, [nil, nil, nil, 50.0, 50.0]} =
+               Native.stats_percent_rank([nil, 1.0, 3.0, 2.0, 2.5], 2)
+    end
+
+    test "returns nil while a gap is in the window" do
+      data = [1.0, 2.0, nil, 4.0, 5.0, 6.0]
+      assert {:ok, [nil, 100.0, nil, nil, 100.0, 100.0]} = Native.stats_percent_rank(data, 1)
+    end
+
+    test "returns error for period < 1" do
+      assert {:error, reason} = Native.stats_percent_rank([1.0, 2.0, 3.0], 0)
+      assert reason =~ "period must be >= 1"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.stats_percent_rank([], 3)
+    end
+
+    test "handles insufficient data (period >= data length)" do
+      assert {:ok, [nil, nil, nil]} = Native.stats_percent_rank([1.0, 2.0, 3.0], 3)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: matches a naive percent rank" do
+    property "batch percent rank matches counting each window" do
+      check all(
+              data <- list_of(integer(1..50), min_length: 10, max_length: 200),
+              period <- integer(1..20)
+            ) do
+        data = Enum.map(data, &(&1 * 1.0))
+        {:ok, result} = Native.stats_percent_rank(data, period)
+
+        expected =
+          data
+          |> Enum.chunk_every(period + 1, 1, :discard)
+          |> Enum.map(fn window ->
+            {previous, [current]} = Enum.split(window, period)
+            Enum.count(previous, &(&1 < current)) * 100.0 / period
+          end)
+
+        assert result == List.duplicate(nil, min(period, length(data))) ++ expected
+      end
+    end
+  end
+end
@@ -0,0 +1,77 @@
+defmodule TheoryCraftTA.Stats.SEMTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "stats_sem/2" do
+    test "calculates correctly with period=8" do
+      # Window [2, 4, 4, 4, 5, 5, 7, 9] has mean 5 and stddev 2
+      data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+
+      assert {:ok, result} = Native.stats_sem(data, 8)
+      assert Enum.take(result, 7) == List.duplicate(nil, 7)
+      assert_in_delta List.last(result), 2.0 / :math.sqrt(8.0), 1.0e-9
+    end
+
+    test "matches a direct computation on a short window" do
+      data = [1.0, 4.0, 2.0, 8.0, 5.0]
+
+      assert {:ok, [nil, nil, sem1, sem2, sem3]} = Native.stats_sem(data, 3)
+
+      windows = [[1.0, 4.0, 2.0], [4.0, 2.0, 8.0], [2.0, 8.0, 5.0]]
+
+      for {value, window} <- Enum.zip([sem1, sem2, sem3], windows) do
+        mean = Enum.sum(window) / 3
+        variance = Enum.sum(Enum.map(window, &((&1 - mean) ** 2))) / 3
+        assert_in_delta value, :math.sqrt(variance) / :math.sqrt(3.0), 1.0e-9
+      end
+    end
+
+    test "returns zero for a constant series (zero stddev)" do
+      assert {:ok, result} = Native.stats_sem(List.duplicate(42.0, 6), 5)
+      assert result == [nil, nil, nil, nil, 0.0, 0.0]
+    end
+
+    test "skips leading nil values" do
+      assert {:ok, [nil, nil, nil, value]} = Native.stats_sem([nil, 1.0, 2.0, 3.0], 3)
+      assert_in_delta value, :math.sqrt(2.0 / 3.0) / :math.sqrt(3.0), 1.0e-9
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.stats_sem([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.stats_sem([], 3)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, [nil, nil]} = Native.stats_sem([1.0, 2.0], 3)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: consistent with STDDEV" do
+    property "SEM = STDDEV / sqrt(period)" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, sem} = Native.stats_sem(data, period)
+        {:ok, stddev} = Native.stats_stddev(data, period, 1.0)
+
+        sem
+        |> Enum.zip(stddev)
+        |> Enum.each(fn
+          {nil, stddev} -> assert stddev == nil
+          {sem, stddev} -> assert_in_delta(sem, stddev / :math.sqrt(period), 1.0e-6)
+        end)
+      end
+    end
+  end
+end
@@ -0,0 +1,277 @@
+defmodule TheoryCraftTA.Stats.VARTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, IndicatorValue, MarketEvent}
+  alias TheoryCraftTA.Stats.VAR
+
+  doctest TheoryCraftTA.Stats.VAR
+
+  ## Batch calculation tests
+
+  describe "var/3 with list input" do
+    test "calculates correctly with period=3" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      # Python result: [nan nan 0.16666667 0.38888889 0.38888889 0.38888889 0.38888889 0.38888889]
+      assert {:ok, result} = VAR.var(data, 3, 1.0)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        0.1666666666666714,
+        0.38888888888889994,
+        0.38888888888888573,
+        0.38888888888888573,
+        0.38888888888888573,
+        0.38888888888891415
+      ])
+    end
+
+    test "ignores nbdev, like TA-Lib" do
+      data = [10.0, 11.0, 10.5, 12.0]
+      assert {:ok, result} = VAR.var(data, 3, 2.0)
+      assert {:ok, ^result} = VAR.var(data, 3, 1.0)
+    end
+
+    test "handles period=1 (minimum valid)" do
+      data = [1.0, 2.0, 3.0]
+      assert {:ok, [0.0, 0.0, 0.0]} = VAR.var(data, 1, 1.0)
+    end
+
+    test "raises for period=0" do
+      data = [1.0, 2.0, 3.0]
+      assert {:error, reason} = VAR.var(data, 0, 1.0)
+      assert reason =~ "period must be >= 1"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = VAR.var([], 3, 1.0)
+    end
+
+    test "handles insufficient data" do
+      data = [1.0, 2.0]
+      assert {:ok, result} = VAR.var(data, 3, 1.0)
+      assert result == [nil, nil]
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      data = [nil, nil, 1.0, 2.0, 3.0, 4.0, 5.0]
+      assert {:ok, result} = VAR.var(data, 3, 1.0)
+
+      assert_values_in_delta(result, [
+        nil,
+        nil,
+        nil,
+        nil,
+        0.6666666666666667,
+        0.6666666666666667,
+        0.6666666666666667
+      ])
+    end
+  end
+
+  describe "var/3 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      data =
+        DataSeries.new()
+        |> DataSeries.add(1.0)
+        |> DataSeries.add(2.0)
+        |> DataSeries.add(3.0)
+        |> DataSeries.add(4.0)
+        |> DataSeries.add(5.0)
+
+      assert {:ok, result} = VAR.var(data, 3, 1.0)
+      assert %DataSeries{} = result
+      assert [_, _, _, nil, nil] = DataSeries.values(result)
+    end
+  end
+
+  describe "var/3 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      ts =
+        TimeSeries.new()
+        |> TimeSeries.add(~U[2024-01-01 00:00:00Z], 1.0)
+        |> TimeSeries.add(~U[2024-01-01 00:01:00Z], 2.0)
+        |> TimeSeries.add(~U[2024-01-01 00:02:00Z], 3.0)
+        |> TimeSeries.add(~U[2024-01-01 00:03:00Z], 4.0)
+        |> TimeSeries.add(~U[2024-01-01 00:04:00Z], 5.0)
+
+      assert {:ok, result} = VAR.var(ts, 3, 1.0)
+      assert %TimeSeries{} = result
+      assert [_, _, _, nil, nil] = TimeSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} =
+               VAR.init(period: 20, nbdev: 2.0, data: "eurusd_m1", name: "var20")
+    end
+
+    test "returns error for period < 1" do
+      assert {:error, msg} =
+               VAR.init(period: 0, nbdev: 1.0, data: "eurusd_m1", name: "var0")
+
+      assert msg =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "emits the first value exactly at the lookback, like batch" do
+      data = [10.0, 11.0, 10.5, 12.0, 11.5, 13.0, 12.5, 14.0]
+      {:ok, batch_result} = VAR.var(data, 3, 1.0)
+      {:ok, state} = VAR.init(period: 3, nbdev: 1.0, data: "eurusd_m1", name: "var3")
+
+      {stream_result, _state} =
+        Enum.map_reduce(data, state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_st} = VAR.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert Enum.find_index(stream_result, & &1) == 2
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "processes bars correctly in UPDATE mode" do
+      {:ok, state} = VAR.init(period: 3, nbdev: 1.0, data: "eurusd_m1", name: "var3")
+
+      state =
+        Enum.reduce([1.0, 2.0, 3.0, 4.0], state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, _result, new_st} = VAR.next(event, st)
+          new_st
+        end)
+
+      # Replace the last bar (4.0 -> 8.0): same as batch over [1.0, 2.0, 3.0, 8.0]
+      event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: 8.0, new_bar?: false}}}
+      {:ok, result, _state} = VAR.next(event, state)
+
+      {:ok, batch_result} = VAR.var([1.0, 2.0, 3.0, 8.0], 3, 1.0)
+      assert_in_delta(result.value, List.last(batch_result), 1.0e-9)
+    end
+
+    test "handles nil values from upstream indicators" do
+      {:ok, state} = VAR.init(period: 2, nbdev: 1.0, data: "indicator", name: "var2")
+
+      {results, _state} =
+        Enum.map_reduce([nil, 100.0, 110.0], state, fn value, st ->
+          event = %MarketEvent{
+            data: %{
+              "indicator" => %IndicatorValue{value: value, data_name: "eurusd_m1"},
+              "eurusd_m1" => %Bar{close: 1.23, new_bar?: true}
+            }
+          }
+
+          {:ok, result, new_st} = VAR.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert [nil, nil, value] = results
+      assert_in_delta(value, 25.0, 1.0e-9)
+    end
+
+    test "returns 0.0 for a flat window" do
+      {:ok, state} = VAR.init(period: 3, nbdev: 1.0, data: "eurusd_m1", name: "var3")
+
+      {results, _state} =
+        Enum.map_reduce([5.0, 5.0, 5.0], state, fn value, st ->
+          event = %MarketEvent{data: %{"eurusd_m1" => %Bar{close: value, new_bar?: true}}}
+          {:ok, result, new_st} = VAR.next(event, st)
+          {result.value, new_st}
+        end)
+
+      assert results == [nil, nil, 0.0]
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch VAR" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 21, max_length: 500),
+              period <- integer(1..50),
+              nbdev <- float(min: 0.5, max: 3.0)
+            ) do
+        {:ok, batch_result} = VAR.var(data, period, nbdev)
+
+        {:ok, initial_state} =
+          VAR.init(period: period, nbdev: nbdev, data: "test", name: "var")
+
+        data
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {value, expected_value}, state ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: value, new_bar?: true}}
+          }
+
+          {:ok, result, new_state} = VAR.next(event, state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 0.0001)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last value" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 15, max_length: 200),
+              period <- integer(2..30),
+              update_values <-
+                list_of(float(min: 1.0, max: 1000.0), min_length: 2, max_length: 5)
+            ) do
+        {:ok, state} = VAR.init(period: period, nbdev: 1.0, data: "test", name: "var")
+
+        final_state =
+          Enum.reduce(data, state, fn value, st ->
+            event = %MarketEvent{
+              data: %{"test" => %Bar{close: value, new_bar?: true}}
+            }
+
+            {:ok, _result, new_state} = VAR.next(event, st)
+            new_state
+          end)
+
+        Enum.reduce(update_values, {final_state, data}, fn update_value, {state, current_data} ->
+          event = %MarketEvent{
+            data: %{"test" => %Bar{close: update_value, new_bar?: false}}
+          }
+
+          {:ok, result, new_state} = VAR.next(event, state)
+
+          updated_data = List.replace_at(current_data, -1, update_value)
+          {:ok, batch_result} = VAR.var(updated_data, period, 1.0)
+          batch_var = List.last(batch_result)
+
+          case {result.value, batch_var} do
+            {nil, nil} ->
+              :ok
+
+            {s_val, b_val} when is_float(s_val) and is_float(b_val) ->
+              assert_in_delta(s_val, b_val, 0.0001)
+
+            _ ->
+              flunk("Mismatch between state UPDATE and batch")
+          end
+
+          {new_state, updated_data}
+        end)
+      end
+    end
+  end
+end
@@ -0,0 +1,82 @@
+defmodule TheoryCraftTA.Stats.ZScoreTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraftTA.Native
+
+  ## Batch calculation tests
+
+  describe "stats_zscore/2" do
+    test "calculates correctly with period=8" do
+      # Window [2, 4, 4, 4, 5, 5, 7, 9] has mean 5 and stddev 2
+      data = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]
+
+      assert {:ok, result} = Native.stats_zscore(data, 8)
+      assert Enum.take(result, 7) == List.duplicate(nil, 7)
+      assert_in_delta List.last(result), 2.0, 1.0e-9
+    end
+
+    test "returns nil for a constant series (zero stddev)" do
+      assert {:ok, result} = Native.stats_zscore(List.duplicate(42.0, 20), 5)
+      assert result == List.duplicate(nil, 20)
+    end
+
+    test "gives a stable z-score on a linear ramp" do
+      data = Enum.map(1..30, &(&1 * 0.5))
+
+      # The newest value of n evenly spaced values is (n - 1) / 2 steps above the
+      # mean, and their stddev is sqrt((n^2 - 1) / 12) steps
+      expected = 2.0 / :math.sqrt(2.0)
+
+      assert {:ok, result} = Native.stats_zscore(data, 5)
+      assert Enum.take(result, 4) == List.duplicate(nil, 4)
+
+      result
+      |> Enum.drop(4)
+      |> Enum.each(&assert_in_delta(&1, expected, 1.0e-6))
+    end
+
+    test "skips leading nil values" do
+      assert {:ok, [nil, nil, nil, value]} = Native.stats_zscore([nil, 1.0, 2.0, 3.0], 3)
+      assert_in_delta value, :math.sqrt(1.5), 1.0e-9
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.stats_zscore([1.0, 2.0, 3.0], 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.stats_zscore([], 3)
+    end
+
+    test "handles insufficient data (period > data length)" do
+      assert {:ok, [nil, nil]} = Native.stats_zscore([1.0, 2.0], 3)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: consistent with SMA and STDDEV" do
+    property "z-score = (value - SMA) / STDDEV" do
+      check all(
+              data <- list_of(float(min: 1.0, max: 1000.0), min_length: 10, max_length: 200),
+              period <- integer(2..20)
+            ) do
+        {:ok, zscore} = Native.stats_zscore(data, period)
+        {:ok, sma} = Native.overlap_sma(data, period)
+        {:ok, stddev} = Native.stats_stddev(data, period, 1.0)
+
+        [data, zscore, sma, stddev]
+        |> Enum.zip()
+        |> Enum.each(fn
+          {_value, nil, _sma, _stddev} ->
+            :ok
+
+          {value, zscore, sma, stddev} ->
+            assert_in_delta(zscore, (value - sma) / stddev, 1.0e-6)
+        end)
+      end
+    end
+  end
+end
@@ -135,4 +135,634 @@ defmodule TheoryCraftTA.TATest do
                 ]}
     end
   end
+
+  describe "kama/3" do
+    test "with accessor syntax" do
+      spec = TA.kama(eurusd[:close], 10, name: "kama10")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.KAMA,
+                [period: 10, data: "eurusd", source: :close, name: "kama10"]}
+    end
+
+    test "without accessor" do
+      spec = TA.kama("eurusd", 10, name: "kama10")
+      assert spec == {TheoryCraftTA.Overlap.KAMA, [period: 10, data: "eurusd", name: "kama10"]}
+    end
+  end
+
+  describe "rsi/3" do
+    test "with accessor syntax" do
+      spec = TA.rsi(eurusd[:close], 14, name: "rsi14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.RSI,
+                [period: 14, data: "eurusd", source: :close, name: "rsi14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.rsi("eurusd", 14, name: "rsi14")
+      assert spec == {TheoryCraftTA.Momentum.RSI, [period: 14, data: "eurusd", name: "rsi14"]}
+    end
+  end
+
+  describe "atr/3" do
+    test "with bar variable" do
+      spec = TA.atr(eurusd, 14, name: "atr14")
+      assert spec == {TheoryCraftTA.Volatility.ATR, [period: 14, data: "eurusd", name: "atr14"]}
+    end
+
+    test "with bar name" do
+      spec = TA.atr("eurusd", 14, name: "atr14")
+      assert spec == {TheoryCraftTA.Volatility.ATR, [period: 14, data: "eurusd", name: "atr14"]}
+    end
+  end
+
+  describe "trange/2" do
+    test "with bar variable" do
+      spec = TA.trange(eurusd, name: "trange")
+      assert spec == {TheoryCraftTA.Volatility.TRANGE, [data: "eurusd", name: "trange"]}
+    end
+
+    test "with bar name" do
+      spec = TA.trange("eurusd", name: "trange")
+      assert spec == {TheoryCraftTA.Volatility.TRANGE, [data: "eurusd", name: "trange"]}
+    end
+  end
+
+  describe "stddev/4" do
+    test "with accessor syntax" do
+      spec = TA.stddev(eurusd[:close], 20, 2.0, name: "stddev20")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.STDDEV,
+                [period: 20, nbdev: 2.0, data: "eurusd", source: :close, name: "stddev20"]}
+    end
+
+    test "without accessor" do
+      spec = TA.stddev("eurusd", 20, 2.0, name: "stddev20")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.STDDEV,
+                [period: 20, nbdev: 2.0, data: "eurusd", name: "stddev20"]}
+    end
+  end
+
+  describe "var/4" do
+    test "with accessor syntax" do
+      spec = TA.var(eurusd[:close], 20, 1.0, name: "var20")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.VAR,
+                [period: 20, nbdev: 1.0, data: "eurusd", source: :close, name: "var20"]}
+    end
+
+    test "without accessor" do
+      spec = TA.var("eurusd", 20, 1.0, name: "var20")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.VAR, [period: 20, nbdev: 1.0, data: "eurusd", name: "var20"]}
+    end
+  end
+
+  describe "mom/3" do
+    test "with accessor syntax" do
+      spec = TA.mom(eurusd[:close], 10, name: "mom10")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.MOM,
+                [period: 10, data: "eurusd", source: :close, name: "mom10"]}
+    end
+
+    test "without accessor" do
+      spec = TA.mom("eurusd", 10, name: "mom10")
+      assert spec == {TheoryCraftTA.Momentum.MOM, [period: 10, data: "eurusd", name: "mom10"]}
+    end
+  end
+
+  describe "roc/3" do
+    test "with accessor syntax" do
+      spec = TA.roc(eurusd[:close], 10, name: "roc10")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ROC,
+                [period: 10, data: "eurusd", source: :close, name: "roc10"]}
+    end
+
+    test "without accessor" do
+      spec = TA.roc("eurusd", 10, name: "roc10")
+      assert spec == {TheoryCraftTA.Momentum.ROC, [period: 10, data: "eurusd", name: "roc10"]}
+    end
+  end
+
+  describe "midprice/3" do
+    test "with bar variable" do
+      spec = TA.midprice(eurusd, 14, name: "midprice14")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.MIDPRICE, [period: 14, data: "eurusd", name: "midprice14"]}
+    end
+
+    test "with bar name" do
+      spec = TA.midprice("eurusd", 14, name: "midprice14")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.MIDPRICE, [period: 14, data: "eurusd", name: "midprice14"]}
+    end
+  end
+
+  describe "obv/2" do
+    test "with bar variable" do
+      spec = TA.obv(eurusd, name: "obv")
+      assert spec == {TheoryCraftTA.Volume.OBV, [data: "eurusd", name: "obv"]}
+    end
+
+    test "with bar name" do
+      spec = TA.obv("eurusd", name: "obv")
+      assert spec == {TheoryCraftTA.Volume.OBV, [data: "eurusd", name: "obv"]}
+    end
+  end
+
+  describe "bbands/3" do
+    test "with accessor syntax" do
+      spec = TA.bbands(eurusd[:close], 20, name: "bb20")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.BBANDS,
+                [period: 20, data: "eurusd", source: :close, name: "bb20"]}
+    end
+
+    test "with deviation options" do
+      spec = TA.bbands("eurusd", 20, nb_dev_up: 2.5, nb_dev_dn: 1.5, name: "bb20")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.BBANDS,
+                [period: 20, data: "eurusd", nb_dev_up: 2.5, nb_dev_dn: 1.5, name: "bb20"]}
+    end
+  end
+
+  describe "cci/3" do
+    test "with bar variable" do
+      spec = TA.cci(eurusd, 14, name: "cci14")
+      assert spec == {TheoryCraftTA.Momentum.CCI, [period: 14, data: "eurusd", name: "cci14"]}
+    end
+
+    test "with bar name" do
+      spec = TA.cci("eurusd", 14, name: "cci14")
+      assert spec == {TheoryCraftTA.Momentum.CCI, [period: 14, data: "eurusd", name: "cci14"]}
+    end
+  end
+
+  describe "willr/3" do
+    test "with bar variable" do
+      spec = TA.willr(eurusd, 14, name: "willr14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.WILLR, [period: 14, data: "eurusd", name: "willr14"]}
+    end
+
+    test "with bar name" do
+      spec = TA.willr("eurusd", 14, name: "willr14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.WILLR, [period: 14, data: "eurusd", name: "willr14"]}
+    end
+  end
+
+  describe "macd/5" do
+    test "with accessor syntax" do
+      spec = TA.macd(eurusd[:close], 12, 26, 9, name: "macd")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.MACD,
+                [
+                  fast_period: 12,
+                  slow_period: 26,
+                  signal_period: 9,
+                  data: "eurusd",
+                  source: :close,
+                  name: "macd"
+                ]}
+    end
+
+    test "without accessor" do
+      spec = TA.macd("eurusd", 12, 26, 9, name: "macd")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.MACD,
+                [
+                  fast_period: 12,
+                  slow_period: 26,
+                  signal_period: 9,
+                  data: "eurusd",
+                  name: "macd"
+                ]}
+    end
+  end
+
+  describe "ma/4" do
+    test "with accessor syntax" do
+      spec = TA.ma(eurusd[:close], 20, 1, name: "ma20")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.MA,
+                [period: 20, ma_type: 1, data: "eurusd", source: :close, name: "ma20"]}
+    end
+
+    test "without accessor" do
+      spec = TA.ma("eurusd", 20, 1, name: "ma20")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.MA, [period: 20, ma_type: 1, data: "eurusd", name: "ma20"]}
+    end
+  end
+
+  describe "rma/3" do
+    test "with accessor syntax" do
+      spec = TA.rma(eurusd[:close], 14, name: "rma14")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.RMA,
+                [period: 14, data: "eurusd", source: :close, name: "rma14"]}
+    end
+  end
+
+  describe "zlema/3" do
+    test "with accessor syntax" do
+      spec = TA.zlema(eurusd[:close], 14, name: "zlema14")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.ZLEMA,
+                [period: 14, data: "eurusd", source: :close, name: "zlema14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.zlema("eurusd", 14, name: "zlema14")
+      assert spec == {TheoryCraftTA.Overlap.ZLEMA, [period: 14, data: "eurusd", name: "zlema14"]}
+    end
+  end
+
+  describe "median/3" do
+    test "with accessor syntax" do
+      spec = TA.median(eurusd[:close], 14, name: "median14")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.Median,
+                [period: 14, data: "eurusd", source: :close, name: "median14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.median("eurusd", 14, name: "median14")
+      assert spec == {TheoryCraftTA.Stats.Median, [period: 14, data: "eurusd", name: "median14"]}
+    end
+  end
+
+  describe "apo/5" do
+    test "with accessor syntax" do
+      spec = TA.apo(eurusd[:close], 12, 26, 1, name: "apo")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.APO,
+                [
+                  fast_period: 12,
+                  slow_period: 26,
+                  ma_type: 1,
+                  data: "eurusd",
+                  source: :close,
+                  name: "apo"
+                ]}
+    end
+
+    test "without accessor" do
+      spec = TA.apo("eurusd", 12, 26, 1, name: "apo")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.APO,
+                [fast_period: 12, slow_period: 26, ma_type: 1, data: "eurusd", name: "apo"]}
+    end
+  end
+
+  describe "ppo/5" do
+    test "with accessor syntax" do
+      spec = TA.ppo(eurusd[:close], 12, 26, 1, name: "ppo")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.PPO,
+                [
+                  fast_period: 12,
+                  slow_period: 26,
+                  ma_type: 1,
+                  data: "eurusd",
+                  source: :close,
+                  name: "ppo"
+                ]}
+    end
+
+    test "without accessor" do
+      spec = TA.ppo("eurusd", 12, 26, 1, name: "ppo")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.PPO,
+                [fast_period: 12, slow_period: 26, ma_type: 1, data: "eurusd", name: "ppo"]}
+    end
+  end
+
+  describe "trix/3" do
+    test "with accessor syntax" do
+      spec = TA.trix(eurusd[:close], 14, name: "trix14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.TRIX,
+                [period: 14, data: "eurusd", source: :close, name: "trix14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.trix("eurusd", 14, name: "trix14")
+      assert spec == {TheoryCraftTA.Momentum.TRIX, [period: 14, data: "eurusd", name: "trix14"]}
+    end
+  end
+
+  describe "cmo/3" do
+    test "with accessor syntax" do
+      spec = TA.cmo(eurusd[:close], 14, name: "cmo14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.CMO,
+                [period: 14, data: "eurusd", source: :close, name: "cmo14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.cmo("eurusd", 14, name: "cmo14")
+      assert spec == {TheoryCraftTA.Momentum.CMO, [period: 14, data: "eurusd", name: "cmo14"]}
+    end
+  end
+
+  describe "adx/3" do
+    test "with bar variable" do
+      spec = TA.adx(eurusd, 14, name: "adx14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ADX, [period: 14, data: "eurusd", name: "adx14"]}
+    end
+
+    test "with bar name" do
+      spec = TA.adx("eurusd", 14, name: "adx14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ADX, [period: 14, data: "eurusd", name: "adx14"]}
+    end
+  end
+
+  describe "max/3" do
+    test "with accessor syntax" do
+      spec = TA.max(eurusd[:close], 14, name: "max14")
+
+      assert spec ==
+               {TheoryCraftTA.Math.MAX,
+                [period: 14, data: "eurusd", source: :close, name: "max14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.max("eurusd", 14, name: "max14")
+      assert spec == {TheoryCraftTA.Math.MAX, [period: 14, data: "eurusd", name: "max14"]}
+    end
+  end
+
+  describe "min/3" do
+    test "with accessor syntax" do
+      spec = TA.min(eurusd[:close], 14, name: "min14")
+
+      assert spec ==
+               {TheoryCraftTA.Math.MIN,
+                [period: 14, data: "eurusd", source: :close, name: "min14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.min("eurusd", 14, name: "min14")
+      assert spec == {TheoryCraftTA.Math.MIN, [period: 14, data: "eurusd", name: "min14"]}
+    end
+  end
+
+  describe "sum/3" do
+    test "with accessor syntax" do
+      spec = TA.sum(eurusd[:close], 14, name: "sum14")
+
+      assert spec ==
+               {TheoryCraftTA.Math.SUM,
+                [period: 14, data: "eurusd", source: :close, name: "sum14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.sum("eurusd", 14, name: "sum14")
+      assert spec == {TheoryCraftTA.Math.SUM, [period: 14, data: "eurusd", name: "sum14"]}
+    end
+  end
+
+  describe "linearreg/3" do
+    test "with accessor syntax" do
+      spec = TA.linearreg(eurusd[:close], 14, name: "linearreg14")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.LINEARREG,
+                [period: 14, data: "eurusd", source: :close, name: "linearreg14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.linearreg("eurusd", 14, name: "linearreg14")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.LINEARREG, [period: 14, data: "eurusd", name: "linearreg14"]}
+    end
+  end
+
+  describe "tsf/3" do
+    test "with accessor syntax" do
+      spec = TA.tsf(eurusd[:close], 14, name: "tsf14")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.TSF,
+                [period: 14, data: "eurusd", source: :close, name: "tsf14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.tsf("eurusd", 14, name: "tsf14")
+      assert spec == {TheoryCraftTA.Stats.TSF, [period: 14, data: "eurusd", name: "tsf14"]}
+    end
+  end
+
+  describe "correl/4" do
+    test "with accessor syntax" do
+      spec = TA.correl(eurusd[:close], gbpusd[:open], 20, name: "correl20")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.CORREL,
+                [
+                  period: 20,
+                  data: "eurusd",
+                  source: :close,
+                  other: "gbpusd",
+                  other_source: :open,
+                  name: "correl20"
+                ]}
+    end
+
+    test "without accessor" do
+      spec = TA.correl("eurusd", "gbpusd", 20, name: "correl20")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.CORREL,
+                [period: 20, data: "eurusd", other: "gbpusd", name: "correl20"]}
+    end
+  end
+
+  describe "beta/4" do
+    test "with accessor syntax" do
+      spec = TA.beta(eurusd[:close], gbpusd[:open], 20, name: "beta20")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.BETA,
+                [
+                  period: 20,
+                  data: "eurusd",
+                  source: :close,
+                  other: "gbpusd",
+                  other_source: :open,
+                  name: "beta20"
+                ]}
+    end
+
+    test "without accessor" do
+      spec = TA.beta("eurusd", "gbpusd", 20, name: "beta20")
+
+      assert spec ==
+               {TheoryCraftTA.Stats.BETA,
+                [period: 20, data: "eurusd", other: "gbpusd", name: "beta20"]}
+    end
+  end
+
+  describe "mcginley/3" do
+    test "with accessor syntax" do
+      spec = TA.mcginley(eurusd[:close], 14, name: "mcginley14")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.McGinley,
+                [period: 14, data: "eurusd", source: :close, name: "mcginley14"]}
+    end
+
+    test "without accessor" do
+      spec = TA.mcginley("eurusd", 14, name: "mcginley14")
+
+      assert spec ==
+               {TheoryCraftTA.Overlap.McGinley, [period: 14, data: "eurusd", name: "mcginley14"]}
+    end
+  end
+
+  describe "dx/3" do
+    test "with bar variable" do
+      spec = TA.dx(eurusd, 14, name: "dx14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.DX, [period: 14, data: "eurusd", name: "dx14"]}
+    end
+
+    test "with bar name" do
+      spec = TA.dx("eurusd", 14, name: "dx14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.DX, [period: 14, data: "eurusd", name: "dx14"]}
+    end
+  end
+
+  describe "rocp/3" do
+    test "with accessor syntax" do
+      spec = TA.rocp(eurusd[:close], 10, name: "rocp10")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ROCP,
+                [period: 10, data: "eurusd", source: :close, name: "rocp10"]}
+    end
+
+    test "without accessor" do
+      spec = TA.rocp("eurusd", 10, name: "rocp10")
+      assert spec == {TheoryCraftTA.Momentum.ROCP, [period: 10, data: "eurusd", name: "rocp10"]}
+    end
+  end
+
+  describe "rocr/3" do
+    test "with accessor syntax" do
+      spec = TA.rocr(eurusd[:close], 10, name: "rocr10")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ROCR,
+                [period: 10, data: "eurusd", source: :close, name: "rocr10"]}
+    end
+
+    test "without accessor" do
+      spec = TA.rocr("eurusd", 10, name: "rocr10")
+      assert spec == {TheoryCraftTA.Momentum.ROCR, [period: 10, data: "eurusd", name: "rocr10"]}
+    end
+  end
+
+  describe "rocr100/3" do
+    test "with accessor syntax" do
+      spec = TA.rocr100(eurusd[:close], 10, name: "rocr100_10")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ROCR100,
+                [period: 10, data: "eurusd", source: :close, name: "rocr100_10"]}
+    end
+
+    test "without accessor" do
+      spec = TA.rocr100("eurusd", 10, name: "rocr100_10")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ROCR100, [period: 10, data: "eurusd", name: "rocr100_10"]}
+    end
+  end
+
+  describe "ultosc/5" do
+    test "with bar variable" do
+      spec = TA.ultosc(eurusd, 7, 14, 28, name: "ultosc")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ULTOSC,
+                [period1: 7, period2: 14, period3: 28, data: "eurusd", name: "ultosc"]}
+    end
+
+    test "with bar name" do
+      spec = TA.ultosc("eurusd", 7, 14, 28, name: "ultosc")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.ULTOSC,
+                [period1: 7, period2: 14, period3: 28, data: "eurusd", name: "ultosc"]}
+    end
+  end
+
+  describe "aroon/3" do
+    test "with bar variable" do
+      spec = TA.aroon(eurusd, 14, name: "aroon14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.AROON, [period: 14, data: "eurusd", name: "aroon14"]}
+    end
+
+    test "with bar name" do
+      spec = TA.aroon("eurusd", 14, name: "aroon14")
+
+      assert spec ==
+               {TheoryCraftTA.Momentum.AROON, [period: 14, data: "eurusd", name: "aroon14"]}
+    end
+  end
+
+  describe "mfi/3" do
+    test "with bar variable" do
+      spec = TA.mfi(eurusd, 14, name: "mfi14")
+      assert spec == {TheoryCraftTA.Volume.MFI, [period: 14, data: "eurusd", name: "mfi14"]}
+    end
+
+    test "with bar name" do
+      spec = TA.mfi("eurusd", 14, name: "mfi14")
+      assert spec == {TheoryCraftTA.Volume.MFI, [period: 14, data: "eurusd", name: "mfi14"]}
+    end
+  end
 end
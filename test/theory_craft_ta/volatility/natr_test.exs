@@ -0,0 +1,221 @@
+defmodule TheoryCraftTA.Volatility.NATRTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Volatility.{ATR, NATR}
+
+  doctest TheoryCraftTA.Volatility.NATR
+
+  @high [12.0, 12.5, 12.2, 13.0, 13.4, 13.1, 13.8, 14.2]
+  @low [11.0, 11.6, 11.4, 12.1, 12.6, 12.4, 12.9, 13.3]
+  @close [11.5, 12.3, 11.9, 12.8, 13.2, 12.6, 13.6, 13.9]
+
+  ## Batch calculation tests
+
+  describe "natr/4 with list input" do
+    test "is the ATR as a percentage of the close" do
+      {:ok, atr} = ATR.atr(@high, @low, @close, 3)
+      assert {:ok, result} = NATR.natr(@high, @low, @close, 3)
+
+      expected =
+        Enum.zip_with(atr, @close, fn
+          nil, _close -> nil
+          atr, close -> atr / close * 100.0
+        end)
+
+      assert_values_in_delta(result, expected)
+    end
+
+    test "raises for period=1" do
+      assert {:error, reason} = NATR.natr(@high, @low, @close, 1)
+      assert reason =~ "period must be >= 2"
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = NATR.natr(@high, @low, Enum.drop(@close, 1), 3)
+      assert reason == "NATR: high/low/close length mismatch (8/8/7)"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = NATR.natr([], [], [], 3)
+    end
+  end
+
+  describe "natr/4 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      [high, low, close] =
+        for values <- [@high, @low, @close] do
+          Enum.reduce(values, DataSeries.new(), &DataSeries.add(&2, &1))
+        end
+
+      assert {:ok, result} = NATR.natr(high, low, close, 3)
+      assert %DataSeries{} = result
+      assert [value | _] = DataSeries.values(result)
+      assert is_float(value)
+    end
+  end
+
+  describe "natr/4 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      times = Enum.map(0..7, &DateTime.add(~U[2024-01-01 00:00:00Z], &1, :minute))
+
+      [high, low, close] =
+        for values <- [@high, @low, @close] do
+          times
+          |> Enum.zip(values)
+          |> Enum.reduce(TimeSeries.new(), fn {time, value}, ts ->
+            TimeSeries.add(ts, time, value)
+          end)
+        end
+
+      assert {:ok, result} = NATR.natr(high, low, close, 3)
+      assert %TimeSeries{} = result
+      assert [value | _] = TimeSeries.values(result)
+      assert is_float(value)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} = NATR.init(period: 14, data: "eurusd_m1", name: "natr14")
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = NATR.init(period: 1, data: "eurusd_m1", name: "natr1")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "replaying the series matches batch" do
+      {:ok, batch_result} = NATR.natr(@high, @low, @close, 3)
+      {:ok, state} = NATR.init(period: 3, data: "eurusd_m1", name: "natr3")
+
+      {stream_result, _state} =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = NATR.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "returns nil for a zero close, which still feeds the ATR" do
+      bars = [{2.0, 1.0, 1.5}, {2.5, 1.5, 2.0}, {1.0, 0.0, 0.0}, {1.5, 0.5, 1.0}]
+
+      {:ok, natr_state} = NATR.init(period: 2, data: "eurusd_m1", name: "natr2")
+      {:ok, atr_state} = ATR.init(period: 2, data: "eurusd_m1", name: "atr2")
+
+      {results, _states} =
+        Enum.map_reduce(bars, {natr_state, atr_state}, fn bar, {natr_state, atr_state} ->
+          {:ok, natr, natr_state} = NATR.next(bar_event(bar, true), natr_state)
+          {:ok, atr, atr_state} = ATR.next(bar_event(bar, true), atr_state)
+          {{natr.value, atr.value}, {natr_state, atr_state}}
+        end)
+
+      assert [{nil, nil}, {nil, nil}, {nil, atr}, {natr, last_atr}] = results
+      assert is_float(atr)
+      assert_in_delta natr, last_atr / 1.0 * 100.0, 1.0e-9
+    end
+
+    test "handles nil values without modifying the state" do
+      {:ok, state} = NATR.init(period: 2, data: "eurusd_m1", name: "natr2")
+
+      {:ok, result, state2} = NATR.next(bar_event({nil, nil, nil}, true), state)
+      assert result.value == nil
+      assert state2 == state
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch NATR" do
+      check all(
+              bars <- bars_generator(21, 300),
+              period <- integer(2..50)
+            ) do
+        {high, low, close} = unzip_bars(bars)
+        {:ok, batch_result} = NATR.natr(high, low, close, period)
+
+        {:ok, initial_state} = NATR.init(period: period, data: "test", name: "natr")
+
+        {stream_result, _state} =
+          Enum.map_reduce(bars, initial_state, fn bar, state ->
+            {:ok, result, new_state} = NATR.next(bar_event("test", bar, true), state)
+            {result.value, new_state}
+          end)
+
+        assert_values_in_delta(stream_result, batch_result)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last bar" do
+      check all(
+              bars <- bars_generator(15, 200),
+              period <- integer(2..14),
+              update_bars <- bars_generator(2, 5)
+            ) do
+        {:ok, state} = NATR.init(period: period, data: "test", name: "natr")
+
+        final_state =
+          Enum.reduce(bars, state, fn bar, st ->
+            {:ok, _result, new_state} = NATR.next(bar_event("test", bar, true), st)
+            new_state
+          end)
+
+        Enum.reduce(update_bars, {final_state, bars}, fn update_bar, {state, current_bars} ->
+          {:ok, result, new_state} = NATR.next(bar_event("test", update_bar, false), state)
+
+          updated_bars = List.replace_at(current_bars, -1, update_bar)
+          {high, low, close} = unzip_bars(updated_bars)
+          {:ok, batch_result} = NATR.natr(high, low, close, period)
+
+          assert_in_delta(result.value, List.last(batch_result), 1.0e-9)
+
+          {new_state, updated_bars}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp bar_event(data_name \\ "eurusd_m1", {high, low, close}, new_bar?) do
+    %MarketEvent{
+      data: %{data_name => %Bar{high: high, low: low, close: close, new_bar?: new_bar?}}
+    }
+  end
+
+  defp bars_generator(min_length, max_length) do
+    price = float(min: 20.0, max: 1000.0)
+    spread = float(min: 0.0, max: 10.0)
+
+    gen all(
+          bars <-
+            list_of(
+              tuple({price, spread, spread}),
+              min_length: min_length,
+              max_length: max_length
+            )
+        ) do
+      Enum.map(bars, fn {close, up, down} -> {close + up, close - down, close} end)
+    end
+  end
+
+  defp unzip_bars(bars) do
+    {Enum.map(bars, &elem(&1, 0)), Enum.map(bars, &elem(&1, 1)), Enum.map(bars, &elem(&1, 2))}
+  end
+end
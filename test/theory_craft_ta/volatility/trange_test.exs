@@ -0,0 +1,208 @@
+defmodule TheoryCraftTA.Volatility.TRANGETest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Volatility.TRANGE
+
+  doctest TheoryCraftTA.Volatility.TRANGE
+
+  @high [12.0, 12.5, 12.2, 13.0, 13.4, 13.1, 13.8, 14.2]
+  @low [11.0, 11.6, 11.4, 12.1, 12.6, 12.4, 12.9, 13.3]
+  @close [11.5, 12.3, 11.9, 12.8, 13.2, 12.6, 13.6, 13.9]
+
+  ## Batch calculation tests
+
+  describe "trange/3 with list input" do
+    test "calculates correctly" do
+      # Python result: [nan 1. 0.9 1.1 0.8 0.8 1.2 0.9]
+      assert {:ok, result} = TRANGE.trange(@high, @low, @close)
+
+      assert_values_in_delta(result, [nil, 1.0, 0.9, 1.1, 0.8, 0.8, 1.2, 0.9])
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = TRANGE.trange(@high, Enum.drop(@low, 1), @close)
+      assert reason =~ "length mismatch"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = TRANGE.trange([], [], [])
+    end
+
+    test "handles a single bar" do
+      assert {:ok, [nil]} = TRANGE.trange([12.0], [11.0], [11.5])
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      high = [nil, nil | Enum.drop(@high, 2)]
+      low = [nil, nil | Enum.drop(@low, 2)]
+      close = [nil, nil | Enum.drop(@close, 2)]
+      # Python result: [nan nan nan 1.1 0.8 0.8 1.2 0.9]
+      assert {:ok, result} = TRANGE.trange(high, low, close)
+      assert_values_in_delta(result, [nil, nil, nil, 1.1, 0.8, 0.8, 1.2, 0.9])
+    end
+  end
+
+  describe "trange/3 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      high = Enum.reduce(@high, DataSeries.new(), &DataSeries.add(&2, &1))
+      low = Enum.reduce(@low, DataSeries.new(), &DataSeries.add(&2, &1))
+      close = Enum.reduce(@close, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, result} = TRANGE.trange(high, low, close)
+      assert %DataSeries{} = result
+      assert [_, _, _, _, _, _, 1.0, nil] = DataSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} = TRANGE.init(data: "eurusd_m1", name: "trange")
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "first bar yields nil (no previous close)" do
+      {:ok, state} = TRANGE.init(data: "eurusd_m1", name: "trange")
+      {:ok, result, state} = TRANGE.next(bar_event({12.0, 11.0, 11.5}, true), state)
+      assert result.value == nil
+
+      # Updating the first bar still has no previous close
+      {:ok, result, _state} = TRANGE.next(bar_event({12.5, 10.0, 12.0}, false), state)
+      assert result.value == nil
+    end
+
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = TRANGE.trange(@high, @low, @close)
+      {:ok, state} = TRANGE.init(data: "eurusd_m1", name: "trange")
+
+      {stream_result, _state} =
+        [@high, @low, @close]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = TRANGE.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result)
+    end
+
+    test "UPDATE recomputes from the persisted prior close" do
+      {:ok, state} = TRANGE.init(data: "eurusd_m1", name: "trange")
+      {:ok, _result, state} = TRANGE.next(bar_event({12.0, 11.0, 11.5}, true), state)
+      {:ok, result, state} = TRANGE.next(bar_event({12.5, 11.6, 12.3}, true), state)
+      assert_in_delta(result.value, 1.0, 1.0e-9)
+
+      # New high/low on the open bar: the gap is still measured from 11.5
+      {:ok, result, state} = TRANGE.next(bar_event({14.0, 13.0, 13.8}, false), state)
+      assert_in_delta(result.value, 2.5, 1.0e-9)
+
+      # The close of the previous tick (13.8) must not be used
+      {:ok, result, _state} = TRANGE.next(bar_event({11.0, 10.0, 10.5}, false), state)
+      assert_in_delta(result.value, 1.5, 1.0e-9)
+    end
+
+    test "handles nil values without modifying the state" do
+      {:ok, state} = TRANGE.init(data: "eurusd_m1", name: "trange")
+      {:ok, _result, state} = TRANGE.next(bar_event({12.0, 11.0, 11.5}, true), state)
+
+      {:ok, result, state} = TRANGE.next(bar_event({nil, nil, nil}, true), state)
+      assert result.value == nil
+
+      {:ok, result, _state} = TRANGE.next(bar_event({12.5, 11.6, 12.3}, true), state)
+      assert_in_delta(result.value, 1.0, 1.0e-9)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch TRANGE" do
+      check all(bars <- bars_generator(1, 300)) do
+        {high, low, close} = unzip_bars(bars)
+        {:ok, batch_result} = TRANGE.trange(high, low, close)
+
+        {:ok, initial_state} = TRANGE.init(data: "test", name: "trange")
+
+        bars
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {bar, expected_value}, state ->
+          {:ok, result, new_state} = TRANGE.next(bar_event("test", bar, true), state)
+
+          case {result.value, expected_value} do
+            {nil, nil} -> :ok
+            {val, exp} when is_float(val) and is_float(exp) -> assert_in_delta(val, exp, 1.0e-9)
+            _ -> flunk("Mismatch in batch vs incremental results")
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last bar" do
+      check all(
+              bars <- bars_generator(2, 200),
+              update_bars <- bars_generator(2, 5)
+            ) do
+        {:ok, state} = TRANGE.init(data: "test", name: "trange")
+
+        final_state =
+          Enum.reduce(bars, state, fn bar, st ->
+            {:ok, _result, new_state} = TRANGE.next(bar_event("test", bar, true), st)
+            new_state
+          end)
+
+        Enum.reduce(update_bars, {final_state, bars}, fn update_bar, {state, current_bars} ->
+          {:ok, result, new_state} = TRANGE.next(bar_event("test", update_bar, false), state)
+
+          updated_bars = List.replace_at(current_bars, -1, update_bar)
+          {high, low, close} = unzip_bars(updated_bars)
+          {:ok, batch_result} = TRANGE.trange(high, low, close)
+
+          assert_in_delta(result.value, List.last(batch_result), 1.0e-9)
+
+          {new_state, updated_bars}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp bar_event(data_name \\ "eurusd_m1", {high, low, close}, new_bar?) do
+    %MarketEvent{
+      data: %{data_name => %Bar{high: high, low: low, close: close, new_bar?: new_bar?}}
+    }
+  end
+
+  defp bars_generator(min_length, max_length) do
+    price = float(min: 1.0, max: 1000.0)
+    spread = float(min: 0.0, max: 10.0)
+
+    gen all(
+          bars <-
+            list_of(
+              tuple({price, spread, spread}),
+              min_length: min_length,
+              max_length: max_length
+            )
+        ) do
+      Enum.map(bars, fn {close, up, down} -> {close + up, close - down, close} end)
+    end
+  end
+
+  defp unzip_bars(bars) do
+    {Enum.map(bars, &elem(&1, 0)), Enum.map(bars, &elem(&1, 1)), Enum.map(bars, &elem(&1, 2))}
+  end
+end
@@ -0,0 +1,213 @@
+defmodule TheoryCraftTA.Volume.ADTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Volume.AD
+
+  doctest TheoryCraftTA.Volume.AD
+
+  @high [10.0, 11.0, 12.0, 13.0, 12.0, 12.0]
+  @low [8.0, 9.0, 10.0, 11.0, 10.0, 12.0]
+  @close [9.0, 11.0, 10.0, 13.0, 10.5, 12.0]
+  @volume [100.0, 200.0, 300.0, 100.0, 400.0, 500.0]
+
+  ## Batch calculation tests
+
+  describe "ad/4 with list input" do
+    test "calculates correctly" do
+      # CLV: 0, 1, -1, 1, -0.5, and a flat bar that doesn't move the line
+      assert {:ok, result} = AD.ad(@high, @low, @close, @volume)
+      assert result == [0.0, 200.0, -100.0, 0.0, -200.0, -200.0]
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = AD.ad(@high, @low, @close, Enum.drop(@volume, 1))
+      assert reason == "AD: high/low/close/volume length mismatch (6/6/6/5)"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = AD.ad([], [], [], [])
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      [high, low, close, volume] =
+        for values <- [@high, @low, @close, @volume], do: [nil | Enum.drop(values, 1)]
+
+      assert {:ok, result} = AD.ad(high, low, close, volume)
+      assert result == [nil, 200.0, -100.0, 0.0, -200.0, -200.0]
+    end
+  end
+
+  describe "ad/4 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      [high, low, close, volume] =
+        for values <- [@high, @low, @close, @volume] do
+          Enum.reduce(values, DataSeries.new(), &DataSeries.add(&2, &1))
+        end
+
+      assert {:ok, result} = AD.ad(high, low, close, volume)
+      assert %DataSeries{} = result
+      assert [-200.0, -200.0, 0.0 | _] = DataSeries.values(result)
+    end
+  end
+
+  describe "ad/4 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      times = Enum.map(0..5, &DateTime.add(~U[2024-01-01 00:00:00Z], &1, :minute))
+
+      [high, low, close, volume] =
+        for values <- [@high, @low, @close, @volume] do
+          times
+          |> Enum.zip(values)
+          |> Enum.reduce(TimeSeries.new(), fn {time, value}, ts ->
+            TimeSeries.add(ts, time, value)
+          end)
+        end
+
+      assert {:ok, result} = AD.ad(high, low, close, volume)
+      assert %TimeSeries{} = result
+      assert [-200.0, -200.0, 0.0 | _] = TimeSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} = AD.init(data: "eurusd_m1", name: "ad")
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "replaying the series matches batch" do
+      {:ok, batch_result} = AD.ad(@high, @low, @close, @volume)
+      {:ok, state} = AD.init(data: "eurusd_m1", name: "ad")
+
+      {stream_result, _state} =
+        [@high, @low, @close, @volume]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = AD.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert stream_result == batch_result
+    end
+
+    test "UPDATE keeps the total up to the previous bar" do
+      {:ok, state} = AD.init(data: "eurusd_m1", name: "ad")
+
+      state =
+        Enum.reduce([{10.0, 8.0, 9.0, 100.0}, {11.0, 9.0, 11.0, 200.0}], state, fn bar, st ->
+          {:ok, _result, new_st} = AD.next(bar_event(bar, true), st)
+          new_st
+        end)
+
+      # The 2nd bar is still open: it now closes on its low with more volume
+      {:ok, result1, state} = AD.next(bar_event({11.0, 9.0, 9.0, 250.0}, false), state)
+      assert result1.value == -250.0
+
+      # Another tick on the same bar still starts from the 1st bar's total
+      {:ok, result2, state} = AD.next(bar_event({11.0, 9.0, 10.0, 300.0}, false), state)
+      assert result2.value == 0.0
+
+      # The next bar builds on the last value of the updated bar
+      {:ok, result3, _state} = AD.next(bar_event({12.0, 10.0, 12.0, 50.0}, true), state)
+      assert result3.value == 50.0
+    end
+
+    test "handles nil values without modifying the state" do
+      {:ok, state} = AD.init(data: "eurusd_m1", name: "ad")
+
+      {:ok, result, state} = AD.next(bar_event({nil, nil, nil, nil}, true), state)
+      assert result.value == nil
+
+      {:ok, result, _state} = AD.next(bar_event({11.0, 9.0, 11.0, 200.0}, true), state)
+      assert result.value == 200.0
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch AD" do
+      check all(bars <- bars_generator(1, 300)) do
+        {:ok, batch_result} = batch_ad(bars)
+
+        {:ok, initial_state} = AD.init(data: "test", name: "ad")
+
+        bars
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {bar, expected_value}, state ->
+          {:ok, result, new_state} = AD.next(bar_event("test", bar, true), state)
+          assert_in_delta(result.value, expected_value, 1.0e-6)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last bar" do
+      check all(
+              bars <- bars_generator(1, 200),
+              update_bars <- bars_generator(2, 5)
+            ) do
+        {:ok, state} = AD.init(data: "test", name: "ad")
+
+        final_state =
+          Enum.reduce(bars, state, fn bar, st ->
+            {:ok, _result, new_state} = AD.next(bar_event("test", bar, true), st)
+            new_state
+          end)
+
+        Enum.reduce(update_bars, {final_state, bars}, fn update_bar, {state, current_bars} ->
+          {:ok, result, new_state} = AD.next(bar_event("test", update_bar, false), state)
+
+          updated_bars = List.replace_at(current_bars, -1, update_bar)
+          {:ok, batch_result} = batch_ad(updated_bars)
+
+          assert_in_delta(result.value, List.last(batch_result), 1.0e-6)
+
+          {new_state, updated_bars}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp batch_ad(bars) do
+    [high, low, close, volume] =
+      for index <- 0..3, do: Enum.map(bars, &elem(&1, index))
+
+    AD.ad(high, low, close, volume)
+  end
+
+  defp bar_event(data_name \\ "eurusd_m1", {high, low, close, volume}, new_bar?) do
+    %MarketEvent{
+      data: %{
+        data_name => %Bar{high: high, low: low, close: close, volume: volume, new_bar?: new_bar?}
+      }
+    }
+  end
+
+  defp bars_generator(min_length, max_length) do
+    # The close is placed in the range, which can be empty (high == low)
+    bar =
+      gen all(
+            low <- float(min: 1.0, max: 1000.0),
+            range <- one_of([constant(0.0), float(min: 0.0, max: 50.0)]),
+            position <- float(min: 0.0, max: 1.0),
+            volume <- float(min: 0.0, max: 10_000.0)
+          ) do
+        {low + range, low, low + range * position, volume}
+      end
+
+    list_of(bar, min_length: min_length, max_length: max_length)
+  end
+end
@@ -0,0 +1,230 @@
+defmodule TheoryCraftTA.Volume.ADOSCTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Volume.ADOSC
+
+  doctest TheoryCraftTA.Volume.ADOSC
+
+  # A/D line: [0.0, 200.0, -100.0, 0.0]
+  @high [10.0, 11.0, 12.0, 13.0]
+  @low [8.0, 9.0, 10.0, 11.0]
+  @close [9.0, 11.0, 10.0, 13.0]
+  @volume [100.0, 200.0, 300.0, 100.0]
+
+  ## Batch calculation tests
+
+  describe "adosc/6 with list input" do
+    test "calculates correctly" do
+      # Both EMAs start at the first A/D value:
+      # fast (k = 2/3): 0, 133.33, -22.22, -7.41 - slow (k = 1/2): 0, 100, 0, 0
+      assert {:ok, [nil, nil, value1, value2]} =
+               ADOSC.adosc(@high, @low, @close, @volume, 2, 3)
+
+      assert_in_delta value1, -22.2222222, 1.0e-6
+      assert_in_delta value2, -7.4074074, 1.0e-6
+    end
+
+    test "returns error for a period < 2" do
+      assert {:error, reason} = ADOSC.adosc(@high, @low, @close, @volume, 1, 3)
+      assert reason =~ "must be >= 2"
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = ADOSC.adosc(@high, Enum.drop(@low, 1), @close, @volume, 2, 3)
+      assert reason == "ADOSC: high/low/close/volume length mismatch (4/3/4/4)"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = ADOSC.adosc([], [], [], [], 2, 3)
+    end
+  end
+
+  describe "adosc/6 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      [high, low, close, volume] =
+        for values <- [@high, @low, @close, @volume] do
+          Enum.reduce(values, DataSeries.new(), &DataSeries.add(&2, &1))
+        end
+
+      assert {:ok, result} = ADOSC.adosc(high, low, close, volume, 2, 3)
+      assert %DataSeries{} = result
+      assert [_, _, nil, nil] = DataSeries.values(result)
+    end
+  end
+
+  describe "adosc/6 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      times = Enum.map(0..3, &DateTime.add(~U[2024-01-01 00:00:00Z], &1, :minute))
+
+      [high, low, close, volume] =
+        for values <- [@high, @low, @close, @volume] do
+          times
+          |> Enum.zip(values)
+          |> Enum.reduce(TimeSeries.new(), fn {time, value}, ts ->
+            TimeSeries.add(ts, time, value)
+          end)
+        end
+
+      assert {:ok, result} = ADOSC.adosc(high, low, close, volume, 2, 3)
+      assert %TimeSeries{} = result
+      assert [_, _, nil, nil] = TimeSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} =
+               ADOSC.init(fast_period: 3, slow_period: 10, data: "eurusd_m1", name: "adosc")
+    end
+
+    test "returns error for a period < 2" do
+      assert {:error, reason} =
+               ADOSC.init(fast_period: 1, slow_period: 10, data: "eurusd_m1", name: "adosc")
+
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "replaying the series matches batch" do
+      {:ok, state} = ADOSC.init(fast_period: 2, slow_period: 3, data: "eurusd_m1", name: "adosc")
+
+      {[nil, nil, value1, value2], _state} =
+        [@high, @low, @close, @volume]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = ADOSC.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_in_delta value1, -22.2222222, 1.0e-6
+      assert_in_delta value2, -7.4074074, 1.0e-6
+    end
+
+    test "the slowest period sets the warmup, whatever the order of the periods" do
+      {:ok, state} = ADOSC.init(fast_period: 3, slow_period: 2, data: "eurusd_m1", name: "adosc")
+
+      {values, _state} =
+        [@high, @low, @close, @volume]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = ADOSC.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert [nil, nil, value, _] = values
+      assert_in_delta value, 22.2222222, 1.0e-6
+    end
+
+    test "handles nil values without modifying the state" do
+      {:ok, state} = ADOSC.init(fast_period: 2, slow_period: 3, data: "eurusd_m1", name: "adosc")
+
+      {:ok, result, state2} = ADOSC.next(bar_event({nil, nil, nil, nil}, true), state)
+      assert result.value == nil
+      assert state2 == state
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch ADOSC" do
+      check all(
+              bars <- bars_generator(1, 300),
+              fast_period <- integer(2..10),
+              slow_period <- integer(2..20)
+            ) do
+        {:ok, batch_result} = batch_adosc(bars, fast_period, slow_period)
+
+        {:ok, initial_state} =
+          ADOSC.init(
+            fast_period: fast_period,
+            slow_period: slow_period,
+            data: "test",
+            name: "adosc"
+          )
+
+        bars
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {bar, expected_value}, state ->
+          {:ok, result, new_state} = ADOSC.next(bar_event("test", bar, true), state)
+
+          if is_nil(expected_value) do
+            assert result.value == nil
+          else
+            assert_in_delta(result.value, expected_value, 1.0e-6)
+          end
+
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last bar" do
+      check all(
+              bars <- bars_generator(10, 200),
+              update_bars <- bars_generator(2, 5)
+            ) do
+        {:ok, state} = ADOSC.init(fast_period: 3, slow_period: 10, data: "test", name: "adosc")
+
+        final_state =
+          Enum.reduce(bars, state, fn bar, st ->
+            {:ok, _result, new_state} = ADOSC.next(bar_event("test", bar, true), st)
+            new_state
+          end)
+
+        Enum.reduce(update_bars, {final_state, bars}, fn update_bar, {state, current_bars} ->
+          {:ok, result, new_state} = ADOSC.next(bar_event("test", update_bar, false), state)
+
+          updated_bars = List.replace_at(current_bars, -1, update_bar)
+          {:ok, batch_result} = batch_adosc(updated_bars, 3, 10)
+
+          assert_in_delta(result.value, List.last(batch_result), 1.0e-6)
+
+          {new_state, updated_bars}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp batch_adosc(bars, fast_period, slow_period) do
+    [high, low, close, volume] =
+      for index <- 0..3, do: Enum.map(bars, &elem(&1, index))
+
+    ADOSC.adosc(high, low, close, volume, fast_period, slow_period)
+  end
+
+  defp bar_event(data_name \\ "eurusd_m1", {high, low, close, volume}, new_bar?) do
+    %MarketEvent{
+      data: %{
+        data_name => %Bar{high: high, low: low, close: close, volume: volume, new_bar?: new_bar?}
+      }
+    }
+  end
+
+  defp bars_generator(min_length, max_length) do
+    # The close is placed in the range, which can be empty (high == low)
+    bar =
+      gen all(
+            low <- float(min: 1.0, max: 1000.0),
+            range <- one_of([constant(0.0), float(min: 0.0, max: 50.0)]),
+            position <- float(min: 0.0, max: 1.0),
+            volume <- float(min: 0.0, max: 10_000.0)
+          ) do
+        {low + range, low, low + range * position, volume}
+      end
+
+    list_of(bar, min_length: min_length, max_length: max_length)
+  end
+end
@@ -0,0 +1,241 @@
+defmodule TheoryCraftTA.Volume.MFITest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  import TheoryCraftTA.Assertions
+
+  alias TheoryCraft.DataSeries
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Native
+  alias TheoryCraftTA.Volume.MFI
+
+  doctest TheoryCraftTA.Volume.MFI
+
+  # Typical prices: 9, 10, 11, 10, 12 - money flows: +2000, +1650, -3000, +1200
+  @high [10.0, 11.0, 12.0, 11.0, 13.0]
+  @low [8.0, 9.0, 10.0, 9.0, 11.0]
+  @close [9.0, 10.0, 11.0, 10.0, 12.0]
+  @volume [100.0, 200.0, 150.0, 300.0, 100.0]
+
+  ## Batch calculation tests
+
+  describe "volume_mfi/5" do
+    test "calculates correctly with period=2" do
+      assert {:ok, [nil, nil, 100.0, value1, value2]} =
+               Native.volume_mfi(@high, @low, @close, @volume, 2)
+
+      assert_in_delta value1, 1650.0 / 4650.0 * 100.0, 1.0e-9
+      assert_in_delta value2, 1200.0 / 4200.0 * 100.0, 1.0e-9
+    end
+
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.volume_mfi(@high, @low, @close, @volume, 1)
+      assert reason == "MFI: period must be >= 2"
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = Native.volume_mfi(@high, @low, @close, Enum.drop(@volume, 1), 2)
+      assert reason == "MFI: high/low/close/volume length mismatch (5/5/5/4)"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = Native.volume_mfi([], [], [], [], 14)
+    end
+  end
+
+  ## Streaming tests
+
+  describe "volume_mfi_state_init/1" do
+    test "returns error for period < 2" do
+      assert {:error, reason} = Native.volume_mfi_state_init(1)
+      assert reason == "Invalid period: must be >= 2 for MFI"
+    end
+  end
+
+  describe "volume_mfi_state_next/6" do
+    test "replaying the series matches batch" do
+      {:ok, expected} = Native.volume_mfi(@high, @low, @close, @volume, 2)
+      {:ok, state} = Native.volume_mfi_state_init(2)
+
+      bars = Enum.zip([@high, @low, @close, @volume])
+      assert_values_in_delta(stream(state, bars), expected, 1.0e-6)
+    end
+
+    test "returns 100 without any negative flow, and 0 without any flow" do
+      {:ok, state} = Native.volume_mfi_state_init(2)
+      rising = [{10.0, 8.0, 9.0, 100.0}, {11.0, 9.0, 10.0, 100.0}, {12.0, 10.0, 11.0, 100.0}]
+      assert [nil, nil, 100.0] = stream(state, rising)
+
+      {:ok, state} = Native.volume_mfi_state_init(2)
+      flat = List.duplicate({10.0, 8.0, 9.0, 100.0}, 3)
+      assert [nil, nil, 0.0] = stream(state, flat)
+    end
+
+    test "UPDATE keeps the typical price of the previous bar" do
+      {:ok, state} = Native.volume_mfi_state_init(2)
+      bars = Enum.zip([@high, @low, @close, @volume])
+
+      state =
+        Enum.reduce(Enum.take(bars, 4), state, fn bar, st ->
+          {:ok, {_value, new_st}} = step(st, bar, true)
+          new_st
+        end)
+
+      # The forming bar now rises: only positive flows are left in the window
+      assert {:ok, {100.0, state}} = step(state, {13.0, 11.0, 12.0, 300.0}, false)
+
+      # Back to the values of the batch
+      assert {:ok, {value, _state}} = step(state, {11.0, 9.0, 10.0, 300.0}, false)
+      assert_in_delta value, 1650.0 / 4650.0 * 100.0, 1.0e-9
+    end
+
+    test "returns nil without modifying the state on nil input" do
+      {:ok, state} = Native.volume_mfi_state_init(2)
+      {:ok, {nil, state}} = step(state, {10.0, 8.0, 9.0, 100.0}, true)
+
+      assert {:ok, {nil, ^state}} = step(state, {11.0, 9.0, 10.0, nil}, true)
+    end
+  end
+
+  ## Public API tests
+
+  describe "mfi/5" do
+    test "matches the native calculation" do
+      assert MFI.mfi(@high, @low, @close, @volume, 2) ==
+               Native.volume_mfi(@high, @low, @close, @volume, 2)
+    end
+
+    test "maintains DataSeries type in output" do
+      {:ok, expected} = Native.volume_mfi(@high, @low, @close, @volume, 2)
+      [high, low, close, volume] =
+        for values <- [@high, @low, @close, @volume], do: data_series(values)
+
+      assert {:ok, %DataSeries{} = result} = MFI.mfi(high, low, close, volume, 2)
+      assert DataSeries.values(result) == Enum.reverse(expected)
+    end
+  end
+
+  describe "init/1" do
+    test "returns error for an invalid period" do
+      assert {:error, reason} = MFI.init(period: 1, data: "eurusd_m1", name: "mfi")
+      assert reason =~ "Invalid period"
+    end
+  end
+
+  describe "next/2" do
+    test "matches batch in APPEND mode" do
+      {:ok, batch_result} = MFI.mfi(@high, @low, @close, @volume, 2)
+      {:ok, state} = MFI.init(period: 2, data: "eurusd_m1", name: "mfi")
+
+      {stream_result, _state} =
+        [@high, @low, @close, @volume]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = MFI.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert_values_in_delta(stream_result, batch_result, 1.0e-6)
+    end
+
+    test "UPDATE replaces the high, low, close and volume of the current bar" do
+      {:ok, state} = MFI.init(period: 2, data: "eurusd_m1", name: "mfi")
+
+      state =
+        [@high, @low, @close, @volume]
+        |> Enum.zip()
+        |> Enum.take(4)
+        |> Enum.reduce(state, fn bar, st ->
+          {:ok, _result, new_st} = MFI.next(bar_event(bar, true), st)
+          new_st
+        end)
+
+      # The forming bar now rises: only positive flows are left in the window
+      assert {:ok, %{value: 100.0}, _state} =
+               MFI.next(bar_event({13.0, 11.0, 12.0, 300.0}, false), state)
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch MFI" do
+      check all(
+              bars <- bars_generator(1, 300),
+              period <- integer(2..30)
+            ) do
+        {:ok, expected} = batch_mfi(bars, period)
+        {:ok, state} = Native.volume_mfi_state_init(period)
+
+        assert_values_in_delta(stream(state, bars), expected, 1.0e-6)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE before each APPEND ends on the batch result" do
+      check all(
+              bars <- list_of({bar(), bar()}, min_length: 1, max_length: 100),
+              period <- integer(2..20)
+            ) do
+        {:ok, expected} = batch_mfi(Enum.map(bars, fn {_forming, final} -> final end), period)
+        {:ok, state} = Native.volume_mfi_state_init(period)
+
+        {values, _state} =
+          Enum.map_reduce(bars, state, fn {forming, final}, st ->
+            {:ok, {_value, st}} = step(st, forming, true)
+            {:ok, {value, st}} = step(st, final, false)
+            {value, st}
+          end)
+
+        assert_values_in_delta(values, expected, 1.0e-6)
+      end
+    end
+  end
+
+  ## Private helper functions
+
+  defp batch_mfi(bars, period) do
+    [high, low, close, volume] = for index <- 0..3, do: Enum.map(bars, &elem(&1, index))
+    Native.volume_mfi(high, low, close, volume, period)
+  end
+
+  defp step(state, {high, low, close, volume}, new_bar?) do
+    Native.volume_mfi_state_next(state, high, low, close, volume, new_bar?)
+  end
+
+  defp stream(state, bars) do
+    {values, _state} =
+      Enum.map_reduce(bars, state, fn bar, st ->
+        {:ok, {value, new_st}} = step(st, bar, true)
+        {value, new_st}
+      end)
+
+    values
+  end
+
+  defp bar() do
+    # The close is placed in the range, which can be empty (high == low)
+    gen all(
+          low <- float(min: 1.0, max: 1000.0),
+          range <- one_of([constant(0.0), float(min: 0.0, max: 50.0)]),
+          position <- float(min: 0.0, max: 1.0),
+          volume <- one_of([constant(0.0), float(min: 0.0, max: 10_000.0)])
+        ) do
+      {low + range, low, low + range * position, volume}
+    end
+  end
+
+  defp bars_generator(min_length, max_length) do
+    list_of(bar(), min_length: min_length, max_length: max_length)
+  end
+
+  defp bar_event({high, low, close, volume}, new_bar?) do
+    bar = %Bar{high: high, low: low, close: close, volume: volume, new_bar?: new_bar?}
+    %MarketEvent{data: %{"eurusd_m1" => bar}}
+  end
+
+  defp data_series(values) do
+    Enum.reduce(values, DataSeries.new(), &DataSeries.add(&2, &1))
+  end
+end
@@ -0,0 +1,207 @@
+defmodule TheoryCraftTA.Volume.OBVTest do
+  use ExUnit.Case, async: true
+  use ExUnitProperties
+
+  alias TheoryCraft.{DataSeries, TimeSeries}
+  alias TheoryCraft.MarketSource.{Bar, MarketEvent}
+  alias TheoryCraftTA.Volume.OBV
+
+  doctest TheoryCraftTA.Volume.OBV
+
+  @close [10.0, 11.0, 10.5, 10.5, 12.0, 11.8, 12.4, 12.1]
+  @volume [100.0, 200.0, 150.0, 50.0, 300.0, 120.0, 250.0, 80.0]
+
+  ## Batch calculation tests
+
+  describe "obv/2 with list input" do
+    test "calculates correctly" do
+      # Python result: [100. 300. 150. 150. 450. 330. 580. 500.]
+      assert {:ok, result} = OBV.obv(@close, @volume)
+      assert result == [100.0, 300.0, 150.0, 150.0, 450.0, 330.0, 580.0, 500.0]
+    end
+
+    test "returns error when inputs have different lengths" do
+      assert {:error, reason} = OBV.obv(@close, Enum.drop(@volume, 1))
+      assert reason == "OBV: close/volume length mismatch (8/7)"
+
+      assert {:error, reason} = OBV.obv(Enum.drop(@close, 3), @volume)
+      assert reason == "OBV: close/volume length mismatch (5/8)"
+    end
+
+    test "returns empty for empty input" do
+      assert {:ok, []} = OBV.obv([], [])
+    end
+
+    test "handles NaN at beginning (warmup scenario)" do
+      close = [nil, nil | Enum.drop(@close, 2)]
+      volume = [nil, nil | Enum.drop(@volume, 2)]
+      # Python result: [nan nan 150. 150. 450. 330. 580. 500.]
+      assert {:ok, result} = OBV.obv(close, volume)
+      assert result == [nil, nil, 150.0, 150.0, 450.0, 330.0, 580.0, 500.0]
+    end
+  end
+
+  describe "obv/2 with DataSeries input" do
+    test "maintains DataSeries type in output" do
+      close = Enum.reduce(@close, DataSeries.new(), &DataSeries.add(&2, &1))
+      volume = Enum.reduce(@volume, DataSeries.new(), &DataSeries.add(&2, &1))
+
+      assert {:ok, result} = OBV.obv(close, volume)
+      assert %DataSeries{} = result
+      assert [500.0, 580.0 | _] = DataSeries.values(result)
+    end
+  end
+
+  describe "obv/2 with TimeSeries input" do
+    test "maintains TimeSeries type in output" do
+      times = Enum.map(0..7, &DateTime.add(~U[2024-01-01 00:00:00Z], &1, :minute))
+
+      [close, volume] =
+        for values <- [@close, @volume] do
+          times
+          |> Enum.zip(values)
+          |> Enum.reduce(TimeSeries.new(), fn {time, value}, ts ->
+            TimeSeries.add(ts, time, value)
+          end)
+        end
+
+      assert {:ok, result} = OBV.obv(close, volume)
+      assert %TimeSeries{} = result
+      assert [500.0, 580.0 | _] = TimeSeries.values(result)
+    end
+  end
+
+  ## State initialization tests
+
+  describe "init/1" do
+    test "initializes with valid parameters" do
+      assert {:ok, _state} = OBV.init(data: "eurusd_m1", name: "obv")
+    end
+  end
+
+  ## Streaming API tests (next/2 with MarketEvent)
+
+  describe "next/2 with Bar input" do
+    test "replaying the series matches batch" do
+      {:ok, batch_result} = OBV.obv(@close, @volume)
+      {:ok, state} = OBV.init(data: "eurusd_m1", name: "obv")
+
+      {stream_result, _state} =
+        [@close, @volume]
+        |> Enum.zip()
+        |> Enum.map_reduce(state, fn bar, st ->
+          {:ok, result, new_st} = OBV.next(bar_event(bar, true), st)
+          {result.value, new_st}
+        end)
+
+      assert stream_result == batch_result
+    end
+
+    test "UPDATE keeps the total up to the previous bar" do
+      {:ok, state} = OBV.init(data: "eurusd_m1", name: "obv")
+
+      state =
+        Enum.reduce([{10.0, 100.0}, {11.0, 200.0}], state, fn bar, st ->
+          {:ok, _result, new_st} = OBV.next(bar_event(bar, true), st)
+          new_st
+        end)
+
+      # The 2nd bar is still open: it turns into a down bar with more volume
+      {:ok, result1, state} = OBV.next(bar_event({9.5, 250.0}, false), state)
+      assert result1.value == -150.0
+
+      # Another tick on the same bar still starts from the 1st bar's total
+      {:ok, result2, state} = OBV.next(bar_event({10.0, 300.0}, false), state)
+      assert result2.value == 100.0
+
+      # The next bar compares against the last close of the updated bar
+      {:ok, result3, _state} = OBV.next(bar_event({10.5, 50.0}, true), state)
+      assert result3.value == 150.0
+    end
+
+    test "handles nil values without modifying the state" do
+      {:ok, state} = OBV.init(data: "eurusd_m1", name: "obv")
+
+      event = %MarketEvent{
+        data: %{"eurusd_m1" => %Bar{close: nil, volume: nil, new_bar?: true}}
+      }
+
+      {:ok, result, state} = OBV.next(event, state)
+      assert result.value == nil
+
+      {:ok, result, _state} = OBV.next(bar_event({10.0, 100.0}, true), state)
+      assert result.value == 100.0
+    end
+  end
+
+  ## Property-based tests
+
+  describe "property: state-based APPEND matches batch calculation" do
+    property "APPEND mode matches batch OBV" do
+      check all(bars <- bars_generator(1, 300)) do
+        {close, volume} = Enum.unzip(bars)
+        {:ok, batch_result} = OBV.obv(close, volume)
+
+        {:ok, initial_state} = OBV.init(data: "test", name: "obv")
+
+        bars
+        |> Enum.zip(batch_result)
+        |> Enum.reduce(initial_state, fn {bar, expected_value}, state ->
+          {:ok, result, new_state} = OBV.next(bar_event("test", bar, true), state)
+          assert_in_delta(result.value, expected_value, 1.0e-6)
+          new_state
+        end)
+      end
+    end
+  end
+
+  describe "property: UPDATE mode behaves correctly" do
+    property "UPDATE recalculates with replaced last bar" do
+      check all(
+              bars <- bars_generator(1, 200),
+              update_bars <- bars_generator(2, 5)
+            ) do
+        {:ok, state} = OBV.init(data: "test", name: "obv")
+
+        final_state =
+          Enum.reduce(bars, state, fn bar, st ->
+            {:ok, _result, new_state} = OBV.next(bar_event("test", bar, true), st)
+            new_state
+          end)
+
+        Enum.reduce(update_bars, {final_state, bars}, fn update_bar, {state, current_bars} ->
+          {:ok, result, new_state} = OBV.next(bar_event("test", update_bar, false), state)
+
+          updated_bars = List.replace_at(current_bars, -1, update_bar)
+          {close, volume} = Enum.unzip(updated_bars)
+          {:ok, batch_result} = OBV.obv(close, volume)
+
+          assert_in_delta(result.value, List.last(batch_result), 1.0e-6)
+
+          {new_state, updated_bars}
+        end)
+      end
+    end
+  end
+
+  ## Private functions
+
+  defp bar_event(data_name \\ "eurusd_m1", {close, volume}, new_bar?) do
+    %MarketEvent{
+      data: %{data_name => %Bar{close: close, volume: volume, new_bar?: new_bar?}}
+    }
+  end
+
+  defp bars_generator(min_length, max_length) do
+    # Few distinct prices so that flat closes are generated too
+    close = one_of([integer(1..5), float(min: 1.0, max: 1000.0)])
+    volume = float(min: 0.0, max: 10_000.0)
+
+    gen all(
+          bars <-
+            list_of(tuple({close, volume}), min_length: min_length, max_length: max_length)
+        ) do
+      Enum.map(bars, fn {close, volume} -> {close / 1, volume} end)
+    end
+  end
+end
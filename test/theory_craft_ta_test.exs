@@ -2,4 +2,28 @@ defmodule TheoryCraftTATest do
   use ExUnit.Case, async: true
 
   doctest TheoryCraftTA
+
+  describe "ta_lib_version/0" do
+    test "returns the linked TA-Lib version" do
+      version = TheoryCraftTA.ta_lib_version()
+
+      if TheoryCraftTA.has_talib?() do
+        assert version =~ ~r/^\d+\.\d+\.\d+/
+      else
+        assert version == "unavailable"
+      end
+    end
+  end
+
+  describe "NIF load" do
+    test "the load hook succeeds and TA-Lib is usable right after load" do
+      # A failed TA_Initialize fails the load hook, and every NIF call then raises
+      assert is_boolean(TheoryCraftTA.has_talib?())
+      assert is_binary(TheoryCraftTA.ta_lib_version())
+
+      if TheoryCraftTA.has_talib?() do
+        assert {:ok, [nil, nil, 2.0]} = TheoryCraftTA.sma([1.0, 2.0, 3.0], 3)
+      end
+    end
+  end
 end